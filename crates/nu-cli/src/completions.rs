@@ -1,9 +1,9 @@
 use nu_engine::eval_block;
 use nu_parser::{flatten_expression, parse};
 use nu_protocol::{
-    ast::Statement,
+    ast::{Expr, Statement},
     engine::{EngineState, Stack, StateWorkingSet},
-    PipelineData, Span,
+    PipelineData, Signature, Span,
 };
 use reedline::Completer;
 
@@ -12,18 +12,28 @@ const SEP: char = std::path::MAIN_SEPARATOR;
 #[derive(Clone)]
 pub struct NuCompleter {
     engine_state: EngineState,
+    stack: Stack,
 }
 
 impl NuCompleter {
-    pub fn new(engine_state: EngineState) -> Self {
-        Self { engine_state }
+    pub fn new(engine_state: EngineState, stack: Stack) -> Self {
+        Self {
+            engine_state,
+            stack,
+        }
+    }
+
+    fn cwd(&self) -> String {
+        match self.stack.get_env_var(&self.engine_state, "PWD") {
+            Some(d) => d.as_string().unwrap_or_default(),
+            None => "".to_string(),
+        }
     }
 
     fn external_command_completion(&self, prefix: &str) -> Vec<String> {
         let mut executables = vec![];
 
-        let paths;
-        paths = self.engine_state.env_vars.get("PATH");
+        let paths = self.stack.get_env_var(&self.engine_state, "PATH");
 
         if let Some(paths) = paths {
             if let Ok(paths) = paths.as_list() {
@@ -113,6 +123,59 @@ impl NuCompleter {
         output
     }
 
+    fn complete_env_variables(
+        &self,
+        prefix: &[u8],
+        span: Span,
+        offset: usize,
+    ) -> Vec<(reedline::Span, String)> {
+        self.stack
+            .get_env_vars(&self.engine_state)
+            .into_keys()
+            .filter(|name| name.as_bytes().starts_with(prefix))
+            .map(|name| {
+                (
+                    reedline::Span {
+                        start: span.start - offset,
+                        end: span.end - offset,
+                    },
+                    name,
+                )
+            })
+            .collect()
+    }
+
+    fn complete_flags(
+        &self,
+        signature: &Signature,
+        prefix: &[u8],
+        span: Span,
+        offset: usize,
+    ) -> Vec<(reedline::Span, String)> {
+        let mut output = vec![];
+
+        for flag in &signature.named {
+            let mut names = vec![format!("--{}", flag.long)];
+            if let Some(short) = flag.short {
+                names.push(format!("-{}", short));
+            }
+
+            for name in names {
+                if name.as_bytes().starts_with(prefix) {
+                    output.push((
+                        reedline::Span {
+                            start: span.start - offset,
+                            end: span.end - offset,
+                        },
+                        name,
+                    ));
+                }
+            }
+        }
+
+        output
+    }
+
     fn complete_filepath_and_commands(
         &self,
         working_set: &StateWorkingSet,
@@ -121,6 +184,24 @@ impl NuCompleter {
     ) -> Vec<(reedline::Span, String)> {
         let prefix = working_set.get_span_contents(span);
 
+        // A command given by a relative path can only be a file to run, or a directory on the way to one
+        if is_relative_command_path(prefix) {
+            let prefix = String::from_utf8_lossy(prefix).to_string();
+
+            return executable_path_completion(span, &prefix, &self.cwd())
+                .into_iter()
+                .map(|x| {
+                    (
+                        reedline::Span {
+                            start: x.0.start - offset,
+                            end: x.0.end - offset,
+                        },
+                        x.1,
+                    )
+                })
+                .collect();
+        }
+
         let results = working_set
             .find_commands_by_prefix(prefix)
             .into_iter()
@@ -133,14 +214,7 @@ impl NuCompleter {
                     String::from_utf8_lossy(&x).to_string(),
                 )
             });
-        let cwd = if let Some(d) = self.engine_state.env_vars.get("PWD") {
-            match d.as_string() {
-                Ok(s) => s,
-                Err(_) => "".to_string(),
-            }
-        } else {
-            "".to_string()
-        };
+        let cwd = self.cwd();
 
         let prefix = String::from_utf8_lossy(prefix).to_string();
         let results_paths = file_path_completion(span, &prefix, &cwd)
@@ -197,6 +271,24 @@ impl NuCompleter {
                                 );
                             }
 
+                            // Members of `$env` are completed from the live environment
+                            if flat.0.start >= offset + 5
+                                && working_set
+                                    .get_span_contents(Span::new(flat.0.start - 5, flat.0.start))
+                                    == b"$env."
+                            {
+                                return self.complete_env_variables(prefix, flat.0, offset);
+                            }
+
+                            // Flags come from the signature of the called command, which also
+                            // covers externals declared with `extern`
+                            if matches!(flat.1, nu_parser::FlatShape::Flag) {
+                                if let Expr::Call(call) = &expr.expr {
+                                    let signature = working_set.get_decl(call.decl_id).signature();
+                                    return self.complete_flags(&signature, prefix, flat.0, offset);
+                                }
+                            }
+
                             match &flat.1 {
                                 nu_parser::FlatShape::Custom(custom_completion) => {
                                     let prefix = working_set.get_span_contents(flat.0).to_vec();
@@ -208,7 +300,9 @@ impl NuCompleter {
                                         false,
                                     );
 
-                                    let mut stack = Stack::default();
+                                    // Run against a snapshot so the completion sees the user's
+                                    // environment but can't change it
+                                    let mut stack = self.stack.snapshot();
                                     let result = eval_block(
                                         &self.engine_state,
                                         &mut stack,
@@ -253,15 +347,7 @@ impl NuCompleter {
                                 | nu_parser::FlatShape::ExternalArg => {
                                     let prefix = working_set.get_span_contents(flat.0);
                                     let prefix = String::from_utf8_lossy(prefix).to_string();
-                                    let cwd = if let Some(d) = self.engine_state.env_vars.get("PWD")
-                                    {
-                                        match d.as_string() {
-                                            Ok(s) => s,
-                                            Err(_) => "".to_string(),
-                                        }
-                                    } else {
-                                        "".to_string()
-                                    };
+                                    let cwd = self.cwd();
 
                                     let results = file_path_completion(flat.0, &prefix, &cwd);
 
@@ -365,7 +451,95 @@ fn file_path_completion(
     }
 }
 
+fn is_relative_command_path(prefix: &[u8]) -> bool {
+    [b"./".as_slice(), b"../", b".\\", b"..\\"]
+        .iter()
+        .any(|start| prefix.starts_with(start))
+}
+
+/// Files that can be run and directories below `cwd` matching a relative path like `./sc`
+fn executable_path_completion(
+    span: nu_protocol::Span,
+    partial: &str,
+    cwd: &str,
+) -> Vec<(nu_protocol::Span, String)> {
+    file_path_completion(span, partial, cwd)
+        .into_iter()
+        .filter(|(_, path)| {
+            let path = path.trim_matches('"');
+            path.ends_with(SEP)
+                || is_executable::is_executable(nu_path::expand_path_with(path, cwd))
+        })
+        .collect()
+}
+
 fn matches(partial: &str, from: &str) -> bool {
     from.to_ascii_lowercase()
         .starts_with(&partial.to_ascii_lowercase())
 }
+
+#[cfg(all(test, unix))]
+mod test {
+    use super::*;
+    use std::os::unix::fs::PermissionsExt;
+    use std::path::PathBuf;
+
+    fn scripts_dir(name: &str) -> PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("nu-completions-{}-{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(dir.join("tools")).expect("can create the test dir");
+
+        let script = dir.join("script.sh");
+        std::fs::write(&script, "#!/bin/sh\necho hi\n").expect("can write the script");
+        std::fs::set_permissions(&script, std::fs::Permissions::from_mode(0o755))
+            .expect("can make the script executable");
+        std::fs::write(dir.join("notes.txt"), "not runnable").expect("can write a file");
+
+        dir
+    }
+
+    fn completions(partial: &str, cwd: &std::path::Path) -> Vec<String> {
+        let mut paths: Vec<String> =
+            executable_path_completion(Span::test_data(), partial, &cwd.to_string_lossy())
+                .into_iter()
+                .map(|(_, path)| path)
+                .collect();
+        paths.sort();
+        paths
+    }
+
+    #[test]
+    fn relative_prefixes_are_recognised() {
+        assert!(is_relative_command_path(b"./scr"));
+        assert!(is_relative_command_path(b"../bin/x"));
+        assert!(!is_relative_command_path(b"ls"));
+        assert!(!is_relative_command_path(b".hidden"));
+    }
+
+    #[test]
+    fn completes_executables_and_directories() {
+        let dir = scripts_dir("dot");
+
+        assert_eq!(
+            completions("./", &dir),
+            vec!["./script.sh".to_string(), format!("./tools{}", SEP)]
+        );
+        assert_eq!(completions("./sc", &dir), vec!["./script.sh"]);
+        assert!(completions("./no", &dir).is_empty());
+
+        let _ = std::fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn completes_from_the_parent_directory() {
+        let dir = scripts_dir("dotdot");
+
+        assert_eq!(
+            completions("../sc", &dir.join("tools")),
+            vec!["../script.sh"]
+        );
+
+        let _ = std::fs::remove_dir_all(dir);
+    }
+}
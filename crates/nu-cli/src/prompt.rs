@@ -14,6 +14,7 @@ pub struct NushellPrompt {
     right_prompt_string: Option<String>,
     default_prompt_indicator: String,
     default_vi_insert_prompt_indicator: String,
+    default_vi_normal_prompt_indicator: String,
     default_vi_visual_prompt_indicator: String,
     default_menu_prompt_indicator: String,
     default_multiline_indicator: String,
@@ -32,6 +33,7 @@ impl NushellPrompt {
             right_prompt_string: None,
             default_prompt_indicator: "〉".to_string(),
             default_vi_insert_prompt_indicator: ": ".to_string(),
+            default_vi_normal_prompt_indicator: "〉".to_string(),
             default_vi_visual_prompt_indicator: "v ".to_string(),
             default_menu_prompt_indicator: "| ".to_string(),
             default_multiline_indicator: "::: ".to_string(),
@@ -54,6 +56,10 @@ impl NushellPrompt {
         self.default_vi_insert_prompt_indicator = prompt_vi_insert_string;
     }
 
+    pub fn update_prompt_vi_normal(&mut self, prompt_vi_normal_string: String) {
+        self.default_vi_normal_prompt_indicator = prompt_vi_normal_string;
+    }
+
     pub fn update_prompt_vi_visual(&mut self, prompt_vi_visual_string: String) {
         self.default_vi_visual_prompt_indicator = prompt_vi_visual_string;
     }
@@ -69,14 +75,15 @@ impl NushellPrompt {
         prompt_indicator_string: String,
         prompt_indicator_menu: String,
         prompt_multiline_indicator_string: String,
-        prompt_vi: (String, String),
+        prompt_vi: (String, String, String),
     ) {
-        let (prompt_vi_insert_string, prompt_vi_visual_string) = prompt_vi;
+        let (prompt_vi_insert_string, prompt_vi_normal_string, prompt_vi_visual_string) = prompt_vi;
 
         self.left_prompt_string = left_prompt_string;
         self.right_prompt_string = right_prompt_string;
         self.default_prompt_indicator = prompt_indicator_string;
         self.default_vi_insert_prompt_indicator = prompt_vi_insert_string;
+        self.default_vi_normal_prompt_indicator = prompt_vi_normal_string;
         self.default_vi_visual_prompt_indicator = prompt_vi_visual_string;
         self.default_multiline_indicator = prompt_multiline_indicator_string;
         self.default_menu_prompt_indicator = prompt_indicator_menu;
@@ -111,7 +118,7 @@ impl Prompt for NushellPrompt {
             PromptEditMode::Default => self.default_prompt_indicator.as_str().into(),
             PromptEditMode::Emacs => self.default_prompt_indicator.as_str().into(),
             PromptEditMode::Vi(vi_mode) => match vi_mode {
-                PromptViMode::Normal => self.default_prompt_indicator.as_str().into(),
+                PromptViMode::Normal => self.default_vi_normal_prompt_indicator.as_str().into(),
                 PromptViMode::Insert => self.default_vi_insert_prompt_indicator.as_str().into(),
                 PromptViMode::Visual => self.default_vi_visual_prompt_indicator.as_str().into(),
             },
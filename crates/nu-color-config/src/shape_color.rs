@@ -13,6 +13,7 @@ pub fn get_shape_color(shape: String, conf: &Config) -> Style {
             "flatshape_bool" => Style::new().fg(Color::LightCyan),
             "flatshape_int" => Style::new().fg(Color::Purple).bold(),
             "flatshape_float" => Style::new().fg(Color::Purple).bold(),
+            "flatshape_binary" => Style::new().fg(Color::Purple).bold(),
             "flatshape_range" => Style::new().fg(Color::Yellow).bold(),
             "flatshape_internalcall" => Style::new().fg(Color::Cyan).bold(),
             "flatshape_external" => Style::new().fg(Color::Cyan),
@@ -30,6 +31,7 @@ pub fn get_shape_color(shape: String, conf: &Config) -> Style {
             "flatshape_globpattern" => Style::new().fg(Color::Cyan).bold(),
             "flatshape_variable" => Style::new().fg(Color::Purple),
             "flatshape_flag" => Style::new().fg(Color::Blue).bold(),
+            "flatshape_cellpath" => Style::new().fg(Color::Yellow),
             "flatshape_custom" => Style::new().bold(),
             "flatshape_nothing" => Style::new().fg(Color::LightCyan),
             _ => Style::default(),
@@ -0,0 +1,76 @@
+use nu_protocol::ast::Call;
+use nu_protocol::engine::{Command, EngineState, Stack};
+use nu_protocol::{Category, Example, PipelineData, ShellError, Signature, Span, Value};
+
+#[derive(Clone)]
+pub struct SubCommand;
+
+impl Command for SubCommand {
+    fn name(&self) -> &str {
+        "bits not"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("bits not").category(Category::Bits)
+    }
+
+    fn usage(&self) -> &str {
+        "Performs a bitwise NOT on each integer"
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        _stack: &mut Stack,
+        call: &Call,
+        input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let head = call.head;
+        input.map(
+            move |value| not_helper(value, head),
+            engine_state.ctrlc.clone(),
+        )
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![
+            Example {
+                description: "Apply a bitwise NOT to a number",
+                example: "5 | bits not",
+                result: Some(Value::test_int(-6)),
+            },
+            Example {
+                description: "Apply a bitwise NOT to a list of numbers",
+                example: "[0 -1] | bits not",
+                result: Some(Value::List {
+                    vals: vec![Value::test_int(-1), Value::test_int(0)],
+                    span: Span::test_data(),
+                }),
+            },
+        ]
+    }
+}
+
+fn not_helper(value: Value, head: Span) -> Value {
+    match value {
+        Value::Int { val, span } => Value::Int { val: !val, span },
+        _ => Value::Error {
+            error: ShellError::UnsupportedInput(
+                String::from("Only integer values are supported"),
+                head,
+            ),
+        },
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_examples() {
+        use crate::test_examples;
+
+        test_examples(SubCommand {})
+    }
+}
@@ -5,6 +5,8 @@ use nu_protocol::{
     Example, PipelineData, ShellError, Signature, Span, SyntaxShape, Value,
 };
 
+use super::nothing_on_error;
+
 #[derive(Clone)]
 pub struct SubCommand;
 
@@ -14,11 +16,7 @@ impl Command for SubCommand {
     }
 
     fn signature(&self) -> Signature {
-        Signature::build("into decimal").rest(
-            "rest",
-            SyntaxShape::CellPath,
-            "optionally convert text into decimal by column paths",
-        )
+        signature("into decimal")
     }
 
     fn usage(&self) -> &str {
@@ -63,24 +61,44 @@ impl Command for SubCommand {
     }
 }
 
-fn operate(
+/// The signature shared with `into float`
+pub(super) fn signature(name: &str) -> Signature {
+    Signature::build(name)
+        .switch(
+            "ignore-errors",
+            "turn values that can't be converted into null instead of errors",
+            Some('i'),
+        )
+        .rest(
+            "rest",
+            SyntaxShape::CellPath,
+            "optionally convert text into decimal by column paths",
+        )
+}
+
+pub(super) fn operate(
     engine_state: &EngineState,
     stack: &mut Stack,
     call: &Call,
     input: PipelineData,
 ) -> Result<nu_protocol::PipelineData, nu_protocol::ShellError> {
     let head = call.head;
+    let ignore_errors = call.has_flag("ignore-errors");
     let column_paths: Vec<CellPath> = call.rest(engine_state, stack, 0)?;
 
     input.map(
         move |v| {
             if column_paths.is_empty() {
-                action(&v, head)
+                nothing_on_error(action(&v, head), ignore_errors, head)
             } else {
                 let mut ret = v;
                 for path in &column_paths {
-                    let r =
-                        ret.update_cell_path(&path.members, Box::new(move |old| action(old, head)));
+                    let r = ret.update_cell_path(
+                        &path.members,
+                        Box::new(move |old| {
+                            nothing_on_error(action(old, head), ignore_errors, head)
+                        }),
+                    );
                     if let Err(error) = r {
                         return Value::Error { error };
                     }
@@ -93,15 +111,19 @@ fn operate(
     )
 }
 
-fn action(input: &Value, head: Span) -> Value {
+pub(super) fn action(input: &Value, head: Span) -> Value {
     match input {
         Value::String { val: s, span } => {
             let other = s.trim();
 
             match other.parse::<f64>() {
                 Ok(x) => Value::Float { val: x, span: head },
-                Err(reason) => Value::Error {
-                    error: ShellError::CantConvert("float".to_string(), reason.to_string(), *span),
+                Err(_) => Value::Error {
+                    error: ShellError::CantConvert(
+                        "float".to_string(),
+                        format!("'{}'", other),
+                        *span,
+                    ),
                 },
             }
         }
@@ -0,0 +1,78 @@
+use nu_protocol::{
+    ast::Call,
+    engine::{Command, EngineState, Stack},
+    Example, PipelineData, Signature, Span, Value,
+};
+
+use super::decimal;
+
+/// `into decimal` under the name the type has elsewhere
+#[derive(Clone)]
+pub struct SubCommand;
+
+impl Command for SubCommand {
+    fn name(&self) -> &str {
+        "into float"
+    }
+
+    fn signature(&self) -> Signature {
+        decimal::signature("into float")
+    }
+
+    fn usage(&self) -> &str {
+        "converts text into float, the same as 'into decimal'"
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        input: PipelineData,
+    ) -> Result<nu_protocol::PipelineData, nu_protocol::ShellError> {
+        decimal::operate(engine_state, stack, call, input)
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![
+            Example {
+                description: "Convert string to float",
+                example: "'1.345' | into float",
+                result: Some(Value::test_float(1.345)),
+            },
+            Example {
+                description:
+                    "Convert a list of strings, turning the ones that aren't numbers into null",
+                example: "['1.5' 'x' '2'] | into float --ignore-errors",
+                result: Some(Value::List {
+                    vals: vec![
+                        Value::test_float(1.5),
+                        Value::Nothing {
+                            span: Span::test_data(),
+                        },
+                        Value::test_float(2.0),
+                    ],
+                    span: Span::test_data(),
+                }),
+            },
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_examples() {
+        use crate::test_examples;
+
+        test_examples(SubCommand {})
+    }
+
+    #[test]
+    fn int_strings_become_floats() {
+        let actual = decimal::action(&Value::test_string(" 7 "), Span::test_data());
+        assert_eq!(actual, Value::test_float(7.0));
+    }
+}
@@ -5,8 +5,11 @@ use nu_protocol::{
     Category, Example, PipelineData, ShellError, Signature, Span, SyntaxShape, Value,
 };
 
+use super::nothing_on_error;
+
 struct Arguments {
     radix: Option<Value>,
+    ignore_errors: bool,
     column_paths: Vec<CellPath>,
 }
 
@@ -21,6 +24,11 @@ impl Command for SubCommand {
     fn signature(&self) -> Signature {
         Signature::build("into int")
             .named("radix", SyntaxShape::Number, "radix of integer", Some('r'))
+            .switch(
+                "ignore-errors",
+                "turn values that can't be converted into null instead of errors",
+                Some('i'),
+            )
             .rest(
                 "rest",
                 SyntaxShape::CellPath,
@@ -33,6 +41,10 @@ impl Command for SubCommand {
         "Convert value to integer"
     }
 
+    fn extra_usage(&self) -> &str {
+        "Strings can start with 0x, 0o or 0b to give their radix, whatever --radix is."
+    }
+
     fn run(
         &self,
         engine_state: &EngineState,
@@ -91,6 +103,24 @@ impl Command for SubCommand {
                 example: "'FF' |  into int -r 16",
                 result: Some(Value::test_int(255)),
             },
+            Example {
+                description: "Convert to integer from octal",
+                example: "'0o755' | into int",
+                result: Some(Value::test_int(493)),
+            },
+            Example {
+                description: "Turn values that aren't integers into null",
+                example: "['1' 'one'] | into int -i",
+                result: Some(Value::List {
+                    vals: vec![
+                        Value::test_int(1),
+                        Value::Nothing {
+                            span: Span::test_data(),
+                        },
+                    ],
+                    span: Span::test_data(),
+                }),
+            },
         ]
     }
 }
@@ -105,6 +135,7 @@ fn into_int(
 
     let options = Arguments {
         radix: call.get_flag(engine_state, stack, "radix")?,
+        ignore_errors: call.has_flag("ignore-errors"),
         column_paths: call.rest(engine_state, stack, 0)?,
     };
 
@@ -123,16 +154,20 @@ fn into_int(
         }
     }
 
+    let ignore_errors = options.ignore_errors;
+
     input.map(
         move |v| {
             if options.column_paths.is_empty() {
-                action(&v, head, radix)
+                nothing_on_error(action(&v, head, radix), ignore_errors, head)
             } else {
                 let mut ret = v;
                 for path in &options.column_paths {
                     let r = ret.update_cell_path(
                         &path.members,
-                        Box::new(move |old| action(old, head, radix)),
+                        Box::new(move |old| {
+                            nothing_on_error(action(old, head, radix), ignore_errors, head)
+                        }),
                     );
                     if let Err(error) = r {
                         return Value::Error { error };
@@ -187,7 +222,7 @@ fn convert_int(input: &Value, head: Span, radix: u32) -> Value {
     let i = match input {
         Value::Int { val, .. } => val.to_string(),
         Value::String { val, .. } => {
-            if val.starts_with("0x") || val.starts_with("0b") {
+            if val.starts_with("0x") || val.starts_with("0o") || val.starts_with("0b") {
                 match int_from_string(&val.to_string(), head) {
                     Ok(x) => return Value::Int { val: x, span: head },
                     Err(e) => return Value::Error { error: e },
@@ -204,50 +239,39 @@ fn convert_int(input: &Value, head: Span, radix: u32) -> Value {
             }
         }
     };
-    match i64::from_str_radix(&i, radix) {
+    match i64::from_str_radix(i.trim(), radix) {
         Ok(n) => Value::Int { val: n, span: head },
-        Err(reason) => Value::Error {
-            error: ShellError::CantConvert("".to_string(), reason.to_string(), head),
+        Err(_) => Value::Error {
+            error: ShellError::CantConvert(
+                "int".to_string(),
+                format!("'{}' in base {}", i, radix),
+                input.span().unwrap_or(head),
+            ),
         },
     }
 }
 
 fn int_from_string(a_string: &str, span: Span) -> Result<i64, ShellError> {
     let trimmed = a_string.trim();
-    match trimmed {
-        b if b.starts_with("0b") => {
-            let num = match i64::from_str_radix(b.trim_start_matches("0b"), 2) {
-                Ok(n) => n,
-                Err(reason) => {
-                    return Err(ShellError::CantConvert(
-                        "could not parse as integer".to_string(),
-                        reason.to_string(),
-                        span,
-                    ))
-                }
-            };
-            Ok(num)
-        }
-        h if h.starts_with("0x") => {
-            let num = match i64::from_str_radix(h.trim_start_matches("0x"), 16) {
-                Ok(n) => n,
-                Err(reason) => {
-                    return Err(ShellError::CantConvert(
-                        "could not parse as int".to_string(),
-                        reason.to_string(),
-                        span,
-                    ))
-                }
-            };
-            Ok(num)
-        }
-        _ => match a_string.parse::<i64>() {
+    let prefixed = [("0b", 2), ("0o", 8), ("0x", 16)]
+        .iter()
+        .find_map(|(prefix, radix)| trimmed.strip_prefix(prefix).map(|rest| (rest, *radix)));
+
+    match prefixed {
+        Some((digits, radix)) => i64::from_str_radix(digits, radix).map_err(|_| {
+            ShellError::CantConvert(
+                "int".to_string(),
+                format!("'{}' in base {}", trimmed, radix),
+                span,
+            )
+        }),
+        None => match trimmed.parse::<i64>() {
             Ok(n) => Ok(n),
-            Err(_) => match a_string.parse::<f64>() {
+            Err(_) => match trimmed.parse::<f64>() {
                 Ok(f) => Ok(f as i64),
                 _ => Err(ShellError::CantConvert(
-                    "into int".to_string(),
-                    "string".to_string(),
+                    "int".to_string(),
+                    format!("'{}'", trimmed),
                     span,
                 )),
             },
@@ -291,6 +315,39 @@ mod test {
         assert_eq!(actual, Value::test_int(255));
     }
 
+    #[test]
+    fn turns_octal_to_integer() {
+        let s = Value::test_string("0o17");
+        let actual = action(&s, Span::test_data(), 10);
+        assert_eq!(actual, Value::test_int(15));
+
+        let s = Value::test_string("17");
+        let actual = action(&s, Span::test_data(), 8);
+        assert_eq!(actual, Value::test_int(15));
+    }
+
+    #[test]
+    fn digits_outside_the_radix_are_an_error() {
+        let s = Value::test_string("19");
+        let actual = action(&s, Span::test_data(), 8);
+        assert_eq!(actual.get_type(), Error);
+    }
+
+    #[test]
+    fn errors_become_nothing_when_ignored() {
+        let s = Value::test_string("nope");
+        let actual = nothing_on_error(action(&s, Span::test_data(), 10), true, Span::test_data());
+        assert_eq!(
+            actual,
+            Value::Nothing {
+                span: Span::test_data()
+            }
+        );
+
+        let actual = nothing_on_error(action(&s, Span::test_data(), 10), false, Span::test_data());
+        assert_eq!(actual.get_type(), Error);
+    }
+
     #[test]
     fn communicates_parsing_error_given_an_invalid_integerlike_string() {
         let integer_str = Value::test_string("36anra");
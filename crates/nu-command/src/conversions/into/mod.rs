@@ -4,6 +4,7 @@ mod command;
 mod datetime;
 mod decimal;
 mod filesize;
+mod float;
 mod int;
 mod string;
 
@@ -13,5 +14,16 @@ pub use binary::SubCommand as IntoBinary;
 pub use command::Into;
 pub use datetime::SubCommand as IntoDatetime;
 pub use decimal::SubCommand as IntoDecimal;
+pub use float::SubCommand as IntoFloat;
 pub use int::SubCommand as IntoInt;
 pub use string::SubCommand as IntoString;
+
+use nu_protocol::{Span, Value};
+
+/// With `--ignore-errors`, a value that can't be converted becomes null instead of an error
+fn nothing_on_error(value: Value, ignore_errors: bool, span: Span) -> Value {
+    match value {
+        Value::Error { .. } if ignore_errors => Value::Nothing { span },
+        value => value,
+    }
+}
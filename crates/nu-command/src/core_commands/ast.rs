@@ -0,0 +1,294 @@
+use nu_engine::CallExt;
+use nu_parser::{flatten_block, parse};
+use nu_protocol::ast::{Block, Call, Expr, Expression, Statement};
+use nu_protocol::engine::{Command, EngineState, Stack, StateWorkingSet};
+use nu_protocol::{
+    Category, Example, IntoPipelineData, PipelineData, ShellError, Signature, Span, Spanned,
+    SyntaxShape, Value,
+};
+
+#[derive(Clone)]
+pub struct Ast;
+
+impl Command for Ast {
+    fn name(&self) -> &str {
+        "ast"
+    }
+
+    fn usage(&self) -> &str {
+        "Print the abstract syntax tree (ast) for a pipeline."
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("ast")
+            .required(
+                "pipeline",
+                SyntaxShape::String,
+                "the pipeline to print the ast for",
+            )
+            .switch(
+                "flatten",
+                "list each token with its shape, the way the highlighter sees them",
+                Some('f'),
+            )
+            .category(Category::Core)
+    }
+
+    fn extra_usage(&self) -> &str {
+        r#"The pipeline is parsed on its own, against the commands defined so far, and never run.
+Spans are offsets into the given string. Parse errors don't stop the output: the part that
+failed shows up as garbage, and the error message is in the error column."#
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        _input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let pipeline: Spanned<String> = call.req(engine_state, stack, 0)?;
+        let flatten = call.has_flag("flatten");
+        let head = call.head;
+
+        let mut working_set = StateWorkingSet::new(engine_state);
+        let offset = working_set.next_span_start();
+        let (block, err) = parse(&mut working_set, None, pipeline.item.as_bytes(), false);
+
+        let ast = AstValues {
+            working_set: &working_set,
+            offset,
+            head,
+        };
+
+        if flatten {
+            let tokens = flatten_block(&working_set, &block)
+                .into_iter()
+                .map(|(span, shape)| Value::Record {
+                    cols: vec!["text".into(), "shape".into(), "span".into()],
+                    vals: vec![
+                        ast.text(span),
+                        Value::string(shape.to_string(), head),
+                        ast.span(span),
+                    ],
+                    span: head,
+                })
+                .collect();
+
+            return Ok(Value::List {
+                vals: tokens,
+                span: head,
+            }
+            .into_pipeline_data());
+        }
+
+        Ok(Value::Record {
+            cols: vec!["statements".into(), "error".into()],
+            vals: vec![
+                ast.block(&block),
+                match err {
+                    Some(err) => Value::string(err.to_string(), head),
+                    None => Value::nothing(head),
+                },
+            ],
+            span: head,
+        }
+        .into_pipeline_data())
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![
+            Example {
+                description: "Print the ast of a pipeline",
+                example: "ast 'ls | where name =~ foo'",
+                result: None,
+            },
+            Example {
+                description: "Show how each token of a pipeline will be highlighted",
+                example: "ast --flatten 'ls | length'",
+                result: None,
+            },
+        ]
+    }
+}
+
+/// Turns the parsed block into values, with spans relative to the parsed string
+struct AstValues<'a> {
+    working_set: &'a StateWorkingSet<'a>,
+    offset: usize,
+    head: Span,
+}
+
+impl<'a> AstValues<'a> {
+    fn block(&self, block: &Block) -> Value {
+        Value::List {
+            vals: block
+                .stmts
+                .iter()
+                .map(|stmt| self.statement(stmt))
+                .collect(),
+            span: self.head,
+        }
+    }
+
+    fn statement(&self, stmt: &Statement) -> Value {
+        let (kind, name, expressions) = match stmt {
+            Statement::Declaration(decl_id) => (
+                "declaration",
+                Value::string(self.working_set.get_decl(*decl_id).name(), self.head),
+                vec![],
+            ),
+            Statement::Pipeline(pipeline) => (
+                "pipeline",
+                Value::nothing(self.head),
+                pipeline
+                    .expressions
+                    .iter()
+                    .map(|expr| self.expression(expr))
+                    .collect(),
+            ),
+        };
+
+        Value::Record {
+            cols: vec!["kind".into(), "name".into(), "expressions".into()],
+            vals: vec![
+                Value::string(kind, self.head),
+                name,
+                Value::List {
+                    vals: expressions,
+                    span: self.head,
+                },
+            ],
+            span: self.head,
+        }
+    }
+
+    fn expression(&self, expr: &Expression) -> Value {
+        let mut name = Value::nothing(self.head);
+        let mut block = Value::nothing(self.head);
+        let mut parts: Vec<&Expression> = vec![];
+
+        match &expr.expr {
+            Expr::Call(call) => {
+                name = Value::string(self.working_set.get_decl(call.decl_id).name(), self.head);
+                parts.extend(call.positional.iter());
+                parts.extend(call.named.iter().filter_map(|(_, arg)| arg.as_ref()));
+            }
+            Expr::ExternalCall(head, args) => {
+                parts.push(head);
+                parts.extend(args.iter());
+            }
+            Expr::Range(from, next, to, _) => {
+                parts.extend([from, next, to].into_iter().flatten().map(|part| &**part));
+            }
+            Expr::BinaryOp(lhs, op, rhs) => parts.extend([&**lhs, &**op, &**rhs]),
+            Expr::List(items) | Expr::StringInterpolation(items) => parts.extend(items.iter()),
+            Expr::Table(headers, rows) => {
+                parts.extend(headers.iter());
+                parts.extend(rows.iter().flatten());
+            }
+            Expr::Record(fields) => {
+                parts.extend(fields.iter().flat_map(|(key, val)| [key, val]));
+            }
+            Expr::Keyword(_, _, inner) | Expr::ValueWithUnit(inner, _) => parts.push(inner),
+            Expr::FullCellPath(path) => parts.push(&path.head),
+            Expr::Block(block_id)
+            | Expr::Subexpression(block_id)
+            | Expr::RowCondition(block_id) => {
+                block = self.block(self.working_set.get_block(*block_id));
+            }
+            _ => {}
+        }
+
+        Value::Record {
+            cols: vec![
+                "expr".into(),
+                "text".into(),
+                "span".into(),
+                "type".into(),
+                "name".into(),
+                "parts".into(),
+                "block".into(),
+            ],
+            vals: vec![
+                Value::string(expr_kind(&expr.expr), self.head),
+                self.text(expr.span),
+                self.span(expr.span),
+                Value::string(expr.ty.to_string(), self.head),
+                name,
+                Value::List {
+                    vals: parts
+                        .into_iter()
+                        .map(|part| self.expression(part))
+                        .collect(),
+                    span: self.head,
+                },
+                block,
+            ],
+            span: self.head,
+        }
+    }
+
+    fn text(&self, span: Span) -> Value {
+        Value::string(
+            String::from_utf8_lossy(self.working_set.get_span_contents(span)),
+            self.head,
+        )
+    }
+
+    fn span(&self, span: Span) -> Value {
+        Value::Record {
+            cols: vec!["start".into(), "end".into()],
+            vals: vec![
+                Value::int(span.start.saturating_sub(self.offset) as i64, self.head),
+                Value::int(span.end.saturating_sub(self.offset) as i64, self.head),
+            ],
+            span: self.head,
+        }
+    }
+}
+
+/// The name of the expression's variant, without what it holds
+fn expr_kind(expr: &Expr) -> &'static str {
+    match expr {
+        Expr::Bool(_) => "Bool",
+        Expr::Int(_) => "Int",
+        Expr::Float(_) => "Float",
+        Expr::Binary(_) => "Binary",
+        Expr::Range(..) => "Range",
+        Expr::Var(_) => "Var",
+        Expr::VarDecl(_) => "VarDecl",
+        Expr::Call(_) => "Call",
+        Expr::ExternalCall(..) => "ExternalCall",
+        Expr::Operator(_) => "Operator",
+        Expr::RowCondition(_) => "RowCondition",
+        Expr::BinaryOp(..) => "BinaryOp",
+        Expr::Subexpression(_) => "Subexpression",
+        Expr::Block(_) => "Block",
+        Expr::List(_) => "List",
+        Expr::Table(..) => "Table",
+        Expr::Record(_) => "Record",
+        Expr::Keyword(..) => "Keyword",
+        Expr::ValueWithUnit(..) => "ValueWithUnit",
+        Expr::Filepath(_) => "Filepath",
+        Expr::GlobPattern(_) => "GlobPattern",
+        Expr::String(_) => "String",
+        Expr::CellPath(_) => "CellPath",
+        Expr::FullCellPath(_) => "FullCellPath",
+        Expr::ImportPattern(_) => "ImportPattern",
+        Expr::Signature(_) => "Signature",
+        Expr::StringInterpolation(_) => "StringInterpolation",
+        Expr::Nothing => "Nothing",
+        Expr::Garbage => "Garbage",
+    }
+}
+
+#[cfg(test)]
+mod test {
+    #[test]
+    fn test_examples() {
+        use super::Ast;
+        use crate::test_examples;
+        test_examples(Ast {})
+    }
+}
@@ -0,0 +1,52 @@
+use nu_protocol::ast::Call;
+use nu_protocol::engine::{Command, EngineState, LoopControl, Stack};
+use nu_protocol::{Category, Example, PipelineData, ShellError, Signature};
+
+#[derive(Clone)]
+pub struct Break;
+
+impl Command for Break {
+    fn name(&self) -> &str {
+        "break"
+    }
+
+    fn usage(&self) -> &str {
+        "Stop the loop it is used in."
+    }
+
+    fn extra_usage(&self) -> &str {
+        "Only works in the body of a 'for' or 'while' loop. Blocks given to commands like 'each' aren't loops, so a break inside them is an error."
+    }
+
+    fn signature(&self) -> nu_protocol::Signature {
+        Signature::build("break").category(Category::Core)
+    }
+
+    fn run(
+        &self,
+        _engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        _input: PipelineData,
+    ) -> Result<nu_protocol::PipelineData, nu_protocol::ShellError> {
+        match &stack.loop_signal {
+            // The rest of the body is skipped, and the loop stops
+            Some(signal) => {
+                signal.raise(LoopControl::Break);
+                Ok(PipelineData::new(call.head))
+            }
+            None => Err(ShellError::LoopControlOutsideLoop(
+                "break".into(),
+                call.head,
+            )),
+        }
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![Example {
+            description: "Stop a loop at the first even number",
+            example: "for i in [1 3 4 5] { if $i mod 2 == 0 { break }; $i }",
+            result: None,
+        }]
+    }
+}
@@ -0,0 +1,52 @@
+use nu_protocol::ast::Call;
+use nu_protocol::engine::{Command, EngineState, LoopControl, Stack};
+use nu_protocol::{Category, Example, PipelineData, ShellError, Signature};
+
+#[derive(Clone)]
+pub struct Continue;
+
+impl Command for Continue {
+    fn name(&self) -> &str {
+        "continue"
+    }
+
+    fn usage(&self) -> &str {
+        "Skip the rest of the current iteration of a loop."
+    }
+
+    fn extra_usage(&self) -> &str {
+        "Only works in the body of a 'for' or 'while' loop. Blocks given to commands like 'each' aren't loops, so a continue inside them is an error."
+    }
+
+    fn signature(&self) -> nu_protocol::Signature {
+        Signature::build("continue").category(Category::Core)
+    }
+
+    fn run(
+        &self,
+        _engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        _input: PipelineData,
+    ) -> Result<nu_protocol::PipelineData, nu_protocol::ShellError> {
+        match &stack.loop_signal {
+            // The rest of the body is skipped, and the loop goes on with the next iteration
+            Some(signal) => {
+                signal.raise(LoopControl::Continue);
+                Ok(PipelineData::new(call.head))
+            }
+            None => Err(ShellError::LoopControlOutsideLoop(
+                "continue".into(),
+                call.head,
+            )),
+        }
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![Example {
+            description: "Skip the even numbers",
+            example: "for i in 1..5 { if $i mod 2 == 0 { continue }; $i }",
+            result: None,
+        }]
+    }
+}
@@ -2,6 +2,9 @@ use nu_protocol::ast::Call;
 use nu_protocol::engine::{Command, EngineState, Stack};
 use nu_protocol::{Category, Example, PipelineData, ShellError, Signature, Value};
 
+/// How deep lists and records are described before their contents are left out
+const MAX_DEPTH: usize = 3;
+
 #[derive(Clone)]
 pub struct Describe;
 
@@ -20,43 +23,111 @@ impl Command for Describe {
 
     fn run(
         &self,
-        engine_state: &EngineState,
+        _engine_state: &EngineState,
         _stack: &mut Stack,
         call: &Call,
         input: PipelineData,
     ) -> Result<PipelineData, ShellError> {
-        let head = call.head;
-        if matches!(input, PipelineData::ByteStream(..)) {
-            Ok(PipelineData::Value(
-                Value::string("binary", call.head),
-                None,
-            ))
-        } else {
-            input.map(
-                move |x| Value::String {
-                    val: x.get_type().to_string(),
-                    span: head,
-                },
-                engine_state.ctrlc.clone(),
-            )
-        }
+        let description = match input {
+            PipelineData::Value(value, ..) => describe(&value, 0),
+            // Only the first element of a stream is looked at, the rest is never read
+            PipelineData::ListStream(mut stream, ..) => match stream.next() {
+                Some(value) => format!("stream<{}>", describe(&value, 1)),
+                None => "stream".into(),
+            },
+            PipelineData::StringStream(..) => "stream<string>".into(),
+            PipelineData::ByteStream(..) => "stream<binary>".into(),
+        };
+
+        Ok(PipelineData::Value(
+            Value::string(description, call.head),
+            None,
+        ))
     }
 
     fn examples(&self) -> Vec<Example> {
-        vec![Example {
-            description: "Describe the type of a string",
-            example: "'hello' | describe",
-            result: Some(Value::test_string("string")),
-        }]
+        vec![
+            Example {
+                description: "Describe the type of a string",
+                example: "'hello' | describe",
+                result: Some(Value::test_string("string")),
+            },
+            Example {
+                description: "Describe the type of a record",
+                example: "{name: 'nu', size: 10kb} | describe",
+                result: Some(Value::test_string("record<name: string, size: filesize>")),
+            },
+            Example {
+                description: "Describe the type of a list",
+                example: "[1 2 3] | describe",
+                result: Some(Value::test_string("list<int>")),
+            },
+        ]
+    }
+}
+
+fn describe(value: &Value, depth: usize) -> String {
+    match value {
+        Value::List { vals, .. } => {
+            if depth >= MAX_DEPTH {
+                return "list<...>".into();
+            }
+
+            if !vals.is_empty() && vals.iter().all(|val| matches!(val, Value::Record { .. })) {
+                return "table".into();
+            }
+
+            let mut descriptions = vals.iter().map(|val| describe(val, depth + 1));
+
+            match descriptions.next() {
+                Some(first) if descriptions.all(|other| other == first) => {
+                    format!("list<{}>", first)
+                }
+                _ => "list<any>".into(),
+            }
+        }
+        Value::Record { cols, vals, .. } => {
+            if depth >= MAX_DEPTH {
+                return "record<...>".into();
+            }
+
+            format!(
+                "record<{}>",
+                cols.iter()
+                    .zip(vals.iter())
+                    .map(|(col, val)| format!("{}: {}", col, describe(val, depth + 1)))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )
+        }
+        // Custom values are described by their own name, like `dataframe`
+        Value::CustomValue { val, .. } => val.typetag_name().to_string(),
+        value => value.get_type().to_string(),
     }
 }
 
 #[cfg(test)]
 mod test {
+    use super::*;
+
     #[test]
     fn test_examples() {
-        use super::Describe;
         use crate::test_examples;
         test_examples(Describe {})
     }
+
+    #[test]
+    fn nested_values_are_depth_limited() {
+        let span = nu_protocol::Span::test_data();
+        let mut value = Value::int(1, span);
+
+        for _ in 0..5 {
+            value = Value::List {
+                vals: vec![value],
+                span,
+            };
+        }
+
+        assert_eq!(describe(&value, 0), "list<list<list<list<...>>>>");
+    }
 }
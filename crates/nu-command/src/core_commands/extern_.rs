@@ -0,0 +1,45 @@
+use nu_protocol::ast::Call;
+use nu_protocol::engine::{Command, EngineState, Stack};
+use nu_protocol::{Category, Example, PipelineData, Signature, SyntaxShape};
+
+#[derive(Clone)]
+pub struct Extern;
+
+impl Command for Extern {
+    fn name(&self) -> &str {
+        "extern"
+    }
+
+    fn usage(&self) -> &str {
+        "Define a signature for an external command"
+    }
+
+    fn signature(&self) -> nu_protocol::Signature {
+        Signature::build("extern")
+            .required("def_name", SyntaxShape::String, "definition name")
+            .required("params", SyntaxShape::Signature, "parameters")
+            .category(Category::Core)
+    }
+
+    fn extra_usage(&self) -> &str {
+        "Calls to the external command are checked against the signature when parsed, and its flags and arguments can be completed. Externals without a signature accept anything."
+    }
+
+    fn run(
+        &self,
+        _engine_state: &EngineState,
+        _stack: &mut Stack,
+        call: &Call,
+        _input: PipelineData,
+    ) -> Result<nu_protocol::PipelineData, nu_protocol::ShellError> {
+        Ok(PipelineData::new(call.head))
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![Example {
+            description: "Declare the flags of `git push`",
+            example: r#"extern "git push" [remote?: string, branch?: string, --force(-f)]"#,
+            result: None,
+        }]
+    }
+}
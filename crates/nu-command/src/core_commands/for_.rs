@@ -1,9 +1,9 @@
-use nu_engine::{eval_block, eval_expression, CallExt};
-use nu_protocol::ast::Call;
-use nu_protocol::engine::{CaptureBlock, Command, EngineState, Stack};
+use nu_engine::{eval_block, eval_expression, eval_subexpression, CallExt};
+use nu_protocol::ast::{Call, Expr};
+use nu_protocol::engine::{CaptureBlock, Command, EngineState, LoopControl, LoopSignal, Stack};
 use nu_protocol::{
-    Category, Example, IntoInterruptiblePipelineData, PipelineData, Signature, Span, SyntaxShape,
-    Value,
+    Category, Example, IntoInterruptiblePipelineData, IntoPipelineData, PipelineData, Signature,
+    Span, SyntaxShape, Value,
 };
 
 #[derive(Clone)]
@@ -15,7 +15,7 @@ impl Command for For {
     }
 
     fn usage(&self) -> &str {
-        "Loop over a range"
+        "Loop over a list, range, record or stream"
     }
 
     fn signature(&self) -> nu_protocol::Signature {
@@ -59,7 +59,14 @@ impl Command for For {
         let keyword_expr = call.positional[1]
             .as_keyword()
             .expect("internal error: missing keyword");
-        let values = eval_expression(engine_state, stack, keyword_expr)?;
+        // A subexpression is run without collecting its output, so a stream is iterated lazily
+        let values = match &keyword_expr.expr {
+            Expr::Subexpression(block_id) => {
+                let block = engine_state.get_block(*block_id);
+                eval_subexpression(engine_state, stack, block, PipelineData::new(head))?
+            }
+            _ => eval_expression(engine_state, stack, keyword_expr)?.into_pipeline_data(),
+        };
 
         let capture_block: CaptureBlock = call.req(engine_state, stack, 2)?;
 
@@ -69,80 +76,74 @@ impl Command for For {
         let engine_state = engine_state.clone();
         let block = engine_state.get_block(capture_block.block_id).clone();
         let mut stack = stack.captures_to_stack(&capture_block.captures);
+        let signal = LoopSignal::default();
+        stack.loop_signal = Some(signal.clone());
         let orig_env_vars = stack.env_vars.clone();
         let orig_env_hidden = stack.env_hidden.clone();
 
-        match values {
-            Value::List { vals, .. } => Ok(vals
-                .into_iter()
-                .enumerate()
-                .map(move |(idx, x)| {
-                    stack.with_env(&orig_env_vars, &orig_env_hidden);
-
-                    stack.add_var(
-                        var_id,
-                        if numbered {
-                            Value::Record {
-                                cols: vec!["index".into(), "item".into()],
-                                vals: vec![
-                                    Value::Int {
-                                        val: idx as i64,
-                                        span: head,
-                                    },
-                                    x,
-                                ],
-                                span: head,
-                            }
-                        } else {
-                            x
-                        },
-                    );
-
-                    //let block = engine_state.get_block(block_id);
-                    match eval_block(&engine_state, &mut stack, &block, PipelineData::new(head)) {
-                        Ok(pipeline_data) => pipeline_data.into_value(head),
-                        Err(error) => Value::Error { error },
-                    }
-                })
-                .into_pipeline_data(ctrlc)),
-            Value::Range { val, .. } => Ok(val
-                .into_range_iter()?
-                .enumerate()
-                .map(move |(idx, x)| {
-                    stack.with_env(&orig_env_vars, &orig_env_hidden);
-
-                    stack.add_var(
-                        var_id,
-                        if numbered {
-                            Value::Record {
-                                cols: vec!["index".into(), "item".into()],
-                                vals: vec![
-                                    Value::Int {
-                                        val: idx as i64,
-                                        span: head,
-                                    },
-                                    x,
-                                ],
-                                span: head,
-                            }
-                        } else {
-                            x
-                        },
-                    );
-
-                    //let block = engine_state.get_block(block_id);
-                    match eval_block(&engine_state, &mut stack, &block, PipelineData::new(head)) {
-                        Ok(pipeline_data) => pipeline_data.into_value(head),
-                        Err(error) => Value::Error { error },
-                    }
-                })
-                .into_pipeline_data(ctrlc)),
-            x => {
+        let values: Box<dyn Iterator<Item = Value> + Send> = match values {
+            PipelineData::Value(Value::List { vals, .. }, ..) => Box::new(vals.into_iter()),
+            PipelineData::Value(Value::Range { val, .. }, ..) => Box::new(val.into_range_iter()?),
+            PipelineData::Value(Value::Record { cols, vals, .. }, ..) => Box::new(
+                cols.into_iter()
+                    .zip(vals)
+                    .map(move |(key, value)| Value::Record {
+                        cols: vec!["key".into(), "value".into()],
+                        vals: vec![Value::string(key, head), value],
+                        span: head,
+                    }),
+            ),
+            stream @ PipelineData::ListStream(..)
+            | stream @ PipelineData::StringStream(..)
+            | stream @ PipelineData::ByteStream(..) => Box::new(stream.into_iter()),
+            PipelineData::Value(x, ..) => {
                 stack.add_var(var_id, x);
 
-                eval_block(&engine_state, &mut stack, &block, PipelineData::new(head))
+                let result = eval_block(&engine_state, &mut stack, &block, PipelineData::new(head));
+                return match signal.take() {
+                    Some(_) => Ok(PipelineData::new(head)),
+                    None => result,
+                };
             }
-        }
+        };
+
+        Ok(values
+            .enumerate()
+            .map(move |(idx, x)| {
+                stack.with_env(&orig_env_vars, &orig_env_hidden);
+
+                stack.add_var(
+                    var_id,
+                    if numbered {
+                        Value::Record {
+                            cols: vec!["index".into(), "item".into()],
+                            vals: vec![
+                                Value::Int {
+                                    val: idx as i64,
+                                    span: head,
+                                },
+                                x,
+                            ],
+                            span: head,
+                        }
+                    } else {
+                        x
+                    },
+                );
+
+                let result = eval_block(&engine_state, &mut stack, &block, PipelineData::new(head))
+                    .map(|pipeline_data| pipeline_data.into_value(head));
+
+                (result, signal.take())
+            })
+            // `break` ends the loop, `continue` drops what was left of the iteration
+            .take_while(|(_, control)| *control != Some(LoopControl::Break))
+            .filter_map(|(result, control)| match (result, control) {
+                (Err(error), _) => Some(Value::Error { error }),
+                (Ok(_), Some(LoopControl::Continue)) => None,
+                (Ok(value), _) => Some(value),
+            })
+            .into_pipeline_data(ctrlc))
     }
 
     fn examples(&self) -> Vec<Example> {
@@ -172,6 +173,23 @@ impl Command for For {
                     span,
                 }),
             },
+            Example {
+                description: "Loop over the key/value pairs of a record",
+                example: "for x in {a: 1, b: 2} { $\"($x.key)=($x.value)\" }",
+                result: Some(Value::List {
+                    vals: vec![
+                        Value::String {
+                            val: "a=1".into(),
+                            span,
+                        },
+                        Value::String {
+                            val: "b=2".into(),
+                            span,
+                        },
+                    ],
+                    span,
+                }),
+            },
             Example {
                 description: "Number each item and echo a message",
                 example: "for $it in ['bob' 'fred'] --numbered { $\"($it.index) is ($it.item)\" }",
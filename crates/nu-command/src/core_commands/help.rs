@@ -6,6 +6,7 @@ use nu_protocol::{
 };
 
 use nu_engine::{get_full_help, CallExt};
+use std::collections::BTreeMap;
 
 #[derive(Clone)]
 pub struct Help;
@@ -25,7 +26,7 @@ impl Command for Help {
             .named(
                 "find",
                 SyntaxShape::String,
-                "string to find in command usage",
+                "string to find in command names and usage",
                 Some('f'),
             )
             .category(Category::Core)
@@ -231,6 +232,7 @@ fn help(
 
 Here are some tips to help you get started.
   * help commands - list all available commands
+  * help --find <text> - search command names and usage
   * help <command name> - display help about a particular command
 
 Nushell works on the idea of a "pipeline". Pipelines are commands connected with the '|' character.
@@ -249,8 +251,26 @@ Get the processes on your system actively using CPU:
 
 You can also learn more at https://www.nushell.sh/book/"#;
 
+        // Every command, custom ones included, under its category
+        let mut categories: BTreeMap<String, Vec<(String, String)>> = BTreeMap::new();
+        for (sig, _, _, _) in full_commands {
+            categories
+                .entry(sig.category.to_string())
+                .or_default()
+                .push((sig.name, sig.usage));
+        }
+
+        let mut listing = String::from("\n\n[Commands]\n");
+        for (category, mut commands) in categories {
+            commands.sort();
+            listing.push_str(&format!("\n{}:\n", category));
+            for (name, usage) in commands {
+                listing.push_str(&format!("  {} - {}\n", name, usage));
+            }
+        }
+
         Ok(Value::String {
-            val: msg.into(),
+            val: format!("{}{}", msg, listing),
             span: head,
         }
         .into_pipeline_data())
@@ -1,7 +1,8 @@
 use nu_protocol::ast::Call;
 use nu_protocol::engine::{Command, EngineState, Stack};
 use nu_protocol::{
-    Category, IntoInterruptiblePipelineData, PipelineData, ShellError, Signature, Value,
+    Category, Example, IntoInterruptiblePipelineData, PipelineData, ShellError, Signature, Span,
+    Value,
 };
 
 const NEWLINE_ESCAPE_CODE: &str = "<\\n>";
@@ -22,6 +23,10 @@ impl Command for History {
         "Get the command history"
     }
 
+    fn extra_usage(&self) -> &str {
+        "The history is read from $nu.history-path. Repeated runs of the same command show up once."
+    }
+
     fn signature(&self) -> nu_protocol::Signature {
         Signature::build("history")
             .switch("clear", "Clears out the history entries", Some('c'))
@@ -45,19 +50,17 @@ impl Command for History {
             history_path.push("history.txt");
 
             if clear {
-                let _ = std::fs::remove_file(history_path);
+                // Truncate rather than remove, so the shell keeps a file to write to
+                if history_path.exists() {
+                    std::fs::File::create(&history_path)
+                        .map_err(|err| ShellError::IOError(err.to_string()))?;
+                }
                 Ok(PipelineData::new(head))
             } else {
                 let contents = std::fs::read_to_string(history_path);
 
                 if let Ok(contents) = contents {
-                    Ok(contents
-                        .lines()
-                        .map(move |x| Value::String {
-                            val: decode_newlines(x),
-                            span: head,
-                        })
-                        .collect::<Vec<_>>()
+                    Ok(history_entries(&contents, head)
                         .into_iter()
                         .into_pipeline_data(ctrlc))
                 } else {
@@ -68,4 +71,89 @@ impl Command for History {
             Err(ShellError::FileNotFound(head))
         }
     }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![
+            Example {
+                description: "Get the last five cargo commands",
+                example: "history | where command =~ cargo | last 5",
+                result: None,
+            },
+            Example {
+                description: "Clear the history",
+                example: "history --clear",
+                result: None,
+            },
+        ]
+    }
+}
+
+/// Turn the lines of a history file into `{index, command}` rows, collapsing consecutive
+/// identical commands into one
+fn history_entries(contents: &str, span: Span) -> Vec<Value> {
+    let mut commands: Vec<String> = vec![];
+
+    for line in contents.lines() {
+        let command = decode_newlines(line);
+        if commands.last() != Some(&command) {
+            commands.push(command);
+        }
+    }
+
+    commands
+        .into_iter()
+        .enumerate()
+        .map(|(index, command)| Value::Record {
+            cols: vec!["index".into(), "command".into()],
+            vals: vec![Value::int(index as i64, span), Value::string(command, span)],
+            span,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn commands(entries: &[Value]) -> Vec<String> {
+        entries
+            .iter()
+            .filter_map(|e| e.get_data_by_key("command"))
+            .filter_map(|c| c.as_string().ok())
+            .collect()
+    }
+
+    #[test]
+    fn entries_are_indexed_and_decoded() {
+        let history = "ls\ncargo build\ndef f [] {<\\n>  1<\\n>}\n";
+        let entries = history_entries(history, Span::test_data());
+
+        assert_eq!(
+            commands(&entries),
+            vec!["ls", "cargo build", "def f [] {\n  1\n}"]
+        );
+
+        let indices: Vec<i64> = entries
+            .iter()
+            .filter_map(|e| e.get_data_by_key("index"))
+            .filter_map(|i| i.as_integer().ok())
+            .collect();
+        assert_eq!(indices, vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn consecutive_duplicates_are_collapsed() {
+        let history = "cargo test\ncargo test\nls\ncargo test\nls\nls\n";
+        let entries = history_entries(history, Span::test_data());
+
+        assert_eq!(
+            commands(&entries),
+            vec!["cargo test", "ls", "cargo test", "ls"]
+        );
+    }
+
+    #[test]
+    fn empty_history_has_no_entries() {
+        assert!(history_entries("", Span::test_data()).is_empty());
+    }
 }
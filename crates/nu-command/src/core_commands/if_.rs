@@ -1,5 +1,5 @@
 use nu_engine::{eval_block, eval_expression, CallExt};
-use nu_protocol::ast::Call;
+use nu_protocol::ast::{Call, Expr, Expression, Statement};
 use nu_protocol::engine::{CaptureBlock, Command, EngineState, Stack};
 use nu_protocol::{
     Category, Example, FromValue, IntoPipelineData, PipelineData, ShellError, Signature,
@@ -18,6 +18,12 @@ impl Command for If {
         "Conditionally run a block."
     }
 
+    fn extra_usage(&self) -> &str {
+        r#"When the condition is an external command, or a pipeline ending in one, its exit status
+is the condition, like in POSIX shells: 0 is true and anything else is false. Its output is
+discarded. Values from nu's own commands are never converted this way."#
+    }
+
     fn signature(&self) -> nu_protocol::Signature {
         Signature::build("if")
             .required("cond", SyntaxShape::Expression, "condition to check")
@@ -45,12 +51,16 @@ impl Command for If {
         let then_block: CaptureBlock = call.req(engine_state, stack, 1)?;
         let else_case = call.positional.get(2);
 
-        let result = eval_expression(engine_state, stack, cond)?;
+        let result = if ends_in_external(engine_state, cond) {
+            exit_status(engine_state, stack, cond)?
+        } else {
+            eval_expression(engine_state, stack, cond)?
+        };
         match &result {
             Value::Bool { val, .. } => {
                 if *val {
                     let block = engine_state.get_block(then_block.block_id);
-                    let mut stack = stack.captures_to_stack(&then_block.captures);
+                    let mut stack = body_stack(stack, &then_block);
                     eval_block(engine_state, &mut stack, block, input)
                 } else if let Some(else_case) = else_case {
                     if let Some(else_expr) = else_case.as_keyword() {
@@ -58,7 +68,7 @@ impl Command for If {
                             let result = eval_expression(engine_state, stack, else_expr)?;
                             let else_block: CaptureBlock = FromValue::from_value(&result)?;
 
-                            let mut stack = stack.captures_to_stack(&else_block.captures);
+                            let mut stack = body_stack(stack, &else_block);
                             let block = engine_state.get_block(block_id);
                             eval_block(engine_state, &mut stack, block, input)
                         } else {
@@ -93,6 +103,11 @@ impl Command for If {
                 example: "if 5 < 3 { 'yes!' } else { 'no!' }",
                 result: Some(Value::test_string("no!")),
             },
+            Example {
+                description: "Check the exit status of an external command",
+                example: "if (^grep -q nu config.nu) { 'found' } else { 'not found' }",
+                result: None,
+            },
             Example {
                 description: "Chain multiple if's together",
                 example: "if 5 < 3 { 'yes!' } else if 4 < 5 { 'no!' } else { 'okay!' }",
@@ -102,6 +117,52 @@ impl Command for If {
     }
 }
 
+/// Whether the condition is an external call, or a subexpression whose last pipeline ends in one
+fn ends_in_external(engine_state: &EngineState, cond: &Expression) -> bool {
+    match &cond.expr {
+        Expr::ExternalCall(..) => true,
+        // Parentheses parse as a cell path with nothing to follow
+        Expr::FullCellPath(path) if path.tail.is_empty() => {
+            ends_in_external(engine_state, &path.head)
+        }
+        Expr::Subexpression(block_id) => matches!(
+            engine_state.get_block(*block_id).stmts.last(),
+            Some(Statement::Pipeline(pipeline)) if matches!(
+                pipeline.expressions.last(),
+                Some(Expression {
+                    expr: Expr::ExternalCall(..),
+                    ..
+                })
+            )
+        ),
+        _ => false,
+    }
+}
+
+/// Run the external of the condition and turn its exit status into a bool
+fn exit_status(
+    engine_state: &EngineState,
+    stack: &mut Stack,
+    cond: &Expression,
+) -> Result<Value, ShellError> {
+    // Collecting the output also lets the external finish
+    eval_expression(engine_state, stack, cond)?;
+    let exit_code = stack.update_last_exit_code();
+
+    Ok(Value::Bool {
+        val: exit_code == Some(0),
+        span: cond.span,
+    })
+}
+
+/// The stack a branch runs on. A branch inside a loop body is part of it, so `break` and
+/// `continue` in the branch control the loop.
+fn body_stack(stack: &Stack, block: &CaptureBlock) -> Stack {
+    let mut body = stack.captures_to_stack(&block.captures);
+    body.loop_signal = stack.loop_signal.clone();
+    body
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
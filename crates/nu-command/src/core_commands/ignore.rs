@@ -23,8 +23,14 @@ impl Command for Ignore {
         _engine_state: &EngineState,
         _stack: &mut Stack,
         call: &Call,
-        _input: PipelineData,
+        input: PipelineData,
     ) -> Result<nu_protocol::PipelineData, nu_protocol::ShellError> {
+        // Streams are read to the end, so an external command feeding them runs to completion
+        // instead of being cut off when its output is dropped
+        if !matches!(input, PipelineData::Value(..)) {
+            for _ in input {}
+        }
+
         Ok(PipelineData::new(call.head))
     }
 
@@ -1,7 +1,9 @@
+use nu_engine::CallExt;
 use nu_protocol::ast::Call;
 use nu_protocol::engine::{Command, EngineState, Stack};
 use nu_protocol::{
-    Category, DataSource, Example, PipelineData, PipelineMetadata, Signature, Value,
+    Category, DataSource, Example, IntoPipelineData, PipelineData, PipelineMetadata, Signature,
+    Span, SyntaxShape, Value,
 };
 
 #[derive(Clone)]
@@ -17,79 +19,109 @@ impl Command for Metadata {
     }
 
     fn signature(&self) -> nu_protocol::Signature {
-        Signature::build("metadata").category(Category::Core)
+        Signature::build("metadata")
+            .optional(
+                "expression",
+                SyntaxShape::Any,
+                "the expression you want metadata for",
+            )
+            .category(Category::Core)
     }
 
     fn run(
         &self,
         engine_state: &EngineState,
-        _stack: &mut Stack,
+        stack: &mut Stack,
         call: &Call,
         input: PipelineData,
     ) -> Result<nu_protocol::PipelineData, nu_protocol::ShellError> {
         let head = call.head;
-        let ctrlc = engine_state.ctrlc.clone();
-
         let metadata = input.metadata();
 
-        input.map(
-            move |x| {
-                let span = x.span();
-
-                let mut cols = vec![];
-                let mut vals = vec![];
-
-                cols.push("span".into());
-                if let Ok(span) = span {
-                    vals.push(Value::Record {
-                        cols: vec!["start".into(), "end".into()],
-                        vals: vec![
-                            Value::Int {
-                                val: span.start as i64,
-                                span,
-                            },
-                            Value::Int {
-                                val: span.end as i64,
-                                span,
-                            },
-                        ],
-                        span: head,
-                    });
-                }
-
-                if let Some(x) = &metadata {
-                    match x {
-                        PipelineMetadata {
-                            data_source: DataSource::Ls,
-                        } => {
-                            cols.push("source".into());
-                            vals.push(Value::String {
-                                val: "ls".into(),
-                                span: head,
-                            })
-                        }
-                    }
-                }
-
-                Value::Record {
-                    cols,
-                    vals,
+        let mut cols = vec![];
+        let mut vals = vec![];
+
+        let value: Option<Value> = call.opt(engine_state, stack, 0)?;
+
+        // Without an argument, the input itself is described. Streams have no single span
+        // to report, so only their source is known
+        let value = match (value, input) {
+            (Some(value), _) => Some(value),
+            (None, PipelineData::Value(value, ..)) => Some(value),
+            (None, _) => None,
+        };
+
+        if let Some(Ok(span)) = value.as_ref().map(|value| value.span()) {
+            cols.push("span".into());
+            vals.push(Value::Record {
+                cols: vec!["start".into(), "end".into()],
+                vals: vec![
+                    Value::Int {
+                        val: span.start as i64,
+                        span,
+                    },
+                    Value::Int {
+                        val: span.end as i64,
+                        span,
+                    },
+                ],
+                span: head,
+            });
+
+            if let Some(contents) = origin(engine_state, span) {
+                cols.push("origin".into());
+                vals.push(Value::String {
+                    val: contents,
                     span: head,
-                }
-            },
-            ctrlc,
-        )
+                });
+            }
+        }
+
+        if let Some(PipelineMetadata { data_source }) = metadata {
+            let source = match data_source {
+                DataSource::Ls => "ls".into(),
+                DataSource::Calendar { .. } => "cal".into(),
+                DataSource::FilePath(path) => path.to_string_lossy().to_string(),
+            };
+
+            cols.push("source".into());
+            vals.push(Value::String {
+                val: source,
+                span: head,
+            })
+        }
+
+        Ok(Value::Record {
+            cols,
+            vals,
+            span: head,
+        }
+        .into_pipeline_data())
     }
 
     fn examples(&self) -> Vec<Example> {
-        vec![Example {
-            description: "Get the metadata of a value",
-            example: "3 | metadata",
-            result: None,
-        }]
+        vec![
+            Example {
+                description: "Get the metadata of a variable",
+                example: "let a = 42; metadata $a",
+                result: None,
+            },
+            Example {
+                description: "Get the metadata of the input",
+                example: "ls | metadata",
+                result: None,
+            },
+        ]
     }
 }
 
+/// The source code the span points to, if the value was written out in the source
+fn origin(engine_state: &EngineState, span: Span) -> Option<String> {
+    engine_state
+        .find_span_contents(&span)
+        .map(|contents| String::from_utf8_lossy(contents).to_string())
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -1,4 +1,7 @@
 mod alias;
+mod ast;
+mod break_;
+mod continue_;
 mod debug;
 mod def;
 mod describe;
@@ -7,6 +10,7 @@ mod echo;
 mod export;
 mod export_def;
 mod export_env;
+mod extern_;
 mod for_;
 mod help;
 mod hide;
@@ -17,10 +21,15 @@ mod let_;
 mod metadata;
 mod module;
 mod source;
+mod tutor;
 mod use_;
 mod version;
+mod while_;
 
 pub use alias::Alias;
+pub use ast::Ast;
+pub use break_::Break;
+pub use continue_::Continue;
 pub use debug::Debug;
 pub use def::Def;
 pub use describe::Describe;
@@ -29,6 +38,7 @@ pub use echo::Echo;
 pub use export::ExportCommand;
 pub use export_def::ExportDef;
 pub use export_env::ExportEnv;
+pub use extern_::Extern;
 pub use for_::For;
 pub use help::Help;
 pub use hide::Hide;
@@ -39,8 +49,10 @@ pub use let_::Let;
 pub use metadata::Metadata;
 pub use module::Module;
 pub use source::Source;
+pub use tutor::Tutor;
 pub use use_::Use;
 pub use version::Version;
+pub use while_::While;
 #[cfg(feature = "plugin")]
 mod register;
 
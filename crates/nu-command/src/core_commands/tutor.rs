@@ -0,0 +1,322 @@
+use crossterm::tty::IsTty;
+use nu_color_config::get_shape_color;
+use nu_engine::CallExt;
+use nu_parser::{flatten_block, parse};
+use nu_protocol::ast::Call;
+use nu_protocol::engine::{Command, EngineState, Stack, StateWorkingSet};
+use nu_protocol::{
+    Category, Config, Example, IntoPipelineData, PipelineData, ShellError, Signature, Span,
+    Spanned, SyntaxShape, Value,
+};
+
+#[derive(Clone)]
+pub struct Tutor;
+
+impl Command for Tutor {
+    fn name(&self) -> &str {
+        "tutor"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("tutor")
+            .optional("topic", SyntaxShape::String, "the lesson to show")
+            .named(
+                "find",
+                SyntaxShape::String,
+                "list the lessons that mention this text",
+                Some('f'),
+            )
+            .category(Category::Core)
+    }
+
+    fn usage(&self) -> &str {
+        "Run the tutorial, one short lesson at a time."
+    }
+
+    fn extra_usage(&self) -> &str {
+        r#"Without a topic, tutor lists the lessons. The code samples in a lesson are highlighted the
+way the line editor would highlight them, when output goes to a terminal and
+$config.use_ansi_coloring is on."#
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        _input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let head = call.head;
+        let topic: Option<Spanned<String>> = call.opt(engine_state, stack, 0)?;
+        let find: Option<String> = call.get_flag(engine_state, stack, "find")?;
+
+        if let Some(topic) = topic {
+            let lesson = find_lesson(&topic.item).ok_or_else(|| {
+                ShellError::SpannedLabeledError(
+                    "No such lesson".into(),
+                    format!(
+                        "the lessons are: {}",
+                        LESSONS
+                            .iter()
+                            .map(|lesson| lesson.topic)
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    ),
+                    topic.span,
+                )
+            })?;
+
+            let config = stack.get_config().unwrap_or_default();
+            let color = config.use_ansi_coloring && std::io::stdout().is_tty();
+
+            return Ok(
+                Value::string(lesson.render(engine_state, &config, color), head)
+                    .into_pipeline_data(),
+            );
+        }
+
+        let find = find.map(|text| text.to_lowercase());
+        let lessons = LESSONS
+            .iter()
+            .filter(|lesson| match &find {
+                Some(text) => lesson.mentions(text),
+                None => true,
+            })
+            .map(|lesson| Value::Record {
+                cols: vec!["topic".into(), "summary".into()],
+                vals: vec![
+                    Value::string(lesson.topic, head),
+                    Value::string(lesson.summary, head),
+                ],
+                span: head,
+            })
+            .collect();
+
+        Ok(Value::List {
+            vals: lessons,
+            span: head,
+        }
+        .into_pipeline_data())
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![
+            Example {
+                description: "List the lessons",
+                example: "tutor",
+                result: None,
+            },
+            Example {
+                description: "Show the lesson on pipelines",
+                example: "tutor pipelines",
+                result: None,
+            },
+            Example {
+                description: "Find the lessons that talk about blocks",
+                example: "tutor --find block",
+                result: Some(Value::List {
+                    vals: ["filters", "commands"]
+                        .iter()
+                        .filter_map(|topic| find_lesson(topic))
+                        .map(|lesson| Value::Record {
+                            cols: vec!["topic".into(), "summary".into()],
+                            vals: vec![
+                                Value::test_string(lesson.topic),
+                                Value::test_string(lesson.summary),
+                            ],
+                            span: Span::test_data(),
+                        })
+                        .collect(),
+                    span: Span::test_data(),
+                }),
+            },
+        ]
+    }
+}
+
+/// A lesson of the tutorial. In the body, lines starting with "> " are code samples.
+///
+/// To add a lesson, add it to `LESSONS`; the order there is the order `tutor` lists them in.
+struct Lesson {
+    topic: &'static str,
+    summary: &'static str,
+    body: &'static str,
+    /// Topics of lessons to read next
+    see_also: &'static [&'static str],
+}
+
+const LESSONS: &[Lesson] = &[
+    Lesson {
+        topic: "pipelines",
+        summary: "Connect commands so the output of one is the input of the next",
+        body: r#"Commands are joined with '|'. Each command gets the output of the one before it:
+> ls | sort-by size | reverse
+
+The data that flows through is structured: ls gives a table, not text, so later commands
+can pick out columns by name:
+> ls | get name
+
+A pipeline can start from a value, too:
+> [3 1 2] | sort | first
+
+Commands read their input as it comes, so a pipeline over a long listing starts producing
+output right away."#,
+        see_also: &["variables", "filters"],
+    },
+    Lesson {
+        topic: "variables",
+        summary: "Name a value with let and use it with $",
+        body: r#"let gives a value a name. The name is used with a '$' in front of it:
+> let answer = 42
+> $answer + 1
+
+A variable can hold anything a pipeline gives out. Run the pipeline in parentheses to
+keep its output:
+> let files = (ls | length)
+
+Variables can't be changed once set; use let again to shadow one. Environment variables
+live in $env and are set with let-env:
+> let-env GREETING = "hello"
+> $env.GREETING"#,
+        see_also: &["pipelines", "commands"],
+    },
+    Lesson {
+        topic: "filters",
+        summary: "Keep, drop and reshape the rows of a table",
+        body: r#"Filters are commands that take a table and give back a smaller or different one.
+where keeps the rows matching a condition:
+> ls | where size > 10kb
+
+select keeps columns, and reject drops them:
+> ls | select name size
+> ls | reject modified
+
+each runs a block on every row, with the row as its parameter:
+> [1 2 3] | each {|it| $it * 2 }
+
+first, last, skip and every pick rows by position:
+> ls | every 2"#,
+        see_also: &["pipelines", "commands"],
+    },
+    Lesson {
+        topic: "commands",
+        summary: "Define your own commands with def",
+        body: r#"def defines a command with a name, a list of parameters and a block to run:
+> def greet [name: string] { $"hello ($name)" }
+> greet world
+
+Parameters can have types, and switches are declared with '--':
+> def add [x: int, y: int, --double] { if $double { ($x + $y) * 2 } else { $x + $y } }
+> add 1 2 --double
+
+A custom command takes input from the pipeline as $in, so it can be used like any other:
+> def count-rs [] { $in | where name =~ '\.rs$' | length }
+> ls | count-rs
+
+Put your commands in the config file to have them in every session."#,
+        see_also: &["variables", "filters"],
+    },
+];
+
+fn find_lesson(topic: &str) -> Option<&'static Lesson> {
+    LESSONS.iter().find(|lesson| lesson.topic == topic)
+}
+
+impl Lesson {
+    fn mentions(&self, text: &str) -> bool {
+        self.topic.contains(text)
+            || self.summary.to_lowercase().contains(text)
+            || self.body.to_lowercase().contains(text)
+    }
+
+    fn render(&self, engine_state: &EngineState, config: &Config, color: bool) -> String {
+        let mut output = format!("{}\n\n", self.summary);
+
+        for line in self.body.lines() {
+            match line.strip_prefix("> ") {
+                Some(code) if color => {
+                    output.push_str(&format!("  > {}\n", highlight(engine_state, config, code)))
+                }
+                Some(code) => output.push_str(&format!("  > {}\n", code)),
+                None => {
+                    output.push_str(line);
+                    output.push('\n');
+                }
+            }
+        }
+
+        output.push_str(&format!(
+            "\nNext: {}\n",
+            self.see_also
+                .iter()
+                .map(|topic| format!("tutor '{}'", topic))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ));
+
+        output
+    }
+}
+
+/// Color a code sample by the shapes the parser finds in it, like the line editor does. The
+/// sample is parsed in a working set that is thrown away, so it can't define anything.
+fn highlight(engine_state: &EngineState, config: &Config, code: &str) -> String {
+    let mut working_set = StateWorkingSet::new(engine_state);
+    let offset = working_set.next_span_start();
+    let (block, _) = parse(&mut working_set, None, code.as_bytes(), false);
+
+    let mut output = String::new();
+    let mut last_seen = offset;
+    for (span, shape) in flatten_block(&working_set, &block) {
+        if span.end <= last_seen {
+            continue;
+        }
+        if span.start > last_seen {
+            output.push_str(&code[(last_seen - offset)..(span.start - offset)]);
+        }
+        let token = &code[(span.start - offset)..(span.end - offset)];
+        output.push_str(
+            &get_shape_color(shape.to_string(), config)
+                .paint(token)
+                .to_string(),
+        );
+        last_seen = span.end;
+    }
+    output.push_str(&code[(last_seen - offset)..]);
+
+    output
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_examples() {
+        use crate::test_examples;
+
+        test_examples(Tutor {})
+    }
+
+    #[test]
+    fn every_topic_resolves() {
+        for lesson in LESSONS {
+            let found = find_lesson(lesson.topic).map(|found| found.body);
+            assert_eq!(
+                found,
+                Some(lesson.body),
+                "topic {} is listed twice",
+                lesson.topic
+            );
+
+            for topic in lesson.see_also {
+                assert!(
+                    find_lesson(topic).is_some(),
+                    "lesson {} points to missing topic {}",
+                    lesson.topic,
+                    topic
+                );
+            }
+        }
+    }
+}
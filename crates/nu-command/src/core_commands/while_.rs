@@ -0,0 +1,109 @@
+use nu_engine::{eval_block, eval_expression, CallExt};
+use nu_protocol::ast::Call;
+use nu_protocol::engine::{CaptureBlock, Command, EngineState, LoopControl, LoopSignal, Stack};
+use nu_protocol::{
+    Category, Example, IntoInterruptiblePipelineData, PipelineData, ShellError, Signature,
+    SyntaxShape, Value,
+};
+
+#[derive(Clone)]
+pub struct While;
+
+impl Command for While {
+    fn name(&self) -> &str {
+        "while"
+    }
+
+    fn usage(&self) -> &str {
+        "Run a block as long as a condition holds"
+    }
+
+    fn extra_usage(&self) -> &str {
+        "Environment variables set with let-env in the block are seen by the condition and the next iteration, but don't outlive the loop."
+    }
+
+    fn signature(&self) -> nu_protocol::Signature {
+        Signature::build("while")
+            .required("cond", SyntaxShape::Expression, "condition to check")
+            .required(
+                "block",
+                SyntaxShape::Block(Some(vec![])),
+                "the block to run",
+            )
+            .category(Category::Core)
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        _input: PipelineData,
+    ) -> Result<nu_protocol::PipelineData, nu_protocol::ShellError> {
+        let head = call.head;
+        let cond = call.positional[0].clone();
+        let capture_block: CaptureBlock = call.req(engine_state, stack, 1)?;
+
+        let ctrlc = engine_state.ctrlc.clone();
+        let engine_state = engine_state.clone();
+        let block = engine_state.get_block(capture_block.block_id).clone();
+
+        // The condition is checked against a copy of the caller's stack, which picks up the
+        // environment changes made by each iteration
+        let mut loop_stack = stack.snapshot();
+        let signal = LoopSignal::default();
+        let mut done = false;
+
+        Ok(std::iter::from_fn(move || {
+            while !done {
+                match eval_expression(&engine_state, &mut loop_stack, &cond) {
+                    Ok(Value::Bool { val: true, .. }) => {}
+                    Ok(Value::Bool { val: false, .. }) => return None,
+                    Ok(x) => {
+                        done = true;
+                        return Some(Value::Error {
+                            error: ShellError::CantConvert(
+                                "bool".into(),
+                                x.get_type().to_string(),
+                                cond.span,
+                            ),
+                        });
+                    }
+                    Err(error) => {
+                        done = true;
+                        return Some(Value::Error { error });
+                    }
+                }
+
+                let mut stack = loop_stack.captures_to_stack(&capture_block.captures);
+                stack.loop_signal = Some(signal.clone());
+                let result = eval_block(&engine_state, &mut stack, &block, PipelineData::new(head))
+                    .map(|pipeline_data| pipeline_data.into_value(head));
+
+                if let Some(env_vars) = stack.env_vars.pop() {
+                    for (name, value) in env_vars {
+                        loop_stack.add_env_var(name, value);
+                    }
+                }
+
+                match (result, signal.take()) {
+                    (Err(error), _) => return Some(Value::Error { error }),
+                    (Ok(_), Some(LoopControl::Continue)) => {}
+                    (Ok(_), Some(LoopControl::Break)) => done = true,
+                    (Ok(value), None) => return Some(value),
+                }
+            }
+
+            None
+        })
+        .into_pipeline_data(ctrlc))
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![Example {
+            description: "Count up to three",
+            example: "let-env N = 0; while $env.N < 3 { let-env N = ($env.N + 1); $env.N }",
+            result: None,
+        }]
+    }
+}
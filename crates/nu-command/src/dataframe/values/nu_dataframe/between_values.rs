@@ -241,14 +241,14 @@ pub(super) fn compute_series_single_value(
         Operator::Divide => match &right {
             Value::Int { val, span } => {
                 if *val == 0 {
-                    Err(ShellError::DivisionByZero(*span))
+                    Err(ShellError::DivisionByZero(operator.span, *span))
                 } else {
                     compute_series_i64(&lhs, *val, <ChunkedArray<Int64Type>>::div, lhs_span)
                 }
             }
             Value::Float { val, span } => {
                 if val.is_zero() {
-                    Err(ShellError::DivisionByZero(*span))
+                    Err(ShellError::DivisionByZero(operator.span, *span))
                 } else {
                     compute_series_decimal(&lhs, *val, <ChunkedArray<Float64Type>>::div, lhs_span)
                 }
@@ -341,7 +341,7 @@ pub(super) fn compute_series_single_value(
                 rhs_span: right.span()?,
             }),
         },
-        Operator::Contains => match &right {
+        Operator::RegexMatch => match &right {
             Value::String { val, .. } => contains_series_pat(&lhs, val, lhs_span),
             _ => Err(ShellError::OperatorMismatch {
                 op_span: operator.span,
@@ -26,6 +26,9 @@ pub fn create_default_context(cwd: impl AsRef<Path>) -> EngineState {
         // Core
         bind_command! {
             Alias,
+            Ast,
+            Break,
+            Continue,
             Debug,
             Def,
             Describe,
@@ -34,6 +37,7 @@ pub fn create_default_context(cwd: impl AsRef<Path>) -> EngineState {
             ExportCommand,
             ExportDef,
             ExportEnv,
+            Extern,
             For,
             Help,
             Hide,
@@ -44,8 +48,10 @@ pub fn create_default_context(cwd: impl AsRef<Path>) -> EngineState {
             Metadata,
             Module,
             Source,
+            Tutor,
             Use,
             Version,
+            While,
         };
 
         // Filters
@@ -58,15 +64,20 @@ pub fn create_default_context(cwd: impl AsRef<Path>) -> EngineState {
             Compact,
             Drop,
             DropColumn,
+            DropColumns,
             DropNth,
             Each,
             Empty,
+            Enumerate,
             Every,
             First,
             Flatten,
             Get,
             GroupBy,
+            Histogram,
+            Insert,
             Keep,
+            KeepColumns,
             Merge,
             KeepUntil,
             KeepWhile,
@@ -79,7 +90,11 @@ pub fn create_default_context(cwd: impl AsRef<Path>) -> EngineState {
             Range,
             Reduce,
             Reject,
+            Rename,
             Reverse,
+            Roll,
+            RollLeft,
+            RollRight,
             Select,
             Shuffle,
             Skip,
@@ -125,6 +140,8 @@ pub fn create_default_context(cwd: impl AsRef<Path>) -> EngineState {
             Char,
             Decode,
             Format,
+            FormatDuration,
+            FormatFilesize,
             Parse,
             Size,
             Split,
@@ -144,6 +161,7 @@ pub fn create_default_context(cwd: impl AsRef<Path>) -> EngineState {
             StrLength,
             StrLpad,
             StrPascalCase,
+            StrReplace,
             StrReverse,
             StrRpad,
             StrScreamingSnakeCase,
@@ -173,6 +191,9 @@ pub fn create_default_context(cwd: impl AsRef<Path>) -> EngineState {
             AnsiGradient,
             AnsiStrip,
             Clear,
+            Keybindings,
+            KeybindingsDefault,
+            KeybindingsList,
             Input,
             Kill,
             Sleep,
@@ -223,6 +244,7 @@ pub fn create_default_context(cwd: impl AsRef<Path>) -> EngineState {
             ToHtml,
             ToJson,
             ToMd,
+            ToText,
             ToToml,
             ToTsv,
             ToCsv,
@@ -249,6 +271,7 @@ pub fn create_default_context(cwd: impl AsRef<Path>) -> EngineState {
             IntoBinary,
             IntoDatetime,
             IntoDecimal,
+            IntoFloat,
             IntoFilesize,
             IntoInt,
             IntoString,
@@ -318,6 +341,12 @@ pub fn create_default_context(cwd: impl AsRef<Path>) -> EngineState {
             Base64,
         };
 
+        // Bits
+        bind_command! {
+            Bits,
+            BitsNot,
+        };
+
         #[cfg(feature = "plugin")]
         bind_command!(Register);
 
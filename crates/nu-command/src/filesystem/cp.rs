@@ -1,12 +1,20 @@
 use std::path::PathBuf;
+use std::sync::atomic::AtomicBool;
+use std::sync::Arc;
 
-use super::util::get_interactive_confirmation;
+use super::util::{
+    copy_file, expand_glob, get_interactive_confirmation, is_interrupted, transfer_result,
+    FileCopy, LARGE_FILE_SIZE,
+};
 use nu_engine::env::current_dir;
 use nu_engine::CallExt;
 use nu_path::canonicalize_with;
 use nu_protocol::ast::Call;
 use nu_protocol::engine::{Command, EngineState, Stack};
-use nu_protocol::{Category, PipelineData, ShellError, Signature, SyntaxShape};
+use nu_protocol::{
+    Category, IntoInterruptiblePipelineData, PipelineData, ShellError, Signature, Span, Spanned,
+    SyntaxShape, Value,
+};
 
 use crate::filesystem::util::FileStructure;
 
@@ -34,6 +42,16 @@ impl Command for Cp {
             )
             .switch("force", "suppress error when no file", Some('f'))
             .switch("interactive", "ask user to confirm action", Some('i'))
+            .switch(
+                "verbose",
+                "report a table of the copied files and show progress for large files",
+                Some('v'),
+            )
+            .switch(
+                "preserve",
+                "keep the access and modification times of the copied files",
+                Some('p'),
+            )
             .category(Category::FileSystem)
     }
 
@@ -44,17 +62,17 @@ impl Command for Cp {
         call: &Call,
         _input: PipelineData,
     ) -> Result<PipelineData, ShellError> {
-        let source: String = call.req(engine_state, stack, 0)?;
+        let source: Spanned<String> = call.req(engine_state, stack, 0)?;
         let destination: String = call.req(engine_state, stack, 1)?;
         let interactive = call.has_flag("interactive");
         let force = call.has_flag("force");
+        let verbose = call.has_flag("verbose");
+        let preserve = call.has_flag("preserve");
 
         let path = current_dir(engine_state, stack)?;
-        let source = path.join(source.as_str());
         let destination = path.join(destination.as_str());
 
-        let mut sources =
-            glob::glob(&source.to_string_lossy()).map_or_else(|_| Vec::new(), Iterator::collect);
+        let mut sources = expand_glob(&source, &path)?;
         if sources.is_empty() {
             return Err(ShellError::FileNotFound(call.positional[0].span));
         }
@@ -68,7 +86,7 @@ impl Command for Cp {
             });
         }
 
-        let any_source_is_dir = sources.iter().any(|f| matches!(f, Ok(f) if f.is_dir()));
+        let any_source_is_dir = sources.iter().any(|f| f.is_dir());
         let recursive: bool = call.has_flag("recursive");
         if any_source_is_dir && !recursive {
             return Err(ShellError::MoveNotPossibleSingle(
@@ -82,13 +100,7 @@ impl Command for Cp {
             for (index, file) in sources.iter().enumerate() {
                 let prompt = format!(
                     "Are you shure that you want to copy {} to {}?",
-                    file.as_ref()
-                        .map_err(|err| ShellError::SpannedLabeledError(
-                            "Reference error".into(),
-                            err.to_string(),
-                            call.head
-                        ))?
-                        .file_name()
+                    file.file_name()
                         .ok_or_else(|| ShellError::SpannedLabeledError(
                             "File name error".into(),
                             "Unable to get file name".into(),
@@ -133,12 +145,16 @@ impl Command for Cp {
             }
         }
 
-        for entry in sources.into_iter().flatten() {
+        let span = call.head;
+        let ctrlc = engine_state.ctrlc.clone();
+        let mut copies = vec![];
+
+        for entry in sources {
             let mut sources = FileStructure::new();
             sources.walk_decorate(&entry, engine_state, stack)?;
 
-            if entry.is_file() {
-                let sources = sources.paths_applying_with(|(source_file, _depth_level)| {
+            let sources = if entry.is_file() {
+                sources.paths_applying_with(|(source_file, _depth_level)| {
                     if destination.is_dir() {
                         let mut dest = canonicalize_with(&destination, &path)?;
                         if let Some(name) = entry.file_name() {
@@ -148,22 +164,7 @@ impl Command for Cp {
                     } else {
                         Ok((source_file, destination.clone()))
                     }
-                })?;
-
-                for (src, dst) in sources {
-                    if src.is_file() {
-                        std::fs::copy(&src, dst).map_err(|e| {
-                            ShellError::MoveNotPossibleSingle(
-                                format!(
-                                    "failed to move containing file \"{}\": {}",
-                                    src.to_string_lossy(),
-                                    e
-                                ),
-                                call.positional[0].span,
-                            )
-                        })?;
-                    }
-                }
+                })?
             } else if entry.is_dir() {
                 let destination = if !destination.exists() {
                     destination.clone()
@@ -186,7 +187,7 @@ impl Command for Cp {
                     )
                 })?;
 
-                let sources = sources.paths_applying_with(|(source_file, depth_level)| {
+                sources.paths_applying_with(|(source_file, depth_level)| {
                     let mut dest = destination.clone();
                     let path = canonicalize_with(&source_file, &path)?;
                     let components = path
@@ -197,34 +198,60 @@ impl Command for Cp {
 
                     components.for_each(|fragment| dest.push(fragment));
                     Ok((PathBuf::from(&source_file), dest))
-                })?;
+                })?
+            } else {
+                vec![]
+            };
 
-                for (src, dst) in sources {
-                    if src.is_dir() && !dst.exists() {
-                        std::fs::create_dir_all(&dst).map_err(|e| {
-                            ShellError::MoveNotPossibleSingle(
-                                format!(
-                                    "failed to create containing directory \"{}\": {}",
-                                    dst.to_string_lossy(),
-                                    e
-                                ),
-                                call.positional[1].span,
-                            )
-                        })?;
-                    }
+            for (src, dst) in sources {
+                if src.is_dir() && !dst.exists() {
+                    std::fs::create_dir_all(&dst).map_err(|e| {
+                        ShellError::MoveNotPossibleSingle(
+                            format!(
+                                "failed to create containing directory \"{}\": {}",
+                                dst.to_string_lossy(),
+                                e
+                            ),
+                            call.positional[1].span,
+                        )
+                    })?;
+                }
 
-                    if src.is_file() {
-                        std::fs::copy(&src, &dst).map_err(|e| {
-                            ShellError::MoveNotPossibleSingle(
-                                format!(
-                                    "failed to move containing file \"{}\": {}",
-                                    src.to_string_lossy(),
-                                    e
-                                ),
-                                call.positional[0].span,
-                            )
-                        })?;
-                    }
+                if src.is_file() {
+                    copies.push((src, dst));
+                }
+            }
+        }
+
+        if verbose {
+            let rows = CopyRows {
+                pending: copies.into_iter(),
+                current: None,
+                reported_tenth: 0,
+                preserve,
+                ctrlc,
+                span,
+                done: false,
+            };
+
+            // The rows check ctrl-c themselves, to report the copy that was interrupted
+            return Ok(rows.into_pipeline_data(None));
+        }
+
+        for (src, dst) in copies {
+            match copy_file(&src, &dst, &ctrlc, preserve) {
+                Ok(Some(_)) => {}
+                // The partial file is already gone, don't start on anything else
+                Ok(None) => break,
+                Err(e) => {
+                    return Err(ShellError::MoveNotPossibleSingle(
+                        format!(
+                            "failed to move containing file \"{}\": {}",
+                            src.to_string_lossy(),
+                            e
+                        ),
+                        call.positional[0].span,
+                    ))
                 }
             }
         }
@@ -232,3 +259,79 @@ impl Command for Cp {
         Ok(PipelineData::new(call.head))
     }
 }
+
+/// The rows of `cp --verbose`. Files are copied as the rows are read, so the table shows each
+/// file as soon as it's done. Large files add a row for every tenth of them that is copied.
+struct CopyRows {
+    pending: std::vec::IntoIter<(PathBuf, PathBuf)>,
+    current: Option<(PathBuf, PathBuf, FileCopy)>,
+    reported_tenth: u64,
+    preserve: bool,
+    ctrlc: Option<Arc<AtomicBool>>,
+    span: Span,
+    done: bool,
+}
+
+impl Iterator for CopyRows {
+    type Item = Value;
+
+    fn next(&mut self) -> Option<Value> {
+        if self.done {
+            return None;
+        }
+
+        loop {
+            let (src, dst, mut copy) = match self.current.take() {
+                Some(current) => current,
+                None => {
+                    let (src, dst) = self.pending.next()?;
+                    self.reported_tenth = 0;
+
+                    match FileCopy::new(&src, &dst) {
+                        Ok(copy) => (src, dst, copy),
+                        Err(e) => {
+                            return Some(transfer_result(&src, &dst, &e.to_string(), self.span))
+                        }
+                    }
+                }
+            };
+
+            // Dropping the copy removes what was written of it
+            if is_interrupted(&self.ctrlc) {
+                self.done = true;
+                return Some(transfer_result(&src, &dst, "interrupted", self.span));
+            }
+
+            match copy.step() {
+                Ok(true) => {
+                    let tenth = copy.copied().saturating_mul(10) / copy.total().max(1);
+                    let report = copy.total() >= LARGE_FILE_SIZE
+                        && tenth > self.reported_tenth
+                        && tenth < 10;
+
+                    let row = if report {
+                        self.reported_tenth = tenth;
+                        let status = format!("copying {}%", tenth * 10);
+                        Some(transfer_result(&src, &dst, &status, self.span))
+                    } else {
+                        None
+                    };
+
+                    self.current = Some((src, dst, copy));
+
+                    if row.is_some() {
+                        return row;
+                    }
+                }
+                Ok(false) => {
+                    let status = match copy.finish(self.preserve) {
+                        Ok(_) => "copied".to_string(),
+                        Err(e) => e.to_string(),
+                    };
+                    return Some(transfer_result(&src, &dst, &status, self.span));
+                }
+                Err(e) => return Some(transfer_result(&src, &dst, &e.to_string(), self.span)),
+            }
+        }
+    }
+}
@@ -0,0 +1,145 @@
+use git2::{Repository, Status, StatusOptions};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// Git statuses for the entries of a listing. The repository is asked once per directory, so a
+/// listing reads the index as many times as it has directories, not entries.
+#[derive(Default)]
+pub(crate) struct GitStatuses {
+    dirs: HashMap<PathBuf, Option<DirStatuses>>,
+}
+
+impl GitStatuses {
+    /// The status of `path`, or None when it isn't inside a git work tree
+    pub(crate) fn status(&mut self, path: &Path) -> Option<&'static str> {
+        let dir = path.parent()?;
+        let name = path.file_name()?;
+
+        let statuses = self
+            .dirs
+            .entry(dir.to_path_buf())
+            .or_insert_with(|| DirStatuses::read(dir))
+            .as_ref()?;
+
+        Some(statuses.status_of(name.as_ref()).name())
+    }
+}
+
+/// What git reports below one directory, with paths relative to the work tree
+struct DirStatuses {
+    prefix: PathBuf,
+    ignored: bool,
+    paths: Vec<(PathBuf, GitStatus)>,
+}
+
+impl DirStatuses {
+    fn read(dir: &Path) -> Option<DirStatuses> {
+        let repo = Repository::discover(dir).ok()?;
+        let workdir = repo.workdir()?.canonicalize().ok()?;
+        let prefix = dir
+            .canonicalize()
+            .ok()?
+            .strip_prefix(&workdir)
+            .ok()?
+            .to_path_buf();
+
+        // Git doesn't look inside an ignored directory, so neither does the pathspec below
+        let ignored = !prefix.as_os_str().is_empty() && repo.is_path_ignored(&prefix).ok()?;
+
+        let mut opts = StatusOptions::new();
+        opts.include_untracked(true)
+            .include_ignored(true)
+            .recurse_untracked_dirs(true);
+        if !prefix.as_os_str().is_empty() {
+            opts.pathspec(prefix.as_path());
+        }
+
+        let paths = repo
+            .statuses(Some(&mut opts))
+            .ok()?
+            .iter()
+            .filter_map(|entry| {
+                let path = PathBuf::from(entry.path()?);
+                Some((path, GitStatus::from_flags(entry.status())))
+            })
+            .collect();
+
+        Some(DirStatuses {
+            prefix,
+            ignored,
+            paths,
+        })
+    }
+
+    /// A file has its own status. A directory takes the most pressing status of what's inside it,
+    /// leaving out ignored files, so a tracked directory holding build output is still clean.
+    fn status_of(&self, name: &Path) -> GitStatus {
+        if self.ignored {
+            return GitStatus::Ignored;
+        }
+
+        let path = self.prefix.join(name);
+        self.paths
+            .iter()
+            .filter_map(|(other, status)| {
+                if path.starts_with(other) {
+                    Some(*status)
+                } else if other.starts_with(&path) && *status != GitStatus::Ignored {
+                    Some(*status)
+                } else {
+                    None
+                }
+            })
+            .max()
+            .unwrap_or(GitStatus::Clean)
+    }
+}
+
+/// Ordered by how much the status asks for attention
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+enum GitStatus {
+    Clean,
+    Ignored,
+    Untracked,
+    Staged,
+    Modified,
+}
+
+impl GitStatus {
+    /// Changes not yet staged win over staged ones, since they're what `git add` would pick up
+    fn from_flags(status: Status) -> GitStatus {
+        if status.intersects(
+            Status::WT_MODIFIED
+                | Status::WT_DELETED
+                | Status::WT_TYPECHANGE
+                | Status::WT_RENAMED
+                | Status::CONFLICTED,
+        ) {
+            GitStatus::Modified
+        } else if status.intersects(
+            Status::INDEX_NEW
+                | Status::INDEX_MODIFIED
+                | Status::INDEX_DELETED
+                | Status::INDEX_RENAMED
+                | Status::INDEX_TYPECHANGE,
+        ) {
+            GitStatus::Staged
+        } else if status.contains(Status::WT_NEW) {
+            GitStatus::Untracked
+        } else if status.contains(Status::IGNORED) {
+            GitStatus::Ignored
+        } else {
+            GitStatus::Clean
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            GitStatus::Clean => "clean",
+            GitStatus::Ignored => "ignored",
+            GitStatus::Untracked => "untracked",
+            GitStatus::Staged => "staged",
+            GitStatus::Modified => "modified",
+        }
+    }
+}
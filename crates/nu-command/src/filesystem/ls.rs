@@ -1,19 +1,27 @@
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, Datelike, FixedOffset, Utc};
 use pathdiff::diff_paths;
 
+#[cfg(feature = "git-status")]
+use super::git_status::GitStatuses;
+use super::util::glob_prefix;
+use crate::{natural_cmp, value_to_json_value};
 use nu_engine::env::current_dir;
-use nu_engine::CallExt;
+use nu_engine::{observe_with_hook, CallExt};
 use nu_path::{canonicalize_with, expand_path_with};
 use nu_protocol::ast::Call;
 use nu_protocol::engine::{Command, EngineState, Stack};
 use nu_protocol::{
-    Category, DataSource, IntoInterruptiblePipelineData, PipelineData, PipelineMetadata,
-    ShellError, Signature, Span, Spanned, SyntaxShape, Value,
+    ByteStream, Category, Config, DataSource, Example, IntoInterruptiblePipelineData,
+    IntoPipelineData, PipelineData, PipelineMetadata, ShellError, Signature, Span, Spanned,
+    SyntaxShape, Type, Value,
 };
 
+use std::collections::HashMap;
 #[cfg(unix)]
 use std::os::unix::fs::PermissionsExt;
-use std::path::{Component, PathBuf};
+use std::path::PathBuf;
+use std::sync::{mpsc, Arc};
+use std::time::{Duration, Instant};
 
 #[derive(Clone)]
 pub struct Ls;
@@ -27,8 +35,42 @@ impl Command for Ls {
         "List the files in a directory."
     }
 
+    fn extra_usage(&self) -> &str {
+        r#"Sizes are the length in bytes by default (--apparent-size). With --disk-usage they are
+the space allocated on disk instead, which is smaller for sparse files and rounded up to whole
+blocks otherwise; outside of unix the two are the same. A directory's size is that of the
+directory entry itself, not a total of what it holds: a recursive total would need a --du
+option, which ls doesn't have yet.
+
+With --human-readable the size columns hold text, like "1.5 KiB", instead of file sizes.
+--binary and --decimal pick the units of that text, powers of 1024 or of 1000, in place of
+$config.filesize_metric; either one implies --human-readable. Sorting still goes by the sizes
+themselves.
+
+With --git-status each entry inside a git work tree gets a git column. A directory shows the
+most pressing status of what it holds, ignoring ignored files. Entries outside a work tree get no
+column, so outside a repository the flag changes nothing. It needs nu built with the git-status
+feature.
+
+With --timeout the listing stops reading metadata once it has spent that long reading it, so a
+stalled network mount can't freeze the shell. Entries whose metadata wasn't read in time are left
+out, or with --keep-unread listed with empty metadata; every entry then gets a timed_out column.
+Only the metadata reads count against the timeout: reading the directory itself can still block,
+and time spent by the rest of the pipeline doesn't count.
+
+With --check-links each entry gets a broken column: for a symlink, whether its target is
+missing, and empty for anything else, or when the target couldn't be checked. It costs one more
+stat per symlink, which counts against --timeout.
+
+With --jsonl the listing comes out as JSON lines, ready for 'save' or 'from json --objects'.
+
+A block in $config.hooks.ls is called with the command name and a { count } record once the
+listing has been read. It only observes: the listing is returned as it was, even if it fails."#
+    }
+
     fn signature(&self) -> nu_protocol::Signature {
         Signature::build("ls")
+            .input_output_type(Type::Nothing, Type::Table)
             .optional(
                 "pattern",
                 SyntaxShape::GlobPattern,
@@ -46,6 +88,133 @@ impl Command for Ls {
                 Some('s'),
             )
             .switch("full-paths", "display paths as absolute paths", Some('f'))
+            .switch(
+                "canonical",
+                "display paths as absolute paths with symlinks and '..' resolved",
+                Some('c'),
+            )
+            .named(
+                "sort",
+                SyntaxShape::String,
+                "sort entries by 'name', 'modified', 'size', 'extension' or 'version' (overrides $config.ls_newest_first)",
+                None,
+            )
+            .switch("reverse", "Reverse the sort order", Some('r'))
+            .switch(
+                "group-directories-first",
+                "List directories before other entries, keeping the sort order within each group",
+                None,
+            )
+            .switch(
+                "compact",
+                "Show directories holding nothing but one other directory as a single 'a/b/c' entry",
+                None,
+            )
+            .switch(
+                "no-symlinks",
+                "Leave symlinks out of the listing, whatever they point to",
+                None,
+            )
+            .named(
+                "min-links",
+                SyntaxShape::Int,
+                "Only list entries with at least this many hard links (unix only)",
+                None,
+            )
+            .named(
+                "max-links",
+                SyntaxShape::Int,
+                "Only list entries with at most this many hard links (unix only)",
+                None,
+            )
+            .switch(
+                "total-only",
+                "Return a single {count, total_size} record for the matched entries instead of the entries",
+                None,
+            )
+            .switch(
+                "count-only",
+                "Return only the number of matched entries, without reading their metadata",
+                None,
+            )
+            .named(
+                "offset",
+                SyntaxShape::Int,
+                "Skip this many entries, after sorting, before listing the rest",
+                None,
+            )
+            .switch(
+                "recency-buckets",
+                "Add a bucket column grouping entries by when they were modified: today, this week, this month or older",
+                None,
+            )
+            .switch(
+                "index",
+                "Add an index column with the position of each entry",
+                Some('i'),
+            )
+            .named(
+                "stat",
+                SyntaxShape::String,
+                "Return just this field of each entry: 'name', 'type', 'size', 'modified', 'created' or 'accessed'",
+                Some('q'),
+            )
+            .named(
+                "timeout",
+                SyntaxShape::Duration,
+                "Stop reading metadata after spending this long on it; entries not read by then are left out",
+                None,
+            )
+            .switch(
+                "keep-unread",
+                "With --timeout, list the entries left unread too, with empty metadata and timed_out set",
+                None,
+            )
+            .switch(
+                "git-status",
+                "Add a git column with each entry's status: modified, staged, untracked, ignored or clean",
+                None,
+            )
+            .switch(
+                "check-links",
+                "Add a broken column telling whether the target of each symlink is missing",
+                None,
+            )
+            .switch(
+                "jsonl",
+                "Stream each entry as a line of JSON, with the same columns",
+                None,
+            )
+            .switch(
+                "apparent-size",
+                "Report sizes as the length in bytes, which is the default",
+                None,
+            )
+            .switch(
+                "disk-usage",
+                "Report sizes as the space allocated on disk",
+                None,
+            )
+            .switch(
+                "all-sizes",
+                "Report both sizes, as apparent_size and disk_usage columns",
+                None,
+            )
+            .switch(
+                "human-readable",
+                "Show sizes as text with a unit, the way $config would display them",
+                None,
+            )
+            .switch(
+                "binary",
+                "Show sizes with binary units (KiB, MiB), whatever $config says",
+                None,
+            )
+            .switch(
+                "decimal",
+                "Show sizes with decimal units (KB, MB), whatever $config says",
+                None,
+            )
             // .switch(
             //     "du",
             //     "Display the apparent directory size in place of the directory metadata size",
@@ -61,10 +230,172 @@ impl Command for Ls {
         call: &Call,
         _input: PipelineData,
     ) -> Result<nu_protocol::PipelineData, nu_protocol::ShellError> {
+        let entries = self.list(engine_state, stack, call)?;
+        let entries = observe_with_hook(engine_state, stack, self.name(), entries, call.head);
+
+        if call.has_flag("jsonl") {
+            Ok(json_lines(entries, call.head))
+        } else {
+            Ok(entries)
+        }
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![
+            Example {
+                description: "List the files in the current directory",
+                example: "ls",
+                result: None,
+            },
+            Example {
+                description: "List the Rust files below the current directory",
+                example: "ls **/*.rs",
+                result: None,
+            },
+            Example {
+                description: "List everything, with the full metadata of each entry",
+                example: "ls -la",
+                result: None,
+            },
+            Example {
+                description: "Find the symlinks whose target is missing",
+                example: "ls --check-links | where broken == $true",
+                result: None,
+            },
+            Example {
+                description: "Stream the listing as JSON lines",
+                example: "ls --jsonl",
+                result: None,
+            },
+        ]
+    }
+}
+
+impl Ls {
+    fn list(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+    ) -> Result<PipelineData, ShellError> {
         let all = call.has_flag("all");
         let long = call.has_flag("long");
         let short_names = call.has_flag("short-names");
         let full_paths = call.has_flag("full-paths");
+        let canonical = call.has_flag("canonical");
+        let sort_flag = call
+            .get_flag::<Spanned<String>>(engine_state, stack, "sort")?
+            .map(LsSort::from_spanned)
+            .transpose()?;
+        let reverse = call.has_flag("reverse");
+        let group_directories = call.has_flag("group-directories-first");
+        let index = call.has_flag("index");
+        let recency_buckets = call.has_flag("recency-buckets");
+        let compact = call.has_flag("compact");
+        let no_symlinks = call.has_flag("no-symlinks");
+        let git_status = call.has_flag("git-status");
+        let check_links = call.has_flag("check-links");
+        #[cfg(not(feature = "git-status"))]
+        if git_status {
+            let span = call
+                .named
+                .iter()
+                .find(|(name, _)| name.item == "git-status")
+                .map_or(call.head, |(name, _)| name.span);
+            return Err(ShellError::FeatureNotEnabled(span));
+        }
+        let timeout = call.get_flag::<Spanned<i64>>(engine_state, stack, "timeout")?;
+        let keep_unread = call.has_flag("keep-unread");
+        if keep_unread && timeout.is_none() {
+            return Err(ShellError::SpannedLabeledError(
+                "Missing --timeout".into(),
+                "--keep-unread only applies with --timeout".into(),
+                call.head,
+            ));
+        }
+        let mut metadata_reader = MetadataReader::new(
+            Arc::new(FileSystem),
+            timeout
+                .map(|timeout| {
+                    u64::try_from(timeout.item)
+                        .map(Duration::from_nanos)
+                        .map_err(|_| {
+                            ShellError::UnsupportedInput(
+                                "timeout cannot be negative".into(),
+                                timeout.span,
+                            )
+                        })
+                })
+                .transpose()?,
+        );
+        let total_only = call.has_flag("total-only");
+        let count_only = call.has_flag("count-only");
+        if total_only && count_only {
+            return Err(ShellError::IncompatibleParametersSingle(
+                "Can't use \"--total-only\" with \"--count-only\"".to_string(),
+                call.head,
+            ));
+        }
+        let offset = call
+            .get_flag::<Spanned<i64>>(engine_state, stack, "offset")?
+            .map(|offset| {
+                usize::try_from(offset.item).map_err(|_| {
+                    ShellError::UnsupportedInput("offset cannot be negative".into(), offset.span)
+                })
+            })
+            .transpose()?;
+        let links = LinkFilter {
+            min: link_bound(call.get_flag(engine_state, stack, "min-links")?)?,
+            max: link_bound(call.get_flag(engine_state, stack, "max-links")?)?,
+        };
+        if cfg!(not(unix)) && links.is_active() {
+            return Err(ShellError::SpannedLabeledError(
+                "Link counts aren't available on this platform".into(),
+                "--min-links and --max-links only work on unix".into(),
+                call.head,
+            ));
+        }
+        let sizes = LsSizes::from_flags(
+            call.has_flag("apparent-size"),
+            call.has_flag("disk-usage"),
+            call.has_flag("all-sizes"),
+            call.head,
+        )?;
+        let stat = call
+            .get_flag::<Spanned<String>>(engine_state, stack, "stat")?
+            .map(LsStat::from_spanned)
+            .transpose()?;
+        if stat.is_some() {
+            let conflicting = [
+                ("total-only", total_only),
+                ("count-only", count_only),
+                ("index", index),
+                ("recency-buckets", recency_buckets),
+                ("git-status", git_status),
+                ("check-links", check_links),
+                ("all-sizes", sizes == LsSizes::All),
+            ];
+            if let Some((flag, _)) = conflicting.iter().find(|(_, set)| *set) {
+                return Err(ShellError::IncompatibleParametersSingle(
+                    format!("Can't use \"--stat\" with \"--{}\"", flag),
+                    call.head,
+                ));
+            }
+        }
+        let config = stack.get_config().unwrap_or_default();
+        let size_text = size_text_config(call, &config)?;
+        let ordering = sort_order(sort_flag, reverse, config.ls_newest_first);
+        // Sorting and grouping look at whole entries, so the field is picked out afterwards
+        let needs_records = ordering.is_some() || group_directories;
+        let long = long || stat.map_or(false, LsStat::needs_long);
+        // Entries that are written out as they're listed can go straight to JSON, the rest are
+        // made into records first
+        let json_entries = call.has_flag("jsonl")
+            && !needs_records
+            && stat.is_none()
+            && !index
+            && !recency_buckets
+            && size_text.is_none();
 
         let call_span = call.head;
         let cwd = current_dir(engine_state, stack)?;
@@ -82,16 +413,7 @@ impl Command for Ls {
             if path.to_string_lossy().contains('*') {
                 // Path is a glob pattern => do not check for existence
                 // Select the longest prefix until the first '*'
-                let mut p = PathBuf::new();
-                for c in path.components() {
-                    if let Component::Normal(os) = c {
-                        if os.to_string_lossy().contains('*') {
-                            break;
-                        }
-                    }
-                    p.push(c);
-                }
-                (Some(p), path)
+                (Some(glob_prefix(&path)), path)
             } else {
                 let path = if let Ok(p) = canonicalize_with(path, &cwd) {
                     p
@@ -124,6 +446,17 @@ impl Command for Ls {
                     }
 
                     if is_empty_dir(&path) {
+                        if total_only {
+                            let nothing = PipelineData::new(call_span);
+                            return Ok(total_summary(nothing, call_span).into_pipeline_data());
+                        }
+                        if count_only {
+                            return Ok(Value::Int {
+                                val: 0,
+                                span: call_span,
+                            }
+                            .into_pipeline_data());
+                        }
                         return Ok(PipelineData::new(call_span));
                     }
 
@@ -147,15 +480,26 @@ impl Command for Ls {
         })?;
 
         let hidden_dir_specified = is_hidden_dir(&pattern);
+        // A recursive glob lists the rest of a collapsed chain by itself
+        let recursive = pattern.contains("**");
         let mut hidden_dirs = vec![];
+        #[cfg(feature = "git-status")]
+        let mut git_statuses = git_status.then(GitStatuses::default);
+        let mut owner_names = OwnerNames::default();
 
-        Ok(glob
+        let entries = glob
             .into_iter()
             .filter_map(move |x| match x {
                 Ok(path) => {
-                    let metadata = match std::fs::symlink_metadata(&path) {
-                        Ok(metadata) => Some(metadata),
-                        Err(_) => None,
+                    // Counting only needs the metadata to leave out symlinks or filter on links
+                    let (metadata, timed_out) = if !count_only || no_symlinks || links.is_active() {
+                        match metadata_reader.read(&path) {
+                            MetadataRead::Read(metadata) => (metadata, false),
+                            MetadataRead::TimedOut if keep_unread => (None, true),
+                            MetadataRead::TimedOut => return None,
+                        }
+                    } else {
+                        (None, false)
                     };
                     if path_contains_hidden_folder(&path, &hidden_dirs) {
                         return None;
@@ -168,8 +512,44 @@ impl Command for Ls {
                         return None;
                     }
 
+                    if no_symlinks
+                        && metadata
+                            .as_ref()
+                            .map_or(false, |md| md.file_type().is_symlink())
+                    {
+                        return None;
+                    }
+
+                    if !links.matches(metadata.as_ref()) {
+                        return None;
+                    }
+
+                    let chain_end = if compact {
+                        single_dir_chain(&path)
+                    } else {
+                        path.clone()
+                    };
+                    if recursive && chain_end != path {
+                        return None;
+                    }
+
+                    if count_only {
+                        return Some(Value::Nothing { span: call_span });
+                    }
+
+                    // Only the size is needed for the summary, so no record is built
+                    if total_only {
+                        let size = metadata.map_or(0, |md| sizes.summary_size(&md));
+                        return Some(Value::Filesize {
+                            val: size as i64,
+                            span: call_span,
+                        });
+                    }
+
                     let display_name = if short_names {
                         path.file_name().map(|os| os.to_string_lossy().to_string())
+                    } else if canonical {
+                        Some(canonical_path(&path, &cwd).to_string_lossy().to_string())
                     } else if full_paths {
                         Some(path.to_string_lossy().to_string())
                     } else if let Some(prefix) = &prefix {
@@ -197,8 +577,111 @@ impl Command for Ls {
 
                     match display_name {
                         Ok(name) => {
-                            let entry =
-                                dir_entry_dict(&path, &name, metadata.as_ref(), call_span, long);
+                            // A collapsed chain shows the metadata of its last directory, and is
+                            // unread when that metadata is
+                            let (name, metadata, timed_out) = match chain_end.strip_prefix(&path) {
+                                Ok(rest) if !rest.as_os_str().is_empty() => {
+                                    let name =
+                                        Path::new(&name).join(rest).to_string_lossy().to_string();
+                                    match metadata_reader.read(&chain_end) {
+                                        MetadataRead::Read(metadata) => (name, metadata, timed_out),
+                                        MetadataRead::TimedOut if keep_unread => (name, None, true),
+                                        MetadataRead::TimedOut => return None,
+                                    }
+                                }
+                                _ => (name, metadata, timed_out),
+                            };
+
+                            if let (Some(stat), false) = (stat, needs_records) {
+                                return Some(stat.value(
+                                    &name,
+                                    metadata.as_ref(),
+                                    sizes,
+                                    call_span,
+                                ));
+                            }
+
+                            if json_entries {
+                                #[cfg(feature = "git-status")]
+                                let git = git_statuses
+                                    .as_mut()
+                                    .and_then(|statuses| statuses.status(&chain_end));
+                                #[cfg(not(feature = "git-status"))]
+                                let git: Option<&str> = None;
+
+                                let line = dir_entry_json(
+                                    &chain_end,
+                                    &name,
+                                    metadata.as_ref(),
+                                    long,
+                                    sizes,
+                                    &mut owner_names,
+                                )
+                                .and_then(|mut line| {
+                                    if let Some(status) = git {
+                                        line.field("git", Field::Text(status.into()))?;
+                                    }
+                                    if check_links {
+                                        line.field(
+                                            "broken",
+                                            broken_field(
+                                                &mut metadata_reader,
+                                                &chain_end,
+                                                metadata.as_ref(),
+                                            ),
+                                        )?;
+                                    }
+                                    if keep_unread {
+                                        line.field("timed_out", Field::Bool(timed_out))?;
+                                    }
+                                    Ok(line)
+                                });
+
+                                // Carried as bytes until `json_lines` writes them out
+                                return Some(match line {
+                                    Ok(line) => Value::Binary {
+                                        val: line.finish(),
+                                        span: call_span,
+                                    },
+                                    Err(error) => Value::Error { error },
+                                });
+                            }
+
+                            let entry = dir_entry_dict(
+                                &chain_end,
+                                &name,
+                                metadata.as_ref(),
+                                call_span,
+                                long,
+                                sizes,
+                                &mut owner_names,
+                            );
+                            #[cfg(feature = "git-status")]
+                            let entry = match (entry, git_statuses.as_mut()) {
+                                (Ok(value), Some(statuses)) => Ok(with_git_status(
+                                    value,
+                                    statuses.status(&chain_end),
+                                    call_span,
+                                )),
+                                (entry, _) => entry,
+                            };
+                            let entry = match entry {
+                                Ok(value) if check_links => Ok(with_broken(
+                                    value,
+                                    &mut metadata_reader,
+                                    &chain_end,
+                                    metadata.as_ref(),
+                                    call_span,
+                                )),
+                                entry => entry,
+                            };
+                            let entry = match entry {
+                                Ok(value) if keep_unread => {
+                                    Ok(with_timed_out(value, timed_out, call_span))
+                                }
+                                entry => entry,
+                            };
+
                             match entry {
                                 Ok(value) => Some(value),
                                 Err(err) => Some(Value::Error { error: err }),
@@ -214,267 +697,1483 @@ impl Command for Ls {
                     data_source: DataSource::Ls,
                 },
                 engine_state.ctrlc.clone(),
-            ))
-    }
-}
+            );
 
-fn permission_denied(dir: impl AsRef<Path>) -> bool {
-    match dir.as_ref().read_dir() {
-        Err(e) => matches!(e.kind(), std::io::ErrorKind::PermissionDenied),
-        Ok(_) => false,
-    }
-}
+        if total_only {
+            return Ok(total_summary(entries, call_span).into_pipeline_data());
+        }
 
-fn is_hidden_dir(dir: impl AsRef<Path>) -> bool {
-    #[cfg(windows)]
-    {
-        use std::os::windows::fs::MetadataExt;
+        // Sorting doesn't change how many there are, so only the offset matters
+        if count_only {
+            let count = entries.into_iter().count();
+            return Ok(Value::Int {
+                val: count.saturating_sub(offset.unwrap_or(0)) as i64,
+                span: call_span,
+            }
+            .into_pipeline_data());
+        }
 
-        if let Ok(metadata) = dir.as_ref().metadata() {
-            let attributes = metadata.file_attributes();
-            // https://docs.microsoft.com/en-us/windows/win32/fileio/file-attribute-constants
-            (attributes & 0x2) != 0
+        // The glob already yields entries by name, so they can be streamed as they come
+        let entries = if ordering.is_none() && !group_directories {
+            entries
         } else {
-            false
+            let mut entries: Vec<Value> = entries.into_iter().collect();
+            if let Some((sort, descending)) = ordering {
+                sort_entries(&mut entries, sort, descending);
+            }
+            if group_directories {
+                // A stable sort keeps the chosen order inside both groups
+                entries.sort_by_key(|entry| !is_dir_entry(entry));
+            }
+
+            entries.into_iter().into_pipeline_data_with_metadata(
+                PipelineMetadata {
+                    data_source: DataSource::Ls,
+                },
+                engine_state.ctrlc.clone(),
+            )
+        };
+
+        let entries = match stat {
+            Some(stat) if needs_records => entries.map(
+                move |entry| stat.pick(entry, call_span),
+                engine_state.ctrlc.clone(),
+            )?,
+            _ => entries,
+        };
+
+        // Skipping lazily keeps an unsorted listing streaming
+        let entries = match offset {
+            Some(offset) => entries
+                .into_iter()
+                .skip(offset)
+                .into_pipeline_data_with_metadata(
+                    PipelineMetadata {
+                        data_source: DataSource::Ls,
+                    },
+                    engine_state.ctrlc.clone(),
+                ),
+            None => entries,
+        };
+
+        if !index && !recency_buckets && size_text.is_none() {
+            return Ok(entries);
         }
-    }
 
-    #[cfg(not(windows))]
-    {
-        dir.as_ref()
-            .file_name()
-            .map(|name| name.to_string_lossy().starts_with('.'))
-            .unwrap_or(false)
+        let now = Utc::now();
+
+        // Numbered last, so the index is the position in the final output
+        let mut position = 0;
+        entries
+            .map(
+                move |entry| {
+                    let entry = if recency_buckets {
+                        with_bucket(entry, now, call_span)
+                    } else {
+                        entry
+                    };
+                    let entry = if index {
+                        with_index(entry, position, call_span)
+                    } else {
+                        entry
+                    };
+                    let entry = match &size_text {
+                        Some(config) => with_size_text(entry, config),
+                        None => entry,
+                    };
+                    position += 1;
+                    entry
+                },
+                engine_state.ctrlc.clone(),
+            )
+            .map(|entries| {
+                entries.set_metadata(Some(PipelineMetadata {
+                    data_source: DataSource::Ls,
+                }))
+            })
     }
 }
 
-fn is_empty_dir(dir: impl AsRef<Path>) -> bool {
-    match dir.as_ref().read_dir() {
-        Err(_) => true,
-        Ok(mut s) => s.next().is_none(),
+/// The config to turn sizes into text with, when --human-readable, --binary or --decimal asks for
+/// it. The unit family of --binary or --decimal replaces the one in $config.
+fn size_text_config(call: &Call, config: &Config) -> Result<Option<Config>, ShellError> {
+    let binary = call.has_flag("binary");
+    let decimal = call.has_flag("decimal");
+
+    if binary && decimal {
+        return Err(ShellError::IncompatibleParametersSingle(
+            "Can't use \"--binary\" with \"--decimal\"".to_string(),
+            call.head,
+        ));
+    }
+    if !binary && !decimal && !call.has_flag("human-readable") {
+        return Ok(None);
     }
-}
 
-fn path_contains_hidden_folder(path: &Path, folders: &[PathBuf]) -> bool {
-    let path_str = path.to_str().expect("failed to read path");
-    if folders
-        .iter()
-        .any(|p| path_str.starts_with(&p.to_str().expect("failed to read hidden paths")))
-    {
-        return true;
+    let mut config = config.clone();
+    if binary || decimal {
+        config.filesize_metric = decimal;
     }
-    false
+
+    Ok(Some(config))
 }
 
-#[cfg(unix)]
-use std::os::unix::fs::FileTypeExt;
-use std::path::Path;
+/// Replace the file sizes of an entry, or a size on its own, with their text
+fn with_size_text(entry: Value, config: &Config) -> Value {
+    match entry {
+        Value::Record { cols, vals, span } => {
+            let vals = cols
+                .iter()
+                .zip(vals)
+                .map(|(col, val)| match col.as_str() {
+                    "size" | "apparent_size" | "disk_usage" => with_size_text(val, config),
+                    _ => val,
+                })
+                .collect();
 
-pub fn get_file_type(md: &std::fs::Metadata) -> &str {
-    let ft = md.file_type();
-    let mut file_type = "unknown";
-    if ft.is_dir() {
-        file_type = "dir";
-    } else if ft.is_file() {
-        file_type = "file";
-    } else if ft.is_symlink() {
-        file_type = "symlink";
-    } else {
-        #[cfg(unix)]
-        {
-            if ft.is_block_device() {
-                file_type = "block device";
-            } else if ft.is_char_device() {
-                file_type = "char device";
-            } else if ft.is_fifo() {
-                file_type = "pipe";
-            } else if ft.is_socket() {
-                file_type = "socket";
-            }
+            Value::Record { cols, vals, span }
         }
+        Value::Filesize { span, .. } => Value::String {
+            val: entry.into_string("", config),
+            span,
+        },
+        entry => entry,
     }
-    file_type
 }
 
-#[allow(clippy::too_many_arguments)]
-pub(crate) fn dir_entry_dict(
-    filename: &std::path::Path, // absolute path
-    display_name: &str,         // gile name to be displayed
-    metadata: Option<&std::fs::Metadata>,
-    span: Span,
-    long: bool,
-) -> Result<Value, ShellError> {
-    let mut cols = vec![];
-    let mut vals = vec![];
+/// Serialize each entry to a line of JSON as the listing streams, so it's never collected into a
+/// table first. Entries that were already written as JSON while listing come as binary lines.
+fn json_lines(entries: PipelineData, span: Span) -> PipelineData {
+    let ctrlc = match &entries {
+        PipelineData::ListStream(stream, ..) => stream.ctrlc.clone(),
+        _ => None,
+    };
 
-    cols.push("name".into());
-    vals.push(Value::String {
-        val: display_name.to_string(),
-        span,
+    let lines = entries.into_iter().filter_map(move |entry| match entry {
+        Value::Binary { val, .. } => Some(Ok(val)),
+        Value::Nothing { .. } => None,
+        Value::Error { error } => Some(Err(error)),
+        entry => Some(json_line(&entry, span)),
     });
 
-    if let Some(md) = metadata {
-        cols.push("type".into());
-        vals.push(Value::String {
-            val: get_file_type(md).to_string(),
-            span,
-        });
-    } else {
-        cols.push("type".into());
-        vals.push(Value::nothing(span));
-    }
+    PipelineData::ByteStream(
+        ByteStream {
+            stream: Box::new(lines),
+            ctrlc,
+        },
+        span,
+        None,
+    )
+}
 
-    if long {
-        cols.push("target".into());
-        if let Some(md) = metadata {
-            if md.file_type().is_symlink() {
-                if let Ok(path_to_link) = filename.read_link() {
-                    vals.push(Value::String {
-                        val: path_to_link.to_string_lossy().to_string(),
-                        span,
-                    });
-                } else {
-                    vals.push(Value::String {
-                        val: "Could not obtain target file's path".to_string(),
-                        span,
-                    });
-                }
-            } else {
-                vals.push(Value::nothing(span));
-            }
-        }
-    }
+fn json_line(entry: &Value, span: Span) -> Result<Vec<u8>, ShellError> {
+    let json = value_to_json_value(entry)?;
+    let mut line = nu_json::to_string_raw(&json)
+        .map_err(|_| ShellError::CantConvert("JSON".into(), entry.get_type().to_string(), span))?;
+    line.push('\n');
 
-    if long {
-        if let Some(md) = metadata {
-            cols.push("readonly".into());
-            vals.push(Value::Bool {
-                val: md.permissions().readonly(),
-                span,
-            });
+    Ok(line.into_bytes())
+}
 
-            #[cfg(unix)]
-            {
-                use std::os::unix::fs::MetadataExt;
-                let mode = md.permissions().mode();
-                cols.push("mode".into());
-                vals.push(Value::String {
-                    val: umask::Mode::from(mode).to_string(),
-                    span,
-                });
+/// Count the entries and add up their sizes, the way `ls | get size | math sum` would
+fn total_summary(entries: PipelineData, span: Span) -> Value {
+    let (count, total_size) = entries
+        .into_iter()
+        .filter_map(|entry| match entry {
+            Value::Filesize { val, .. } => Some(val),
+            _ => None,
+        })
+        .fold((0, 0), |(count, total), size| (count + 1, total + size));
 
-                let nlinks = md.nlink();
-                cols.push("num_links".into());
-                vals.push(Value::Int {
-                    val: nlinks as i64,
-                    span,
-                });
+    Value::Record {
+        cols: vec!["count".into(), "total_size".into()],
+        vals: vec![
+            Value::Int { val: count, span },
+            Value::Filesize {
+                val: total_size,
+                span,
+            },
+        ],
+        span,
+    }
+}
 
-                let inode = md.ino();
-                cols.push("inode".into());
-                vals.push(Value::Int {
-                    val: inode as i64,
-                    span,
-                });
+/// Bounds on the number of hard links an entry has, from `--min-links` and `--max-links`
+#[derive(Clone, Copy, Debug)]
+struct LinkFilter {
+    min: Option<u64>,
+    max: Option<u64>,
+}
 
-                cols.push("uid".into());
-                if let Some(user) = users::get_user_by_uid(md.uid()) {
-                    vals.push(Value::String {
-                        val: user.name().to_string_lossy().into(),
-                        span,
-                    });
-                } else {
-                    vals.push(Value::nothing(span))
-                }
+impl LinkFilter {
+    fn is_active(self) -> bool {
+        self.min.is_some() || self.max.is_some()
+    }
 
-                cols.push("group".into());
-                if let Some(group) = users::get_group_by_gid(md.gid()) {
-                    vals.push(Value::String {
-                        val: group.name().to_string_lossy().into(),
-                        span,
-                    });
-                } else {
-                    vals.push(Value::nothing(span))
-                }
+    /// Entries whose metadata can't be read are only kept when there are no bounds
+    fn matches(self, metadata: Option<&std::fs::Metadata>) -> bool {
+        if !self.is_active() {
+            return true;
+        }
+
+        match metadata.map(link_count) {
+            Some(links) => {
+                self.min.map_or(true, |min| links >= min)
+                    && self.max.map_or(true, |max| links <= max)
             }
+            None => false,
         }
     }
+}
 
-    cols.push("size".to_string());
-    if let Some(md) = metadata {
-        if md.is_dir() {
-            let dir_size: u64 = md.len();
+fn link_bound(bound: Option<Spanned<i64>>) -> Result<Option<u64>, ShellError> {
+    bound
+        .map(|bound| {
+            u64::try_from(bound.item).map_err(|_| {
+                ShellError::UnsupportedInput("link count cannot be negative".into(), bound.span)
+            })
+        })
+        .transpose()
+}
 
-            vals.push(Value::Filesize {
-                val: dir_size as i64,
-                span,
-            });
-        } else if md.is_file() {
-            vals.push(Value::Filesize {
-                val: md.len() as i64,
+#[cfg(unix)]
+fn link_count(md: &std::fs::Metadata) -> u64 {
+    use std::os::unix::fs::MetadataExt;
+    md.nlink()
+}
+
+/// Link counts are rejected before listing outside of unix, so this is never asked
+#[cfg(not(unix))]
+fn link_count(_md: &std::fs::Metadata) -> u64 {
+    1
+}
+
+/// Which size, or sizes, each entry reports
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum LsSizes {
+    /// The length in bytes, what reading the file would give
+    Apparent,
+    /// The space allocated on disk
+    DiskUsage,
+    /// Both, side by side as `apparent_size` and `disk_usage` columns
+    All,
+}
+
+impl LsSizes {
+    fn from_flags(
+        apparent: bool,
+        disk_usage: bool,
+        all: bool,
+        span: Span,
+    ) -> Result<Self, ShellError> {
+        match (apparent, disk_usage, all) {
+            (_, false, false) => Ok(LsSizes::Apparent),
+            (false, true, false) => Ok(LsSizes::DiskUsage),
+            (false, false, true) => Ok(LsSizes::All),
+            _ => Err(ShellError::IncompatibleParametersSingle(
+                "Only one of \"--apparent-size\", \"--disk-usage\" and \"--all-sizes\" can be used"
+                    .to_string(),
                 span,
-            });
-        } else if md.file_type().is_symlink() {
-            if let Ok(symlink_md) = filename.symlink_metadata() {
-                vals.push(Value::Filesize {
-                    val: symlink_md.len() as i64,
-                    span,
-                });
-            } else {
-                vals.push(Value::nothing(span));
+            )),
+        }
+    }
+
+    /// The size `--total-only` adds up and `--stat size` reports: disk usage if asked for,
+    /// otherwise the apparent size
+    fn summary_size(self, md: &std::fs::Metadata) -> u64 {
+        match self {
+            LsSizes::DiskUsage => disk_usage(md),
+            LsSizes::Apparent | LsSizes::All => apparent_size(md),
+        }
+    }
+}
+
+fn apparent_size(md: &std::fs::Metadata) -> u64 {
+    md.len()
+}
+
+/// Allocated blocks are always counted in 512 byte units, whatever the filesystem's block size
+#[cfg(unix)]
+fn disk_usage(md: &std::fs::Metadata) -> u64 {
+    use std::os::unix::fs::MetadataExt;
+    md.blocks() * 512
+}
+
+/// Without a portable way to ask for the allocation, the length is the best guess
+#[cfg(not(unix))]
+fn disk_usage(md: &std::fs::Metadata) -> u64 {
+    md.len()
+}
+
+/// Put an `index` column in front of the entry's other columns
+fn with_index(entry: Value, index: usize, span: Span) -> Value {
+    match entry {
+        Value::Record {
+            mut cols,
+            mut vals,
+            span: record_span,
+        } => {
+            cols.insert(0, "index".into());
+            vals.insert(0, Value::int(index as i64, span));
+
+            Value::Record {
+                cols,
+                vals,
+                span: record_span,
             }
         }
-    } else {
-        vals.push(Value::nothing(span));
+        entry => entry,
     }
+}
 
-    if let Some(md) = metadata {
-        if long {
-            cols.push("created".to_string());
-            if let Ok(c) = md.created() {
-                let utc: DateTime<Utc> = c.into();
-                vals.push(Value::Date {
-                    val: utc.into(),
-                    span,
-                });
-            } else {
-                vals.push(Value::nothing(span));
+/// Add a `bucket` column saying how recently the entry was modified
+fn with_bucket(entry: Value, now: DateTime<Utc>, span: Span) -> Value {
+    let bucket = match modified(&entry) {
+        Some(modified) => Value::string(recency_bucket(modified, now), span),
+        None => Value::nothing(span),
+    };
+
+    match entry {
+        Value::Record {
+            mut cols,
+            mut vals,
+            span: record_span,
+        } => {
+            cols.push("bucket".into());
+            vals.push(bucket);
+
+            Value::Record {
+                cols,
+                vals,
+                span: record_span,
+            }
+        }
+        entry => entry,
+    }
+}
+
+/// Add the git column to an entry, leaving out entries that aren't in a work tree
+#[cfg(feature = "git-status")]
+fn with_git_status(entry: Value, status: Option<&str>, span: Span) -> Value {
+    match status {
+        Some(status) => with_field(entry, "git", Field::Text(status.into()), span),
+        None => entry,
+    }
+}
+
+/// Add whether the target of a symlink is missing. Entries that aren't symlinks, or whose metadata
+/// wasn't read, get an empty column.
+fn with_broken(
+    entry: Value,
+    reader: &mut MetadataReader,
+    path: &Path,
+    metadata: Option<&std::fs::Metadata>,
+    span: Span,
+) -> Value {
+    with_field(entry, "broken", broken_field(reader, path, metadata), span)
+}
+
+fn broken_field(
+    reader: &mut MetadataReader,
+    path: &Path,
+    metadata: Option<&std::fs::Metadata>,
+) -> Field {
+    match metadata {
+        Some(md) if md.file_type().is_symlink() => match reader.read_target(path) {
+            Some(Ok(_)) => Field::Bool(false),
+            Some(Err(err)) if err.kind() == std::io::ErrorKind::NotFound => Field::Bool(true),
+            // A target that can't be read, or wasn't read in time, may still be there
+            _ => Field::Nothing,
+        },
+        _ => Field::Nothing,
+    }
+}
+
+fn with_timed_out(entry: Value, timed_out: bool, span: Span) -> Value {
+    with_field(entry, "timed_out", Field::Bool(timed_out), span)
+}
+
+fn with_field(entry: Value, name: &str, field: Field, span: Span) -> Value {
+    match entry {
+        Value::Record {
+            mut cols,
+            mut vals,
+            span: record_span,
+        } => {
+            cols.push(name.into());
+            vals.push(field.into_value(span));
+            Value::Record {
+                cols,
+                vals,
+                span: record_span,
             }
+        }
+        other => other,
+    }
+}
+
+/// The calendar period, in UTC, that `modified` falls in relative to `now`. Each bucket leaves
+/// out the ones before it, so "this week" doesn't include today. Times in the future count as today.
+fn recency_bucket(modified: DateTime<FixedOffset>, now: DateTime<Utc>) -> &'static str {
+    let modified = modified.with_timezone(&Utc);
+
+    if modified.date() >= now.date() {
+        "today"
+    } else if modified.iso_week() == now.iso_week() {
+        "this week"
+    } else if modified.year() == now.year() && modified.month() == now.month() {
+        "this month"
+    } else {
+        "older"
+    }
+}
+
+/// The column `ls` sorts its entries by
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum LsSort {
+    Name,
+    Modified,
+    /// By size, the apparent size when both sizes are shown. Entries without a size come first.
+    Size,
+    /// By extension, then by name. Entries without an extension come first.
+    Extension,
+    /// By the version numbers in the name, so `v1.9` comes before `v1.10`.
+    ///
+    /// Names are walked from the start. Runs of digits are compared by their numeric value,
+    /// ignoring leading zeros, and any other character is compared with its counterpart by code
+    /// point. The first difference decides; a name that ends first (`v1.0` against `v1.0-rc1`)
+    /// comes first, and names that only differ in leading zeros fall back to a plain comparison.
+    Version,
+}
+
+impl LsSort {
+    fn from_spanned(sort: Spanned<String>) -> Result<Self, ShellError> {
+        match sort.item.as_str() {
+            "name" => Ok(LsSort::Name),
+            "modified" => Ok(LsSort::Modified),
+            "size" => Ok(LsSort::Size),
+            "extension" => Ok(LsSort::Extension),
+            "version" => Ok(LsSort::Version),
+            _ => Err(ShellError::UnsupportedInput(
+                "expected 'name', 'modified', 'size', 'extension' or 'version'".into(),
+                sort.span,
+            )),
+        }
+    }
+}
+
+/// The single field `--stat` returns for each entry
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum LsStat {
+    Name,
+    Type,
+    Size,
+    Modified,
+    Created,
+    Accessed,
+}
 
-            cols.push("accessed".to_string());
-            if let Ok(a) = md.accessed() {
-                let utc: DateTime<Utc> = a.into();
-                vals.push(Value::Date {
+impl LsStat {
+    fn from_spanned(stat: Spanned<String>) -> Result<Self, ShellError> {
+        match stat.item.as_str() {
+            "name" => Ok(LsStat::Name),
+            "type" => Ok(LsStat::Type),
+            "size" => Ok(LsStat::Size),
+            "modified" => Ok(LsStat::Modified),
+            "created" => Ok(LsStat::Created),
+            "accessed" => Ok(LsStat::Accessed),
+            _ => Err(ShellError::UnsupportedInput(
+                "expected 'name', 'type', 'size', 'modified', 'created' or 'accessed'".into(),
+                stat.span,
+            )),
+        }
+    }
+
+    fn column(self) -> &'static str {
+        match self {
+            LsStat::Name => "name",
+            LsStat::Type => "type",
+            LsStat::Size => "size",
+            LsStat::Modified => "modified",
+            LsStat::Created => "created",
+            LsStat::Accessed => "accessed",
+        }
+    }
+
+    /// Whether the field is only listed with `--long`
+    fn needs_long(self) -> bool {
+        matches!(self, LsStat::Created | LsStat::Accessed)
+    }
+
+    /// Read the field straight from the metadata, the same way `dir_entry_dict` lists it
+    fn value(
+        self,
+        display_name: &str,
+        metadata: Option<&std::fs::Metadata>,
+        sizes: LsSizes,
+        span: Span,
+    ) -> Value {
+        let date = |time: std::io::Result<std::time::SystemTime>| match time {
+            Ok(time) => {
+                let utc: DateTime<Utc> = time.into();
+                Value::Date {
                     val: utc.into(),
                     span,
-                });
-            } else {
-                vals.push(Value::nothing(span));
+                }
             }
-        }
+            Err(_) => Value::nothing(span),
+        };
 
-        cols.push("modified".to_string());
-        if let Ok(m) = md.modified() {
-            let utc: DateTime<Utc> = m.into();
-            vals.push(Value::Date {
-                val: utc.into(),
+        match (self, metadata) {
+            (LsStat::Name, _) => Value::string(display_name, span),
+            (_, None) => Value::nothing(span),
+            (LsStat::Type, Some(md)) => Value::string(get_file_type(md), span),
+            (LsStat::Size, Some(md)) => Value::Filesize {
+                val: sizes.summary_size(md) as i64,
                 span,
-            });
+            },
+            (LsStat::Modified, Some(md)) => date(md.modified()),
+            (LsStat::Created, Some(md)) => date(md.created()),
+            (LsStat::Accessed, Some(md)) => date(md.accessed()),
+        }
+    }
+
+    /// Pick the field out of an entry that was listed in full. Errors are passed on as they are.
+    fn pick(self, entry: Value, span: Span) -> Value {
+        match entry {
+            Value::Record { .. } => entry
+                .get_data_by_key(self.column())
+                .unwrap_or_else(|| Value::nothing(span)),
+            entry => entry,
+        }
+    }
+}
+
+/// Decide how entries are ordered, if at all
+///
+/// Explicit flags win over `$config.ls_newest_first`, which wins over the default of ascending
+/// names. `--reverse` always flips whatever order was picked. `None` means the entries keep the
+/// glob's own (name ascending) order.
+fn sort_order(
+    sort_flag: Option<LsSort>,
+    reverse: bool,
+    newest_first: bool,
+) -> Option<(LsSort, bool)> {
+    match sort_flag {
+        Some(sort) => Some((sort, reverse)),
+        None if newest_first => Some((LsSort::Modified, !reverse)),
+        None if reverse => Some((LsSort::Name, true)),
+        None => None,
+    }
+}
+
+fn sort_entries(entries: &mut [Value], sort: LsSort, descending: bool) {
+    entries.sort_by(|a, b| {
+        let ordering = match sort {
+            LsSort::Name => name(a).cmp(&name(b)),
+            LsSort::Modified => modified(a).cmp(&modified(b)),
+            LsSort::Size => size(a).cmp(&size(b)),
+            LsSort::Extension => extension(a)
+                .cmp(&extension(b))
+                .then_with(|| name(a).cmp(&name(b))),
+            LsSort::Version => match (name(a), name(b)) {
+                (Some(a), Some(b)) => natural_cmp(&a, &b),
+                (a, b) => a.cmp(&b),
+            },
+        };
+
+        if descending {
+            ordering.reverse()
+        } else {
+            ordering
+        }
+    });
+}
+
+fn name(entry: &Value) -> Option<String> {
+    entry
+        .get_data_by_key("name")
+        .and_then(|v| v.as_string().ok())
+}
+
+/// The extension of the entry's file name; dotfiles like `.bashrc` have none
+fn extension(entry: &Value) -> Option<String> {
+    name(entry).and_then(|name| {
+        Path::new(&name)
+            .extension()
+            .map(|ext| ext.to_string_lossy().to_string())
+    })
+}
+
+fn is_dir_entry(entry: &Value) -> bool {
+    matches!(
+        entry.get_data_by_key("type"),
+        Some(Value::String { val, .. }) if val == "dir"
+    )
+}
+
+fn modified(entry: &Value) -> Option<DateTime<FixedOffset>> {
+    match entry.get_data_by_key("modified") {
+        Some(Value::Date { val, .. }) => Some(val),
+        _ => None,
+    }
+}
+
+fn size(entry: &Value) -> Option<i64> {
+    match entry
+        .get_data_by_key("size")
+        .or_else(|| entry.get_data_by_key("apparent_size"))
+    {
+        Some(Value::Filesize { val, .. }) => Some(val),
+        _ => None,
+    }
+}
+
+fn permission_denied(dir: impl AsRef<Path>) -> bool {
+    match dir.as_ref().read_dir() {
+        Err(e) => matches!(e.kind(), std::io::ErrorKind::PermissionDenied),
+        Ok(_) => false,
+    }
+}
+
+fn is_hidden_dir(dir: impl AsRef<Path>) -> bool {
+    #[cfg(windows)]
+    {
+        use std::os::windows::fs::MetadataExt;
+
+        if let Ok(metadata) = dir.as_ref().metadata() {
+            let attributes = metadata.file_attributes();
+            // https://docs.microsoft.com/en-us/windows/win32/fileio/file-attribute-constants
+            (attributes & 0x2) != 0
         } else {
-            vals.push(Value::nothing(span));
+            false
         }
+    }
+
+    #[cfg(not(windows))]
+    {
+        dir.as_ref()
+            .file_name()
+            .map(|name| name.to_string_lossy().starts_with('.'))
+            .unwrap_or(false)
+    }
+}
+
+fn is_empty_dir(dir: impl AsRef<Path>) -> bool {
+    match dir.as_ref().read_dir() {
+        Err(_) => true,
+        Ok(mut s) => s.next().is_none(),
+    }
+}
+
+/// Compared as lossy strings, so a name that isn't valid UTF-8 doesn't abort the listing
+fn path_contains_hidden_folder(path: &Path, folders: &[PathBuf]) -> bool {
+    let path = path.as_os_str().to_string_lossy();
+    folders
+        .iter()
+        .any(|folder| path.starts_with(folder.as_os_str().to_string_lossy().as_ref()))
+}
+
+#[cfg(unix)]
+use std::os::unix::fs::FileTypeExt;
+use std::path::Path;
+
+pub fn get_file_type(md: &std::fs::Metadata) -> &str {
+    let ft = md.file_type();
+    let mut file_type = "unknown";
+    if ft.is_dir() {
+        file_type = "dir";
+    } else if ft.is_file() {
+        file_type = "file";
+    } else if ft.is_symlink() {
+        file_type = "symlink";
     } else {
-        if long {
-            cols.push("created".to_string());
-            vals.push(Value::nothing(span));
+        #[cfg(unix)]
+        {
+            if ft.is_block_device() {
+                file_type = "block device";
+            } else if ft.is_char_device() {
+                file_type = "char device";
+            } else if ft.is_fifo() {
+                file_type = "pipe";
+            } else if ft.is_socket() {
+                file_type = "socket";
+            }
+        }
+    }
+    file_type
+}
+
+/// The value of one column of an entry, before it becomes part of a record or a line of JSON
+#[cfg_attr(not(unix), allow(dead_code))]
+enum Field {
+    Text(String),
+    Bool(bool),
+    Int(i64),
+    Size(u64),
+    Date(DateTime<FixedOffset>),
+    Nothing,
+}
 
-            cols.push("accessed".to_string());
-            vals.push(Value::nothing(span));
+impl Field {
+    fn into_value(self, span: Span) -> Value {
+        match self {
+            Field::Text(val) => Value::String { val, span },
+            Field::Bool(val) => Value::Bool { val, span },
+            Field::Int(val) => Value::Int { val, span },
+            Field::Size(val) => Value::Filesize {
+                val: val as i64,
+                span,
+            },
+            Field::Date(val) => Value::Date { val, span },
+            Field::Nothing => Value::nothing(span),
         }
+    }
 
-        cols.push("modified".to_string());
-        vals.push(Value::nothing(span));
+    fn date(time: std::io::Result<std::time::SystemTime>) -> Field {
+        match time {
+            Ok(time) => {
+                let utc: DateTime<Utc> = time.into();
+                Field::Date(utc.into())
+            }
+            Err(_) => Field::Nothing,
+        }
     }
+}
+
+/// An entry written as a line of JSON, the same line `to json --raw` would write for its record
+struct JsonLine {
+    line: Vec<u8>,
+}
+
+impl JsonLine {
+    fn new() -> Self {
+        JsonLine { line: vec![b'{'] }
+    }
+
+    fn field(&mut self, name: &str, field: Field) -> Result<(), ShellError> {
+        if self.line.len() > 1 {
+            self.line.push(b',');
+        }
+        // Column names are plain words, so they need no escaping
+        self.line.push(b'"');
+        self.line.extend_from_slice(name.as_bytes());
+        self.line.extend_from_slice(b"\":");
+
+        let text = match field {
+            Field::Text(text) => text,
+            Field::Date(date) => date.to_string(),
+            Field::Bool(val) => return self.raw(&val.to_string()),
+            Field::Int(val) => return self.raw(&val.to_string()),
+            Field::Size(val) => return self.raw(&val.to_string()),
+            Field::Nothing => return self.raw("null"),
+        };
+
+        nu_json::to_writer(&mut self.line, &text)
+            .map_err(|err| ShellError::IOError(err.to_string()))
+    }
+
+    fn raw(&mut self, text: &str) -> Result<(), ShellError> {
+        self.line.extend_from_slice(text.as_bytes());
+        Ok(())
+    }
+
+    fn finish(mut self) -> Vec<u8> {
+        self.line.extend_from_slice(b"}\n");
+        self.line
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+#[cfg_attr(not(unix), allow(unused_variables))]
+pub(crate) fn dir_entry_dict(
+    filename: &std::path::Path, // absolute path
+    display_name: &str,         // gile name to be displayed
+    metadata: Option<&std::fs::Metadata>,
+    span: Span,
+    long: bool,
+    sizes: LsSizes,
+    owner_names: &mut OwnerNames,
+) -> Result<Value, ShellError> {
+    let mut cols = vec![];
+    let mut vals = vec![];
+
+    dir_entry_fields(
+        filename,
+        display_name,
+        metadata,
+        long,
+        sizes,
+        owner_names,
+        |name, field| {
+            cols.push(name.to_string());
+            vals.push(field.into_value(span));
+            Ok(())
+        },
+    )?;
 
     Ok(Value::Record { cols, vals, span })
 }
+
+/// Write an entry as a line of JSON straight from its metadata, without making a record first
+fn dir_entry_json(
+    filename: &std::path::Path,
+    display_name: &str,
+    metadata: Option<&std::fs::Metadata>,
+    long: bool,
+    sizes: LsSizes,
+    owner_names: &mut OwnerNames,
+) -> Result<JsonLine, ShellError> {
+    let mut line = JsonLine::new();
+
+    dir_entry_fields(
+        filename,
+        display_name,
+        metadata,
+        long,
+        sizes,
+        owner_names,
+        |name, field| line.field(name, field),
+    )?;
+
+    Ok(line)
+}
+
+/// Hand each column of an entry to `column`, in the order they're shown
+#[cfg_attr(not(unix), allow(unused_variables))]
+fn dir_entry_fields(
+    filename: &std::path::Path,
+    display_name: &str,
+    metadata: Option<&std::fs::Metadata>,
+    long: bool,
+    sizes: LsSizes,
+    owner_names: &mut OwnerNames,
+    mut column: impl FnMut(&'static str, Field) -> Result<(), ShellError>,
+) -> Result<(), ShellError> {
+    column("name", Field::Text(display_name.to_string()))?;
+    column(
+        "type",
+        match metadata {
+            Some(md) => Field::Text(get_file_type(md).to_string()),
+            None => Field::Nothing,
+        },
+    )?;
+
+    if long {
+        column(
+            "target",
+            match metadata {
+                Some(md) if md.file_type().is_symlink() => {
+                    Field::Text(match filename.read_link() {
+                        Ok(path_to_link) => path_to_link.to_string_lossy().to_string(),
+                        Err(_) => "Could not obtain target file's path".to_string(),
+                    })
+                }
+                _ => Field::Nothing,
+            },
+        )?;
+    }
+
+    if long {
+        if let Some(md) = metadata {
+            column("readonly", Field::Bool(md.permissions().readonly()))?;
+
+            #[cfg(unix)]
+            {
+                use std::os::unix::fs::MetadataExt;
+                let mode = md.permissions().mode();
+                column("mode", Field::Text(umask::Mode::from(mode).to_string()))?;
+                column("num_links", Field::Int(md.nlink() as i64))?;
+                column("inode", Field::Int(md.ino() as i64))?;
+                column(
+                    "uid",
+                    owner_names
+                        .user(md.uid())
+                        .map_or(Field::Nothing, Field::Text),
+                )?;
+                column(
+                    "group",
+                    owner_names
+                        .group(md.gid())
+                        .map_or(Field::Nothing, Field::Text),
+                )?;
+            }
+        }
+    }
+
+    let filesize = |size: Option<u64>| size.map_or(Field::Nothing, Field::Size);
+    match sizes {
+        LsSizes::Apparent => column("size", filesize(metadata.map(apparent_size)))?,
+        LsSizes::DiskUsage => column("size", filesize(metadata.map(disk_usage)))?,
+        LsSizes::All => {
+            column("apparent_size", filesize(metadata.map(apparent_size)))?;
+            column("disk_usage", filesize(metadata.map(disk_usage)))?;
+        }
+    }
+
+    match metadata {
+        Some(md) => {
+            if long {
+                column("created", Field::date(md.created()))?;
+                column("accessed", Field::date(md.accessed()))?;
+            }
+            column("modified", Field::date(md.modified()))
+        }
+        None => {
+            if long {
+                column("created", Field::Nothing)?;
+                column("accessed", Field::Nothing)?;
+            }
+            column("modified", Field::Nothing)
+        }
+    }
+}
+
+/// Follow directories that hold nothing but a single directory, returning the last one of the
+/// chain. Symlinked directories aren't followed, so a link back up the tree can't loop.
+fn single_dir_chain(path: &Path) -> PathBuf {
+    let mut current = path.to_path_buf();
+
+    while let Some(child) = only_child_dir(&current) {
+        current = child;
+    }
+
+    current
+}
+
+fn only_child_dir(path: &Path) -> Option<PathBuf> {
+    if !std::fs::symlink_metadata(path).ok()?.is_dir() {
+        return None;
+    }
+
+    let mut children = std::fs::read_dir(path).ok()?;
+    let child = children.next()?.ok()?.path();
+
+    if children.next().is_some() || !std::fs::symlink_metadata(&child).ok()?.is_dir() {
+        return None;
+    }
+
+    Some(child)
+}
+
+/// Where ls reads the metadata of entries from, so tests can put a slow source in its place
+pub(crate) trait MetadataSource: Send + Sync {
+    fn symlink_metadata(&self, path: &Path) -> std::io::Result<std::fs::Metadata>;
+
+    /// The metadata of what `path` points to, when it's a symlink
+    fn metadata(&self, path: &Path) -> std::io::Result<std::fs::Metadata>;
+}
+
+struct FileSystem;
+
+impl MetadataSource for FileSystem {
+    fn symlink_metadata(&self, path: &Path) -> std::io::Result<std::fs::Metadata> {
+        std::fs::symlink_metadata(path)
+    }
+
+    fn metadata(&self, path: &Path) -> std::io::Result<std::fs::Metadata> {
+        std::fs::metadata(path)
+    }
+}
+
+/// The names of the users and groups that own entries. Files in a directory mostly share an
+/// owner, and a lookup can go out to a slow name service, so each id is resolved once per `ls`.
+#[derive(Default)]
+#[cfg_attr(not(unix), allow(dead_code))]
+pub(crate) struct OwnerNames {
+    users: HashMap<u32, Option<String>>,
+    groups: HashMap<u32, Option<String>>,
+}
+
+#[cfg(unix)]
+impl OwnerNames {
+    fn user(&mut self, uid: u32) -> Option<String> {
+        cached_name(&mut self.users, uid, |uid| {
+            users::get_user_by_uid(uid).map(|user| user.name().to_string_lossy().into())
+        })
+    }
+
+    fn group(&mut self, gid: u32) -> Option<String> {
+        cached_name(&mut self.groups, gid, |gid| {
+            users::get_group_by_gid(gid).map(|group| group.name().to_string_lossy().into())
+        })
+    }
+}
+
+/// Look `id` up in `names`, asking `lookup` only the first time. Ids without a name are
+/// remembered too.
+#[cfg(unix)]
+fn cached_name(
+    names: &mut HashMap<u32, Option<String>>,
+    id: u32,
+    lookup: impl FnOnce(u32) -> Option<String>,
+) -> Option<String> {
+    names.entry(id).or_insert_with(|| lookup(id)).clone()
+}
+
+enum MetadataRead {
+    Read(Option<std::fs::Metadata>),
+    TimedOut,
+}
+
+/// Reads metadata, giving up once the reads have taken as long as --timeout allows, when there
+/// is a timeout. Only the time spent waiting on reads counts, not the time between them.
+struct MetadataReader {
+    source: Arc<dyn MetadataSource>,
+    remaining: Option<Duration>,
+    worker: Option<MetadataWorker>,
+}
+
+impl MetadataReader {
+    fn new(source: Arc<dyn MetadataSource>, timeout: Option<Duration>) -> Self {
+        MetadataReader {
+            source,
+            remaining: timeout,
+            worker: None,
+        }
+    }
+
+    fn read(&mut self, path: &Path) -> MetadataRead {
+        match self.request(path, false) {
+            Some(metadata) => MetadataRead::Read(metadata.ok()),
+            None => MetadataRead::TimedOut,
+        }
+    }
+
+    /// Read the metadata of the target of the symlink at `path`. Nothing is given back when the
+    /// time ran out first.
+    fn read_target(&mut self, path: &Path) -> Option<std::io::Result<std::fs::Metadata>> {
+        self.request(path, true)
+    }
+
+    fn request(
+        &mut self,
+        path: &Path,
+        follow_links: bool,
+    ) -> Option<std::io::Result<std::fs::Metadata>> {
+        let remaining = match self.remaining {
+            Some(remaining) if remaining.is_zero() => return None,
+            Some(remaining) => remaining,
+            None => return Some(read_metadata(self.source.as_ref(), path, follow_links)),
+        };
+
+        let start = Instant::now();
+        let source = &self.source;
+        let worker = self
+            .worker
+            .get_or_insert_with(|| MetadataWorker::start(source.clone()));
+        let read = worker
+            .requests
+            .send((path.to_path_buf(), follow_links))
+            .ok()
+            .and_then(|_| worker.reads.recv_timeout(remaining).ok());
+        self.remaining = Some(remaining.saturating_sub(start.elapsed()));
+
+        if read.is_none() {
+            // The worker is stuck on this read, so a later one needs a worker of its own
+            self.worker = None;
+        }
+
+        read
+    }
+}
+
+fn read_metadata(
+    source: &dyn MetadataSource,
+    path: &Path,
+    follow_links: bool,
+) -> std::io::Result<std::fs::Metadata> {
+    if follow_links {
+        source.metadata(path)
+    } else {
+        source.symlink_metadata(path)
+    }
+}
+
+/// The thread a reader with a timeout reads metadata on. A read from a stalled mount may never
+/// return, so the reader waits for the answer no longer than the time it has left and leaves the
+/// thread behind if it doesn't come.
+struct MetadataWorker {
+    requests: mpsc::Sender<(PathBuf, bool)>,
+    reads: mpsc::Receiver<std::io::Result<std::fs::Metadata>>,
+}
+
+impl MetadataWorker {
+    fn start(source: Arc<dyn MetadataSource>) -> Self {
+        let (requests, paths) = mpsc::channel::<(PathBuf, bool)>();
+        let (replies, reads) = mpsc::channel();
+
+        // The thread ends once its reader is gone
+        std::thread::spawn(move || {
+            for (path, follow_links) in paths {
+                let read = read_metadata(source.as_ref(), &path, follow_links);
+                if replies.send(read).is_err() {
+                    break;
+                }
+            }
+        });
+
+        MetadataWorker { requests, reads }
+    }
+}
+
+/// Resolve symlinks and `..` in `path`. Paths that can't be resolved, like broken symlinks, fall
+/// back to the lexically absolute path.
+fn canonical_path(path: &Path, cwd: &Path) -> PathBuf {
+    canonicalize_with(path, cwd).unwrap_or_else(|_| expand_path_with(path, cwd))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn entry(name: &str, modified: i64) -> Value {
+        let span = Span::test_data();
+        let modified = FixedOffset::east(0).timestamp(modified, 0);
+
+        Value::Record {
+            cols: vec!["name".into(), "modified".into()],
+            vals: vec![
+                Value::test_string(name),
+                Value::Date {
+                    val: modified,
+                    span,
+                },
+            ],
+            span,
+        }
+    }
+
+    fn names(entries: &[Value]) -> Vec<String> {
+        entries
+            .iter()
+            .filter_map(|e| e.get_data_by_key("name"))
+            .filter_map(|n| n.as_string().ok())
+            .collect()
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn owner_names_are_looked_up_once() {
+        let mut names = HashMap::new();
+        let mut lookups = vec![];
+        // Most entries share an owner; 7 has no name
+        for uid in [1000, 1000, 0, 1000, 7, 0, 7] {
+            let name = cached_name(&mut names, uid, |uid| {
+                lookups.push(uid);
+                (uid != 7).then(|| format!("user{}", uid))
+            });
+            assert_eq!(name, (uid != 7).then(|| format!("user{}", uid)));
+        }
+
+        assert_eq!(lookups, vec![1000, 0, 7]);
+    }
+
+    #[test]
+    fn default_order_is_untouched() {
+        assert_eq!(sort_order(None, false, false), None);
+        assert_eq!(sort_order(None, true, false), Some((LsSort::Name, true)));
+    }
+
+    #[test]
+    fn config_makes_newest_first_the_default() {
+        assert_eq!(
+            sort_order(None, false, true),
+            Some((LsSort::Modified, true))
+        );
+        assert_eq!(
+            sort_order(None, true, true),
+            Some((LsSort::Modified, false))
+        );
+    }
+
+    #[test]
+    fn flags_override_config() {
+        assert_eq!(
+            sort_order(Some(LsSort::Name), false, true),
+            Some((LsSort::Name, false))
+        );
+        assert_eq!(
+            sort_order(Some(LsSort::Modified), false, true),
+            Some((LsSort::Modified, false))
+        );
+    }
+
+    #[test]
+    fn sorts_by_modified_and_name() {
+        let mut entries = vec![entry("b", 20), entry("a", 10), entry("c", 30)];
+
+        sort_entries(&mut entries, LsSort::Modified, true);
+        assert_eq!(names(&entries), vec!["c", "b", "a"]);
+
+        sort_entries(&mut entries, LsSort::Name, false);
+        assert_eq!(names(&entries), vec!["a", "b", "c"]);
+    }
+
+    #[test]
+    fn sorts_by_size() {
+        let sized = |name: &str, size: i64| match entry(name, 0) {
+            Value::Record {
+                mut cols,
+                mut vals,
+                span,
+            } => {
+                cols.push("size".into());
+                vals.push(Value::Filesize { val: size, span });
+                Value::Record { cols, vals, span }
+            }
+            other => other,
+        };
+        let mut entries = vec![sized("b", 200), entry("dir", 0), sized("a", 3000)];
+
+        sort_entries(&mut entries, LsSort::Size, false);
+        assert_eq!(names(&entries), vec!["dir", "b", "a"]);
+
+        sort_entries(&mut entries, LsSort::Size, true);
+        assert_eq!(names(&entries), vec!["a", "b", "dir"]);
+    }
+
+    #[test]
+    fn canonical_path_resolves_dots() {
+        let cwd = std::env::current_dir().expect("current dir");
+        let expected = cwd.canonicalize().expect("canonical current dir");
+
+        assert_eq!(canonical_path(&cwd.join("src").join(".."), &cwd), expected);
+    }
+
+    #[test]
+    fn canonical_path_falls_back_for_missing_paths() {
+        let cwd = std::env::current_dir().expect("current dir");
+        let missing = cwd.join("does-not-exist").join("..").join("nor-this");
+
+        assert_eq!(canonical_path(&missing, &cwd), cwd.join("nor-this"));
+    }
+
+    #[test]
+    fn sorts_by_extension_then_name() {
+        let mut entries = vec![
+            entry("b.rs", 0),
+            entry("README", 0),
+            entry("a.toml", 0),
+            entry("a.rs", 0),
+            entry(".gitignore", 0),
+        ];
+
+        sort_entries(&mut entries, LsSort::Extension, false);
+        assert_eq!(
+            names(&entries),
+            vec![".gitignore", "README", "a.rs", "b.rs", "a.toml"]
+        );
+    }
+
+    #[test]
+    fn index_goes_before_name() {
+        let indexed = with_index(entry("a.rs", 0), 3, Span::test_data());
+
+        match indexed {
+            Value::Record { cols, vals, .. } => {
+                assert_eq!(cols, vec!["index", "name", "modified"]);
+                assert_eq!(vals[0].as_integer().ok(), Some(3));
+            }
+            _ => panic!("expected a record"),
+        }
+    }
+
+    #[test]
+    fn sorts_by_version() {
+        let mut entries = vec![
+            entry("v1.10.0.tar.gz", 0),
+            entry("v1.9.2.tar.gz", 0),
+            entry("v1.9.10.tar.gz", 0),
+            entry("v2.0.0.tar.gz", 0),
+            entry("v1.9.2-rc1.tar.gz", 0),
+            entry("v1.09.3.tar.gz", 0),
+        ];
+
+        sort_entries(&mut entries, LsSort::Version, false);
+        assert_eq!(
+            names(&entries),
+            vec![
+                "v1.9.2-rc1.tar.gz",
+                "v1.9.2.tar.gz",
+                "v1.09.3.tar.gz",
+                "v1.9.10.tar.gz",
+                "v1.10.0.tar.gz",
+                "v2.0.0.tar.gz",
+            ]
+        );
+    }
+
+    #[test]
+    fn buckets_by_recency() {
+        // A Wednesday
+        let now = Utc.ymd(2022, 1, 19).and_hms(12, 0, 0);
+        let at = |y, m, d, h| FixedOffset::east(0).ymd(y, m, d).and_hms(h, 0, 0);
+
+        assert_eq!(recency_bucket(at(2022, 1, 19, 1), now), "today");
+        assert_eq!(recency_bucket(at(2022, 1, 20, 9), now), "today");
+        assert_eq!(recency_bucket(at(2022, 1, 17, 23), now), "this week");
+        assert_eq!(recency_bucket(at(2022, 1, 16, 23), now), "this month");
+        assert_eq!(recency_bucket(at(2022, 1, 1, 0), now), "this month");
+        assert_eq!(recency_bucket(at(2021, 12, 31, 23), now), "older");
+        assert_eq!(recency_bucket(at(2021, 1, 19, 12), now), "older");
+    }
+
+    #[test]
+    fn bucket_column_follows_the_sort() {
+        let now = Utc.ymd(2022, 1, 19).and_hms(12, 0, 0);
+        let mut entries = vec![
+            entry("old.txt", Utc.ymd(2021, 6, 1).and_hms(0, 0, 0).timestamp()),
+            entry("new.txt", now.timestamp()),
+            entry(
+                "monday.txt",
+                Utc.ymd(2022, 1, 17).and_hms(8, 0, 0).timestamp(),
+            ),
+        ];
+
+        sort_entries(&mut entries, LsSort::Modified, true);
+        let buckets: Vec<String> = entries
+            .into_iter()
+            .map(|e| with_bucket(e, now, Span::test_data()))
+            .filter_map(|e| e.get_data_by_key("bucket"))
+            .filter_map(|b| b.as_string().ok())
+            .collect();
+
+        assert_eq!(buckets, vec!["today", "this week", "older"]);
+    }
+
+    #[test]
+    fn size_flags_pick_one_mode() {
+        let span = Span::test_data();
+
+        assert_eq!(
+            LsSizes::from_flags(false, false, false, span).ok(),
+            Some(LsSizes::Apparent)
+        );
+        assert_eq!(
+            LsSizes::from_flags(true, false, false, span).ok(),
+            Some(LsSizes::Apparent)
+        );
+        assert_eq!(
+            LsSizes::from_flags(false, true, false, span).ok(),
+            Some(LsSizes::DiskUsage)
+        );
+        assert_eq!(
+            LsSizes::from_flags(false, false, true, span).ok(),
+            Some(LsSizes::All)
+        );
+        assert!(LsSizes::from_flags(true, true, false, span).is_err());
+        assert!(LsSizes::from_flags(false, true, true, span).is_err());
+    }
+
+    struct SlowSource(Duration);
+
+    impl MetadataSource for SlowSource {
+        fn symlink_metadata(&self, path: &Path) -> std::io::Result<std::fs::Metadata> {
+            std::thread::sleep(self.0);
+            std::fs::symlink_metadata(path)
+        }
+
+        fn metadata(&self, path: &Path) -> std::io::Result<std::fs::Metadata> {
+            std::thread::sleep(self.0);
+            std::fs::metadata(path)
+        }
+    }
+
+    fn read_with(delay: Duration, timeout: Option<Duration>) -> MetadataRead {
+        let mut reader = MetadataReader::new(Arc::new(SlowSource(delay)), timeout);
+        reader.read(Path::new("."))
+    }
+
+    #[test]
+    fn slow_metadata_times_out() {
+        let read = read_with(Duration::from_secs(5), Some(Duration::from_millis(20)));
+        assert!(matches!(read, MetadataRead::TimedOut));
+    }
+
+    #[test]
+    fn metadata_read_in_time_is_kept() {
+        let read = read_with(Duration::from_millis(0), Some(Duration::from_secs(5)));
+        assert!(matches!(read, MetadataRead::Read(Some(_))));
+
+        let read = read_with(Duration::from_millis(20), None);
+        assert!(matches!(read, MetadataRead::Read(Some(_))));
+    }
+
+    #[test]
+    fn stalled_read_still_ends_at_the_timeout() {
+        let start = Instant::now();
+        let mut reader = MetadataReader::new(
+            Arc::new(SlowSource(Duration::from_secs(5))),
+            Some(Duration::from_millis(50)),
+        );
+
+        assert!(matches!(
+            reader.read(Path::new(".")),
+            MetadataRead::TimedOut
+        ));
+        assert!(matches!(
+            reader.read(Path::new(".")),
+            MetadataRead::TimedOut
+        ));
+        assert!(start.elapsed() < Duration::from_secs(1));
+    }
+
+    /// Remembers which threads it was read on
+    #[derive(Default)]
+    struct ThreadsSource(std::sync::Mutex<Vec<std::thread::ThreadId>>);
+
+    impl MetadataSource for ThreadsSource {
+        fn symlink_metadata(&self, path: &Path) -> std::io::Result<std::fs::Metadata> {
+            if let Ok(mut threads) = self.0.lock() {
+                threads.push(std::thread::current().id());
+            }
+            std::fs::symlink_metadata(path)
+        }
+
+        fn metadata(&self, path: &Path) -> std::io::Result<std::fs::Metadata> {
+            self.symlink_metadata(path)
+        }
+    }
+
+    #[test]
+    fn reads_share_one_thread() {
+        let source = Arc::new(ThreadsSource::default());
+        let mut reader = MetadataReader::new(source.clone(), Some(Duration::from_secs(60)));
+
+        for _ in 0..10 {
+            assert!(matches!(
+                reader.read(Path::new(".")),
+                MetadataRead::Read(Some(_))
+            ));
+        }
+
+        let mut threads = source.0.lock().expect("threads were recorded").clone();
+        threads.dedup();
+        assert_eq!(threads.len(), 1);
+        assert_ne!(threads[0], std::thread::current().id());
+    }
+
+    #[test]
+    fn time_between_reads_does_not_count() {
+        let mut reader = MetadataReader::new(
+            Arc::new(SlowSource(Duration::from_millis(0))),
+            Some(Duration::from_millis(200)),
+        );
+
+        for _ in 0..3 {
+            std::thread::sleep(Duration::from_millis(100));
+            assert!(matches!(
+                reader.read(Path::new(".")),
+                MetadataRead::Read(Some(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn spent_timeout_reads_nothing() {
+        let read = read_with(Duration::from_millis(0), Some(Duration::from_millis(0)));
+        assert!(matches!(read, MetadataRead::TimedOut));
+    }
+}
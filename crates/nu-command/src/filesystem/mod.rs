@@ -1,5 +1,7 @@
 mod cd;
 mod cp;
+#[cfg(feature = "git-status")]
+mod git_status;
 mod ls;
 mod mkdir;
 mod mv;
@@ -11,7 +13,7 @@ mod util;
 
 pub use cd::Cd;
 pub use cp::Cp;
-pub use ls::Ls;
+pub use ls::{get_file_type, Ls};
 pub use mkdir::Mkdir;
 pub use mv::Mv;
 pub use open::Open;
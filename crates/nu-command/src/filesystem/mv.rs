@@ -1,11 +1,14 @@
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
-use super::util::get_interactive_confirmation;
+use super::util::{copy_file, expand_glob, get_interactive_confirmation, transfer_result};
 use nu_engine::env::current_dir;
 use nu_engine::CallExt;
 use nu_protocol::ast::Call;
 use nu_protocol::engine::{Command, EngineState, Stack};
-use nu_protocol::{Category, PipelineData, ShellError, Signature, Spanned, SyntaxShape};
+use nu_protocol::{
+    Category, IntoInterruptiblePipelineData, PipelineData, ShellError, Signature, Spanned,
+    SyntaxShape,
+};
 
 #[derive(Clone)]
 pub struct Mv;
@@ -34,6 +37,7 @@ impl Command for Mv {
             )
             .switch("interactive", "ask user to confirm action", Some('i'))
             .switch("force", "suppress error when no file", Some('f'))
+            .switch("verbose", "report a table of the moved files", Some('v'))
             .category(Category::FileSystem)
     }
 
@@ -49,13 +53,12 @@ impl Command for Mv {
         let destination: String = call.req(engine_state, stack, 1)?;
         let interactive = call.has_flag("interactive");
         let force = call.has_flag("force");
+        let verbose = call.has_flag("verbose");
 
         let path = current_dir(engine_state, stack)?;
-        let source = path.join(spanned_source.item.as_str());
         let destination = path.join(destination.as_str());
 
-        let mut sources =
-            glob::glob(&source.to_string_lossy()).map_or_else(|_| Vec::new(), Iterator::collect);
+        let mut sources = expand_glob(&spanned_source, &path)?;
 
         if sources.is_empty() {
             return Err(ShellError::FileNotFound(spanned_source.span));
@@ -66,13 +69,7 @@ impl Command for Mv {
             for (index, file) in sources.iter().enumerate() {
                 let prompt = format!(
                     "Are you shure that you want to move {} to {}?",
-                    file.as_ref()
-                        .map_err(|err| ShellError::SpannedLabeledError(
-                            "Reference error".into(),
-                            err.to_string(),
-                            call.head
-                        ))?
-                        .file_name()
+                    file.file_name()
                         .ok_or_else(|| ShellError::SpannedLabeledError(
                             "File name error".into(),
                             "Unable to get file name".into(),
@@ -128,11 +125,9 @@ impl Command for Mv {
             });
         }
 
-        let some_if_source_is_destination = sources
-            .iter()
-            .find(|f| matches!(f, Ok(f) if destination.starts_with(f)));
+        let some_if_source_is_destination = sources.iter().find(|f| destination.starts_with(f));
         if destination.exists() && destination.is_dir() && sources.len() == 1 {
-            if let Some(Ok(_filename)) = some_if_source_is_destination {
+            if some_if_source_is_destination.is_some() {
                 return Err(ShellError::MoveNotPossible {
                     source_message: "Can't move directory".to_string(),
                     source_span: call.positional[0].span,
@@ -142,22 +137,35 @@ impl Command for Mv {
             }
         }
 
-        if let Some(Ok(_filename)) = some_if_source_is_destination {
+        if some_if_source_is_destination.is_some() {
             sources = sources
                 .into_iter()
-                .filter(|f| matches!(f, Ok(f) if !destination.starts_with(f)))
+                .filter(|f| !destination.starts_with(f))
                 .collect();
         }
 
-        for entry in sources.into_iter().flatten() {
-            move_file(call, &entry, &destination)?
+        let mut results = vec![];
+        for entry in sources {
+            let moved_to = move_file(engine_state, call, &entry, &destination)?;
+            results.push(transfer_result(&entry, &moved_to, "moved", call.head));
         }
 
-        Ok(PipelineData::new(call.head))
+        if verbose {
+            Ok(results
+                .into_iter()
+                .into_pipeline_data(engine_state.ctrlc.clone()))
+        } else {
+            Ok(PipelineData::new(call.head))
+        }
     }
 }
 
-fn move_file(call: &Call, from: &Path, to: &Path) -> Result<(), ShellError> {
+fn move_file(
+    engine_state: &EngineState,
+    call: &Call,
+    from: &Path,
+    to: &Path,
+) -> Result<PathBuf, ShellError> {
     if to.exists() && from.is_dir() && to.is_file() {
         return Err(ShellError::MoveNotPossible {
             source_message: "Can't move a directory".to_string(),
@@ -187,16 +195,37 @@ fn move_file(call: &Call, from: &Path, to: &Path) -> Result<(), ShellError> {
         to.push(from_file_name);
     }
 
-    move_item(call, from, &to)
+    move_item(engine_state, call, from, &to)?;
+
+    Ok(to)
 }
 
-fn move_item(call: &Call, from: &Path, to: &Path) -> Result<(), ShellError> {
-    // We first try a rename, which is a quick operation. If that doesn't work, we'll try a copy
-    // and remove the old file/folder. This is necessary if we're moving across filesystems or devices.
-    std::fs::rename(&from, &to).map_err(|_| ShellError::MoveNotPossible {
+fn move_item(
+    engine_state: &EngineState,
+    call: &Call,
+    from: &Path,
+    to: &Path,
+) -> Result<(), ShellError> {
+    let move_error = || ShellError::MoveNotPossible {
         source_message: "failed to move".to_string(),
         source_span: call.positional[0].span,
         destination_message: "into".to_string(),
         destination_span: call.positional[1].span,
-    })
+    };
+
+    // We first try a rename, which is a quick operation. If that doesn't work, we'll try a copy
+    // and remove the old file/folder. This is necessary if we're moving across filesystems or devices.
+    if std::fs::rename(&from, &to).is_ok() {
+        return Ok(());
+    }
+
+    if !from.is_file() {
+        return Err(move_error());
+    }
+
+    // Only remove the source once all of it has been copied, not when ctrl-c stopped the copy
+    match copy_file(from, to, &engine_state.ctrlc, true) {
+        Ok(Some(_)) => std::fs::remove_file(from).map_err(|_| move_error()),
+        _ => Err(move_error()),
+    }
 }
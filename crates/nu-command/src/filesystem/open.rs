@@ -1,10 +1,12 @@
+use crate::formats::registry::{decl_for_extension, format_for_extension, Direction};
 use nu_engine::CallExt;
-use nu_protocol::ast::Call;
+use nu_protocol::ast::{Call, RangeInclusion};
 use nu_protocol::engine::{Command, EngineState, Stack};
 use nu_protocol::{
-    ByteStream, Category, PipelineData, ShellError, Signature, Spanned, SyntaxShape, Value,
+    ByteStream, Category, DataSource, Example, PipelineData, PipelineMetadata, ShellError,
+    Signature, Spanned, StringStream, SyntaxShape, Value,
 };
-use std::io::{BufRead, BufReader, Read};
+use std::io::{BufRead, BufReader, Read, Seek, SeekFrom};
 
 #[cfg(unix)]
 use std::os::unix::fs::PermissionsExt;
@@ -26,9 +28,53 @@ impl Command for Open {
         Signature::build("open")
             .required("filename", SyntaxShape::Filepath, "the filename to use")
             .switch("raw", "open file as raw binary", Some('r'))
+            .named(
+                "offset",
+                SyntaxShape::Int,
+                "start reading at this byte, implies --raw",
+                None,
+            )
+            .named(
+                "length",
+                SyntaxShape::Int,
+                "read at most this many bytes, implies --raw",
+                None,
+            )
+            .named(
+                "lines",
+                SyntaxShape::Range,
+                "read only this range of lines, counting from 0, as text",
+                None,
+            )
             .category(Category::FileSystem)
     }
 
+    fn extra_usage(&self) -> &str {
+        r#"--offset, --length and --lines read part of a file without loading the rest, for looking
+into large files. An offset past the end of the file, or lines past the last one, give an empty
+stream. The part read isn't parsed, whatever the extension of the file."#
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![
+            Example {
+                description: "Open a file, parsed by its extension",
+                example: "open Cargo.toml",
+                result: None,
+            },
+            Example {
+                description: "Read 64 bytes of a file, starting at byte 1024",
+                example: "open big.log --offset 1024 --length 64",
+                result: None,
+            },
+            Example {
+                description: "Read the lines 100 to 109 of a file",
+                example: "open big.log --lines 100..109",
+                result: None,
+            },
+        ]
+    }
+
     fn run(
         &self,
         engine_state: &EngineState,
@@ -45,6 +91,20 @@ impl Command for Open {
         let arg_span = path.span;
         let path = Path::new(&path.item);
 
+        let offset = byte_bound(call.get_flag(engine_state, stack, "offset")?)?;
+        let length = byte_bound(call.get_flag(engine_state, stack, "length")?)?;
+        let lines = line_range(engine_state, stack, call)?;
+        let partial = offset.is_some() || length.is_some();
+        if partial && lines.is_some() {
+            return Err(ShellError::IncompatibleParametersSingle(
+                "Can't use \"--lines\" with \"--offset\" or \"--length\"".to_string(),
+                call_span,
+            ));
+        }
+        let metadata = Some(PipelineMetadata {
+            data_source: DataSource::FilePath(path.to_path_buf()),
+        });
+
         if permission_denied(&path) {
             #[cfg(unix)]
             let error_msg = format!(
@@ -68,7 +128,7 @@ impl Command for Open {
                 None,
             ))
         } else {
-            let file = match std::fs::File::open(path) {
+            let mut file = match std::fs::File::open(path) {
                 Ok(file) => file,
                 Err(err) => {
                     return Ok(PipelineData::Value(
@@ -84,7 +144,24 @@ impl Command for Open {
                 }
             };
 
-            let buf_reader = BufReader::new(file);
+            if let Some(lines) = lines {
+                return Ok(PipelineData::StringStream(
+                    StringStream::from_stream(lines.read(BufReader::new(file)), ctrlc),
+                    call_span,
+                    metadata,
+                ));
+            }
+
+            if let Some(offset) = offset {
+                // Seeking past the end is fine: reading from there gives nothing
+                file.seek(SeekFrom::Start(offset))
+                    .map_err(|err| ShellError::IOError(err.to_string()))?;
+            }
+            let reader: Box<dyn Read + Send> = match length {
+                Some(length) => Box::new(file.take(length)),
+                None => Box::new(file),
+            };
+            let buf_reader = BufReader::new(reader);
 
             let output = PipelineData::ByteStream(
                 ByteStream {
@@ -92,10 +169,10 @@ impl Command for Open {
                     ctrlc,
                 },
                 call_span,
-                None,
+                metadata,
             );
 
-            let ext = if raw {
+            let ext = if raw || partial {
                 None
             } else {
                 path.extension()
@@ -103,13 +180,21 @@ impl Command for Open {
             };
 
             if let Some(ext) = ext {
-                match engine_state.find_decl(format!("from {}", ext).as_bytes()) {
+                match decl_for_extension(&ext, Direction::From, engine_state) {
                     Some(converter_id) => engine_state.get_decl(converter_id).run(
                         engine_state,
                         stack,
                         &Call::new(),
                         output,
                     ),
+                    // A known format this build can't read, rather than plain text
+                    None if format_for_extension(&ext).is_some() => {
+                        Err(ShellError::SpannedLabeledError(
+                            format!("No parser registered for {}", ext),
+                            "use --raw to open the file as it is".into(),
+                            arg_span,
+                        ))
+                    }
                     None => Ok(output),
                 }
             } else {
@@ -119,6 +204,102 @@ impl Command for Open {
     }
 }
 
+fn byte_bound(bound: Option<Spanned<i64>>) -> Result<Option<u64>, ShellError> {
+    bound
+        .map(|bound| {
+            u64::try_from(bound.item).map_err(|_| {
+                ShellError::UnsupportedInput("byte counts cannot be negative".into(), bound.span)
+            })
+        })
+        .transpose()
+}
+
+/// The lines `--lines` asks for, as how many to skip and how many to read after that
+struct LineRange {
+    skip: usize,
+    take: Option<usize>,
+}
+
+fn line_range(
+    engine_state: &EngineState,
+    stack: &mut Stack,
+    call: &Call,
+) -> Result<Option<LineRange>, ShellError> {
+    let (range, span) = match call.get_flag(engine_state, stack, "lines")? {
+        Some(Value::Range { val, span }) => (*val, span),
+        Some(other) => {
+            return Err(ShellError::UnsupportedInput(
+                format!("expected a range of lines, got {}", other.get_type()),
+                other.span()?,
+            ))
+        }
+        None => return Ok(None),
+    };
+
+    // Lines are read one after another, so only a step of one (or minus one, for a range that
+    // ends before it starts and so is empty) makes sense
+    if !matches!(range.incr, Value::Int { val: 1 | -1, .. }) {
+        return Err(ShellError::UnsupportedInput(
+            "a range of lines can't have a step".into(),
+            span,
+        ));
+    }
+
+    // A range like `100..` is given a placeholder end spanning the whole range, where a written
+    // end only spans itself. The range may come from a variable or a subexpression, so this is
+    // checked on the value rather than on how the flag was written
+    let open_end = range.to.span()? == span;
+
+    let line = |value: &Value| match value {
+        Value::Int { val, .. } => usize::try_from(*val).map_err(|_| {
+            ShellError::UnsupportedInput("line numbers cannot be negative".into(), span)
+        }),
+        _ => Err(ShellError::UnsupportedInput(
+            "line numbers must be integers".into(),
+            span,
+        )),
+    };
+
+    let skip = line(&range.from)?;
+    let take = if open_end {
+        None
+    } else {
+        let end = line(&range.to)? + matches!(range.inclusion, RangeInclusion::Inclusive) as usize;
+        Some(end.saturating_sub(skip))
+    };
+
+    Ok(Some(LineRange { skip, take }))
+}
+
+impl LineRange {
+    /// Stream the lines in the range, each with its line ending. Only the lines up to the end of
+    /// the range are read.
+    fn read<R: BufRead + Send + 'static>(
+        self,
+        mut reader: R,
+    ) -> impl Iterator<Item = Result<String, ShellError>> + Send + 'static {
+        // Some errors, like reading a directory, come back on every read, so the first one ends
+        // the lines
+        let mut failed = false;
+        let lines = std::iter::from_fn(move || {
+            if failed {
+                return None;
+            }
+            let mut line = vec![];
+            match reader.read_until(b'\n', &mut line) {
+                Ok(0) => None,
+                Ok(_) => Some(Ok(String::from_utf8_lossy(&line).to_string())),
+                Err(err) => {
+                    failed = true;
+                    Some(Err(ShellError::IOError(err.to_string())))
+                }
+            }
+        });
+
+        lines.skip(self.skip).take(self.take.unwrap_or(usize::MAX))
+    }
+}
+
 fn permission_denied(dir: impl AsRef<Path>) -> bool {
     match dir.as_ref().read_dir() {
         Err(e) => matches!(e.kind(), std::io::ErrorKind::PermissionDenied),
@@ -153,3 +334,33 @@ impl<R: Read> Iterator for BufferedReader<R> {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// A reader that fails every time, the way reading a directory does
+    struct Failing;
+
+    impl Read for Failing {
+        fn read(&mut self, _buf: &mut [u8]) -> std::io::Result<usize> {
+            Err(std::io::Error::new(
+                std::io::ErrorKind::Other,
+                "always fails",
+            ))
+        }
+    }
+
+    #[test]
+    fn line_range_ends_at_the_first_error() {
+        let range = LineRange {
+            skip: 0,
+            take: None,
+        };
+
+        let lines: Vec<_> = range.read(BufReader::new(Failing)).collect();
+
+        assert_eq!(lines.len(), 1);
+        assert!(lines[0].is_err());
+    }
+}
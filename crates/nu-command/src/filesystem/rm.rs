@@ -1,6 +1,6 @@
 #[cfg(unix)]
 use std::os::unix::prelude::FileTypeExt;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use super::util::get_interactive_confirmation;
 
@@ -9,8 +9,8 @@ use nu_engine::CallExt;
 use nu_protocol::ast::Call;
 use nu_protocol::engine::{Command, EngineState, Stack};
 use nu_protocol::{
-    Category, IntoInterruptiblePipelineData, PipelineData, ShellError, Signature, SyntaxShape,
-    Value,
+    Category, IntoInterruptiblePipelineData, PipelineData, ShellError, Signature, Span,
+    SyntaxShape, Value,
 };
 
 #[derive(Clone)]
@@ -260,7 +260,12 @@ fn rm_helper(call: &Call, args: RmArgs) -> Vec<Value> {
                         };
                     }
 
-                    if let Err(e) = result {
+                    if trash {
+                        match result {
+                            Ok(()) => trash_result(&f, "trashed", call.positional[i].span),
+                            Err(e) => trash_result(&f, &e.to_string(), call.positional[i].span),
+                        }
+                    } else if let Err(e) = result {
                         Value::Error {
                             error: ShellError::RemoveNotPossible(
                                 format!("Could not delete because: {:}\nTry '--trash' flag", e),
@@ -273,6 +278,12 @@ fn rm_helper(call: &Call, args: RmArgs) -> Vec<Value> {
                             span: call.positional[i].span,
                         }
                     }
+                } else if trash {
+                    trash_result(
+                        &f,
+                        "cannot remove, try --recursive",
+                        call.positional[i].span,
+                    )
                 } else {
                     Value::Error {
                         error: ShellError::RemoveNotPossible(
@@ -281,6 +292,8 @@ fn rm_helper(call: &Call, args: RmArgs) -> Vec<Value> {
                         ),
                     }
                 }
+            } else if trash {
+                trash_result(&f, "no such file or directory", call.positional[i].span)
             } else {
                 Value::Error {
                     error: ShellError::RemoveNotPossible(
@@ -292,3 +305,21 @@ fn rm_helper(call: &Call, args: RmArgs) -> Vec<Value> {
         })
         .collect()
 }
+
+/// With `--trash`, every target gets a row, so one failure doesn't hide what did get trashed
+fn trash_result(path: &Path, status: &str, span: Span) -> Value {
+    Value::Record {
+        cols: vec!["path".into(), "status".into()],
+        vals: vec![
+            Value::String {
+                val: path.to_string_lossy().to_string(),
+                span,
+            },
+            Value::String {
+                val: status.into(),
+                span,
+            },
+        ],
+        span,
+    }
+}
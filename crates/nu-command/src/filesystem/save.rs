@@ -1,8 +1,12 @@
+use crate::formats::registry::{decl_for_extension, Direction};
 use nu_engine::CallExt;
 use nu_protocol::ast::Call;
 use nu_protocol::engine::{Command, EngineState, Stack};
-use nu_protocol::{Category, PipelineData, ShellError, Signature, Spanned, SyntaxShape, Value};
-use std::io::Write;
+use nu_protocol::{
+    Category, Example, PipelineData, ShellError, Signature, Spanned, SyntaxShape, Value,
+};
+use std::fs::{File, OpenOptions};
+use std::io::{BufWriter, Write};
 
 use std::path::Path;
 
@@ -18,10 +22,28 @@ impl Command for Save {
         "Save a file."
     }
 
+    fn extra_usage(&self) -> &str {
+        "The extension of the file picks the format, so saving to 'data.json' runs the input through 'to json' first. Other extensions, and --raw, save the input as it is."
+    }
+
     fn signature(&self) -> nu_protocol::Signature {
         Signature::build("save")
             .required("filename", SyntaxShape::Filepath, "the filename to use")
-            .switch("raw", "open file as raw binary", Some('r'))
+            .switch(
+                "raw",
+                "save the input as it is, without converting it",
+                Some('r'),
+            )
+            .switch(
+                "append",
+                "append to the end of the file instead of replacing it",
+                Some('a'),
+            )
+            .switch(
+                "mkdir",
+                "create the directories leading to the file when they are missing",
+                None,
+            )
             .category(Category::FileSystem)
     }
 
@@ -33,6 +55,8 @@ impl Command for Save {
         input: PipelineData,
     ) -> Result<nu_protocol::PipelineData, nu_protocol::ShellError> {
         let raw = call.has_flag("raw");
+        let append = call.has_flag("append");
+        let mkdir = call.has_flag("mkdir");
 
         let span = call.head;
 
@@ -42,7 +66,15 @@ impl Command for Save {
         let arg_span = path.span;
         let path = Path::new(&path.item);
 
-        let mut file = match std::fs::File::create(path) {
+        if mkdir {
+            if let Some(parent) = path.parent() {
+                if let Err(err) = std::fs::create_dir_all(parent) {
+                    return Err(ShellError::CreateNotPossible(err.to_string(), arg_span));
+                }
+            }
+        }
+
+        let file = match open_file(path, append) {
             Ok(file) => file,
             Err(err) => {
                 return Ok(PipelineData::Value(
@@ -65,54 +97,55 @@ impl Command for Save {
                 .map(|name| name.to_string_lossy().to_string())
         };
 
-        if let Some(ext) = ext {
-            match engine_state.find_decl(format!("to {}", ext).as_bytes()) {
-                Some(converter_id) => {
-                    let output = engine_state.get_decl(converter_id).run(
-                        engine_state,
-                        stack,
-                        &Call::new(),
-                        input,
-                    )?;
-
-                    let output = output.into_value(span);
-
-                    match output {
-                        Value::String { val, .. } => {
-                            if let Err(err) = file.write_all(val.as_bytes()) {
-                                return Err(ShellError::IOError(err.to_string()));
-                            }
-
-                            Ok(PipelineData::new(span))
-                        }
-                        Value::Binary { val, .. } => {
-                            if let Err(err) = file.write_all(&val) {
-                                return Err(ShellError::IOError(err.to_string()));
-                            }
-
-                            Ok(PipelineData::new(span))
-                        }
-                        v => Err(ShellError::UnsupportedInput(v.get_type().to_string(), span)),
-                    }
-                }
-                None => {
-                    let output = input.collect_string("", &config)?;
-
-                    if let Err(err) = file.write_all(output.as_bytes()) {
-                        return Err(ShellError::IOError(err.to_string()));
-                    }
+        let converter = ext.and_then(|ext| decl_for_extension(&ext, Direction::To, engine_state));
 
-                    Ok(PipelineData::new(span))
-                }
-            }
-        } else {
-            let output = input.collect_string("", &config)?;
-
-            if let Err(err) = file.write_all(output.as_bytes()) {
-                return Err(ShellError::IOError(err.to_string()));
+        let output = match converter {
+            Some(converter_id) => {
+                engine_state
+                    .get_decl(converter_id)
+                    .run(engine_state, stack, &Call::new(), input)?
             }
+            None => input,
+        };
 
-            Ok(PipelineData::new(span))
+        if let PipelineData::Value(Value::Block { .. }, ..) = output {
+            return Err(ShellError::UnsupportedInput("block".into(), span));
         }
+
+        let mut file = BufWriter::new(file);
+        output.drain_to_writer(&mut file, &config)?;
+
+        file.flush()
+            .map_err(|err| ShellError::IOError(err.to_string()))?;
+
+        Ok(PipelineData::new(span))
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![
+            Example {
+                description: "Save a string to foo.txt in the current directory",
+                example: "echo 'save me' | save foo.txt",
+                result: None,
+            },
+            Example {
+                description: "Save the listing of the current directory as JSON",
+                example: "ls | save listing.json",
+                result: None,
+            },
+            Example {
+                description: "Add a line to the end of a log, creating its directory if needed",
+                example: "echo \"done\\n\" | save --append --mkdir logs/build.log",
+                result: None,
+            },
+        ]
+    }
+}
+
+fn open_file(path: &Path, append: bool) -> std::io::Result<File> {
+    if append {
+        OpenOptions::new().append(true).create(true).open(path)
+    } else {
+        File::create(path)
     }
 }
@@ -1,13 +1,23 @@
-use std::path::{Path, PathBuf};
+use std::fs::{File, Metadata};
+use std::io::{ErrorKind, Read, Write};
+use std::path::{Component, Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use tempfile::NamedTempFile;
 
 use nu_engine::env::current_dir_str;
-use nu_path::canonicalize_with;
+use nu_path::{canonicalize_with, expand_path_with};
 use nu_protocol::engine::{EngineState, Stack};
-use nu_protocol::ShellError;
+use nu_protocol::{ShellError, Span, Spanned, Value};
 
 use dialoguer::Input;
 use std::error::Error;
 
+/// Files at least this big get progress reports while they are copied
+pub const LARGE_FILE_SIZE: u64 = 16 * 1024 * 1024;
+
+const COPY_CHUNK_SIZE: usize = 1024 * 1024;
+
 #[derive(Default)]
 pub struct FileStructure {
     pub resources: Vec<Resource>,
@@ -119,3 +129,246 @@ pub fn get_interactive_confirmation(prompt: String) -> Result<bool, Box<dyn Erro
         Ok(false)
     }
 }
+
+/// The longest leading part of a glob pattern that contains no wildcard
+pub fn glob_prefix(pattern: &Path) -> PathBuf {
+    let mut prefix = PathBuf::new();
+    for c in pattern.components() {
+        if let Component::Normal(os) = c {
+            if os.to_string_lossy().contains('*') {
+                break;
+            }
+        }
+        prefix.push(c);
+    }
+    prefix
+}
+
+/// Expand a glob pattern relative to the current directory into the paths it matches
+pub fn expand_glob(pattern: &Spanned<String>, cwd: &Path) -> Result<Vec<PathBuf>, ShellError> {
+    let path = expand_path_with(&pattern.item, cwd);
+
+    let paths = glob::glob(&path.to_string_lossy()).map_err(|err| {
+        ShellError::SpannedLabeledError(
+            "Error extracting glob pattern".into(),
+            err.to_string(),
+            pattern.span,
+        )
+    })?;
+
+    paths.collect::<Result<Vec<_>, _>>().map_err(|err| {
+        ShellError::SpannedLabeledError(
+            "Error reading glob matches".into(),
+            err.to_string(),
+            pattern.span,
+        )
+    })
+}
+
+/// A file being copied one chunk at a time, so a copy can report progress and stop in between
+///
+/// The copy is written to a temporary file next to the destination, which takes the place of the
+/// destination only once the copy is finished. Dropping it before then removes the temporary
+/// file, and leaves a destination that already existed as it was.
+pub struct FileCopy {
+    reader: File,
+    writer: NamedTempFile,
+    to: PathBuf,
+    metadata: Metadata,
+    buffer: Vec<u8>,
+    copied: u64,
+}
+
+impl FileCopy {
+    pub fn new(from: &Path, to: &Path) -> std::io::Result<Self> {
+        let reader = File::open(from)?;
+        let metadata = reader.metadata()?;
+        let dir = match to.parent() {
+            Some(dir) if !dir.as_os_str().is_empty() => dir,
+            _ => Path::new("."),
+        };
+        // In the same directory, so it can be renamed over the destination
+        let writer = tempfile::Builder::new()
+            .prefix(".nu-copy-")
+            .tempfile_in(dir)?;
+
+        Ok(FileCopy {
+            reader,
+            writer,
+            to: to.to_path_buf(),
+            metadata,
+            buffer: vec![0; COPY_CHUNK_SIZE],
+            copied: 0,
+        })
+    }
+
+    pub fn total(&self) -> u64 {
+        self.metadata.len()
+    }
+
+    pub fn copied(&self) -> u64 {
+        self.copied
+    }
+
+    /// Copy the next chunk. Returns false once the whole file has been read.
+    pub fn step(&mut self) -> std::io::Result<bool> {
+        loop {
+            match self.reader.read(&mut self.buffer) {
+                Ok(0) => return Ok(false),
+                Ok(read) => {
+                    self.writer.write_all(&self.buffer[..read])?;
+                    self.copied += read as u64;
+                    return Ok(true);
+                }
+                // A signal arrived during the read, which isn't a reason to give up
+                Err(e) if e.kind() == ErrorKind::Interrupted => continue,
+                Err(e) => return Err(e),
+            }
+        }
+    }
+
+    /// Finish a copy whose chunks have all been copied, putting it in place of the destination.
+    /// With `preserve`, the access and modification times of the source are carried over.
+    pub fn finish(mut self, preserve: bool) -> std::io::Result<u64> {
+        self.writer.flush()?;
+
+        std::fs::set_permissions(self.writer.path(), self.metadata.permissions())?;
+
+        if preserve {
+            let accessed = filetime::FileTime::from_last_access_time(&self.metadata);
+            let modified = filetime::FileTime::from_last_modification_time(&self.metadata);
+            filetime::set_file_times(self.writer.path(), accessed, modified)?;
+        }
+
+        self.writer.persist(&self.to).map_err(|err| err.error)?;
+
+        Ok(self.copied)
+    }
+}
+
+/// Copy a file, stopping as soon as ctrl-c is pressed. Returns the bytes copied, or `None` when
+/// the copy was interrupted.
+pub fn copy_file(
+    from: &Path,
+    to: &Path,
+    ctrlc: &Option<Arc<AtomicBool>>,
+    preserve: bool,
+) -> std::io::Result<Option<u64>> {
+    let mut copy = FileCopy::new(from, to)?;
+
+    while copy.step()? {
+        if is_interrupted(ctrlc) {
+            return Ok(None);
+        }
+    }
+
+    copy.finish(preserve).map(Some)
+}
+
+/// Whether ctrl-c has been pressed
+pub fn is_interrupted(ctrlc: &Option<Arc<AtomicBool>>) -> bool {
+    match ctrlc {
+        Some(ctrlc) => ctrlc.load(Ordering::SeqCst),
+        None => false,
+    }
+}
+
+/// A row of the table `cp` and `mv` report back
+pub fn transfer_result(source: &Path, destination: &Path, status: &str, span: Span) -> Value {
+    Value::Record {
+        cols: vec!["source".into(), "destination".into(), "status".into()],
+        vals: vec![
+            Value::String {
+                val: source.to_string_lossy().to_string(),
+                span,
+            },
+            Value::String {
+                val: destination.to_string_lossy().to_string(),
+                span,
+            },
+            Value::String {
+                val: status.into(),
+                span,
+            },
+        ],
+        span,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn scratch_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("nu-copy-{}-{}", std::process::id(), name))
+    }
+
+    #[test]
+    fn unfinished_copy_removes_the_file_it_created() {
+        let from = Path::new(env!("CARGO_MANIFEST_DIR")).join("Cargo.toml");
+        let to = scratch_path("created");
+
+        let mut copy = FileCopy::new(&from, &to).expect("start copy");
+        assert!(copy.step().expect("copy a chunk"));
+        drop(copy);
+
+        assert!(!to.exists());
+    }
+
+    #[test]
+    fn unfinished_copy_keeps_an_existing_destination() {
+        let from = Path::new(env!("CARGO_MANIFEST_DIR")).join("Cargo.toml");
+        let to = scratch_path("existing");
+        std::fs::write(&to, "old").expect("write destination");
+
+        let mut copy = FileCopy::new(&from, &to).expect("start copy");
+        assert!(copy.step().expect("copy a chunk"));
+        drop(copy);
+
+        assert_eq!(std::fs::read(&to).expect("read destination"), b"old");
+        let _ = std::fs::remove_file(&to);
+    }
+
+    #[test]
+    fn finished_copy_replaces_an_existing_destination() {
+        let from = Path::new(env!("CARGO_MANIFEST_DIR")).join("Cargo.toml");
+        let to = scratch_path("replaced");
+        std::fs::write(&to, "old").expect("write destination");
+
+        copy_file(&from, &to, &None, false).expect("copy");
+
+        assert_eq!(
+            std::fs::read(&from).expect("read source"),
+            std::fs::read(&to).expect("read copy")
+        );
+        let _ = std::fs::remove_file(&to);
+    }
+
+    #[test]
+    fn finished_copy_is_kept() {
+        let from = Path::new(env!("CARGO_MANIFEST_DIR")).join("Cargo.toml");
+        let to = scratch_path("finished");
+
+        let copied = copy_file(&from, &to, &None, false).expect("copy");
+
+        assert_eq!(
+            copied,
+            Some(std::fs::metadata(&from).expect("metadata").len())
+        );
+        assert_eq!(
+            std::fs::read(&from).expect("read source"),
+            std::fs::read(&to).expect("read copy")
+        );
+        let _ = std::fs::remove_file(&to);
+    }
+
+    #[test]
+    fn copy_stops_on_ctrlc() {
+        let from = Path::new(env!("CARGO_MANIFEST_DIR")).join("Cargo.toml");
+        let to = scratch_path("interrupted");
+        let ctrlc = Some(Arc::new(AtomicBool::new(true)));
+
+        assert_eq!(copy_file(&from, &to, &ctrlc, false).expect("copy"), None);
+        assert!(!to.exists());
+    }
+}
@@ -26,6 +26,12 @@ impl Command for Compact {
         "Creates a table with non-empty rows."
     }
 
+    fn extra_usage(&self) -> &str {
+        r#"A value counts as empty when it's nothing, an empty string, an empty list, or a record
+with no columns. With columns, records missing any of them or holding an empty value
+in one of them are dropped. Without columns, empty values themselves are dropped."#
+    }
+
     fn run(
         &self,
         engine_state: &EngineState,
@@ -66,6 +72,31 @@ impl Command for Compact {
                     span: Span::test_data(),
                 }),
             },
+            Example {
+                description: "Filter out empty strings and lists",
+                example: r#"echo ["a", "", [], "b"] | compact"#,
+                result: Some(Value::List {
+                    vals: vec![Value::test_string("a"), Value::test_string("b")],
+                    span: Span::test_data(),
+                }),
+            },
+            Example {
+                description: "Keep only the symlinks of a long listing",
+                example: "ls -l | compact target",
+                result: None,
+            },
+            Example {
+                description: "Filter out all records where 'name' is empty",
+                example: r#"echo [[name]; [""] ["nu"]] | compact name"#,
+                result: Some(Value::List {
+                    vals: vec![Value::Record {
+                        cols: vec!["name".into()],
+                        vals: vec![Value::test_string("nu")],
+                        span: Span::test_data(),
+                    }],
+                    span: Span::test_data(),
+                }),
+            },
         ]
     }
 }
@@ -80,24 +111,16 @@ pub fn compact(
     input.filter(
         move |item| {
             match item {
-                // Nothing is filtered out
-                Value::Nothing { .. } => false,
-                Value::Record { .. } => {
-                    for column in columns.iter() {
-                        match item.get_data_by_key(column) {
-                            None => return false,
-                            Some(x) => {
-                                if let Value::Nothing { .. } = x {
-                                    return false;
-                                }
-                            }
-                        }
-                    }
-                    // No defined columns contained Nothing
-                    true
+                Value::Record { .. } if !columns.is_empty() => {
+                    columns
+                        .iter()
+                        .all(|column| match item.get_data_by_key(column) {
+                            Some(x) => !x.is_empty(),
+                            None => false,
+                        })
                 }
-                // Any non-Nothing, non-record should be kept
-                _ => true,
+                // Without columns, the value itself has to hold something
+                _ => !item.is_empty(),
             }
         },
         engine_state.ctrlc.clone(),
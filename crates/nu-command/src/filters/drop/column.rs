@@ -1,9 +1,8 @@
 use nu_engine::CallExt;
-use nu_protocol::ast::{Call, CellPath};
+use nu_protocol::ast::Call;
 use nu_protocol::engine::{Command, EngineState, Stack};
 use nu_protocol::{
-    Category, FromValue, IntoInterruptiblePipelineData, IntoPipelineData, PipelineData, ShellError,
-    Signature, Span, SyntaxShape, Value,
+    Category, Example, PipelineData, ShellError, Signature, Span, Spanned, SyntaxShape, Value,
 };
 
 #[derive(Clone)]
@@ -28,6 +27,10 @@ impl Command for DropColumn {
         "Remove the last number of columns. If you want to remove columns by name, try 'reject'."
     }
 
+    fn extra_usage(&self) -> &str {
+        "Each row loses its own last columns, so a stream is trimmed as it comes. Asking for more columns than a row has leaves it empty."
+    }
+
     fn run(
         &self,
         engine_state: &EngineState,
@@ -36,128 +39,86 @@ impl Command for DropColumn {
         input: PipelineData,
     ) -> Result<PipelineData, ShellError> {
         // the number of columns to drop
-        let columns: Option<i64> = call.opt(engine_state, stack, 0)?;
-        let span = call.head;
-
-        let columns_to_drop = if let Some(quantity) = columns {
-            quantity
-        } else {
-            1
+        let columns: Option<Spanned<i64>> = call.opt(engine_state, stack, 0)?;
+
+        let columns_to_drop = match columns {
+            Some(Spanned { item, span }) if item < 0 => {
+                return Err(ShellError::SpannedLabeledError(
+                    "Negative column count".into(),
+                    "expected zero or more columns to drop".into(),
+                    span,
+                ))
+            }
+            Some(Spanned { item, .. }) => item as usize,
+            None => 1,
         };
 
-        dropcol(engine_state, span, input, columns_to_drop)
+        input.map(
+            move |value| dropcol(value, columns_to_drop),
+            engine_state.ctrlc.clone(),
+        )
     }
-}
-
-fn dropcol(
-    engine_state: &EngineState,
-    span: Span,
-    input: PipelineData,
-    columns: i64, // the number of columns to drop
-) -> Result<PipelineData, ShellError> {
-    let mut keep_columns = vec![];
 
-    match input {
-        PipelineData::Value(
-            Value::List {
-                vals: input_vals,
-                span,
+    fn examples(&self) -> Vec<Example> {
+        vec![
+            Example {
+                description: "Remove the last column of a table",
+                example: "[[lib, extension]; [nu-lib, rs] [nu-core, rb]] | drop column",
+                result: Some(Value::List {
+                    vals: vec![
+                        Value::Record {
+                            cols: vec!["lib".into()],
+                            vals: vec![Value::test_string("nu-lib")],
+                            span: Span::test_data(),
+                        },
+                        Value::Record {
+                            cols: vec!["lib".into()],
+                            vals: vec![Value::test_string("nu-core")],
+                            span: Span::test_data(),
+                        },
+                    ],
+                    span: Span::test_data(),
+                }),
             },
-            ..,
-        ) => {
-            let mut output = vec![];
-            let input_cols = get_input_cols(input_vals.clone());
-            let kc = get_keep_columns(input_cols, columns);
-            keep_columns = get_cellpath_columns(kc, span);
-
-            for input_val in input_vals {
-                let mut cols = vec![];
-                let mut vals = vec![];
-
-                for path in &keep_columns {
-                    let fetcher = input_val.clone().follow_cell_path(&path.members)?;
-                    cols.push(path.into_string());
-                    vals.push(fetcher);
-                }
-                output.push(Value::Record { cols, vals, span })
-            }
-
-            Ok(output
-                .into_iter()
-                .into_pipeline_data(engine_state.ctrlc.clone()))
-        }
-        PipelineData::ListStream(stream, ..) => {
-            let mut output = vec![];
-
-            let v: Vec<_> = stream.into_iter().collect();
-            let input_cols = get_input_cols(v.clone());
-            let kc = get_keep_columns(input_cols, columns);
-            keep_columns = get_cellpath_columns(kc, span);
-
-            for input_val in v {
-                let mut cols = vec![];
-                let mut vals = vec![];
-
-                for path in &keep_columns {
-                    let fetcher = input_val.clone().follow_cell_path(&path.members)?;
-                    cols.push(path.into_string());
-                    vals.push(fetcher);
-                }
-                output.push(Value::Record { cols, vals, span })
-            }
-
-            Ok(output
-                .into_iter()
-                .into_pipeline_data(engine_state.ctrlc.clone()))
-        }
-        PipelineData::Value(v, ..) => {
-            let mut cols = vec![];
-            let mut vals = vec![];
-
-            for cell_path in &keep_columns {
-                let result = v.clone().follow_cell_path(&cell_path.members)?;
-
-                cols.push(cell_path.into_string());
-                vals.push(result);
-            }
-
-            Ok(Value::Record { cols, vals, span }.into_pipeline_data())
-        }
-        x => Ok(x),
-    }
-}
-
-fn get_input_cols(input: Vec<Value>) -> Vec<String> {
-    let rec = input.first();
-    match rec {
-        Some(Value::Record { cols, vals: _, .. }) => cols.to_vec(),
-        _ => vec!["".to_string()],
+            Example {
+                description: "Remove the last two columns of a record",
+                example: "{a: 1, b: 2, c: 3} | drop column 2",
+                result: Some(Value::Record {
+                    cols: vec!["a".into()],
+                    vals: vec![Value::test_int(1)],
+                    span: Span::test_data(),
+                }),
+            },
+        ]
     }
 }
 
-fn get_cellpath_columns(keep_cols: Vec<String>, span: Span) -> Vec<CellPath> {
-    let mut output = vec![];
-    for keep_col in keep_cols {
-        let val = Value::String {
-            val: keep_col,
+fn dropcol(value: Value, columns: usize) -> Value {
+    match value {
+        Value::Record {
+            mut cols,
+            mut vals,
             span,
-        };
-        let cell_path = match CellPath::from_value(&val) {
-            Ok(v) => v,
-            Err(_) => return vec![],
-        };
-        output.push(cell_path);
+        } => {
+            let keep = cols.len().saturating_sub(columns);
+            cols.truncate(keep);
+            vals.truncate(keep);
+
+            Value::Record { cols, vals, span }
+        }
+        // Values that aren't records have no columns to drop
+        value => value,
     }
-    output
 }
 
-fn get_keep_columns(input: Vec<String>, mut num_of_columns_to_drop: i64) -> Vec<String> {
-    let vlen: i64 = input.len() as i64;
+#[cfg(test)]
+mod test {
+    use super::*;
 
-    if num_of_columns_to_drop > vlen {
-        num_of_columns_to_drop = vlen;
-    }
+    #[test]
+    fn test_examples() {
+        use crate::test_examples;
 
-    let num_of_columns_to_keep = (vlen - num_of_columns_to_drop) as usize;
-    input[0..num_of_columns_to_keep].to_vec()
+        test_examples(DropColumn {})
+    }
 }
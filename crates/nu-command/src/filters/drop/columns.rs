@@ -0,0 +1,83 @@
+use crate::filters::keep::filter_columns;
+use nu_protocol::ast::Call;
+use nu_protocol::engine::{Command, EngineState, Stack};
+use nu_protocol::{
+    Category, Example, PipelineData, ShellError, Signature, Span, SyntaxShape, Value,
+};
+
+#[derive(Clone)]
+pub struct DropColumns;
+
+impl Command for DropColumns {
+    fn name(&self) -> &str {
+        "drop columns"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build(self.name())
+            .required(
+                "predicate",
+                SyntaxShape::Block(Some(vec![SyntaxShape::String, SyntaxShape::Any])),
+                "the predicate, given the name and the value of a column, that removed columns match",
+            )
+            .category(Category::Filters)
+    }
+
+    fn usage(&self) -> &str {
+        "Remove the columns for which a predicate is true. To remove the last columns, try 'drop column'."
+    }
+
+    fn extra_usage(&self) -> &str {
+        r#"The predicate runs on every column of every row, so rows with different columns are each
+filtered on their own. A predicate that looks at the values can remove a column from one row and
+leave it in another. Values that aren't records are passed on as they are."#
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![
+            Example {
+                description: "Remove the columns whose name starts with 'a'",
+                example: "[[ab ac b]; [1 2 3]] | drop columns {|name| $name =~ '^a' }",
+                result: Some(Value::List {
+                    vals: vec![Value::Record {
+                        cols: vec!["b".into()],
+                        vals: vec![Value::test_int(3)],
+                        span: Span::test_data(),
+                    }],
+                    span: Span::test_data(),
+                }),
+            },
+            Example {
+                description: "Remove the columns that are empty",
+                example: "{a: 1, b: '', c: 3} | drop columns {|name, value| $value == '' }",
+                result: Some(Value::Record {
+                    cols: vec!["a".into(), "c".into()],
+                    vals: vec![Value::test_int(1), Value::test_int(3)],
+                    span: Span::test_data(),
+                }),
+            },
+        ]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        filter_columns(engine_state, stack, call, input, false)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_examples() {
+        use crate::test_examples;
+
+        test_examples(DropColumns {})
+    }
+}
@@ -1,7 +1,9 @@
 pub mod column;
+pub mod columns;
 pub mod command;
 pub mod nth;
 
 pub use column::DropColumn;
+pub use columns::DropColumns;
 pub use command::Drop;
 pub use nth::DropNth;
@@ -0,0 +1,114 @@
+use nu_engine::CallExt;
+use nu_protocol::ast::Call;
+use nu_protocol::engine::{Command, EngineState, Stack};
+use nu_protocol::{
+    Category, Example, IntoInterruptiblePipelineData, PipelineData, ShellError, Signature, Span,
+    SyntaxShape, Value,
+};
+
+#[derive(Clone)]
+pub struct Enumerate;
+
+impl Command for Enumerate {
+    fn name(&self) -> &str {
+        "enumerate"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("enumerate")
+            .named(
+                "offset",
+                SyntaxShape::Int,
+                "the index of the first item (default 0)",
+                Some('o'),
+            )
+            .category(Category::Filters)
+    }
+
+    fn usage(&self) -> &str {
+        "Pair each item of the input with its index, as records with an index and an item."
+    }
+
+    fn extra_usage(&self) -> &str {
+        "Rows of a table become the item as they are, so their columns are under item. The input is read as it comes."
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let head = call.head;
+        let offset: i64 = call.get_flag(engine_state, stack, "offset")?.unwrap_or(0);
+
+        Ok(input
+            .into_iter()
+            .enumerate()
+            .map(move |(idx, item)| {
+                let index = i64::try_from(idx)
+                    .ok()
+                    .and_then(|idx| offset.checked_add(idx));
+
+                match index {
+                    Some(index) => Value::Record {
+                        cols: vec!["index".into(), "item".into()],
+                        vals: vec![Value::int(index, head), item],
+                        span: head,
+                    },
+                    None => Value::Error {
+                        error: ShellError::OperatorOverflow("the index overflowed".into(), head),
+                    },
+                }
+            })
+            .into_pipeline_data(engine_state.ctrlc.clone()))
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        let indexed = |first: i64| {
+            Some(Value::List {
+                vals: ["a", "b"]
+                    .iter()
+                    .zip(first..)
+                    .map(|(item, index)| Value::Record {
+                        cols: vec!["index".into(), "item".into()],
+                        vals: vec![Value::test_int(index), Value::test_string(*item)],
+                        span: Span::test_data(),
+                    })
+                    .collect(),
+                span: Span::test_data(),
+            })
+        };
+
+        vec![
+            Example {
+                description: "Number the items of a list",
+                example: "[a b] | enumerate",
+                result: indexed(0),
+            },
+            Example {
+                description: "Number the items from one, for display",
+                example: "[a b] | enumerate --offset 1",
+                result: indexed(1),
+            },
+            Example {
+                description: "Keep every other file, by index",
+                example: "ls | enumerate | where index mod 2 == 0 | get item",
+                result: None,
+            },
+        ]
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_examples() {
+        use crate::test_examples;
+
+        test_examples(Enumerate {})
+    }
+}
@@ -4,7 +4,7 @@ use nu_protocol::ast::Call;
 use nu_protocol::engine::{Command, EngineState, Stack};
 use nu_protocol::{
     Category, Example, IntoInterruptiblePipelineData, PipelineData, ShellError, Signature, Span,
-    SyntaxShape, Value,
+    Spanned, SyntaxShape, Value,
 };
 
 #[derive(Clone)]
@@ -27,6 +27,12 @@ impl Command for Every {
                 "skip the rows that would be returned, instead of selecting them",
                 Some('s'),
             )
+            .named(
+                "offset",
+                SyntaxShape::Int,
+                "start counting from this row instead of the first one",
+                Some('o'),
+            )
             .category(Category::Filters)
     }
 
@@ -34,6 +40,15 @@ impl Command for Every {
         "Show (or skip) every n-th row, starting from the first one."
     }
 
+    fn extra_usage(&self) -> &str {
+        r#"Rows are counted from zero, so 'every 10' returns rows 0, 10, 20 and so on: for 1..100
+that is 1, 11, 21. With --offset n the count starts at row n instead, so
+'1..100 | every 10 --offset 9' returns 10, 20, 30. The rows before the offset are never
+selected, which means --skip returns them along with the rest it doesn't select.
+
+The input is read as it comes, so every works on endless streams too."#
+    }
+
     fn examples(&self) -> Vec<Example> {
         vec![
             Example {
@@ -44,6 +59,14 @@ impl Command for Every {
                     span: Span::test_data(),
                 }),
             },
+            Example {
+                example: "1..10 | every 3 --offset 2",
+                description: "Get every third row, starting from the third",
+                result: Some(Value::List {
+                    vals: vec![Value::test_int(3), Value::test_int(6), Value::test_int(9)],
+                    span: Span::test_data(),
+                }),
+            },
             Example {
                 example: "[1 2 3 4 5] | every 2 --skip",
                 description: "Skip every second row",
@@ -68,12 +91,19 @@ impl Command for Every {
         };
 
         let skip = call.has_flag("skip");
+        let offset = match call.get_flag::<Spanned<i64>>(engine_state, stack, "offset")? {
+            Some(offset) => usize::try_from(offset.item).map_err(|_| {
+                ShellError::UnsupportedInput("offset cannot be negative".into(), offset.span)
+            })?,
+            None => 0,
+        };
 
         Ok(input
-            .into_iter()
+            .into_interruptible_iter(engine_state.ctrlc.clone())
             .enumerate()
             .filter_map(move |(i, value)| {
-                if (i % stride != 0) == skip {
+                let selected = i >= offset && (i - offset) % stride == 0;
+                if selected != skip {
                     Some(value)
                 } else {
                     None
@@ -70,7 +70,9 @@ fn first_helper(
         None => 1,
     };
 
-    let mut input_peek = input.into_iter().peekable();
+    let mut input_peek = input
+        .into_interruptible_iter(engine_state.ctrlc.clone())
+        .peekable();
     if input_peek.peek().is_some() {
         match input_peek
             .peek()
@@ -22,6 +22,11 @@ impl Command for Flatten {
                 SyntaxShape::String,
                 "optionally flatten data by column",
             )
+            .switch(
+                "all",
+                "keep flattening until nothing nested is left",
+                Some('a'),
+            )
             .category(Category::Filters)
     }
 
@@ -29,6 +34,14 @@ impl Command for Flatten {
         "Flatten the table."
     }
 
+    fn extra_usage(&self) -> &str {
+        r#"Each pass expands one level: a list becomes its items, a nested record
+becomes columns of its parent and a column holding a list becomes one row
+per item. A nested column whose name is already taken is prefixed with the
+name of the column it came from, as in 'meta_name'. With --all, flattening
+is repeated until no lists or records are left."#
+    }
+
     fn run(
         &self,
         engine_state: &EngineState,
@@ -71,6 +84,29 @@ impl Command for Flatten {
                 example: "[[origin, crate, versions]; [World, ([[name]; ['nu-cli']]), ['0.21', '0.22']]] | flatten versions | last | get versions",
                 result: None, //Some(Value::test_string("0.22")),
             },
+            Example {
+                description: "Flatten a nested record into columns of its parent",
+                example: "{ name: a, meta: { size: 1, name: b } } | flatten",
+                result: Some(Value::List {
+                    vals: vec![Value::test_record(
+                        vec!["name", "size", "meta_name"],
+                        vec![
+                            Value::test_string("a"),
+                            Value::test_int(1),
+                            Value::test_string("b"),
+                        ],
+                    )],
+                    span: Span::test_data(),
+                }),
+            },
+            Example {
+                description: "Flatten every level of nesting",
+                example: "[[1 [2 [3]]]] | flatten --all",
+                result: Some(Value::List {
+                    vals: vec![Value::test_int(1), Value::test_int(2), Value::test_int(3)],
+                    span: Span::test_data(),
+                }),
+            },
             Example {
                 description: "Flatten inner table",
                 example: "{ a: b, d: [ 1 2 3 4 ],  e: [ 4 3  ] } | flatten",
@@ -112,13 +148,37 @@ fn flatten(
 ) -> Result<nu_protocol::PipelineData, nu_protocol::ShellError> {
     let tag = call.head;
     let columns: Vec<CellPath> = call.rest(engine_state, stack, 0)?;
+    let all = call.has_flag("all");
 
     input.flat_map(
-        move |item| flat_value(&columns, &item, tag),
+        move |item| {
+            if all {
+                flat_all(&columns, item, tag)
+            } else {
+                flat_value(&columns, &item, tag)
+            }
+        },
         engine_state.ctrlc.clone(),
     )
 }
 
+/// Flatten pass after pass, stopping at values a pass leaves untouched. Values are compared
+/// with `total_cmp`, since `==` never holds for values like ranges that have no partial order.
+fn flat_all(columns: &[CellPath], item: Value, name_tag: Span) -> Vec<Value> {
+    let flattened = flat_value(columns, &item, name_tag);
+
+    if matches!(item, Value::Error { .. })
+        || flattened.len() == 1 && flattened[0].total_cmp(&item).is_eq()
+    {
+        return flattened;
+    }
+
+    flattened
+        .into_iter()
+        .flat_map(|value| flat_all(columns, value, name_tag))
+        .collect()
+}
+
 enum TableInside<'a> {
     Entries(&'a str, &'a Span, Vec<&'a Value>),
 }
@@ -201,6 +261,19 @@ fn flat_value(columns: &[CellPath], item: &Value, _name_tag: Span) -> Vec<Value>
                             }
                         }
                     }
+                    Value::Record {
+                        cols: inner_cols,
+                        vals: inner_vals,
+                        ..
+                    } if column_requested.is_some() || columns.is_empty() => {
+                        for (k, v) in inner_cols.iter().zip(inner_vals.iter()) {
+                            if out.contains_key(k) {
+                                out.insert(format!("{}_{}", column, k), v.clone());
+                            } else {
+                                out.insert(k.to_string(), v.clone());
+                            }
+                        }
+                    }
                     Value::List {
                         vals: values,
                         span: _,
@@ -1,5 +1,5 @@
-use nu_engine::CallExt;
-use nu_protocol::ast::{Call, CellPath};
+use nu_engine::{eval_block, CallExt};
+use nu_protocol::ast::{Call, CellPath, PathMember};
 use nu_protocol::engine::{Command, EngineState, Stack};
 use nu_protocol::{
     Category, Example, IntoInterruptiblePipelineData, IntoPipelineData, PipelineData, Signature,
@@ -31,6 +31,12 @@ impl Command for Get {
                 "return nothing if path can't be found",
                 Some('i'),
             )
+            .named(
+                "default",
+                SyntaxShape::Any,
+                "value to use where the path is missing or null, or a block computing it",
+                Some('d'),
+            )
             .category(Category::Filters)
     }
 
@@ -45,8 +51,57 @@ impl Command for Get {
         let cell_path: CellPath = call.req(engine_state, stack, 0)?;
         let rest: Vec<CellPath> = call.rest(engine_state, stack, 1)?;
         let ignore_errors = call.has_flag("ignore-errors");
+        let default: Option<Value> = call.get_flag(engine_state, stack, "default")?;
         let ctrlc = engine_state.ctrlc.clone();
 
+        if let Some(default) = default {
+            // A block is only run once, its result is shared by every missing value
+            let default = match default {
+                Value::Block { val, captures, .. } => {
+                    let block = engine_state.get_block(val);
+                    let mut stack = stack.captures_to_stack(&captures);
+                    eval_block(engine_state, &mut stack, block, PipelineData::new(span))?
+                        .into_value(span)
+                }
+                default => default,
+            };
+
+            // A stream is followed row by row as it is read, unless it's indexed into
+            let input = match input {
+                PipelineData::ListStream(mut stream, ..) if rest.is_empty() => {
+                    let members = cell_path.members;
+
+                    if let Some(PathMember::Int { val, .. }) = members.first() {
+                        let row = stream.nth(*val);
+                        let value = match row {
+                            Some(row) => row.follow_cell_path_or(&members[1..], &default),
+                            None => default,
+                        };
+
+                        return Ok(value.into_pipeline_data());
+                    }
+
+                    return Ok(stream
+                        .map(move |row| row.follow_cell_path_or(&members, &default))
+                        .into_pipeline_data(ctrlc));
+                }
+                input => input,
+            };
+
+            let input = input.into_value(span);
+            let mut output = vec![];
+
+            for path in vec![cell_path].into_iter().chain(rest) {
+                output.push(input.clone().follow_cell_path_or(&path.members, &default));
+            }
+
+            return if output.len() == 1 {
+                Ok(output.remove(0).into_pipeline_data())
+            } else {
+                Ok(output.into_iter().into_pipeline_data(ctrlc))
+            };
+        }
+
         if rest.is_empty() {
             let output = input
                 .follow_cell_path(&cell_path.members, call.head)
@@ -99,6 +154,16 @@ impl Command for Get {
                 example: "ls | get 2.name",
                 result: None,
             },
+            Example {
+                description: "Extract the link targets, with 'none' for entries that aren't links",
+                example: "ls -l | get target --default 'none'",
+                result: None,
+            },
+            Example {
+                description: "Extract a column, with a default for the rows which lack it",
+                example: "[{a: 1} {b: 2}] | get a -d 0",
+                result: None,
+            },
             Example {
                 description: "Extract the cpu list from the sys information record",
                 example: "sys | get cpu",
@@ -91,7 +91,9 @@ pub fn group_by(
     let name = call.head;
 
     let grouper: Option<Value> = call.opt(engine_state, stack, 0)?;
-    let values: Vec<Value> = input.into_iter().collect();
+    let values: Vec<Value> = input
+        .into_interruptible_iter(engine_state.ctrlc.clone())
+        .collect();
     let mut keys: Vec<Result<String, ShellError>> = vec![];
     let mut group_strategy = Grouper::ByColumn(None);
 
@@ -0,0 +1,174 @@
+use nu_engine::CallExt;
+use nu_protocol::ast::Call;
+use nu_protocol::engine::{Command, EngineState, Stack};
+use nu_protocol::{
+    Category, Example, IntoPipelineData, PipelineData, ShellError, Signature, Span, Spanned,
+    SyntaxShape, Value,
+};
+use std::cmp::Ordering;
+
+/// The width of the bar of the most frequent value
+const BAR_WIDTH: usize = 20;
+
+#[derive(Clone)]
+pub struct Histogram;
+
+impl Command for Histogram {
+    fn name(&self) -> &str {
+        "histogram"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("histogram")
+            .optional(
+                "column",
+                SyntaxShape::String,
+                "the column whose values to count (the values themselves if not given)",
+            )
+            .switch(
+                "bars",
+                "add a bar showing each count relative to the largest",
+                Some('b'),
+            )
+            .category(Category::Filters)
+    }
+
+    fn usage(&self) -> &str {
+        "Count how often each distinct value occurs, most frequent first."
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let head = call.head;
+        let column: Option<Spanned<String>> = call.opt(engine_state, stack, 0)?;
+        let bars = call.has_flag("bars");
+
+        // Values aren't hashable, so the buckets are kept in the order values were first seen,
+        // with an index into them sorted by `bucket_cmp` to find a value's bucket
+        let mut counts: Vec<(Value, usize)> = vec![];
+        let mut sorted: Vec<usize> = vec![];
+        for value in input.into_interruptible_iter(engine_state.ctrlc.clone()) {
+            let value = match &column {
+                Some(column) => value.get_data_by_key(&column.item).ok_or_else(|| {
+                    ShellError::CantFindColumn(column.span, value.span().unwrap_or(head))
+                })?,
+                None => value,
+            };
+            if let Value::Error { error } = value {
+                return Err(error);
+            }
+
+            match sorted.binary_search_by(|&bucket| bucket_cmp(&counts[bucket].0, &value)) {
+                Ok(position) => counts[sorted[position]].1 += 1,
+                Err(position) => {
+                    sorted.insert(position, counts.len());
+                    counts.push((value, 1));
+                }
+            }
+        }
+
+        // A stable sort, so values with the same count stay in the order they were first seen
+        counts.sort_by(|(_, a), (_, b)| b.cmp(a));
+
+        let max = counts.first().map_or(0, |(_, count)| *count);
+        let value_column = column.map_or_else(|| "value".to_string(), |column| column.item);
+
+        let rows = counts
+            .into_iter()
+            .map(|(value, count)| {
+                let mut cols = vec![value_column.clone(), "count".into()];
+                let mut vals = vec![value, Value::int(count as i64, head)];
+                if bars {
+                    cols.push("bar".into());
+                    vals.push(Value::string(bar(count, max), head));
+                }
+
+                Value::Record {
+                    cols,
+                    vals,
+                    span: head,
+                }
+            })
+            .collect();
+
+        Ok(Value::List {
+            vals: rows,
+            span: head,
+        }
+        .into_pipeline_data())
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![
+            Example {
+                description: "Count the files, directories and symlinks in the current directory",
+                example: "ls | histogram type",
+                result: None,
+            },
+            Example {
+                description: "Count the values of a list",
+                example: "[a b a c a b] | histogram",
+                result: Some(Value::List {
+                    vals: vec![("a", 3), ("b", 2), ("c", 1)]
+                        .into_iter()
+                        .map(|(value, count)| Value::Record {
+                            cols: vec!["value".into(), "count".into()],
+                            vals: vec![Value::test_string(value), Value::test_int(count)],
+                            span: Span::test_data(),
+                        })
+                        .collect(),
+                    span: Span::test_data(),
+                }),
+            },
+            Example {
+                description: "Show the counts of a column as bars",
+                example: "[[lang]; [rust] [go] [rust] [rust]] | histogram lang --bars",
+                result: Some(Value::List {
+                    vals: vec![("rust", 3, "********************"), ("go", 1, "*******")]
+                        .into_iter()
+                        .map(|(value, count, bar)| Value::Record {
+                            cols: vec!["lang".into(), "count".into(), "bar".into()],
+                            vals: vec![
+                                Value::test_string(value),
+                                Value::test_int(count),
+                                Value::test_string(bar),
+                            ],
+                            span: Span::test_data(),
+                        })
+                        .collect(),
+                    span: Span::test_data(),
+                }),
+            },
+        ]
+    }
+}
+
+/// The order of the buckets. Values are compared as values, with the type breaking ties, so `1`
+/// and `1.0` (or `1` and `"1"`) are counted apart even though they print the same
+fn bucket_cmp(lhs: &Value, rhs: &Value) -> Ordering {
+    lhs.total_cmp(rhs)
+        .then_with(|| lhs.get_type().to_string().cmp(&rhs.get_type().to_string()))
+}
+
+/// A bar as long, relative to `BAR_WIDTH`, as `count` is to `max`. Rounded up, so every value
+/// that occurs gets at least one mark.
+fn bar(count: usize, max: usize) -> String {
+    "*".repeat((count * BAR_WIDTH + max - 1) / max)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_examples() {
+        use crate::test_examples;
+
+        test_examples(Histogram {})
+    }
+}
@@ -0,0 +1,151 @@
+use nu_engine::{eval_block, CallExt};
+use nu_protocol::{
+    ast::Call,
+    engine::{CaptureBlock, Command, EngineState, Stack},
+    Category, Example, PipelineData, ShellError, Signature, Span, SyntaxShape, Value,
+};
+
+#[derive(Clone)]
+pub struct KeepColumns;
+
+impl Command for KeepColumns {
+    fn name(&self) -> &str {
+        "keep columns"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build(self.name())
+            .required(
+                "predicate",
+                SyntaxShape::Block(Some(vec![SyntaxShape::String, SyntaxShape::Any])),
+                "the predicate, given the name and the value of a column, that kept columns match",
+            )
+            .category(Category::Filters)
+    }
+
+    fn usage(&self) -> &str {
+        "Keep the columns for which a predicate is true."
+    }
+
+    fn extra_usage(&self) -> &str {
+        r#"The predicate runs on every column of every row, so rows with different columns are each
+filtered on their own. A predicate that looks at the values can keep a column in one row and
+drop it in another. Values that aren't records are passed on as they are."#
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![
+            Example {
+                description: "Keep the columns whose name starts with 'a'",
+                example: "[[ab ac b]; [1 2 3]] | keep columns {|name| $name =~ '^a' }",
+                result: Some(Value::List {
+                    vals: vec![Value::Record {
+                        cols: vec!["ab".into(), "ac".into()],
+                        vals: vec![Value::test_int(1), Value::test_int(2)],
+                        span: Span::test_data(),
+                    }],
+                    span: Span::test_data(),
+                }),
+            },
+            Example {
+                description: "Keep the columns that have a value above 1",
+                example: "{a: 1, b: 2, c: 3} | keep columns {|name, value| $value > 1 }",
+                result: Some(Value::Record {
+                    cols: vec!["b".into(), "c".into()],
+                    vals: vec![Value::test_int(2), Value::test_int(3)],
+                    span: Span::test_data(),
+                }),
+            },
+        ]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        filter_columns(engine_state, stack, call, input, true)
+    }
+}
+
+/// Keep the columns of each row that the predicate block in the call matches, or with `keep` off
+/// drop them. Shared with `drop columns`.
+pub(crate) fn filter_columns(
+    engine_state: &EngineState,
+    stack: &mut Stack,
+    call: &Call,
+    input: PipelineData,
+    keep: bool,
+) -> Result<PipelineData, ShellError> {
+    let span = call.head;
+
+    let capture_block: CaptureBlock = call.req(engine_state, stack, 0)?;
+
+    let block = engine_state.get_block(capture_block.block_id).clone();
+    let name_var = block.signature.get_positional(0).and_then(|arg| arg.var_id);
+    let value_var = block.signature.get_positional(1).and_then(|arg| arg.var_id);
+
+    let mut stack = stack.captures_to_stack(&capture_block.captures);
+
+    let ctrlc = engine_state.ctrlc.clone();
+    let engine_state = engine_state.clone();
+
+    input.map(
+        move |row| match row {
+            Value::Record {
+                cols,
+                vals,
+                span: row_span,
+            } => {
+                let mut kept_cols = Vec::with_capacity(cols.len());
+                let mut kept_vals = Vec::with_capacity(vals.len());
+
+                for (col, val) in cols.into_iter().zip(vals) {
+                    if let Some(var_id) = name_var {
+                        stack.add_var(var_id, Value::string(col.clone(), span));
+                    }
+                    if let Some(var_id) = value_var {
+                        stack.add_var(var_id, val.clone());
+                    }
+
+                    let matched = match eval_block(
+                        &engine_state,
+                        &mut stack,
+                        &block,
+                        PipelineData::new(span),
+                    ) {
+                        Ok(pipeline_data) => pipeline_data.into_value(span).is_true(),
+                        Err(error) => return Value::Error { error },
+                    };
+
+                    if matched == keep {
+                        kept_cols.push(col);
+                        kept_vals.push(val);
+                    }
+                }
+
+                Value::Record {
+                    cols: kept_cols,
+                    vals: kept_vals,
+                    span: row_span,
+                }
+            }
+            row => row,
+        },
+        ctrlc,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_examples() {
+        use crate::test_examples;
+
+        test_examples(KeepColumns)
+    }
+}
@@ -1,7 +1,10 @@
+mod columns;
 mod command;
 mod until;
 mod while_;
 
+pub(crate) use columns::filter_columns;
+pub use columns::KeepColumns;
 pub use command::Keep;
 pub use until::KeepUntil;
 pub use while_::KeepWhile;
@@ -2,8 +2,8 @@ use nu_engine::column::get_columns;
 use nu_protocol::ast::Call;
 use nu_protocol::engine::{Command, EngineState, Stack};
 use nu_protocol::{
-    Category, IntoInterruptiblePipelineData, IntoPipelineData, PipelineData, ShellError, Signature,
-    Span, Value,
+    Category, Example, IntoInterruptiblePipelineData, IntoPipelineData, PipelineData, ShellError,
+    Signature, Span, Type, Value,
 };
 
 #[derive(Clone)]
@@ -21,6 +21,8 @@ impl Command for Length {
     fn signature(&self) -> nu_protocol::Signature {
         Signature::build("length")
             .switch("column", "Show the number of columns in a table", Some('c'))
+            // A single value counts as one
+            .input_output_type(Type::Unknown, Type::Int)
             .category(Category::Filters)
     }
 
@@ -35,9 +37,29 @@ impl Command for Length {
         if col {
             length_col(engine_state, call, input)
         } else {
-            length_row(call, input)
+            length_row(engine_state, call, input)
         }
     }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![
+            Example {
+                description: "Count the number of items in a list",
+                example: "[1 2 3 4 5] | length",
+                result: Some(Value::test_int(5)),
+            },
+            Example {
+                description: "Count the number of columns in a table",
+                example: "[[a, b, c]; [1, 2, 3]] | length -c",
+                result: Some(Value::test_int(3)),
+            },
+            Example {
+                description: "Count the files in the current directory",
+                example: "ls | length",
+                result: None,
+            },
+        ]
+    }
 }
 
 // this simulates calling input | columns | length
@@ -47,21 +69,46 @@ fn length_col(
     input: PipelineData,
 ) -> Result<PipelineData, ShellError> {
     length_row(
+        engine_state,
         call,
         getcol(engine_state, call.head, input)
             .expect("getcol() should not fail used in column command"),
     )
 }
 
-fn length_row(call: &Call, input: PipelineData) -> Result<PipelineData, ShellError> {
+fn length_row(
+    engine_state: &EngineState,
+    call: &Call,
+    input: PipelineData,
+) -> Result<PipelineData, ShellError> {
     match input {
         PipelineData::Value(Value::Nothing { .. }, ..) => Ok(Value::Int {
             val: 0,
             span: call.head,
         }
         .into_pipeline_data()),
+        // Binary data is measured in bytes, not in chunks
+        PipelineData::Value(Value::Binary { val, .. }, ..) => Ok(Value::Int {
+            val: val.len() as i64,
+            span: call.head,
+        }
+        .into_pipeline_data()),
+        PipelineData::ByteStream(stream, ..) => {
+            let mut total = 0;
+            for chunk in stream {
+                total += chunk?.len();
+            }
+
+            Ok(Value::Int {
+                val: total as i64,
+                span: call.head,
+            }
+            .into_pipeline_data())
+        }
         _ => Ok(Value::Int {
-            val: input.into_iter().count() as i64,
+            val: input
+                .into_interruptible_iter(engine_state.ctrlc.clone())
+                .count() as i64,
             span: call.head,
         }
         .into_pipeline_data()),
@@ -103,3 +150,15 @@ fn getcol(
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_examples() {
+        use crate::test_examples;
+
+        test_examples(Length {})
+    }
+}
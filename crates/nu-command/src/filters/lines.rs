@@ -1,7 +1,7 @@
 use nu_protocol::ast::Call;
 use nu_protocol::engine::{Command, EngineState, Stack};
 use nu_protocol::{
-    Category, IntoInterruptiblePipelineData, PipelineData, ShellError, Signature, Value,
+    Category, IntoInterruptiblePipelineData, PipelineData, ShellError, Signature, Type, Value,
 };
 
 #[derive(Clone)]
@@ -19,6 +19,7 @@ impl Command for Lines {
     fn signature(&self) -> nu_protocol::Signature {
         Signature::build("lines")
             .switch("skip-empty", "skip empty lines", Some('s'))
+            .input_output_type(Type::String, Type::List(Box::new(Type::String)))
             .category(Category::Filters)
     }
 
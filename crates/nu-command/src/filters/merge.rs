@@ -15,7 +15,7 @@ impl Command for Merge {
     }
 
     fn usage(&self) -> &str {
-        "Merge a table into an input table"
+        "Merge a table or record into an input table or record"
     }
 
     fn signature(&self) -> nu_protocol::Signature {
@@ -51,6 +51,31 @@ impl Command for Merge {
                     span: Span::test_data(),
                 }),
             },
+            Example {
+                example: "[[name, size]; [a 1] [b 2]] | merge { {kind: 'local'} }",
+                description: "Merge a record into every row of the input table",
+                result: Some(Value::List {
+                    vals: vec![
+                        Value::test_record(
+                            vec!["name", "size", "kind"],
+                            vec![
+                                Value::test_string("a"),
+                                Value::test_int(1),
+                                Value::test_string("local"),
+                            ],
+                        ),
+                        Value::test_record(
+                            vec!["name", "size", "kind"],
+                            vec![
+                                Value::test_string("b"),
+                                Value::test_int(2),
+                                Value::test_string("local"),
+                            ],
+                        ),
+                    ],
+                    span: Span::test_data(),
+                }),
+            },
             Example {
                 example: "{a: 1, b: 2} | merge { {c: 3} }",
                 description: "Merge two records",
@@ -101,8 +126,8 @@ impl Command for Merge {
                     .map(move |inp| match (inp.as_record(), table_iter.next()) {
                         (Ok((inp_cols, inp_vals)), Some(to_merge)) => match to_merge.as_record() {
                             Ok((to_merge_cols, to_merge_vals)) => {
-                                let cols = [inp_cols, to_merge_cols].concat();
-                                let vals = [inp_vals, to_merge_vals].concat();
+                                let (cols, vals) =
+                                    merge_records(inp_cols, inp_vals, to_merge_cols, to_merge_vals);
                                 Value::Record {
                                     cols,
                                     vals,
@@ -116,6 +141,29 @@ impl Command for Merge {
                     })
                     .into_pipeline_data(ctrlc))
             }
+            // table and record: the record is merged into every row
+            (
+                PipelineData::Value(Value::List { .. }, ..) | PipelineData::ListStream { .. },
+                PipelineData::Value(Value::Record { .. }, ..),
+            ) => {
+                let to_merge = table.into_value(call.head);
+
+                Ok(input
+                    .into_iter()
+                    .map(move |inp| match (inp.as_record(), to_merge.as_record()) {
+                        (Ok((inp_cols, inp_vals)), Ok((to_merge_cols, to_merge_vals))) => {
+                            let (cols, vals) =
+                                merge_records(inp_cols, inp_vals, to_merge_cols, to_merge_vals);
+                            Value::Record {
+                                cols,
+                                vals,
+                                span: call.head,
+                            }
+                        }
+                        (Err(error), _) | (_, Err(error)) => Value::Error { error },
+                    })
+                    .into_pipeline_data(ctrlc))
+            }
             // record
             (
                 PipelineData::Value(
@@ -135,11 +183,7 @@ impl Command for Merge {
                     ..,
                 ),
             ) => {
-                let mut cols = inp_cols.to_vec();
-                cols.extend(to_merge_cols.to_vec());
-
-                let mut vals = inp_vals.to_vec();
-                vals.extend(to_merge_vals.to_vec());
+                let (cols, vals) = merge_records(inp_cols, inp_vals, to_merge_cols, to_merge_vals);
 
                 Ok(Value::Record {
                     cols,
@@ -170,6 +214,30 @@ impl Command for Merge {
     }
 }
 
+/// Shallow-merge two records. Columns that are in both keep their place but take the value
+/// from `to_merge`, new columns are added at the end
+fn merge_records(
+    inp_cols: &[String],
+    inp_vals: &[Value],
+    to_merge_cols: &[String],
+    to_merge_vals: &[Value],
+) -> (Vec<String>, Vec<Value>) {
+    let mut cols = inp_cols.to_vec();
+    let mut vals = inp_vals.to_vec();
+
+    for (col, val) in to_merge_cols.iter().zip(to_merge_vals) {
+        match cols.iter().position(|c| c == col) {
+            Some(index) => vals[index] = val.clone(),
+            None => {
+                cols.push(col.clone());
+                vals.push(val.clone());
+            }
+        }
+    }
+
+    (cols, vals)
+}
+
 /*
 fn merge_values(
 left: &UntaggedValue,
@@ -7,11 +7,14 @@ mod compact;
 mod drop;
 mod each;
 mod empty;
+mod enumerate;
 mod every;
 mod first;
 mod flatten;
 mod get;
 mod group_by;
+mod histogram;
+mod insert;
 mod keep;
 mod last;
 mod length;
@@ -23,7 +26,9 @@ mod prepend;
 mod range;
 mod reduce;
 mod reject;
+mod rename;
 mod reverse;
+mod roll;
 mod select;
 mod shuffle;
 mod skip;
@@ -44,11 +49,14 @@ pub use compact::Compact;
 pub use drop::*;
 pub use each::Each;
 pub use empty::Empty;
+pub use enumerate::Enumerate;
 pub use every::Every;
 pub use first::First;
 pub use flatten::Flatten;
 pub use get::Get;
 pub use group_by::GroupBy;
+pub use histogram::Histogram;
+pub use insert::Insert;
 pub use keep::*;
 pub use last::Last;
 pub use length::Length;
@@ -60,11 +68,13 @@ pub use prepend::Prepend;
 pub use range::Range;
 pub use reduce::Reduce;
 pub use reject::Reject;
+pub use rename::Rename;
 pub use reverse::Reverse;
+pub use roll::*;
 pub use select::Select;
 pub use shuffle::Shuffle;
 pub use skip::*;
-pub use sort_by::SortBy;
+pub use sort_by::{natural_cmp, SortBy};
 pub use transpose::Transpose;
 pub use uniq::*;
 pub use update::Update;
@@ -84,7 +84,8 @@ impl Command for Nth {
         let mut rows: Vec<usize> = call.rest(engine_state, stack, 0)?;
         rows.sort_unstable();
         let skip = call.has_flag("skip");
-        let pipeline_iter: PipelineIterator = input.into_iter();
+        let pipeline_iter: PipelineIterator =
+            input.into_interruptible_iter(engine_state.ctrlc.clone());
 
         Ok(NthIterator {
             input: pipeline_iter,
@@ -1,6 +1,6 @@
 use nu_engine::CallExt;
 
-use nu_protocol::ast::Call;
+use nu_protocol::ast::{Call, Expr, Expression, RangeInclusion};
 use nu_protocol::engine::{Command, EngineState, Stack};
 use nu_protocol::{
     Category, Example, IntoInterruptiblePipelineData, PipelineData, ShellError, Signature, Span,
@@ -47,6 +47,27 @@ impl Command for Range {
                     span: Span::test_data(),
                 }),
             },
+            Example {
+                example: "[0,1,2,3,4,5] | range 2..",
+                description: "Get everything from the third item on",
+                result: Some(Value::List {
+                    vals: vec![
+                        Value::test_int(2),
+                        Value::test_int(3),
+                        Value::test_int(4),
+                        Value::test_int(5),
+                    ],
+                    span: Span::test_data(),
+                }),
+            },
+            Example {
+                example: "[0,1,2,3,4,5] | range 1..<3",
+                description: "Get the items from index 1 up to, but not including, index 3",
+                result: Some(Value::List {
+                    vals: vec![Value::test_int(1), Value::test_int(2)],
+                    span: Span::test_data(),
+                }),
+            },
             Example {
                 example: "[0,1,2,3,4,5] | range (-3)..-2",
                 description: "Get the next to last 2 items",
@@ -67,54 +88,82 @@ impl Command for Range {
     ) -> Result<PipelineData, ShellError> {
         let rows: nu_protocol::Range = call.req(engine_state, stack, 0)?;
 
+        // A range like `5..` gets a placeholder end, but it's meant to reach the end of the input
+        let open_end = matches!(
+            call.positional.get(0),
+            Some(Expression {
+                expr: Expr::Range(_, _, None, _),
+                ..
+            })
+        );
+
         let rows_from = get_range_val(rows.from);
-        let rows_to = get_range_val(rows.to);
+        let rows_to = if open_end {
+            None
+        } else {
+            Some(get_range_val(rows.to))
+        };
+        let inclusive = matches!(rows.inclusion, RangeInclusion::Inclusive);
 
         // only collect the input if we have any negative indices
-        if rows_from < 0 || rows_to < 0 {
-            let v: Vec<_> = input.into_iter().collect();
-            let vlen: i64 = v.len() as i64;
-
-            let from = if rows_from < 0 {
-                (vlen + rows_from) as usize
-            } else {
-                rows_from as usize
-            };
-
-            let to = if rows_to < 0 {
-                (vlen + rows_to) as usize
-            } else if rows_to > v.len() as i64 {
-                v.len()
-            } else {
-                rows_to as usize
-            };
-
-            if from > to {
-                Ok(PipelineData::Value(
-                    Value::Nothing { span: call.head },
-                    None,
-                ))
-            } else {
-                let iter = v.into_iter().skip(from).take(to - from + 1);
-                Ok(iter.into_pipeline_data(engine_state.ctrlc.clone()))
-            }
+        let (from, to, iter) = if rows_from < 0 || matches!(rows_to, Some(to) if to < 0) {
+            let v: Vec<_> = input
+                .into_interruptible_iter(engine_state.ctrlc.clone())
+                .collect();
+            let (from, to) = row_bounds(rows_from, rows_to, inclusive, Some(v.len()));
+
+            (
+                from,
+                to,
+                Box::new(v.into_iter()) as Box<dyn Iterator<Item = Value> + Send>,
+            )
         } else {
-            let from = rows_from as usize;
-            let to = rows_to as usize;
-
-            if from > to {
-                Ok(PipelineData::Value(
-                    Value::Nothing { span: call.head },
-                    None,
-                ))
-            } else {
-                let iter = input.into_iter().skip(from).take(to - from + 1);
-                Ok(iter.into_pipeline_data(engine_state.ctrlc.clone()))
-            }
+            let (from, to) = row_bounds(rows_from, rows_to, inclusive, None);
+            let iter = input.into_interruptible_iter(engine_state.ctrlc.clone());
+
+            (
+                from,
+                to,
+                Box::new(iter) as Box<dyn Iterator<Item = Value> + Send>,
+            )
+        };
+
+        if from >= to {
+            Ok(PipelineData::Value(
+                Value::Nothing { span: call.head },
+                None,
+            ))
+        } else {
+            Ok(iter
+                .skip(from)
+                .take(to - from)
+                .into_pipeline_data(engine_state.ctrlc.clone()))
         }
     }
 }
 
+/// The rows `from..to` (end exclusive) selected out of `len` rows. Negative indices count from
+/// the end, which needs the length, and both ends are clamped to the rows there are. A missing
+/// end reaches the last row.
+fn row_bounds(from: i64, to: Option<i64>, inclusive: bool, len: Option<usize>) -> (usize, usize) {
+    let resolve = |index: i64| match len {
+        Some(len) if index < 0 => (len as i64 + index).max(0) as usize,
+        _ => index.max(0) as usize,
+    };
+
+    let from = resolve(from);
+    let to = match to {
+        Some(to) if inclusive => resolve(to).saturating_add(1),
+        Some(to) => resolve(to),
+        None => usize::MAX,
+    };
+
+    match len {
+        Some(len) => (from.min(len), to.min(len)),
+        None => (from, to),
+    }
+}
+
 fn get_range_val(rows_val: Value) -> i64 {
     match rows_val {
         Value::Int { val: x, .. } => x,
@@ -132,4 +181,25 @@ mod test {
 
         test_examples(Range {})
     }
+
+    #[test]
+    fn bounds_are_clamped() {
+        assert_eq!(row_bounds(2, Some(10), true, Some(5)), (2, 5));
+        assert_eq!(row_bounds(-10, Some(1), true, Some(5)), (0, 2));
+        assert_eq!(row_bounds(7, None, true, Some(5)), (5, 5));
+    }
+
+    #[test]
+    fn bounds_without_length() {
+        assert_eq!(row_bounds(0, Some(3), true, None), (0, 4));
+        assert_eq!(row_bounds(0, Some(3), false, None), (0, 3));
+        assert_eq!(row_bounds(5, None, true, None), (5, usize::MAX));
+    }
+
+    #[test]
+    fn negative_bounds_count_from_the_end() {
+        assert_eq!(row_bounds(-2, None, true, Some(6)), (4, 6));
+        assert_eq!(row_bounds(-3, Some(-2), true, Some(6)), (3, 5));
+        assert_eq!(row_bounds(0, Some(-1), false, Some(6)), (0, 5));
+    }
 }
@@ -53,7 +53,7 @@ impl Command for Reduce {
                 }),
             },
             Example {
-                example: r#"[ i o t ] | reduce -f "Arthur, King of the Britons" { $it.acc | str find-replace -a $it.item "X" }"#,
+                example: r#"[ i o t ] | reduce -f "Arthur, King of the Britons" { $it.acc | str replace -a $it.item "X" }"#,
                 description: "Replace selected characters in a string with 'X'",
                 result: Some(Value::String {
                     val: "ArXhur, KXng Xf Xhe BrXXXns".to_string(),
@@ -109,7 +109,7 @@ impl Command for Reduce {
         let orig_env_vars = stack.env_vars.clone();
         let orig_env_hidden = stack.env_hidden.clone();
 
-        let mut input_iter = input.into_iter();
+        let mut input_iter = input.into_interruptible_iter(engine_state.ctrlc.clone());
 
         let (off, start_val) = if let Some(val) = fold {
             (0, val)
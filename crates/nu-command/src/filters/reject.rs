@@ -2,8 +2,8 @@ use nu_engine::CallExt;
 use nu_protocol::ast::{Call, CellPath};
 use nu_protocol::engine::{Command, EngineState, Stack};
 use nu_protocol::{
-    Category, FromValue, IntoInterruptiblePipelineData, IntoPipelineData, PipelineData, ShellError,
-    Signature, Span, SyntaxShape, Value,
+    Category, Example, FromValue, IntoInterruptiblePipelineData, IntoPipelineData, PipelineData,
+    ShellError, Signature, Span, SyntaxShape, Value,
 };
 
 #[derive(Clone)]
@@ -39,6 +39,28 @@ impl Command for Reject {
         let span = call.head;
         reject(engine_state, span, input, columns)
     }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![
+            Example {
+                description: "Remove a column from a table",
+                example: "[[a, b, c]; [1, 2, 3]] | reject b",
+                result: Some(Value::List {
+                    vals: vec![Value::Record {
+                        cols: vec!["a".into(), "c".into()],
+                        vals: vec![Value::test_int(1), Value::test_int(3)],
+                        span: Span::test_data(),
+                    }],
+                    span: Span::test_data(),
+                }),
+            },
+            Example {
+                description: "Remove the size and modified columns from a listing",
+                example: "ls | reject size modified",
+                result: None,
+            },
+        ]
+    }
 }
 
 fn reject(
@@ -155,3 +177,15 @@ fn get_keep_columns(mut input: Vec<String>, rejects: Vec<String>) -> Vec<String>
     }
     input
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_examples() {
+        use crate::test_examples;
+
+        test_examples(Reject {})
+    }
+}
@@ -0,0 +1,221 @@
+use nu_engine::CallExt;
+use nu_protocol::ast::Call;
+use nu_protocol::engine::{Command, EngineState, Stack};
+use nu_protocol::{
+    Category, Example, PipelineData, ShellError, Signature, Span, SyntaxShape, Value,
+};
+
+#[derive(Clone)]
+pub struct Rename;
+
+impl Command for Rename {
+    fn name(&self) -> &str {
+        "rename"
+    }
+
+    fn usage(&self) -> &str {
+        "Creates a new table with columns renamed."
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("rename")
+            .rest(
+                "rest",
+                SyntaxShape::String,
+                "the new names for the columns, in order",
+            )
+            .named(
+                "column",
+                SyntaxShape::Any,
+                "rename columns by name, as old=new or a list of them",
+                Some('c'),
+            )
+            .category(Category::Filters)
+    }
+
+    fn extra_usage(&self) -> &str {
+        r#"Positional names rename the first columns of each row, in order. With --column only the
+named columns are renamed, in the rows that have them. Either way the columns keep their order
+and their values. A row that would end up with two columns of the same name is an error."#
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let head = call.head;
+        let names: Vec<String> = call.rest(engine_state, stack, 0)?;
+        let mapping: Option<Value> = call.get_flag(engine_state, stack, "column")?;
+
+        let renaming = match (names.is_empty(), mapping) {
+            (false, Some(_)) => {
+                return Err(ShellError::IncompatibleParametersSingle(
+                    "Can't rename by position and with --column at the same time".into(),
+                    head,
+                ))
+            }
+            (true, None) => {
+                return Err(ShellError::MissingParameter(
+                    "new column names or --column".into(),
+                    head,
+                ))
+            }
+            (false, None) => Renaming::Positional(names),
+            (true, Some(mapping)) => Renaming::Mapping(parse_mapping(&mapping)?),
+        };
+
+        input.map(
+            move |value| match renaming.apply(value, head) {
+                Ok(value) => value,
+                Err(error) => Value::Error { error },
+            },
+            engine_state.ctrlc.clone(),
+        )
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![
+            Example {
+                description: "Rename the columns in order",
+                example: "[[a, b]; [1, 2]] | rename x y",
+                result: Some(Value::List {
+                    vals: vec![Value::Record {
+                        cols: vec!["x".into(), "y".into()],
+                        vals: vec![Value::test_int(1), Value::test_int(2)],
+                        span: Span::test_data(),
+                    }],
+                    span: Span::test_data(),
+                }),
+            },
+            Example {
+                description: "Rename only the first column",
+                example: "[[a, b]; [1, 2]] | rename x",
+                result: Some(Value::List {
+                    vals: vec![Value::Record {
+                        cols: vec!["x".into(), "b".into()],
+                        vals: vec![Value::test_int(1), Value::test_int(2)],
+                        span: Span::test_data(),
+                    }],
+                    span: Span::test_data(),
+                }),
+            },
+            Example {
+                description: "Rename a column by name",
+                example: "[[a, b]; [1, 2]] | rename --column b=y",
+                result: Some(Value::List {
+                    vals: vec![Value::Record {
+                        cols: vec!["a".into(), "y".into()],
+                        vals: vec![Value::test_int(1), Value::test_int(2)],
+                        span: Span::test_data(),
+                    }],
+                    span: Span::test_data(),
+                }),
+            },
+            Example {
+                description: "Rename the name column of a listing before exporting it",
+                example: "ls | rename --column name=file | to csv",
+                result: None,
+            },
+        ]
+    }
+}
+
+enum Renaming {
+    Positional(Vec<String>),
+    Mapping(Vec<(String, String)>),
+}
+
+impl Renaming {
+    fn apply(&self, value: Value, head: Span) -> Result<Value, ShellError> {
+        let (mut cols, vals, span) = match value {
+            Value::Record { cols, vals, span } => (cols, vals, span),
+            Value::Error { .. } => return Ok(value),
+            other => {
+                return Err(ShellError::UnsupportedInput(
+                    format!("rename needs a table or a record, not {}", other.get_type()),
+                    other.span().unwrap_or(head),
+                ))
+            }
+        };
+
+        match self {
+            Renaming::Positional(names) => {
+                for (col, name) in cols.iter_mut().zip(names) {
+                    *col = name.clone();
+                }
+            }
+            Renaming::Mapping(mapping) => {
+                for col in cols.iter_mut() {
+                    if let Some((_, new)) = mapping.iter().find(|(old, _)| old == col) {
+                        *col = new.clone();
+                    }
+                }
+            }
+        }
+
+        for (index, col) in cols.iter().enumerate() {
+            if cols[..index].contains(col) {
+                return Err(ShellError::SpannedLabeledError(
+                    "Duplicate column name".into(),
+                    format!("renaming would leave two columns named '{}'", col),
+                    head,
+                ));
+            }
+        }
+
+        Ok(Value::Record { cols, vals, span })
+    }
+}
+
+/// Reads `old=new` pairs from a string or a list of strings
+fn parse_mapping(value: &Value) -> Result<Vec<(String, String)>, ShellError> {
+    let pairs = match value {
+        Value::List { vals, .. } => vals.iter().collect(),
+        value => vec![value],
+    };
+
+    let mut mapping: Vec<(String, String)> = vec![];
+    for pair in pairs {
+        let span = pair.span()?;
+        let text = pair.as_string()?;
+
+        let (old, new) = match text.split_once('=') {
+            Some((old, new)) if !old.is_empty() && !new.is_empty() => {
+                (old.to_string(), new.to_string())
+            }
+            _ => {
+                return Err(ShellError::UnsupportedInput(
+                    "expected old=new".into(),
+                    span,
+                ))
+            }
+        };
+
+        if mapping.iter().any(|(_, other)| *other == new) {
+            return Err(ShellError::SpannedLabeledError(
+                "Duplicate column name".into(),
+                format!("more than one column renamed to '{}'", new),
+                span,
+            ));
+        }
+
+        mapping.push((old, new));
+    }
+
+    Ok(mapping)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_examples() {
+        use crate::test_examples;
+
+        test_examples(Rename {})
+    }
+}
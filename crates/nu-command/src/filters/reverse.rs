@@ -45,7 +45,9 @@ impl Command for Reverse {
         input: PipelineData,
     ) -> Result<PipelineData, ShellError> {
         #[allow(clippy::needless_collect)]
-        let v: Vec<_> = input.into_iter().collect();
+        let v: Vec<_> = input
+            .into_interruptible_iter(engine_state.ctrlc.clone())
+            .collect();
         let iter = v.into_iter().rev();
         Ok(iter.into_pipeline_data(engine_state.ctrlc.clone()))
     }
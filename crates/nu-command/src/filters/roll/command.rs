@@ -0,0 +1,110 @@
+use nu_engine::{get_full_help, CallExt};
+use nu_protocol::{
+    ast::Call,
+    engine::{Command, EngineState, Stack},
+    Category, IntoPipelineData, PipelineData, ShellError, Signature, Value,
+};
+
+#[derive(Clone)]
+pub struct Roll;
+
+impl Command for Roll {
+    fn name(&self) -> &str {
+        "roll"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build(self.name()).category(Category::Filters)
+    }
+
+    fn usage(&self) -> &str {
+        "Rotate the items of a list, or the columns of a record."
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        _stack: &mut Stack,
+        call: &Call,
+        _input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        Ok(Value::String {
+            val: get_full_help(&Roll.signature(), &Roll.examples(), engine_state),
+            span: call.head,
+        }
+        .into_pipeline_data())
+    }
+}
+
+pub(super) enum RollDirection {
+    Left,
+    Right,
+}
+
+/// Rotate the input by the count in the call, which defaults to 1. Counts wrap around the length,
+/// so a negative count rotates the other way.
+pub(super) fn roll(
+    engine_state: &EngineState,
+    stack: &mut Stack,
+    call: &Call,
+    input: PipelineData,
+    direction: RollDirection,
+) -> Result<PipelineData, ShellError> {
+    let count: i64 = call.opt(engine_state, stack, 0)?.unwrap_or(1);
+    let head = call.head;
+
+    match input {
+        PipelineData::Value(Value::Record { cols, vals, span }, ..) => {
+            let mut columns: Vec<_> = cols.into_iter().zip(vals).collect();
+            rotate(&mut columns, count, &direction);
+            let (cols, vals) = columns.into_iter().unzip();
+
+            Ok(Value::Record { cols, vals, span }.into_pipeline_data())
+        }
+        input => {
+            let mut vals: Vec<Value> = input.into_iter().collect();
+            rotate(&mut vals, count, &direction);
+
+            Ok(Value::List { vals, span: head }.into_pipeline_data())
+        }
+    }
+}
+
+fn rotate<T>(items: &mut [T], count: i64, direction: &RollDirection) {
+    if items.is_empty() {
+        return;
+    }
+
+    let count = count.rem_euclid(items.len() as i64) as usize;
+    match direction {
+        RollDirection::Left => items.rotate_left(count),
+        RollDirection::Right => items.rotate_right(count),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn counts_wrap_around() {
+        let rolled = |count, direction| {
+            let mut items = vec![1, 2, 3, 4];
+            rotate(&mut items, count, &direction);
+            items
+        };
+
+        assert_eq!(rolled(1, RollDirection::Left), vec![2, 3, 4, 1]);
+        assert_eq!(rolled(5, RollDirection::Left), vec![2, 3, 4, 1]);
+        assert_eq!(rolled(-1, RollDirection::Left), vec![4, 1, 2, 3]);
+        assert_eq!(rolled(6, RollDirection::Right), vec![3, 4, 1, 2]);
+        assert_eq!(rolled(-3, RollDirection::Right), vec![4, 1, 2, 3]);
+    }
+
+    #[test]
+    fn empty_input_stays_empty() {
+        let mut items: Vec<i32> = vec![];
+        rotate(&mut items, 3, &RollDirection::Right);
+        assert!(items.is_empty());
+    }
+}
@@ -0,0 +1,7 @@
+mod command;
+mod left;
+mod right;
+
+pub use command::Roll;
+pub use left::RollLeft;
+pub use right::RollRight;
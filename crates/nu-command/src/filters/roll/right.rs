@@ -0,0 +1,86 @@
+use super::command::{roll, RollDirection};
+use nu_protocol::{
+    ast::Call,
+    engine::{Command, EngineState, Stack},
+    Category, Example, PipelineData, ShellError, Signature, Span, SyntaxShape, Value,
+};
+
+#[derive(Clone)]
+pub struct RollRight;
+
+impl Command for RollRight {
+    fn name(&self) -> &str {
+        "roll right"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build(self.name())
+            .optional(
+                "by",
+                SyntaxShape::Int,
+                "the number of positions to rotate by (default 1)",
+            )
+            .category(Category::Filters)
+    }
+
+    fn usage(&self) -> &str {
+        "Rotate the items of a list to the right, so the last ones move round to the front."
+    }
+
+    fn extra_usage(&self) -> &str {
+        r#"The input is collected before it's rotated. A record has its columns rotated instead.
+Counts larger than the length wrap around, and a negative count rotates the other way."#
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![
+            Example {
+                description: "Rotate a list right by one",
+                example: "[1 2 3] | roll right",
+                result: Some(Value::List {
+                    vals: vec![Value::test_int(3), Value::test_int(1), Value::test_int(2)],
+                    span: Span::test_data(),
+                }),
+            },
+            Example {
+                description: "Counts wrap around the length of the list",
+                example: "[1 2 3] | roll right 5",
+                result: Some(Value::List {
+                    vals: vec![Value::test_int(2), Value::test_int(3), Value::test_int(1)],
+                    span: Span::test_data(),
+                }),
+            },
+            Example {
+                description: "Rotate the columns of a record",
+                example: "{a: 1, b: 2, c: 3} | roll right",
+                result: Some(Value::Record {
+                    cols: vec!["c".into(), "a".into(), "b".into()],
+                    vals: vec![Value::test_int(3), Value::test_int(1), Value::test_int(2)],
+                    span: Span::test_data(),
+                }),
+            },
+        ]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        roll(engine_state, stack, call, input, RollDirection::Right)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_examples() {
+        use crate::test_examples;
+
+        test_examples(RollRight {})
+    }
+}
@@ -1,9 +1,10 @@
+use glob::Pattern;
 use nu_engine::CallExt;
 use nu_protocol::ast::{Call, CellPath};
 use nu_protocol::engine::{Command, EngineState, Stack};
 use nu_protocol::{
     Category, Example, IntoInterruptiblePipelineData, IntoPipelineData, PipelineData, ShellError,
-    Signature, Span, SyntaxShape, Value,
+    Signature, Span, Spanned, SyntaxShape, Value,
 };
 
 #[derive(Clone)]
@@ -21,6 +22,12 @@ impl Command for Select {
                 SyntaxShape::CellPath,
                 "the columns to select from the table",
             )
+            .named(
+                "glob",
+                SyntaxShape::String,
+                "select the columns whose names match this glob pattern",
+                Some('g'),
+            )
             .category(Category::Filters)
     }
 
@@ -28,6 +35,13 @@ impl Command for Select {
         "Down-select table to only these columns."
     }
 
+    fn extra_usage(&self) -> &str {
+        r#"With --glob the columns are picked by pattern, row by row: each row keeps its own matching
+columns, in the order it has them. A table whose rows differ in their columns may so come out with
+rows that differ too. A pattern that matches no column is not an error: it selects nothing,
+leaving empty rows."#
+    }
+
     fn run(
         &self,
         engine_state: &EngineState,
@@ -36,9 +50,22 @@ impl Command for Select {
         input: PipelineData,
     ) -> Result<nu_protocol::PipelineData, nu_protocol::ShellError> {
         let columns: Vec<CellPath> = call.rest(engine_state, stack, 0)?;
+        let glob: Option<Spanned<String>> = call.get_flag(engine_state, stack, "glob")?;
         let span = call.head;
 
-        select(engine_state, span, columns, input)
+        match glob {
+            Some(_) if !columns.is_empty() => Err(ShellError::IncompatibleParametersSingle(
+                "Can't name columns and select with --glob at the same time".into(),
+                span,
+            )),
+            Some(glob) => {
+                let pattern = Pattern::new(&glob.item)
+                    .map_err(|err| ShellError::UnsupportedInput(err.msg.into(), glob.span))?;
+
+                select_matching(engine_state, span, pattern, input)
+            }
+            None => select(engine_state, span, columns, input),
+        }
     }
 
     fn examples(&self) -> Vec<Example> {
@@ -53,6 +80,23 @@ impl Command for Select {
                 example: "ls | select name size",
                 result: None,
             },
+            Example {
+                description: "Select the columns holding a time",
+                example: "ls -l | select --glob '*ed'",
+                result: None,
+            },
+            Example {
+                description: "Select the columns starting with 'a'",
+                example: "[[apple, banana, avocado]; [1, 2, 3]] | select --glob 'a*'",
+                result: Some(Value::List {
+                    vals: vec![Value::Record {
+                        cols: vec!["apple".into(), "avocado".into()],
+                        vals: vec![Value::test_int(1), Value::test_int(3)],
+                        span: Span::test_data(),
+                    }],
+                    span: Span::test_data(),
+                }),
+            },
         ]
     }
 }
@@ -133,3 +177,86 @@ fn select(
         _ => Ok(PipelineData::new(span)),
     }
 }
+
+fn select_matching(
+    engine_state: &EngineState,
+    span: Span,
+    pattern: Pattern,
+    input: PipelineData,
+) -> Result<PipelineData, ShellError> {
+    // A stream can't be looked at ahead, so every row gets its own matching columns, whether
+    // the rows are streamed or not
+    let select_row = move |row: Value| {
+        let columns = match &row {
+            Value::Record { cols, .. } => matching_columns(cols, &pattern),
+            _ => vec![],
+        };
+
+        pick_columns(row, &columns, span)
+    };
+
+    match input {
+        PipelineData::Value(Value::List { vals, .. }, ..) => Ok(vals
+            .into_iter()
+            .map(select_row)
+            .into_pipeline_data(engine_state.ctrlc.clone())),
+        PipelineData::ListStream(stream, ..) => Ok(stream
+            .map(select_row)
+            .into_pipeline_data(engine_state.ctrlc.clone())),
+        PipelineData::Value(row @ Value::Record { .. }, ..) => {
+            Ok(select_row(row).into_pipeline_data())
+        }
+        PipelineData::Value(value, ..) => Err(ShellError::UnsupportedInput(
+            format!(
+                "select --glob needs a table or a record, not {}",
+                value.get_type()
+            ),
+            value.span().unwrap_or(span),
+        )),
+        _ => Ok(PipelineData::new(span)),
+    }
+}
+
+fn matching_columns(columns: &[String], pattern: &Pattern) -> Vec<String> {
+    columns
+        .iter()
+        .filter(|column| pattern.matches(column))
+        .cloned()
+        .collect()
+}
+
+/// The row with only `columns`, in that order, and nothing for the ones it doesn't have
+fn pick_columns(row: Value, columns: &[String], span: Span) -> Value {
+    match row {
+        Value::Record { cols, vals, .. } => Value::Record {
+            cols: columns.to_vec(),
+            vals: columns
+                .iter()
+                .map(|column| match cols.iter().position(|col| col == column) {
+                    Some(index) => vals[index].clone(),
+                    None => Value::Nothing { span },
+                })
+                .collect(),
+            span,
+        },
+        Value::Error { .. } => row,
+        other => Value::Error {
+            error: ShellError::UnsupportedInput(
+                format!("select --glob needs records, not {}", other.get_type()),
+                other.span().unwrap_or(span),
+            ),
+        },
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_examples() {
+        use crate::test_examples;
+
+        test_examples(Select {})
+    }
+}
@@ -27,7 +27,9 @@ impl Command for Shuffle {
         _call: &Call,
         input: PipelineData,
     ) -> Result<PipelineData, ShellError> {
-        let mut v: Vec<_> = input.into_iter().collect();
+        let mut v: Vec<_> = input
+            .into_interruptible_iter(engine_state.ctrlc.clone())
+            .collect();
         v.shuffle(&mut thread_rng());
         let iter = v.into_iter();
         Ok(iter.into_pipeline_data(engine_state.ctrlc.clone()))
@@ -1,3 +1,7 @@
+use std::iter::Peekable;
+use std::str::Chars;
+use std::sync::atomic::Ordering;
+
 use nu_engine::CallExt;
 use nu_protocol::ast::Call;
 use nu_protocol::engine::{Command, EngineState, Stack};
@@ -17,7 +21,18 @@ impl Command for SortBy {
     fn signature(&self) -> nu_protocol::Signature {
         Signature::build("sort-by")
             .rest("columns", SyntaxShape::Any, "the column(s) to sort by")
+            .named(
+                "descending",
+                SyntaxShape::List(Box::new(SyntaxShape::String)),
+                "the column(s) to sort in decreasing order",
+                Some('d'),
+            )
             .switch("reverse", "Sort in reverse order", Some('r'))
+            .switch(
+                "natural",
+                "Sort strings with embedded numbers in natural order (file2 before file10)",
+                Some('n'),
+            )
             .category(Category::Filters)
     }
 
@@ -25,6 +40,10 @@ impl Command for SortBy {
         "Sort by the given columns, in increasing order."
     }
 
+    fn extra_usage(&self) -> &str {
+        "Later columns only break ties left by earlier ones. The sort is stable, so rows that compare equal keep their input order."
+    }
+
     fn examples(&self) -> Vec<Example> {
         vec![
             Example {
@@ -67,6 +86,60 @@ impl Command for SortBy {
                     span: Span::test_data(),
                 }),
             },
+            Example {
+                example: "[[name, size]; [b, 2] [a, 2] [c, 1]] | sort-by size name",
+                description: "sort a table by size, then by name",
+                result: Some(Value::List {
+                    vals: vec![
+                        Value::test_record(
+                            vec!["name", "size"],
+                            vec![Value::test_string("c"), Value::test_int(1)],
+                        ),
+                        Value::test_record(
+                            vec!["name", "size"],
+                            vec![Value::test_string("a"), Value::test_int(2)],
+                        ),
+                        Value::test_record(
+                            vec!["name", "size"],
+                            vec![Value::test_string("b"), Value::test_int(2)],
+                        ),
+                    ],
+                    span: Span::test_data(),
+                }),
+            },
+            Example {
+                example: "[[name, size]; [b, 2] [a, 2] [c, 1]] | sort-by size name -d [size]",
+                description: "sort a table by decreasing size, then by increasing name",
+                result: Some(Value::List {
+                    vals: vec![
+                        Value::test_record(
+                            vec!["name", "size"],
+                            vec![Value::test_string("a"), Value::test_int(2)],
+                        ),
+                        Value::test_record(
+                            vec!["name", "size"],
+                            vec![Value::test_string("b"), Value::test_int(2)],
+                        ),
+                        Value::test_record(
+                            vec!["name", "size"],
+                            vec![Value::test_string("c"), Value::test_int(1)],
+                        ),
+                    ],
+                    span: Span::test_data(),
+                }),
+            },
+            Example {
+                example: "[file10 file2 file1] | sort-by -n",
+                description: "sort strings with numbers in natural order",
+                result: Some(Value::List {
+                    vals: vec![
+                        Value::test_string("file1"),
+                        Value::test_string("file2"),
+                        Value::test_string("file10"),
+                    ],
+                    span: Span::test_data(),
+                }),
+            },
         ]
     }
 
@@ -78,104 +151,155 @@ impl Command for SortBy {
         input: PipelineData,
     ) -> Result<PipelineData, ShellError> {
         let columns: Vec<String> = call.rest(engine_state, stack, 0)?;
+        let descending: Option<Vec<String>> = call.get_flag(engine_state, stack, "descending")?;
         let reverse = call.has_flag("reverse");
-        let mut vec: Vec<_> = input.into_iter().collect();
+        let natural = call.has_flag("natural");
+        let ctrlc = engine_state.ctrlc.clone();
+
+        let descending = descending.unwrap_or_default();
+        if let Some(column) = descending.iter().find(|c| !columns.contains(c)) {
+            let span = call
+                .get_flag_expr("descending")
+                .map(|expr| expr.span)
+                .unwrap_or(call.head);
+
+            return Err(ShellError::SpannedLabeledError(
+                format!("'{}' is not one of the sort columns", column),
+                "only columns given to sort-by can be sorted in decreasing order".into(),
+                span,
+            ));
+        }
 
-        sort(&mut vec, columns, call)?;
+        let keys: Vec<SortKey> = columns
+            .into_iter()
+            .map(|column| {
+                let descending = descending.contains(&column) != reverse;
+                SortKey { column, descending }
+            })
+            .collect();
 
-        if reverse {
-            vec.reverse()
+        let mut vec = vec![];
+        for value in input.into_interruptible_iter(engine_state.ctrlc.clone()) {
+            if let Some(ctrlc) = &ctrlc {
+                if ctrlc.load(Ordering::SeqCst) {
+                    return Ok(PipelineData::new(call.head));
+                }
+            }
+            vec.push(value);
         }
 
+        sort(&mut vec, &keys, reverse, natural);
+
         let iter = vec.into_iter();
-        Ok(iter.into_pipeline_data(engine_state.ctrlc.clone()))
+        Ok(iter.into_pipeline_data(ctrlc))
     }
 }
 
-pub fn sort(vec: &mut [Value], columns: Vec<String>, call: &Call) -> Result<(), ShellError> {
-    match &vec[0] {
-        Value::Record {
-            cols: _cols,
-            vals: _input_vals,
-            ..
-        } => {
-            vec.sort_by(|a, b| {
-                process(a, b, &columns[0], call)
-                    .expect("sort_by Value::Record bug")
-                    .compare()
-            });
-        }
-        _ => {
-            vec.sort_by(|a, b| coerce_compare(a, b).expect("sort_by default bug").compare());
-        }
-    }
-    Ok(())
+pub struct SortKey {
+    pub column: String,
+    pub descending: bool,
 }
 
-pub fn process(
-    left: &Value,
-    right: &Value,
-    column: &str,
-    call: &Call,
-) -> Result<CompareValues, (&'static str, &'static str)> {
-    let left_value = left.get_data_by_key(column);
+/// Stable sort by each key in turn. With no keys the values themselves are compared, in the
+/// direction given by `descending`.
+pub fn sort(vec: &mut [Value], keys: &[SortKey], descending: bool, natural: bool) {
+    if keys.is_empty() {
+        vec.sort_by(|a, b| {
+            let ordering = compare_values(a, b, natural);
+            if descending {
+                ordering.reverse()
+            } else {
+                ordering
+            }
+        });
+        return;
+    }
 
-    let left_res = match left_value {
-        Some(left_res) => left_res,
-        None => Value::Nothing { span: call.head },
-    };
+    vec.sort_by(|a, b| {
+        for key in keys {
+            let lhs = a.get_data_by_key(&key.column);
+            let rhs = b.get_data_by_key(&key.column);
 
-    let right_value = right.get_data_by_key(column);
+            let ordering = match (lhs, rhs) {
+                (Some(lhs), Some(rhs)) => compare_values(&lhs, &rhs, natural),
+                // Rows missing the column go last, whatever the direction
+                (Some(_), None) => return std::cmp::Ordering::Less,
+                (None, Some(_)) => return std::cmp::Ordering::Greater,
+                (None, None) => std::cmp::Ordering::Equal,
+            };
 
-    let right_res = match right_value {
-        Some(right_res) => right_res,
-        None => Value::Nothing { span: call.head },
-    };
+            let ordering = if key.descending {
+                ordering.reverse()
+            } else {
+                ordering
+            };
 
-    coerce_compare(&left_res, &right_res)
-}
+            if ordering != std::cmp::Ordering::Equal {
+                return ordering;
+            }
+        }
 
-#[derive(Debug)]
-pub enum CompareValues {
-    Ints(i64, i64),
-    // Floats(f64, f64),
-    String(String, String),
-    Booleans(bool, bool),
+        std::cmp::Ordering::Equal
+    });
 }
 
-impl CompareValues {
-    pub fn compare(&self) -> std::cmp::Ordering {
-        match self {
-            CompareValues::Ints(left, right) => left.cmp(right),
-            // f64: std::cmp::Ord is required
-            // CompareValues::Floats(left, right) => left.cmp(right),
-            CompareValues::String(left, right) => left.cmp(right),
-            CompareValues::Booleans(left, right) => left.cmp(right),
+/// Values of different types (e.g. a string and a number) are ordered by `Value::total_cmp`, so
+/// a mixed column groups each type together and puts nothing last
+pub fn compare_values(lhs: &Value, rhs: &Value, natural: bool) -> std::cmp::Ordering {
+    match (lhs, rhs) {
+        (Value::String { val: lhs, .. }, Value::String { val: rhs, .. }) if natural => {
+            natural_cmp(lhs, rhs)
         }
+        _ => lhs.total_cmp(rhs),
     }
 }
 
-pub fn coerce_compare(
-    left: &Value,
-    right: &Value,
-) -> Result<CompareValues, (&'static str, &'static str)> {
-    match (left, right) {
-        /*
-        (Value::Float { val: left, .. }, Value::Float { val: right, .. }) => {
-            Ok(CompareValues::Floats(*left, *right))
-        }
-        */
-        (Value::Int { val: left, .. }, Value::Int { val: right, .. }) => {
-            Ok(CompareValues::Ints(*left, *right))
-        }
-        (Value::String { val: left, .. }, Value::String { val: right, .. }) => {
-            Ok(CompareValues::String(left.clone(), right.clone()))
+/// Compare strings so runs of digits are ordered by their numeric value: `file2` < `file10`
+pub fn natural_cmp(lhs: &str, rhs: &str) -> std::cmp::Ordering {
+    use std::cmp::Ordering;
+
+    let mut left = lhs.chars().peekable();
+    let mut right = rhs.chars().peekable();
+
+    loop {
+        match (left.peek().copied(), right.peek().copied()) {
+            // Equal up to leading zeros, e.g. `a01` and `a1`: fall back to a plain comparison
+            (None, None) => return lhs.cmp(rhs),
+            (None, Some(_)) => return Ordering::Less,
+            (Some(_), None) => return Ordering::Greater,
+            (Some(l), Some(r)) if l.is_ascii_digit() && r.is_ascii_digit() => {
+                let l = take_digits(&mut left);
+                let r = take_digits(&mut right);
+                let l = l.trim_start_matches('0');
+                let r = r.trim_start_matches('0');
+
+                let ordering = l.len().cmp(&r.len()).then_with(|| l.cmp(r));
+                if ordering != Ordering::Equal {
+                    return ordering;
+                }
+            }
+            (Some(l), Some(r)) => {
+                let ordering = l.cmp(&r);
+                if ordering != Ordering::Equal {
+                    return ordering;
+                }
+                left.next();
+                right.next();
+            }
         }
-        (Value::Bool { val: left, .. }, Value::Bool { val: right, .. }) => {
-            Ok(CompareValues::Booleans(*left, *right))
+    }
+}
+
+fn take_digits(chars: &mut Peekable<Chars>) -> String {
+    let mut digits = String::new();
+    while let Some(c) = chars.peek().copied() {
+        if !c.is_ascii_digit() {
+            break;
         }
-        _ => Err(("coerce_compare_left", "coerce_compare_right")),
+        digits.push(c);
+        chars.next();
     }
+    digits
 }
 
 #[cfg(test)]
@@ -188,4 +312,16 @@ mod test {
 
         test_examples(SortBy {})
     }
+
+    #[test]
+    fn natural_order() {
+        use std::cmp::Ordering;
+
+        assert_eq!(natural_cmp("file2", "file10"), Ordering::Less);
+        assert_eq!(natural_cmp("file10", "file9"), Ordering::Greater);
+        assert_eq!(natural_cmp("a1b2", "a1b10"), Ordering::Less);
+        assert_eq!(natural_cmp("file", "file1"), Ordering::Less);
+        assert_eq!(natural_cmp("v007", "v7"), Ordering::Less);
+        assert_eq!(natural_cmp("abc", "abc"), Ordering::Equal);
+    }
 }
@@ -3,7 +3,8 @@ use nu_engine::CallExt;
 use nu_protocol::ast::Call;
 use nu_protocol::engine::{Command, EngineState, Stack};
 use nu_protocol::{
-    IntoInterruptiblePipelineData, PipelineData, ShellError, Signature, Spanned, SyntaxShape, Value,
+    Category, Example, IntoInterruptiblePipelineData, IntoPipelineData, PipelineData, ShellError,
+    Signature, Span, Spanned, SyntaxShape, Value,
 };
 
 #[derive(Clone)]
@@ -13,6 +14,7 @@ pub struct TransposeArgs {
     rest: Vec<Spanned<String>>,
     header_row: bool,
     ignore_titles: bool,
+    as_record: bool,
 }
 
 impl Command for Transpose {
@@ -32,11 +34,17 @@ impl Command for Transpose {
                 "don't transpose the column names into values",
                 Some('i'),
             )
+            .switch(
+                "as-record",
+                "return the single transposed row as a record",
+                Some('d'),
+            )
             .rest(
                 "rest",
                 SyntaxShape::String,
                 "the names to give columns once transposed",
             )
+            .category(Category::Filters)
     }
 
     fn usage(&self) -> &str {
@@ -52,6 +60,67 @@ impl Command for Transpose {
     ) -> Result<nu_protocol::PipelineData, nu_protocol::ShellError> {
         transpose(engine_state, stack, call, input)
     }
+
+    fn examples(&self) -> Vec<Example> {
+        let span = Span::test_data();
+        vec![
+            Example {
+                description: "Turn a record into a table of its columns and values",
+                example: "echo {name: nu, stars: 5} | transpose",
+                result: Some(Value::List {
+                    vals: vec![
+                        Value::Record {
+                            cols: vec!["column".into(), "value".into()],
+                            vals: vec![Value::test_string("name"), Value::test_string("nu")],
+                            span,
+                        },
+                        Value::Record {
+                            cols: vec!["column".into(), "value".into()],
+                            vals: vec![Value::test_string("stars"), Value::test_int(5)],
+                            span,
+                        },
+                    ],
+                    span,
+                }),
+            },
+            Example {
+                description: "Turn a table of columns and values back into a record",
+                example: "echo [[column, value]; [name, nu] [stars, 5]] | transpose -r -d",
+                result: Some(Value::Record {
+                    cols: vec!["name".into(), "stars".into()],
+                    vals: vec![Value::test_string("nu"), Value::test_int(5)],
+                    span,
+                }),
+            },
+            Example {
+                description: "Transpose a table, naming the new columns",
+                example: "echo [[a, b]; [1, 2] [3, 4]] | transpose key first second",
+                result: Some(Value::List {
+                    vals: vec![
+                        Value::Record {
+                            cols: vec!["key".into(), "first".into(), "second".into()],
+                            vals: vec![
+                                Value::test_string("a"),
+                                Value::test_int(1),
+                                Value::test_int(3),
+                            ],
+                            span,
+                        },
+                        Value::Record {
+                            cols: vec!["key".into(), "first".into(), "second".into()],
+                            vals: vec![
+                                Value::test_string("b"),
+                                Value::test_int(2),
+                                Value::test_int(4),
+                            ],
+                            span,
+                        },
+                    ],
+                    span,
+                }),
+            },
+        ]
+    }
 }
 
 pub fn transpose(
@@ -64,11 +133,17 @@ pub fn transpose(
     let transpose_args = TransposeArgs {
         header_row: call.has_flag("header-row"),
         ignore_titles: call.has_flag("ignore-titles"),
+        as_record: call.has_flag("as-record"),
         rest: call.rest(engine_state, stack, 0)?,
     };
 
+    // A single record reads best as `{column, value}` rows unless other names were given
+    let record_input = matches!(&input, PipelineData::Value(Value::Record { .. }, ..));
+
     let ctrlc = engine_state.ctrlc.clone();
-    let input: Vec<_> = input.into_iter().collect();
+    let input: Vec<_> = input
+        .into_interruptible_iter(engine_state.ctrlc.clone())
+        .collect();
     let args = transpose_args;
 
     let descs = get_columns(&input);
@@ -118,6 +193,8 @@ pub fn transpose(
         for i in 0..=input.len() {
             if let Some(name) = args.rest.get(i) {
                 headers.push(name.item.clone())
+            } else if record_input {
+                headers.push(if i == 0 { "column" } else { "value" }.into());
             } else {
                 headers.push(format!("Column{}", i));
             }
@@ -130,7 +207,7 @@ pub fn transpose(
         descs
     };
 
-    Ok((descs.into_iter().map(move |desc| {
+    let rows = descs.into_iter().map(move |desc| {
         let mut column_num: usize = 0;
         let mut cols = vec![];
         let mut vals = vec![];
@@ -160,8 +237,22 @@ pub fn transpose(
             vals,
             span: name,
         }
-    }))
-    .into_pipeline_data(ctrlc))
+    });
+
+    if args.as_record {
+        let mut rows: Vec<Value> = rows.collect();
+        if rows.len() == 1 {
+            Ok(rows.remove(0).into_pipeline_data())
+        } else {
+            Err(ShellError::SpannedLabeledError(
+                "Can only return a single row as a record".into(),
+                format!("transposed into {} rows", rows.len()),
+                name,
+            ))
+        }
+    } else {
+        Ok(rows.into_pipeline_data(ctrlc))
+    }
 }
 
 #[cfg(test)]
@@ -1,9 +1,7 @@
-use std::collections::VecDeque;
-
 use nu_protocol::ast::Call;
 use nu_protocol::engine::{Command, EngineState, Stack};
 use nu_protocol::{
-    Category, Example, IntoPipelineData, PipelineData, ShellError, Signature, Span, Value,
+    Category, Example, IntoInterruptiblePipelineData, PipelineData, Signature, Span, Value,
 };
 
 #[derive(Clone)]
@@ -28,11 +26,20 @@ impl Command for Uniq {
                 Some('i'),
             )
             .switch("unique", "Only return unique values", Some('u'))
+            .switch(
+                "global",
+                "Remove duplicates anywhere in the input, not only consecutive ones",
+                Some('g'),
+            )
             .category(Category::Filters)
     }
 
     fn usage(&self) -> &str {
-        "Return the unique rows."
+        "Return the unique rows, collapsing consecutive duplicates."
+    }
+
+    fn extra_usage(&self) -> &str {
+        "Like coreutils uniq, only neighbouring duplicates are collapsed unless --global is given, so sort the input first or use --global. Without --global the input is streamed."
     }
 
     fn run(
@@ -48,8 +55,21 @@ impl Command for Uniq {
     fn examples(&self) -> Vec<Example> {
         vec![
             Example {
-                description: "Remove duplicate rows of a list/table",
-                example: "[2 3 3 4] | uniq",
+                description: "Collapse consecutive duplicate rows of a list/table",
+                example: "[2 3 3 4 3] | uniq",
+                result: Some(Value::List {
+                    vals: vec![
+                        Value::test_int(2),
+                        Value::test_int(3),
+                        Value::test_int(4),
+                        Value::test_int(3),
+                    ],
+                    span: Span::test_data(),
+                }),
+            },
+            Example {
+                description: "Remove duplicate rows wherever they are",
+                example: "[2 3 3 4 3] | uniq --global",
                 result: Some(Value::List {
                     vals: vec![Value::test_int(2), Value::test_int(3), Value::test_int(4)],
                     span: Span::test_data(),
@@ -58,16 +78,22 @@ impl Command for Uniq {
             Example {
                 description: "Only print duplicate lines, one for each group",
                 example: "[1 2 2] | uniq -d",
-                result: Some(Value::test_int(2)),
+                result: Some(Value::List {
+                    vals: vec![Value::test_int(2)],
+                    span: Span::test_data(),
+                }),
             },
             Example {
                 description: "Only print unique lines lines",
                 example: "[1 2 2] | uniq -u",
-                result: Some(Value::test_int(1)),
+                result: Some(Value::List {
+                    vals: vec![Value::test_int(1)],
+                    span: Span::test_data(),
+                }),
             },
             Example {
                 description: "Ignore differences in case when comparing",
-                example: "['hello' 'goodbye' 'Hello'] | uniq -i",
+                example: "['hello' 'goodbye' 'Hello'] | uniq -i -g",
                 result: Some(Value::List {
                     vals: vec![Value::test_string("hello"), Value::test_string("goodbye")],
                     span: Span::test_data(),
@@ -96,18 +122,70 @@ impl Command for Uniq {
     }
 }
 
-fn to_lowercase(value: nu_protocol::Value) -> nu_protocol::Value {
+/// The value duplicates are compared by: strings are lowercased when ignoring case
+fn comparison_key(value: &Value, ignore_case: bool) -> Value {
     match value {
-        Value::String { val: s, span } => Value::String {
-            val: s.to_lowercase(),
-            span,
+        Value::String { val, span } if ignore_case => Value::String {
+            val: val.to_lowercase(),
+            span: *span,
         },
-        other => other,
+        other => other.clone(),
+    }
+}
+
+/// Collapses runs of equal values into the first value of the run and its length,
+/// pulling from the input only as far as the end of the current run
+struct ConsecutiveGroups<I: Iterator<Item = Value>> {
+    input: I,
+    ignore_case: bool,
+    current: Option<(Value, Value, i64)>,
+}
+
+impl<I: Iterator<Item = Value>> Iterator for ConsecutiveGroups<I> {
+    type Item = (Value, i64);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        for item in self.input.by_ref() {
+            let key = comparison_key(&item, self.ignore_case);
+
+            match &mut self.current {
+                Some((_, current_key, count)) if current_key.total_cmp(&key).is_eq() => *count += 1,
+                current => {
+                    if let Some((value, _, count)) = current.replace((item, key, 1)) {
+                        return Some((value, count));
+                    }
+                }
+            }
+        }
+
+        self.current.take().map(|(value, _, count)| (value, count))
+    }
+}
+
+fn global_groups(input: impl Iterator<Item = Value>, ignore_case: bool) -> Vec<(Value, i64)> {
+    let mut groups: Vec<(Value, Value, i64)> = Vec::new();
+
+    for item in input {
+        let key = comparison_key(&item, ignore_case);
+
+        // check if the value item already exists in our collection. if it does, increase counter, otherwise add it to the collection
+        match groups
+            .iter_mut()
+            .find(|group| group.1.total_cmp(&key).is_eq())
+        {
+            Some(group) => group.2 += 1,
+            None => groups.push((item, key, 1)),
+        }
     }
+
+    groups
+        .into_iter()
+        .map(|(value, _, count)| (value, count))
+        .collect()
 }
 
 fn uniq(
-    _engine_state: &EngineState,
+    engine_state: &EngineState,
     _stack: &mut Stack,
     call: &Call,
     input: PipelineData,
@@ -117,90 +195,117 @@ fn uniq(
     let show_repeated = call.has_flag("repeated");
     let ignore_case = call.has_flag("ignore-case");
     let only_uniques = call.has_flag("unique");
+    let global = call.has_flag("global");
+    let ctrlc = engine_state.ctrlc.clone();
 
-    let uniq_values = {
-        let counter = &mut Vec::new();
-        for line in input.into_iter() {
-            let item = if ignore_case {
-                to_lowercase(line)
-            } else {
-                line
-            };
-
-            if counter.is_empty() {
-                counter.push((item, 1));
-            } else {
-                // check if the value item already exists in our collection. if it does, increase counter, otherwise add it to the collection
-                match counter.iter_mut().find(|x| x.0 == item) {
-                    Some(x) => x.1 += 1,
-                    None => counter.push((item, 1)),
-                }
-            }
-        }
-        counter.to_vec()
-    };
-
-    let uv = uniq_values.to_vec();
-    let mut values = if show_repeated {
-        uv.into_iter().filter(|i| i.1 > 1).collect()
+    let input = input.into_interruptible_iter(ctrlc.clone());
+    let groups: Box<dyn Iterator<Item = (Value, i64)> + Send> = if global {
+        Box::new(global_groups(input, ignore_case).into_iter())
     } else {
-        uv
+        Box::new(ConsecutiveGroups {
+            input,
+            ignore_case,
+            current: None,
+        })
     };
 
-    if only_uniques {
-        values = values.into_iter().filter(|i| i.1 == 1).collect::<_>()
-    }
-
-    let mut values_vec_deque = VecDeque::new();
-
-    if should_show_count {
-        for item in values {
-            values_vec_deque.push_back({
-                let cols = vec!["value".to_string(), "count".to_string()];
-                let vals = vec![
-                    item.0,
-                    Value::Int {
-                        val: item.1,
-                        span: head,
-                    },
-                ];
+    Ok(groups
+        .filter(move |(_, count)| (!show_repeated || *count > 1) && (!only_uniques || *count == 1))
+        .map(move |(value, count)| {
+            if should_show_count {
                 Value::Record {
-                    cols,
-                    vals,
+                    cols: vec!["value".to_string(), "count".to_string()],
+                    vals: vec![
+                        value,
+                        Value::Int {
+                            val: count,
+                            span: head,
+                        },
+                    ],
                     span: head,
                 }
-            });
-        }
-    } else {
-        for item in values {
-            values_vec_deque.push_back(item.0);
-        }
-    }
-
-    // keeps the original Nushell semantics
-    if values_vec_deque.len() == 1 {
-        if let Some(x) = values_vec_deque.pop_front() {
-            Ok(x.into_pipeline_data())
-        } else {
-            Err(ShellError::NushellFailed("No input given...".to_string()))
-        }
-    } else {
-        Ok(Value::List {
-            vals: values_vec_deque.into_iter().collect(),
-            span: head,
-        }
-        .into_pipeline_data())
-    }
+            } else {
+                value
+            }
+        })
+        .into_pipeline_data(ctrlc))
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
 
+    fn ints(vals: &[i64]) -> impl Iterator<Item = Value> {
+        vals.iter()
+            .map(|val| Value::test_int(*val))
+            .collect::<Vec<_>>()
+            .into_iter()
+    }
+
     #[test]
     fn test_examples() {
         use crate::test_examples;
 
         test_examples(Uniq {})
     }
+
+    #[test]
+    fn groups_consecutive_runs() {
+        let groups: Vec<_> = ConsecutiveGroups {
+            input: ints(&[1, 1, 2, 1, 3, 3, 3]),
+            ignore_case: false,
+            current: None,
+        }
+        .collect();
+
+        assert_eq!(
+            groups,
+            vec![
+                (Value::test_int(1), 2),
+                (Value::test_int(2), 1),
+                (Value::test_int(1), 1),
+                (Value::test_int(3), 3),
+            ]
+        );
+    }
+
+    #[test]
+    fn consecutive_groups_do_not_read_past_the_run() {
+        let mut input = ints(&[1, 1, 2, 3]);
+        let first = ConsecutiveGroups {
+            input: input.by_ref(),
+            ignore_case: false,
+            current: None,
+        }
+        .next();
+
+        assert_eq!(first, Some((Value::test_int(1), 2)));
+        assert_eq!(input.next(), Some(Value::test_int(3)));
+    }
+
+    #[test]
+    fn groups_globally_in_first_seen_order() {
+        assert_eq!(
+            global_groups(ints(&[1, 2, 1, 3, 2, 1]), false),
+            vec![
+                (Value::test_int(1), 3),
+                (Value::test_int(2), 2),
+                (Value::test_int(3), 1),
+            ]
+        );
+    }
+
+    #[test]
+    fn keeps_the_first_spelling_when_ignoring_case() {
+        let input = vec![
+            Value::test_string("Nu"),
+            Value::test_string("nu"),
+            Value::test_string("NU"),
+        ];
+
+        assert_eq!(
+            global_groups(input.into_iter(), true),
+            vec![(Value::test_string("Nu"), 3)]
+        );
+    }
 }
@@ -98,7 +98,7 @@ fn update(
                 match output {
                     Ok(pd) => {
                         if let Err(e) =
-                            input.replace_data_at_cell_path(&cell_path.members, pd.into_value(span))
+                            input.update_data_at_cell_path(&cell_path.members, pd.into_value(span))
                         {
                             return Value::Error { error: e };
                         }
@@ -115,7 +115,7 @@ fn update(
             move |mut input| {
                 let replacement = replacement.clone();
 
-                if let Err(e) = input.replace_data_at_cell_path(&cell_path.members, replacement) {
+                if let Err(e) = input.update_data_at_cell_path(&cell_path.members, replacement) {
                     return Value::Error { error: e };
                 }
 
@@ -2,8 +2,8 @@ use nu_engine::CallExt;
 use nu_protocol::ast::Call;
 use nu_protocol::engine::{Command, EngineState, Stack};
 use nu_protocol::{
-    Category, IntoInterruptiblePipelineData, IntoPipelineData, PipelineData, Signature,
-    SyntaxShape, Value,
+    Category, Example, IntoInterruptiblePipelineData, IntoPipelineData, PipelineData, Signature,
+    Span, SyntaxShape, Value,
 };
 
 #[derive(Clone)]
@@ -35,7 +35,9 @@ impl Command for Wrap {
         let name: String = call.req(engine_state, stack, 0)?;
 
         match input {
-            PipelineData::Value(Value::List { vals, .. }, ..) => Ok(vals
+            PipelineData::Value(Value::List { .. }, ..)
+            | PipelineData::Value(Value::Range { .. }, ..)
+            | PipelineData::ListStream(..) => Ok(input
                 .into_iter()
                 .map(move |x| Value::Record {
                     cols: vec![name.clone()],
@@ -43,20 +45,22 @@ impl Command for Wrap {
                     span,
                 })
                 .into_pipeline_data(engine_state.ctrlc.clone())),
-            PipelineData::ListStream(stream, ..) => Ok(stream
-                .map(move |x| Value::Record {
-                    cols: vec![name.clone()],
-                    vals: vec![x],
+            // Text and bytes are whole values, so they make a single row
+            PipelineData::StringStream(stream, ..) => Ok(Value::Record {
+                cols: vec![name],
+                vals: vec![Value::String {
+                    val: stream.into_string("")?,
                     span,
-                })
-                .into_pipeline_data(engine_state.ctrlc.clone())),
-            PipelineData::StringStream(stream, ..) => Ok(Value::String {
-                val: stream.into_string("")?,
+                }],
                 span,
             }
             .into_pipeline_data()),
-            PipelineData::ByteStream(stream, ..) => Ok(Value::Binary {
-                val: stream.into_vec()?,
+            PipelineData::ByteStream(stream, ..) => Ok(Value::Record {
+                cols: vec![name],
+                vals: vec![Value::Binary {
+                    val: stream.into_vec()?,
+                    span,
+                }],
                 span,
             }
             .into_pipeline_data()),
@@ -68,4 +72,54 @@ impl Command for Wrap {
             .into_pipeline_data()),
         }
     }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![
+            Example {
+                description: "Wrap each item of a list into a column",
+                example: "[a b] | wrap item",
+                result: Some(Value::List {
+                    vals: vec![
+                        Value::Record {
+                            cols: vec!["item".into()],
+                            vals: vec![Value::test_string("a")],
+                            span: Span::test_data(),
+                        },
+                        Value::Record {
+                            cols: vec!["item".into()],
+                            vals: vec![Value::test_string("b")],
+                            span: Span::test_data(),
+                        },
+                    ],
+                    span: Span::test_data(),
+                }),
+            },
+            Example {
+                description: "Wrap a single value into a record",
+                example: "42 | wrap answer",
+                result: Some(Value::Record {
+                    cols: vec!["answer".into()],
+                    vals: vec![Value::test_int(42)],
+                    span: Span::test_data(),
+                }),
+            },
+            Example {
+                description: "Make a table of file names, to add columns to",
+                example: "ls | get name | wrap file",
+                result: None,
+            },
+        ]
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_examples() {
+        use crate::test_examples;
+
+        test_examples(Wrap {})
+    }
 }
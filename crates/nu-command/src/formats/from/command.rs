@@ -1,6 +1,10 @@
+use crate::formats::registry::{list_formats, run_format, Direction};
+use nu_engine::CallExt;
 use nu_protocol::ast::Call;
 use nu_protocol::engine::{Command, EngineState, Stack};
-use nu_protocol::{Category, PipelineData, ShellError, Signature};
+use nu_protocol::{
+    Category, Example, IntoPipelineData, PipelineData, ShellError, Signature, Spanned, SyntaxShape,
+};
 
 #[derive(Clone)]
 pub struct From;
@@ -14,17 +18,46 @@ impl Command for From {
         "Parse a string or binary data into structured data"
     }
 
+    fn extra_usage(&self) -> &str {
+        "With a format, runs the 'from' command for it, so the format can come from a variable."
+    }
+
     fn signature(&self) -> nu_protocol::Signature {
-        Signature::build("from").category(Category::Formats)
+        Signature::build("from")
+            .optional("format", SyntaxShape::String, "the format to parse from")
+            .switch("list", "list the formats that can be parsed", Some('l'))
+            .category(Category::Formats)
     }
 
     fn run(
         &self,
-        _engine_state: &EngineState,
-        _stack: &mut Stack,
+        engine_state: &EngineState,
+        stack: &mut Stack,
         call: &Call,
-        _input: PipelineData,
+        input: PipelineData,
     ) -> Result<nu_protocol::PipelineData, ShellError> {
-        Ok(PipelineData::new(call.head))
+        if call.has_flag("list") {
+            return Ok(list_formats(Direction::From, engine_state, call.head).into_pipeline_data());
+        }
+
+        match call.opt::<Spanned<String>>(engine_state, stack, 0)? {
+            Some(format) => run_format(Direction::From, &format, engine_state, stack, call, input),
+            None => Ok(PipelineData::new(call.head)),
+        }
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![
+            Example {
+                description: "List the formats this build can parse",
+                example: "from --list",
+                result: None,
+            },
+            Example {
+                description: "Parse with a format chosen at run time",
+                example: "let fmt = 'json'; '[1, 2]' | from $fmt",
+                result: None,
+            },
+        ]
     }
 }
@@ -1,4 +1,5 @@
 mod from;
+pub(crate) mod registry;
 mod to;
 
 pub use from::*;
@@ -0,0 +1,220 @@
+use nu_protocol::ast::Call;
+use nu_protocol::engine::{EngineState, Stack};
+use nu_protocol::{DeclId, PipelineData, ShellError, Span, Spanned, Value};
+
+/// A file format, read with `from <name>` and written with `to <name>`. `open` and `save` pick
+/// the format by the extension of the file.
+///
+/// To add a format, add the command(s) and an entry to `FORMATS`. Whether a format can be read or
+/// written is up to which of the commands are in the engine, so formats behind a feature flag
+/// only show up in builds that have them.
+pub struct FileFormat {
+    pub name: &'static str,
+    pub extensions: &'static [&'static str],
+}
+
+pub const FORMATS: &[FileFormat] = &[
+    FileFormat {
+        name: "csv",
+        extensions: &["csv"],
+    },
+    FileFormat {
+        name: "eml",
+        extensions: &["eml"],
+    },
+    FileFormat {
+        name: "html",
+        extensions: &["html", "htm"],
+    },
+    FileFormat {
+        name: "ics",
+        extensions: &["ics"],
+    },
+    FileFormat {
+        name: "ini",
+        extensions: &["ini"],
+    },
+    FileFormat {
+        name: "json",
+        extensions: &["json"],
+    },
+    FileFormat {
+        name: "md",
+        extensions: &["md", "markdown"],
+    },
+    FileFormat {
+        name: "ods",
+        extensions: &["ods"],
+    },
+    FileFormat {
+        name: "ssv",
+        extensions: &["ssv"],
+    },
+    FileFormat {
+        name: "text",
+        extensions: &[],
+    },
+    FileFormat {
+        name: "toml",
+        extensions: &["toml"],
+    },
+    FileFormat {
+        name: "tsv",
+        extensions: &["tsv"],
+    },
+    FileFormat {
+        name: "url",
+        extensions: &[],
+    },
+    FileFormat {
+        name: "vcf",
+        extensions: &["vcf"],
+    },
+    FileFormat {
+        name: "xlsx",
+        extensions: &["xlsx"],
+    },
+    FileFormat {
+        name: "xml",
+        extensions: &["xml"],
+    },
+    FileFormat {
+        name: "yaml",
+        extensions: &["yaml", "yml"],
+    },
+];
+
+#[derive(Clone, Copy)]
+pub enum Direction {
+    From,
+    To,
+}
+
+impl Direction {
+    fn command(self) -> &'static str {
+        match self {
+            Direction::From => "from",
+            Direction::To => "to",
+        }
+    }
+}
+
+impl FileFormat {
+    /// The command that reads or writes the format, if this engine has it
+    pub fn decl(&self, direction: Direction, engine_state: &EngineState) -> Option<DeclId> {
+        engine_state.find_decl(format!("{} {}", direction.command(), self.name).as_bytes())
+    }
+}
+
+pub fn find_format(name: &str) -> Option<&'static FileFormat> {
+    FORMATS.iter().find(|format| format.name == name)
+}
+
+pub fn format_for_extension(extension: &str) -> Option<&'static FileFormat> {
+    FORMATS.iter().find(|format| {
+        format
+            .extensions
+            .iter()
+            .any(|known| known.eq_ignore_ascii_case(extension))
+    })
+}
+
+/// The converter for a file with `extension`. Extensions that aren't registered still go to a
+/// `from <extension>` or `to <extension>` command when there is one, so custom commands can add
+/// formats of their own.
+pub fn decl_for_extension(
+    extension: &str,
+    direction: Direction,
+    engine_state: &EngineState,
+) -> Option<DeclId> {
+    match format_for_extension(extension) {
+        Some(format) => format.decl(direction, engine_state),
+        None => engine_state.find_decl(format!("{} {}", direction.command(), extension).as_bytes()),
+    }
+}
+
+/// Run the converter for `format`, for `from <format>` and `to <format>` given the format as an
+/// argument
+pub fn run_format(
+    direction: Direction,
+    format: &Spanned<String>,
+    engine_state: &EngineState,
+    stack: &mut Stack,
+    call: &Call,
+    input: PipelineData,
+) -> Result<PipelineData, ShellError> {
+    let decl_id = find_format(&format.item)
+        .and_then(|found| found.decl(direction, engine_state))
+        .ok_or_else(|| {
+            ShellError::SpannedLabeledError(
+                format!("Unknown format {}", format.item),
+                format!(
+                    "see '{} --list' for the formats this build supports",
+                    direction.command()
+                ),
+                format.span,
+            )
+        })?;
+
+    let mut converter_call = Call::new();
+    converter_call.head = call.head;
+    engine_state
+        .get_decl(decl_id)
+        .run(engine_state, stack, &converter_call, input)
+}
+
+/// The formats this engine can read or write, as a table for `from --list` and `to --list`
+pub fn list_formats(direction: Direction, engine_state: &EngineState, span: Span) -> Value {
+    let vals = FORMATS
+        .iter()
+        .filter(|format| format.decl(direction, engine_state).is_some())
+        .map(|format| Value::Record {
+            cols: vec!["format".into(), "extensions".into()],
+            vals: vec![
+                Value::string(format.name, span),
+                Value::List {
+                    vals: format
+                        .extensions
+                        .iter()
+                        .map(|extension| Value::string(*extension, span))
+                        .collect(),
+                    span,
+                },
+            ],
+            span,
+        })
+        .collect();
+
+    Value::List { vals, span }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn names_and_extensions_are_unique() {
+        for (idx, format) in FORMATS.iter().enumerate() {
+            assert!(
+                FORMATS[..idx].iter().all(|other| other.name != format.name),
+                "format {} is registered twice",
+                format.name
+            );
+
+            for extension in format.extensions {
+                assert_eq!(
+                    format_for_extension(extension).map(|found| found.name),
+                    Some(format.name),
+                    "extension {} belongs to more than one format",
+                    extension
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn extensions_ignore_case() {
+        assert_eq!(format_for_extension("YML").map(|f| f.name), Some("yaml"));
+        assert!(format_for_extension("txt").is_none());
+    }
+}
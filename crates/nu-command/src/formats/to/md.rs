@@ -79,7 +79,16 @@ fn to_md(
     config: Config,
     head: Span,
 ) -> Result<PipelineData, ShellError> {
-    let (grouped_input, single_list) = group_by(input, head, &config);
+    let values: Vec<Value> = input.into_iter().collect();
+    let (grouped_input, single_list) = group_by(
+        Value::List {
+            vals: values.clone(),
+            span: head,
+        }
+        .into_pipeline_data(),
+        head,
+        &config,
+    );
     if per_element || single_list {
         return Ok(Value::string(
             grouped_input
@@ -94,7 +103,20 @@ fn to_md(
         )
         .into_pipeline_data());
     }
-    Ok(Value::string(table(grouped_input, pretty, &config), head).into_pipeline_data())
+    // Rows with different columns make one table, with blanks where a row lacks a column
+    Ok(Value::string(
+        table(
+            Value::List {
+                vals: values,
+                span: head,
+            }
+            .into_pipeline_data(),
+            pretty,
+            &config,
+        ),
+        head,
+    )
+    .into_pipeline_data())
 }
 
 fn fragment(input: Value, pretty: bool, config: &Config) -> String {
@@ -162,9 +184,11 @@ fn table(input: PipelineData, pretty: bool, config: &Config) -> String {
             Value::Record { span, .. } => {
                 for i in 0..headers.len() {
                     let data = row.get_data_by_key(&headers[i]);
-                    let value_string = data
-                        .unwrap_or_else(|| Value::nothing(span))
-                        .into_string("|", config);
+                    let value_string = escape_cell(
+                        &data
+                            .unwrap_or_else(|| Value::nothing(span))
+                            .into_string(", ", config),
+                    );
                     let new_column_width = value_string.len();
 
                     escaped_row.push(value_string);
@@ -196,6 +220,13 @@ fn table(input: PipelineData, pretty: bool, config: &Config) -> String {
     output_string
 }
 
+/// A pipe would end the cell early and a newline the row, so neither can be left as it is
+fn escape_cell(text: &str) -> String {
+    text.replace('|', "\\|")
+        .replace("\r\n", " ")
+        .replace('\n', " ")
+}
+
 pub fn group_by(values: PipelineData, head: Span, config: &Config) -> (PipelineData, bool) {
     let mut lists = IndexMap::new();
     let mut single_list = false;
@@ -347,6 +378,20 @@ mod tests {
         test_examples(ToMd {})
     }
 
+    #[test]
+    fn cells_with_pipes_and_newlines_stay_in_the_table() {
+        let value = Value::Record {
+            cols: vec!["note".to_string()],
+            vals: vec![Value::test_string("a|b\nc")],
+            span: Span::test_data(),
+        };
+
+        assert_eq!(
+            table(value.into_pipeline_data(), false, &Config::default()),
+            "|note|\n|-|\n|a\\|b c|"
+        );
+    }
+
     #[test]
     fn render_h1() {
         let value = Value::Record {
@@ -4,6 +4,7 @@ mod delimited;
 mod html;
 mod json;
 mod md;
+mod text;
 mod toml;
 mod tsv;
 mod url;
@@ -15,8 +16,9 @@ pub use self::toml::ToToml;
 pub use self::url::ToUrl;
 pub use command::To;
 pub use html::ToHtml;
-pub use json::ToJson;
+pub use json::{value_to_json_value, ToJson};
 pub use md::ToMd;
+pub use text::ToText;
 pub use tsv::ToTsv;
 pub use xml::ToXml;
 pub use yaml::ToYaml;
@@ -0,0 +1,145 @@
+use crate::formats::to::delimited::merge_descriptors;
+use nu_protocol::ast::Call;
+use nu_protocol::engine::{Command, EngineState, Stack};
+use nu_protocol::{
+    Category, Config, Example, IntoPipelineData, PipelineData, ShellError, Signature, Span, Value,
+};
+
+/// The space between two columns
+const COLUMN_GAP: &str = "  ";
+
+#[derive(Clone)]
+pub struct ToText;
+
+impl Command for ToText {
+    fn name(&self) -> &str {
+        "to text"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("to text").category(Category::Formats)
+    }
+
+    fn usage(&self) -> &str {
+        "Convert table into plain text, with the columns aligned"
+    }
+
+    fn extra_usage(&self) -> &str {
+        "Cells are formatted the way 'into string' would, and columns a row doesn't have are left blank. Input that isn't a table becomes one value per line."
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![
+            Example {
+                description: "Outputs a plain text table",
+                example: "[[name size]; [a.txt 10] [notes.md 2000]] | to text",
+                result: Some(Value::test_string(
+                    "name      size\na.txt     10\nnotes.md  2000",
+                )),
+            },
+            Example {
+                description: "Columns missing from a row are left blank",
+                example: "[{a: 1, b: 2} {a: 3}] | to text",
+                result: Some(Value::test_string("a  b\n1  2\n3")),
+            },
+            Example {
+                description: "Outputs a list one item per line",
+                example: "[1 2 3] | to text",
+                result: Some(Value::test_string("1\n2\n3")),
+            },
+        ]
+    }
+
+    fn run(
+        &self,
+        _engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        input: PipelineData,
+    ) -> Result<nu_protocol::PipelineData, ShellError> {
+        let head = call.head;
+        let config = stack.get_config().unwrap_or_default();
+        Ok(Value::string(to_text(input, &config), head).into_pipeline_data())
+    }
+}
+
+fn to_text(input: PipelineData, config: &Config) -> String {
+    let values: Vec<Value> = input.into_iter().collect();
+
+    if !values
+        .iter()
+        .any(|value| matches!(value, Value::Record { .. }))
+    {
+        return values
+            .iter()
+            .map(|value| value.into_string(", ", config))
+            .collect::<Vec<_>>()
+            .join("\n");
+    }
+
+    let headers = merge_descriptors(&values);
+    let rows: Vec<Vec<String>> = values
+        .iter()
+        .map(|value| match value {
+            Value::Record { .. } => headers
+                .iter()
+                .map(|header| {
+                    value
+                        .get_data_by_key(header)
+                        .map_or_else(String::new, |cell| cell.into_string(", ", config))
+                })
+                .collect(),
+            // Merging the headers gave values that aren't records a column with an empty name
+            other => headers
+                .iter()
+                .map(|header| {
+                    if header.is_empty() {
+                        other.into_string(", ", config)
+                    } else {
+                        String::new()
+                    }
+                })
+                .collect(),
+        })
+        .collect();
+
+    let mut widths: Vec<usize> = headers.iter().map(|header| text_width(header)).collect();
+    for row in &rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(text_width(cell));
+        }
+    }
+
+    std::iter::once(&headers)
+        .chain(&rows)
+        .map(|row| aligned_line(row, &widths))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn aligned_line(cells: &[String], widths: &[usize]) -> String {
+    let line = cells
+        .iter()
+        .zip(widths)
+        .map(|(cell, width)| format!("{}{}", cell, " ".repeat(width - text_width(cell))))
+        .collect::<Vec<_>>()
+        .join(COLUMN_GAP);
+
+    line.trim_end().to_string()
+}
+
+fn text_width(text: &str) -> usize {
+    text.chars().count()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_examples() {
+        use crate::test_examples;
+
+        test_examples(ToText {})
+    }
+}
@@ -4,8 +4,8 @@ use nu_engine::CallExt;
 use nu_protocol::ast::Call;
 use nu_protocol::engine::{Command, EngineState, Stack};
 use nu_protocol::{
-    Category, Example, IntoPipelineData, PipelineData, ShellError, Signature, Span, Spanned,
-    SyntaxShape, Value,
+    Category, DataSource, Example, IntoInterruptiblePipelineData, PipelineData, PipelineMetadata,
+    ShellError, Signature, Span, Spanned, SyntaxShape, Value,
 };
 use std::collections::VecDeque;
 
@@ -55,6 +55,12 @@ impl Command for Cal {
         "Display a calendar."
     }
 
+    fn extra_usage(&self) -> &str {
+        r#"Each week is a row, with a column per weekday holding the day of the month, or nothing
+for the days outside it. The days are numbers, so the calendar can be filtered like any table.
+Today is highlighted when the calendar is displayed as it is, in a terminal."#
+    }
+
     fn run(
         &self,
         engine_state: &EngineState,
@@ -121,7 +127,7 @@ pub fn cal(
         (current_month, current_month)
     };
 
-    add_months_of_year_to_table(
+    let today = add_months_of_year_to_table(
         &arguments,
         &mut calendar_vec_deque,
         tag,
@@ -131,11 +137,15 @@ pub fn cal(
         current_day_option,
     )?;
 
-    Ok(Value::List {
-        vals: calendar_vec_deque.into_iter().collect(),
-        span: tag,
-    }
-    .into_pipeline_data())
+    // The days stay numbers, so the calendar can be filtered; `table` highlights today
+    Ok(calendar_vec_deque
+        .into_iter()
+        .into_pipeline_data_with_metadata(
+            PipelineMetadata {
+                data_source: DataSource::Calendar { today },
+            },
+            engine_state.ctrlc.clone(),
+        ))
 }
 
 fn get_invalid_year_shell_error(head: Span) -> ShellError {
@@ -206,7 +216,9 @@ fn add_months_of_year_to_table(
     (start_month, end_month): (u32, u32),
     current_month: u32,
     current_day_option: Option<u32>,
-) -> Result<(), ShellError> {
+) -> Result<Option<(usize, String)>, ShellError> {
+    let mut today = None;
+
     for month_number in start_month..=end_month {
         let mut new_current_day_option: Option<u32> = None;
 
@@ -225,10 +237,10 @@ fn add_months_of_year_to_table(
             new_current_day_option,
         );
 
-        add_month_to_table_result?
+        today = add_month_to_table_result?.or(today);
     }
 
-    Ok(())
+    Ok(today)
 }
 
 fn add_month_to_table(
@@ -238,7 +250,8 @@ fn add_month_to_table(
     selected_year: i32,
     current_month: u32,
     current_day_option: Option<u32>,
-) -> Result<(), ShellError> {
+) -> Result<Option<(usize, String)>, ShellError> {
+    let mut today = None;
     let month_helper_result = MonthHelper::new(selected_year, current_month);
 
     let full_year_value: &Option<Spanned<i64>> = &arguments.full_year;
@@ -352,11 +365,8 @@ fn add_month_to_table(
                     span: tag,
                 };
 
-                if let Some(current_day) = current_day_option {
-                    if current_day == adjusted_day_number {
-                        // TODO: Update the value here with a color when color support is added
-                        // This colors the current day
-                    }
+                if current_day_option == Some(adjusted_day_number) {
+                    today = Some((calendar_vec_deque.len(), (*day).to_string()));
                 }
             }
 
@@ -379,7 +389,7 @@ fn add_month_to_table(
         })
     }
 
-    Ok(())
+    Ok(today)
 }
 
 #[cfg(test)]
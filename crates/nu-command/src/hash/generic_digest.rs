@@ -1,7 +1,9 @@
 use nu_engine::CallExt;
 use nu_protocol::ast::{Call, CellPath};
 use nu_protocol::engine::{Command, EngineState, Stack};
-use nu_protocol::{Example, PipelineData, ShellError, Signature, SyntaxShape, Value};
+use nu_protocol::{
+    Example, IntoPipelineData, PipelineData, ShellError, Signature, SyntaxShape, Value,
+};
 use std::marker::PhantomData;
 
 pub trait HashDigest: digest::Digest + Clone {
@@ -60,6 +62,22 @@ where
     ) -> Result<nu_protocol::PipelineData, nu_protocol::ShellError> {
         let cell_paths: Vec<CellPath> = call.rest(engine_state, stack, 0)?;
 
+        // A stream is hashed as a whole, a chunk at a time as it comes in
+        let stream_span = match &input {
+            PipelineData::ByteStream(_, span, ..) | PipelineData::StringStream(_, span, ..) => {
+                Some(*span)
+            }
+            _ => None,
+        };
+        if let (Some(span), true) = (stream_span, cell_paths.is_empty()) {
+            let config = stack.get_config().unwrap_or_default();
+            let mut digest = DigestWriter(D::new());
+            input.drain_to_writer(&mut digest, &config)?;
+
+            let val = format!("{:x}", digest.0.finalize());
+            return Ok(Value::String { val, span }.into_pipeline_data());
+        }
+
         input.map(
             move |v| {
                 if cell_paths.is_empty() {
@@ -81,6 +99,20 @@ where
     }
 }
 
+/// Feeds everything written to it into the digest
+struct DigestWriter<D>(D);
+
+impl<D: digest::Digest> std::io::Write for DigestWriter<D> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.update(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
 pub fn action<D>(input: &Value) -> Value
 where
     D: HashDigest,
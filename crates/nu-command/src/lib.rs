@@ -1,3 +1,4 @@
+mod bits;
 mod conversions;
 mod core_commands;
 mod date;
@@ -20,6 +21,7 @@ mod strings;
 mod system;
 mod viewers;
 
+pub use bits::*;
 pub use conversions::*;
 pub use core_commands::*;
 pub use date::*;
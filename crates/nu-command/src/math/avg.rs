@@ -20,6 +20,10 @@ impl Command for SubCommand {
         "Finds the average of a list of numbers or tables"
     }
 
+    fn extra_usage(&self) -> &str {
+        "The average of filesizes or durations is a filesize or duration. An empty list has no average, so it's an error."
+    }
+
     fn run(
         &self,
         _engine_state: &EngineState,
@@ -43,6 +47,13 @@ impl Command for SubCommand {
 }
 
 pub fn average(values: &[Value], head: &Span) -> Result<Value, ShellError> {
+    if values.is_empty() {
+        return Err(ShellError::UnsupportedInput(
+            "Empty input".to_string(),
+            *head,
+        ));
+    }
+
     let sum = reducer_for(Reduce::Summation);
     let total = &sum(
         Value::Int {
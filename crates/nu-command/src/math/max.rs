@@ -20,6 +20,10 @@ impl Command for SubCommand {
         "Finds the maximum within a list of numbers or tables"
     }
 
+    fn extra_usage(&self) -> &str {
+        "Filesizes, durations and dates can be compared as well as numbers, but not with each other. An empty list has no maximum, so it's an error."
+    }
+
     fn run(
         &self,
         _engine_state: &EngineState,
@@ -20,6 +20,10 @@ impl Command for SubCommand {
         "Finds the product of a list of numbers or tables"
     }
 
+    fn extra_usage(&self) -> &str {
+        "An empty list has a product of 1."
+    }
+
     fn run(
         &self,
         _engine_state: &EngineState,
@@ -69,6 +69,7 @@ pub fn min(data: Vec<Value>, head: Span) -> Result<Value, ShellError> {
     Ok(smallest)
 }
 
+/// The sum of nothing is 0
 pub fn sum(data: Vec<Value>, head: Span) -> Result<Value, ShellError> {
     let initial_value = data.get(0);
 
@@ -85,10 +86,7 @@ pub fn sum(data: Vec<Value>, head: Span) -> Result<Value, ShellError> {
             val: 0,
             span: *span,
         }),
-        None => Err(ShellError::UnsupportedInput(
-            "Empty input".to_string(),
-            head,
-        )),
+        None => Ok(Value::Int { val: 0, span: head }),
         _ => Ok(Value::nothing(head)),
     }?;
 
@@ -111,6 +109,7 @@ pub fn sum(data: Vec<Value>, head: Span) -> Result<Value, ShellError> {
     Ok(acc)
 }
 
+/// The product of nothing is 1
 pub fn product(data: Vec<Value>, head: Span) -> Result<Value, ShellError> {
     let initial_value = data.get(0);
 
@@ -119,10 +118,7 @@ pub fn product(data: Vec<Value>, head: Span) -> Result<Value, ShellError> {
             val: 1,
             span: *span,
         }),
-        None => Err(ShellError::UnsupportedInput(
-            "Empty input".to_string(),
-            head,
-        )),
+        None => Ok(Value::Int { val: 1, span: head }),
         _ => Ok(Value::nothing(head)),
     }?;
 
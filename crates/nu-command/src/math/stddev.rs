@@ -22,6 +22,10 @@ impl Command for SubCommand {
         "Finds the stddev of a list of numbers or tables"
     }
 
+    fn extra_usage(&self) -> &str {
+        "The deviation of filesizes or durations is a filesize or duration. An empty list has no deviation, so it's an error."
+    }
+
     fn run(
         &self,
         _engine_state: &EngineState,
@@ -57,8 +61,19 @@ impl Command for SubCommand {
 
 pub fn compute_stddev(sample: bool) -> impl Fn(&[Value], &Span) -> Result<Value, ShellError> {
     move |values: &[Value], span: &Span| {
+        // Filesizes and durations are worked out from their raw amounts and put back in their unit
+        let unit = unit_of(values);
+        let amounts: Vec<Value>;
+        let values = match unit {
+            Some(_) => {
+                amounts = values.iter().map(raw_amount).collect();
+                &amounts
+            }
+            None => values,
+        };
+
         let variance = variance(sample)(values, span);
-        match variance {
+        let stddev = match variance {
             Ok(Value::Float { val, span }) => Ok(Value::Float { val: val.sqrt(), span }),
             Ok(Value::Int { val, span }) => Ok(Value::Float { val: (val as f64).sqrt(), span }),
             Err(ShellError::UnsupportedInput(_, err_span)) => Err(ShellError::UnsupportedInput(
@@ -66,10 +81,61 @@ pub fn compute_stddev(sample: bool) -> impl Fn(&[Value], &Span) -> Result<Value,
                     err_span,
                 )),
             other => other
+        };
+
+        match (unit, stddev) {
+            (Some(unit), Ok(Value::Float { val, span })) => {
+                Ok(unit.value(val.round() as i64, span))
+            }
+            (_, stddev) => stddev,
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Unit {
+    Filesize,
+    Duration,
+}
+
+impl Unit {
+    fn value(self, val: i64, span: Span) -> Value {
+        match self {
+            Unit::Filesize => Value::Filesize { val, span },
+            Unit::Duration => Value::Duration { val, span },
         }
     }
 }
 
+/// The unit shared by all the values, if they are all filesizes or all durations
+fn unit_of(values: &[Value]) -> Option<Unit> {
+    let unit = match values.first()? {
+        Value::Filesize { .. } => Unit::Filesize,
+        Value::Duration { .. } => Unit::Duration,
+        _ => return None,
+    };
+
+    values
+        .iter()
+        .all(|value| {
+            matches!(
+                (unit, value),
+                (Unit::Filesize, Value::Filesize { .. }) | (Unit::Duration, Value::Duration { .. })
+            )
+        })
+        .then(|| unit)
+}
+
+fn raw_amount(value: &Value) -> Value {
+    match value {
+        Value::Filesize { val, span } | Value::Duration { val, span } => Value::Int {
+            val: *val,
+            span: *span,
+        },
+        other => other.clone(),
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -20,6 +20,10 @@ impl Command for SubCommand {
         "Finds the sum of a list of numbers or tables"
     }
 
+    fn extra_usage(&self) -> &str {
+        "Filesizes and durations add up to a filesize or duration, so 'ls | get size | math sum' gives the total size. An empty list sums to 0."
+    }
+
     fn run(
         &self,
         _engine_state: &EngineState,
@@ -20,6 +20,11 @@ fn helper_for_tables(
     name: Span,
     mf: impl Fn(&[Value], &Span) -> Result<Value, ShellError>,
 ) -> Result<Value, ShellError> {
+    // An empty stream is an empty list, not an empty table
+    if values.is_empty() {
+        return mf(values, &name);
+    }
+
     // If we are not dealing with Primitives, then perhaps we are dealing with a table
     // Create a key for each column name
     let mut column_values = IndexMap::new();
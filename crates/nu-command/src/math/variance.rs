@@ -21,6 +21,10 @@ impl Command for SubCommand {
         "Finds the variance of a list of numbers or tables"
     }
 
+    fn extra_usage(&self) -> &str {
+        "Only numbers have a variance: that of filesizes or durations would be in squared units. An empty list has no variance, so it's an error."
+    }
+
     fn run(
         &self,
         _engine_state: &EngineState,
@@ -93,6 +97,13 @@ fn sum_of_squares(values: &[Value], span: &Span) -> Result<Value, ShellError> {
 
 pub fn compute_variance(sample: bool) -> impl Fn(&[Value], &Span) -> Result<Value, ShellError> {
     move |values: &[Value], span: &Span| {
+        if values.is_empty() {
+            return Err(ShellError::UnsupportedInput(
+                "Empty input".to_string(),
+                *span,
+            ));
+        }
+
         let n = if sample {
             values.len() - 1
         } else {
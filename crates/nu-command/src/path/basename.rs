@@ -57,7 +57,7 @@ impl Command for SubCommand {
             replace: call.get_flag(engine_state, stack, "replace")?,
         };
 
-        input.map(
+        super::path_lines(input, engine_state.ctrlc.clone()).map(
             move |value| super::operate(&get_basename, &args, value, head),
             engine_state.ctrlc.clone(),
         )
@@ -65,7 +65,7 @@ impl Command for SubCommand {
             num_levels: call.get_flag(engine_state, stack, "num-levels")?,
         };
 
-        input.map(
+        super::path_lines(input, engine_state.ctrlc.clone()).map(
             move |value| super::operate(&get_dirname, &args, value, head),
             engine_state.ctrlc.clone(),
         )
@@ -1,12 +1,15 @@
 use std::path::Path;
 
+use nu_engine::env::current_dir_str;
 use nu_engine::CallExt;
+use nu_path::expand_path_with;
 use nu_protocol::{engine::Command, Example, Signature, Span, SyntaxShape, Value};
 
 use super::PathSubcommandArguments;
 
 struct Arguments {
     columns: Option<Vec<String>>,
+    cwd: String,
 }
 
 impl PathSubcommandArguments for Arguments {
@@ -46,9 +49,10 @@ impl Command for SubCommand {
         let head = call.head;
         let args = Arguments {
             columns: call.get_flag(engine_state, stack, "columns")?,
+            cwd: current_dir_str(engine_state, stack)?,
         };
 
-        input.map(
+        super::path_lines(input, engine_state.ctrlc.clone()).map(
             move |value| super::operate(&exists, &args, value, head),
             engine_state.ctrlc.clone(),
         )
@@ -93,9 +97,9 @@ impl Command for SubCommand {
     }
 }
 
-fn exists(path: &Path, span: Span, _args: &Arguments) -> Value {
+fn exists(path: &Path, span: Span, args: &Arguments) -> Value {
     Value::Bool {
-        val: path.exists(),
+        val: expand_path_with(path, &args.cwd).exists(),
         span,
     }
 }
@@ -60,7 +60,7 @@ impl Command for SubCommand {
             cwd: current_dir_str(engine_state, stack)?,
         };
 
-        input.map(
+        super::path_lines(input, engine_state.ctrlc.clone()).map(
             move |value| super::operate(&expand, &args, value, head),
             engine_state.ctrlc.clone(),
         )
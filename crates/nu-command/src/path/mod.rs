@@ -10,6 +10,8 @@ mod split;
 mod r#type;
 
 use std::path::Path as StdPath;
+use std::sync::atomic::AtomicBool;
+use std::sync::Arc;
 
 pub use basename::SubCommand as PathBasename;
 pub use command::PathCommand as Path;
@@ -22,7 +24,7 @@ pub use r#type::SubCommand as PathType;
 pub use relative_to::SubCommand as PathRelativeTo;
 pub use split::SubCommand as PathSplit;
 
-use nu_protocol::{ShellError, Span, Value};
+use nu_protocol::{IntoPipelineData, PipelineData, ShellError, Span, Value};
 
 #[cfg(windows)]
 const ALLOWED_COLUMNS: [&str; 4] = ["prefix", "parent", "stem", "extension"];
@@ -81,6 +83,20 @@ where
     }
 }
 
+/// Text is split into lines, so each path printed by an external is handled on its own
+fn path_lines(input: PipelineData, ctrlc: Option<Arc<AtomicBool>>) -> PipelineData {
+    match input {
+        PipelineData::StringStream(stream, span, ..) => stream
+            .into_lines()
+            .map(move |x| match x {
+                Ok(s) => Value::String { val: s, span },
+                Err(err) => Value::Error { error: err },
+            })
+            .into_pipeline_data(ctrlc),
+        input => input,
+    }
+}
+
 fn handle_invalid_values(rest: Value, name: Span) -> Value {
     Value::Error {
         error: err_from_value(&rest, name),
@@ -65,7 +65,7 @@ On Windows, an extra 'prefix' column is added."#
             extension: call.get_flag(engine_state, stack, "extension")?,
         };
 
-        input.map(
+        super::path_lines(input, engine_state.ctrlc.clone()).map(
             move |value| super::operate(&parse, &args, value, head),
             engine_state.ctrlc.clone(),
         )
@@ -64,7 +64,7 @@ path."#
             columns: call.get_flag(engine_state, stack, "columns")?,
         };
 
-        input.map(
+        super::path_lines(input, engine_state.ctrlc.clone()).map(
             move |value| super::operate(&relative_to, &args, value, head),
             engine_state.ctrlc.clone(),
         )
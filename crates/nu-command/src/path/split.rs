@@ -48,7 +48,7 @@ impl Command for SubCommand {
             columns: call.get_flag(engine_state, stack, "columns")?,
         };
 
-        input.map(
+        super::path_lines(input, engine_state.ctrlc.clone()).map(
             move |value| super::operate(&split, &args, value, head),
             engine_state.ctrlc.clone(),
         )
@@ -1,12 +1,16 @@
 use std::path::Path;
 
+use nu_engine::env::current_dir_str;
 use nu_engine::CallExt;
+use nu_path::expand_path_with;
 use nu_protocol::{engine::Command, Example, ShellError, Signature, Span, SyntaxShape, Value};
 
 use super::PathSubcommandArguments;
+use crate::filesystem::get_file_type;
 
 struct Arguments {
     columns: Option<Vec<String>>,
+    cwd: String,
 }
 
 impl PathSubcommandArguments for Arguments {
@@ -46,9 +50,10 @@ impl Command for SubCommand {
         let head = call.head;
         let args = Arguments {
             columns: call.get_flag(engine_state, stack, "columns")?,
+            cwd: current_dir_str(engine_state, stack)?,
         };
 
-        input.map(
+        super::path_lines(input, engine_state.ctrlc.clone()).map(
             move |value| super::operate(&r#type, &args, value, head),
             engine_state.ctrlc.clone(),
         )
@@ -70,8 +75,8 @@ impl Command for SubCommand {
     }
 }
 
-fn r#type(path: &Path, span: Span, _: &Arguments) -> Value {
-    let meta = std::fs::symlink_metadata(path);
+fn r#type(path: &Path, span: Span, args: &Arguments) -> Value {
+    let meta = std::fs::symlink_metadata(expand_path_with(path, &args.cwd));
 
     Value::string(
         match &meta {
@@ -82,33 +87,6 @@ fn r#type(path: &Path, span: Span, _: &Arguments) -> Value {
     )
 }
 
-fn get_file_type(md: &std::fs::Metadata) -> &str {
-    let ft = md.file_type();
-    let mut file_type = "unknown";
-    if ft.is_dir() {
-        file_type = "dir";
-    } else if ft.is_file() {
-        file_type = "file";
-    } else if ft.is_symlink() {
-        file_type = "symlink";
-    } else {
-        #[cfg(unix)]
-        {
-            use std::os::unix::fs::FileTypeExt;
-            if ft.is_block_device() {
-                file_type = "block device";
-            } else if ft.is_char_device() {
-                file_type = "char device";
-            } else if ft.is_fifo() {
-                file_type = "pipe";
-            } else if ft.is_socket() {
-                file_type = "socket";
-            }
-        }
-    }
-    file_type
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
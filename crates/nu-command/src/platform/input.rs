@@ -1,9 +1,14 @@
+use crossterm::event::{Event, KeyCode, KeyModifiers};
+use crossterm::tty::IsTty;
 use nu_engine::CallExt;
 use nu_protocol::ast::Call;
 use nu_protocol::engine::{Command, EngineState, Stack};
 use nu_protocol::{
-    Category, Example, IntoPipelineData, PipelineData, ShellError, Signature, SyntaxShape, Value,
+    Category, Example, IntoPipelineData, PipelineData, ShellError, Signature, Span, SyntaxShape,
+    Value,
 };
+use reedline::{Prompt, PromptEditMode, PromptHistorySearch, Reedline, Signal};
+use std::borrow::Cow;
 use std::io::{Read, Write};
 
 #[derive(Clone)]
@@ -27,9 +32,20 @@ impl Command for Input {
                 "read bytes (not text) until a stop byte",
                 Some('u'),
             )
+            .switch(
+                "suppress-output",
+                "don't echo what is typed, for passwords",
+                Some('s'),
+            )
             .category(Category::Platform)
     }
 
+    fn extra_usage(&self) -> &str {
+        r#"When stdin is a terminal the line can be edited before pressing enter.
+When stdin is piped a line is read as is. Either way, input returns nothing
+at the end of input, so 'while (input) != $nothing { ... }' stops cleanly."#
+    }
+
     fn run(
         &self,
         engine_state: &EngineState,
@@ -39,71 +55,216 @@ impl Command for Input {
     ) -> Result<PipelineData, ShellError> {
         let prompt: Option<String> = call.opt(engine_state, stack, 0)?;
         let bytes_until: Option<String> = call.get_flag(engine_state, stack, "bytes-until")?;
+        let suppress_output = call.has_flag("suppress-output");
+        let interactive = std::io::stdin().is_tty();
 
         if let Some(bytes_until) = bytes_until {
-            let _ = crossterm::terminal::enable_raw_mode();
+            let c = match bytes_until.bytes().next() {
+                Some(c) => c,
+                None => {
+                    return Err(ShellError::IOError(
+                        "input can't stop on this byte".to_string(),
+                    ))
+                }
+            };
 
-            if let Some(prompt) = prompt {
-                print!("{}", prompt);
-                let _ = std::io::stdout().flush();
+            show_prompt(prompt);
+
+            if interactive {
+                let _ = crossterm::terminal::enable_raw_mode();
+            }
+            let buffer = read_bytes_until(c);
+            if interactive {
+                let _ = crossterm::terminal::disable_raw_mode();
             }
-            if let Some(c) = bytes_until.bytes().next() {
-                let mut buf = [0u8; 1];
-                let mut buffer = vec![];
-
-                let mut stdin = std::io::stdin();
-
-                loop {
-                    if let Err(err) = stdin.read_exact(&mut buf) {
-                        let _ = crossterm::terminal::disable_raw_mode();
-                        return Err(ShellError::IOError(err.to_string()));
-                    }
-                    buffer.push(buf[0]);
-
-                    if buf[0] == c {
-                        let _ = crossterm::terminal::disable_raw_mode();
-                        break;
-                    }
-                }
 
-                Ok(Value::Binary {
-                    val: buffer,
+            return Ok(match buffer? {
+                Some(val) => Value::Binary {
+                    val,
                     span: call.head,
-                }
-                .into_pipeline_data())
-            } else {
-                let _ = crossterm::terminal::disable_raw_mode();
-                Err(ShellError::IOError(
-                    "input can't stop on this byte".to_string(),
-                ))
+                },
+                None => Value::Nothing { span: call.head },
             }
+            .into_pipeline_data());
+        }
+
+        let line = if !interactive {
+            show_prompt(prompt);
+            read_piped_line()?
+        } else if suppress_output {
+            show_prompt(prompt);
+            read_hidden_line(call.head)?
         } else {
-            if let Some(prompt) = prompt {
-                print!("{}", prompt);
-                let _ = std::io::stdout().flush();
+            read_edited_line(prompt.unwrap_or_default(), call.head)?
+        };
+
+        Ok(match line {
+            Some(val) => Value::String {
+                val,
+                span: call.head,
+            },
+            None => Value::Nothing { span: call.head },
+        }
+        .into_pipeline_data())
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![
+            Example {
+                description: "Get input from the user, and assign to a variable",
+                example: "let user-input = (input)",
+                result: None,
+            },
+            Example {
+                description: "Ask for a password without echoing it",
+                example: "let password = (input -s 'Password: ')",
+                result: None,
+            },
+            Example {
+                description: "Read lines until the end of input",
+                example: "while (input) != $nothing { 'got a line' }",
+                result: None,
+            },
+        ]
+    }
+}
+
+/// Shows a plain prompt for the readers that don't draw their own
+struct InputPrompt {
+    prompt: String,
+}
+
+impl Prompt for InputPrompt {
+    fn render_prompt_left(&self) -> Cow<str> {
+        self.prompt.as_str().into()
+    }
+
+    fn render_prompt_right(&self) -> Cow<str> {
+        "".into()
+    }
+
+    fn render_prompt_indicator(&self, _edit_mode: PromptEditMode) -> Cow<str> {
+        "".into()
+    }
+
+    fn render_prompt_multiline_indicator(&self) -> Cow<str> {
+        "".into()
+    }
+
+    fn render_prompt_history_search_indicator(
+        &self,
+        _history_search: PromptHistorySearch,
+    ) -> Cow<str> {
+        "".into()
+    }
+}
+
+fn show_prompt(prompt: Option<String>) {
+    if let Some(prompt) = prompt {
+        print!("{}", prompt);
+        let _ = std::io::stdout().flush();
+    }
+}
+
+fn interrupted(span: Span) -> ShellError {
+    ShellError::SpannedLabeledError(
+        "Input interrupted".into(),
+        "ctrl-c was pressed while reading input".into(),
+        span,
+    )
+}
+
+/// Reads a line through reedline so it can be edited, `None` on ctrl-d
+fn read_edited_line(prompt: String, span: Span) -> Result<Option<String>, ShellError> {
+    let mut line_editor = Reedline::create().map_err(|err| ShellError::IOError(err.to_string()))?;
+    let prompt = InputPrompt { prompt };
+
+    loop {
+        match line_editor.read_line(&prompt) {
+            Ok(Signal::Success(line)) => return Ok(Some(line)),
+            Ok(Signal::CtrlD) => return Ok(None),
+            Ok(Signal::CtrlC) => return Err(interrupted(span)),
+            Ok(Signal::CtrlL) => {
+                let _ = line_editor.clear_screen();
             }
+            Err(err) => return Err(ShellError::IOError(err.to_string())),
+        }
+    }
+}
 
-            // Just read a normal line of text
-            let mut buf = String::new();
-            let input = std::io::stdin().read_line(&mut buf);
+/// Reads a line from piped stdin without its line ending, `None` at the end of input
+fn read_piped_line() -> Result<Option<String>, ShellError> {
+    let mut buf = String::new();
 
-            match input {
-                Ok(_) => Ok(Value::String {
-                    val: buf,
-                    span: call.head,
+    match std::io::stdin().read_line(&mut buf) {
+        Ok(0) => Ok(None),
+        Ok(_) => {
+            if buf.ends_with('\n') {
+                buf.pop();
+                if buf.ends_with('\r') {
+                    buf.pop();
                 }
-                .into_pipeline_data()),
-                Err(err) => Err(ShellError::IOError(err.to_string())),
             }
+            Ok(Some(buf))
         }
+        Err(err) => Err(ShellError::IOError(err.to_string())),
     }
+}
 
-    fn examples(&self) -> Vec<Example> {
-        vec![Example {
-            description: "Get input from the user, and assign to a variable",
-            example: "let user-input = (input)",
-            result: None,
-        }]
+/// Reads a line from the terminal in raw mode so nothing typed is echoed
+fn read_hidden_line(span: Span) -> Result<Option<String>, ShellError> {
+    crossterm::terminal::enable_raw_mode().map_err(|err| ShellError::IOError(err.to_string()))?;
+    let line = read_hidden_keys(span);
+    let _ = crossterm::terminal::disable_raw_mode();
+
+    // The enter key wasn't echoed either
+    println!();
+
+    line
+}
+
+fn read_hidden_keys(span: Span) -> Result<Option<String>, ShellError> {
+    let mut line = String::new();
+
+    loop {
+        let key = match crossterm::event::read() {
+            Ok(Event::Key(key)) => key,
+            Ok(_) => continue,
+            Err(err) => return Err(ShellError::IOError(err.to_string())),
+        };
+        let control = key.modifiers.contains(KeyModifiers::CONTROL);
+
+        match key.code {
+            KeyCode::Enter => return Ok(Some(line)),
+            KeyCode::Backspace => {
+                line.pop();
+            }
+            KeyCode::Char('c') if control => return Err(interrupted(span)),
+            KeyCode::Char('d') if control && line.is_empty() => return Ok(None),
+            KeyCode::Char(c) if !control => line.push(c),
+            _ => {}
+        }
+    }
+}
+
+/// Reads bytes up to and including `stop`, `None` if the input ends before anything was read
+fn read_bytes_until(stop: u8) -> Result<Option<Vec<u8>>, ShellError> {
+    let mut buf = [0u8; 1];
+    let mut buffer = vec![];
+    let mut stdin = std::io::stdin();
+
+    loop {
+        match stdin.read(&mut buf) {
+            Ok(0) if buffer.is_empty() => return Ok(None),
+            Ok(0) => return Ok(Some(buffer)),
+            Ok(_) => {
+                buffer.push(buf[0]);
+                if buf[0] == stop {
+                    return Ok(Some(buffer));
+                }
+            }
+            Err(err) => return Err(ShellError::IOError(err.to_string())),
+        }
     }
 }
 
@@ -0,0 +1,45 @@
+use nu_engine::get_full_help;
+use nu_protocol::{
+    ast::Call,
+    engine::{Command, EngineState, Stack},
+    Category, IntoPipelineData, PipelineData, Signature, Value,
+};
+
+#[derive(Clone)]
+pub struct Keybindings;
+
+impl Command for Keybindings {
+    fn name(&self) -> &str {
+        "keybindings"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("keybindings").category(Category::Platform)
+    }
+
+    fn usage(&self) -> &str {
+        "Keybindings related commands"
+    }
+
+    fn extra_usage(&self) -> &str {
+        "Keybindings are set in the keybindings list of $config. Each entry is a record with a mode, a modifier, a keycode and the event to send."
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        _stack: &mut Stack,
+        call: &Call,
+        _input: PipelineData,
+    ) -> Result<nu_protocol::PipelineData, nu_protocol::ShellError> {
+        Ok(Value::String {
+            val: get_full_help(
+                &Keybindings.signature(),
+                &Keybindings.examples(),
+                engine_state,
+            ),
+            span: call.head,
+        }
+        .into_pipeline_data())
+    }
+}
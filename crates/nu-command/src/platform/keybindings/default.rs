@@ -0,0 +1,94 @@
+use nu_protocol::ast::Call;
+use nu_protocol::engine::{Command, EngineState, Stack};
+use nu_protocol::{
+    Category, Example, IntoPipelineData, PipelineData, ShellError, Signature, Span, Value,
+};
+
+#[derive(Clone)]
+pub struct KeybindingsDefault;
+
+impl Command for KeybindingsDefault {
+    fn name(&self) -> &str {
+        "keybindings default"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build(self.name()).category(Category::Platform)
+    }
+
+    fn usage(&self) -> &str {
+        "List the keybindings nushell adds to the line editor by default."
+    }
+
+    fn extra_usage(&self) -> &str {
+        "The entries have the same shape as the keybindings of $config, so they can be copied and changed there. The basic editing keys of the line editor itself are not listed."
+    }
+
+    fn run(
+        &self,
+        _engine_state: &EngineState,
+        _stack: &mut Stack,
+        call: &Call,
+        _input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        Ok(Value::List {
+            vals: default_keybindings(call.head),
+            span: call.head,
+        }
+        .into_pipeline_data())
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![Example {
+            description: "Get the default emacs keybindings",
+            example: "keybindings default | where mode == emacs",
+            result: None,
+        }]
+    }
+}
+
+/// The keybindings installed before the ones of $config, written as $config entries
+pub fn default_keybindings(span: Span) -> Vec<Value> {
+    vec![keybinding(
+        "emacs",
+        "shift",
+        "backtab",
+        Value::List {
+            vals: vec![
+                record(
+                    vec!["edit"],
+                    vec![record(
+                        vec!["cmd", "value"],
+                        vec![Value::string("insertchar", span), Value::string("p", span)],
+                        span,
+                    )],
+                    span,
+                ),
+                record(vec!["send"], vec![Value::string("enter", span)], span),
+            ],
+            span,
+        },
+        span,
+    )]
+}
+
+fn keybinding(mode: &str, modifier: &str, keycode: &str, event: Value, span: Span) -> Value {
+    record(
+        vec!["mode", "modifier", "keycode", "event"],
+        vec![
+            Value::string(mode, span),
+            Value::string(modifier, span),
+            Value::string(keycode, span),
+            event,
+        ],
+        span,
+    )
+}
+
+fn record(cols: Vec<&str>, vals: Vec<Value>, span: Span) -> Value {
+    Value::Record {
+        cols: cols.into_iter().map(String::from).collect(),
+        vals,
+        span,
+    }
+}
@@ -0,0 +1,384 @@
+use crossterm::event::{KeyCode, KeyModifiers};
+use nu_protocol::ast::Call;
+use nu_protocol::engine::{Command, EngineState, Stack};
+use nu_protocol::{
+    Category, Example, IntoPipelineData, PipelineData, ShellError, Signature, Span, Value,
+};
+use reedline::{EditCommand, ReedlineEvent};
+
+/// What an event name in `{send: <event>}` stands for
+pub enum KeybindingEvent {
+    Event(ReedlineEvent),
+    /// Sends the edit command in the `edit` field of the keybinding
+    Edit,
+    /// Replaces the line with the `cmd` field of the keybinding and runs it
+    ExecuteHostCommand,
+}
+
+/// What an edit name in `{edit: {cmd: <edit>}}` stands for, with the `value` field it needs
+pub enum KeybindingEdit {
+    Edit(EditCommand),
+    WithChar(fn(char) -> EditCommand),
+    WithString(fn(String) -> EditCommand),
+}
+
+/// Events that can be sent with `{send: <event>}`. Both the keybindings of $config and
+/// `keybindings list` go by this table.
+// TODO: add ReedlineEvent::Mouse, ReedlineEvent::Resize and ReedlineEvent::Paste
+pub const KEYBINDING_EVENTS: &[(&str, KeybindingEvent)] = &[
+    ("none", KeybindingEvent::Event(ReedlineEvent::None)),
+    (
+        "actionhandler",
+        KeybindingEvent::Event(ReedlineEvent::ActionHandler),
+    ),
+    (
+        "clearscreen",
+        KeybindingEvent::Event(ReedlineEvent::ClearScreen),
+    ),
+    (
+        "historyhintcomplete",
+        KeybindingEvent::Event(ReedlineEvent::HistoryHintComplete),
+    ),
+    (
+        "historyhintwordcomplete",
+        KeybindingEvent::Event(ReedlineEvent::HistoryHintWordComplete),
+    ),
+    ("ctrld", KeybindingEvent::Event(ReedlineEvent::CtrlD)),
+    ("ctrlc", KeybindingEvent::Event(ReedlineEvent::CtrlC)),
+    ("enter", KeybindingEvent::Event(ReedlineEvent::Enter)),
+    ("esc", KeybindingEvent::Event(ReedlineEvent::Esc)),
+    ("escape", KeybindingEvent::Event(ReedlineEvent::Esc)),
+    ("up", KeybindingEvent::Event(ReedlineEvent::Up)),
+    ("down", KeybindingEvent::Event(ReedlineEvent::Down)),
+    ("right", KeybindingEvent::Event(ReedlineEvent::Right)),
+    ("left", KeybindingEvent::Event(ReedlineEvent::Left)),
+    (
+        "searchhistory",
+        KeybindingEvent::Event(ReedlineEvent::SearchHistory),
+    ),
+    (
+        "nexthistory",
+        KeybindingEvent::Event(ReedlineEvent::NextHistory),
+    ),
+    (
+        "previoushistory",
+        KeybindingEvent::Event(ReedlineEvent::PreviousHistory),
+    ),
+    ("repaint", KeybindingEvent::Event(ReedlineEvent::Repaint)),
+    (
+        "contextmenu",
+        KeybindingEvent::Event(ReedlineEvent::ContextMenu),
+    ),
+    ("menudown", KeybindingEvent::Event(ReedlineEvent::MenuDown)),
+    ("menuup", KeybindingEvent::Event(ReedlineEvent::MenuUp)),
+    ("menuleft", KeybindingEvent::Event(ReedlineEvent::MenuLeft)),
+    (
+        "menuright",
+        KeybindingEvent::Event(ReedlineEvent::MenuRight),
+    ),
+    ("menunext", KeybindingEvent::Event(ReedlineEvent::MenuNext)),
+    (
+        "menuprevious",
+        KeybindingEvent::Event(ReedlineEvent::MenuPrevious),
+    ),
+    ("edit", KeybindingEvent::Edit),
+    ("executehostcommand", KeybindingEvent::ExecuteHostCommand),
+];
+
+/// Edit commands that can be sent with `{edit: {cmd: <edit>}}`
+pub const KEYBINDING_EDITS: &[(&str, KeybindingEdit)] = &[
+    (
+        "movetostart",
+        KeybindingEdit::Edit(EditCommand::MoveToStart),
+    ),
+    (
+        "movetolinestart",
+        KeybindingEdit::Edit(EditCommand::MoveToLineStart),
+    ),
+    ("movetoend", KeybindingEdit::Edit(EditCommand::MoveToEnd)),
+    (
+        "movetolineend",
+        KeybindingEdit::Edit(EditCommand::MoveToLineEnd),
+    ),
+    ("moveleft", KeybindingEdit::Edit(EditCommand::MoveLeft)),
+    ("moveright", KeybindingEdit::Edit(EditCommand::MoveRight)),
+    (
+        "movewordleft",
+        KeybindingEdit::Edit(EditCommand::MoveWordLeft),
+    ),
+    (
+        "movewordright",
+        KeybindingEdit::Edit(EditCommand::MoveWordRight),
+    ),
+    (
+        "insertchar",
+        KeybindingEdit::WithChar(EditCommand::InsertChar),
+    ),
+    (
+        "insertstring",
+        KeybindingEdit::WithString(EditCommand::InsertString),
+    ),
+    ("backspace", KeybindingEdit::Edit(EditCommand::Backspace)),
+    ("delete", KeybindingEdit::Edit(EditCommand::Delete)),
+    (
+        "backspaceword",
+        KeybindingEdit::Edit(EditCommand::BackspaceWord),
+    ),
+    ("deleteword", KeybindingEdit::Edit(EditCommand::DeleteWord)),
+    ("clear", KeybindingEdit::Edit(EditCommand::Clear)),
+    (
+        "cleartolineend",
+        KeybindingEdit::Edit(EditCommand::ClearToLineEnd),
+    ),
+    (
+        "cutcurrentline",
+        KeybindingEdit::Edit(EditCommand::CutCurrentLine),
+    ),
+    (
+        "cutfromstart",
+        KeybindingEdit::Edit(EditCommand::CutFromStart),
+    ),
+    (
+        "cutfromlinestart",
+        KeybindingEdit::Edit(EditCommand::CutFromLineStart),
+    ),
+    ("cuttoend", KeybindingEdit::Edit(EditCommand::CutToEnd)),
+    (
+        "cuttolineend",
+        KeybindingEdit::Edit(EditCommand::CutToLineEnd),
+    ),
+    (
+        "cutwordleft",
+        KeybindingEdit::Edit(EditCommand::CutWordLeft),
+    ),
+    (
+        "cutwordright",
+        KeybindingEdit::Edit(EditCommand::CutWordRight),
+    ),
+    (
+        "pastecutbufferbefore",
+        KeybindingEdit::Edit(EditCommand::PasteCutBufferBefore),
+    ),
+    (
+        "pastecutbufferafter",
+        KeybindingEdit::Edit(EditCommand::PasteCutBufferAfter),
+    ),
+    (
+        "uppercaseword",
+        KeybindingEdit::Edit(EditCommand::UppercaseWord),
+    ),
+    (
+        "lowercaseword",
+        KeybindingEdit::Edit(EditCommand::LowercaseWord),
+    ),
+    (
+        "capitalizechar",
+        KeybindingEdit::Edit(EditCommand::CapitalizeChar),
+    ),
+    ("swapwords", KeybindingEdit::Edit(EditCommand::SwapWords)),
+    (
+        "swapgraphemes",
+        KeybindingEdit::Edit(EditCommand::SwapGraphemes),
+    ),
+    ("undo", KeybindingEdit::Edit(EditCommand::Undo)),
+    ("redo", KeybindingEdit::Edit(EditCommand::Redo)),
+    (
+        "cutrightuntil",
+        KeybindingEdit::WithChar(EditCommand::CutRightUntil),
+    ),
+    (
+        "cutrightbefore",
+        KeybindingEdit::WithChar(EditCommand::CutRightBefore),
+    ),
+    (
+        "moverightuntil",
+        KeybindingEdit::WithChar(EditCommand::MoveRightUntil),
+    ),
+    (
+        "moverightbefore",
+        KeybindingEdit::WithChar(EditCommand::MoveRightBefore),
+    ),
+    (
+        "cutleftuntil",
+        KeybindingEdit::WithChar(EditCommand::CutLeftUntil),
+    ),
+    (
+        "cutleftbefore",
+        KeybindingEdit::WithChar(EditCommand::CutLeftBefore),
+    ),
+    (
+        "moveleftuntil",
+        KeybindingEdit::WithChar(EditCommand::MoveLeftUntil),
+    ),
+    (
+        "moveleftbefore",
+        KeybindingEdit::WithChar(EditCommand::MoveLeftBefore),
+    ),
+];
+
+/// Modifiers a keybinding can be given with `modifier: <name>`. Both the keybindings of $config
+/// and `keybindings list` go by this table.
+pub const KEYBINDING_MODIFIERS: &[(&str, KeyModifiers)] = &[
+    ("control", KeyModifiers::CONTROL),
+    ("shift", KeyModifiers::SHIFT),
+    ("alt", KeyModifiers::ALT),
+    ("none", KeyModifiers::NONE),
+    (
+        "control | shift",
+        KeyModifiers::from_bits_truncate(KeyModifiers::CONTROL.bits() | KeyModifiers::SHIFT.bits()),
+    ),
+    (
+        "control | alt",
+        KeyModifiers::from_bits_truncate(KeyModifiers::CONTROL.bits() | KeyModifiers::ALT.bits()),
+    ),
+    (
+        "control | alt | shift",
+        KeyModifiers::from_bits_truncate(
+            KeyModifiers::CONTROL.bits() | KeyModifiers::ALT.bits() | KeyModifiers::SHIFT.bits(),
+        ),
+    ),
+];
+
+/// Keys a keybinding can be given with `keycode: <name>`. Besides these, `char_` followed by a
+/// character stands for that character's key.
+// TODO: Add KeyCode::F(u8) for function keys
+pub const KEYBINDING_KEYCODES: &[(&str, KeyCode)] = &[
+    ("backspace", KeyCode::Backspace),
+    ("enter", KeyCode::Enter),
+    ("down", KeyCode::Down),
+    ("up", KeyCode::Up),
+    ("left", KeyCode::Left),
+    ("right", KeyCode::Right),
+    ("home", KeyCode::Home),
+    ("end", KeyCode::End),
+    ("pageup", KeyCode::PageUp),
+    ("pagedown", KeyCode::PageDown),
+    ("tab", KeyCode::Tab),
+    ("backtab", KeyCode::BackTab),
+    ("delete", KeyCode::Delete),
+    ("insert", KeyCode::Insert),
+    ("null", KeyCode::Null),
+    ("esc", KeyCode::Esc),
+    ("escape", KeyCode::Esc),
+];
+
+/// The prefix of a keycode naming a character, like `char_a`
+pub const KEYBINDING_CHAR_PREFIX: &str = "char_";
+
+#[derive(Clone)]
+pub struct KeybindingsList;
+
+impl Command for KeybindingsList {
+    fn name(&self) -> &str {
+        "keybindings list"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build(self.name())
+            .switch("events", "list the events that can be sent", Some('e'))
+            .switch("edits", "list the edit commands", Some('d'))
+            .switch("modifiers", "list the modifiers", Some('m'))
+            .switch("keycodes", "list the keycodes", Some('k'))
+            .category(Category::Platform)
+    }
+
+    fn usage(&self) -> &str {
+        "List the options that can be used in the keybindings of $config."
+    }
+
+    fn extra_usage(&self) -> &str {
+        "Without flags, every option is listed."
+    }
+
+    fn run(
+        &self,
+        _engine_state: &EngineState,
+        _stack: &mut Stack,
+        call: &Call,
+        _input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let groups = [
+            ("event", "events", keybinding_names(KEYBINDING_EVENTS)),
+            ("edit", "edits", keybinding_names(KEYBINDING_EDITS)),
+            (
+                "modifier",
+                "modifiers",
+                keybinding_names(KEYBINDING_MODIFIERS),
+            ),
+            (
+                "keycode",
+                "keycodes",
+                [
+                    keybinding_names(KEYBINDING_KEYCODES),
+                    vec!["char_<character>"],
+                ]
+                .concat(),
+            ),
+        ];
+
+        let all = groups.iter().all(|(_, flag, _)| !call.has_flag(flag));
+
+        let vals = groups
+            .iter()
+            .filter(|(_, flag, _)| all || call.has_flag(flag))
+            .flat_map(|(kind, _, names)| {
+                names
+                    .iter()
+                    .map(move |name| option_row(kind, name, call.head))
+            })
+            .collect();
+
+        Ok(Value::List {
+            vals,
+            span: call.head,
+        }
+        .into_pipeline_data())
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![
+            Example {
+                description: "List the events that can be sent by a keybinding",
+                example: "keybindings list --events",
+                result: None,
+            },
+            Example {
+                description: "List the modifiers and keycodes",
+                example: "keybindings list -m -k",
+                result: None,
+            },
+        ]
+    }
+}
+
+/// The names in one of the keybinding tables, in the order they're listed
+pub fn keybinding_names<T>(table: &[(&'static str, T)]) -> Vec<&'static str> {
+    table.iter().map(|(name, _)| *name).collect()
+}
+
+fn option_row(kind: &str, name: &str, span: Span) -> Value {
+    Value::Record {
+        cols: vec!["type".into(), "name".into()],
+        vals: vec![Value::string(kind, span), Value::string(name, span)],
+        span,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn names_are_unique() {
+        for names in [
+            keybinding_names(KEYBINDING_EVENTS),
+            keybinding_names(KEYBINDING_EDITS),
+            keybinding_names(KEYBINDING_MODIFIERS),
+            keybinding_names(KEYBINDING_KEYCODES),
+        ] {
+            for (idx, name) in names.iter().enumerate() {
+                assert!(!names[..idx].contains(name), "{} is listed twice", name);
+            }
+        }
+    }
+}
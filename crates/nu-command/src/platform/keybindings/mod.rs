@@ -0,0 +1,10 @@
+mod command;
+mod default;
+mod list;
+
+pub use command::Keybindings;
+pub use default::{default_keybindings, KeybindingsDefault};
+pub use list::{
+    keybinding_names, KeybindingEdit, KeybindingEvent, KeybindingsList, KEYBINDING_CHAR_PREFIX,
+    KEYBINDING_EDITS, KEYBINDING_EVENTS, KEYBINDING_KEYCODES, KEYBINDING_MODIFIERS,
+};
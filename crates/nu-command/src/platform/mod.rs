@@ -1,6 +1,7 @@
 mod ansi;
 mod clear;
 mod input;
+mod keybindings;
 mod kill;
 mod sleep;
 mod term_size;
@@ -8,6 +9,7 @@ mod term_size;
 pub use ansi::{Ansi, AnsiGradient, AnsiStrip};
 pub use clear::Clear;
 pub use input::Input;
+pub use keybindings::*;
 pub use kill::Kill;
 pub use sleep::Sleep;
 pub use term_size::TermSize;
@@ -3,7 +3,7 @@ use nu_protocol::ast::Call;
 use nu_protocol::engine::{Command, EngineState, Stack};
 use nu_protocol::{Category, PipelineData, ShellError, Signature, SyntaxShape, Value};
 
-/// Source a file for environment variables.
+/// Leave the current shell, or the whole process when given an exit code.
 #[derive(Clone)]
 pub struct Exit;
 
@@ -24,7 +24,11 @@ impl Command for Exit {
     }
 
     fn usage(&self) -> &str {
-        "Runs a script file in the current context."
+        "Exit a Nu shell with a given exit code."
+    }
+
+    fn extra_usage(&self) -> &str {
+        "With an exit code or --now, the process ends right away, even from inside a block or while the config file is being loaded at startup. Otherwise the current shell is left, exiting with 0 when it was the last one."
     }
 
     fn run(
@@ -34,6 +38,7 @@ impl Command for Exit {
         call: &Call,
         _input: PipelineData,
     ) -> Result<PipelineData, ShellError> {
+        // The input isn't drained, whatever is still streaming in is dropped with the process
         let exit_code: Option<i64> = call.opt(engine_state, stack, 0)?;
 
         if let Some(exit_code) = exit_code {
@@ -0,0 +1,96 @@
+use super::format_in_units;
+use nu_protocol::ast::Call;
+use nu_protocol::engine::{Command, EngineState, Stack};
+use nu_protocol::{Category, Example, PipelineData, ShellError, Signature, SyntaxShape, Value};
+
+const UNITS: &[(&str, i64)] = &[
+    ("ns", 1),
+    ("us", 1000),
+    ("ms", 1000 * 1000),
+    ("sec", 1000 * 1000 * 1000),
+    ("min", 60 * 1000 * 1000 * 1000),
+    ("hr", 60 * 60 * 1000 * 1000 * 1000),
+    ("day", 24 * 60 * 60 * 1000 * 1000 * 1000),
+    ("wk", 7 * 24 * 60 * 60 * 1000 * 1000 * 1000),
+];
+
+#[derive(Clone)]
+pub struct FormatDuration;
+
+impl Command for FormatDuration {
+    fn name(&self) -> &str {
+        "format duration"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("format duration")
+            .required(
+                "unit",
+                SyntaxShape::String,
+                "the unit to show durations in, e.g. sec or hr",
+            )
+            .rest(
+                "rest",
+                SyntaxShape::CellPath,
+                "optionally format durations by column paths",
+            )
+            .category(Category::Strings)
+    }
+
+    fn usage(&self) -> &str {
+        "Format durations in a fixed unit, whatever their magnitude."
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        format_in_units(
+            engine_state,
+            stack,
+            call,
+            input,
+            UNITS,
+            |value| match value {
+                Value::Duration { val, .. } => Some(*val),
+                _ => None,
+            },
+            "durations",
+        )
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![
+            Example {
+                description: "Show a duration in seconds",
+                example: "2min | format duration sec",
+                result: Some(Value::test_string("120 sec")),
+            },
+            Example {
+                description: "Show a duration in hours, with up to two decimals",
+                example: "90min | format duration hr",
+                result: Some(Value::test_string("1.5 hr")),
+            },
+            Example {
+                description: "Show a column of durations in milliseconds",
+                example: "[[task time]; [build 1sec] [test 250ms]] | format duration ms time",
+                result: None,
+            },
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_examples() {
+        use crate::test_examples;
+
+        test_examples(FormatDuration {})
+    }
+}
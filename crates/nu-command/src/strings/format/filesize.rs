@@ -0,0 +1,99 @@
+use super::format_in_units;
+use nu_protocol::ast::Call;
+use nu_protocol::engine::{Command, EngineState, Stack};
+use nu_protocol::{Category, Example, PipelineData, ShellError, Signature, SyntaxShape, Value};
+
+const UNITS: &[(&str, i64)] = &[
+    ("B", 1),
+    ("KB", 1000),
+    ("MB", 1000 * 1000),
+    ("GB", 1000 * 1000 * 1000),
+    ("TB", 1000 * 1000 * 1000 * 1000),
+    ("PB", 1000 * 1000 * 1000 * 1000 * 1000),
+    ("KiB", 1024),
+    ("MiB", 1024 * 1024),
+    ("GiB", 1024 * 1024 * 1024),
+    ("TiB", 1024 * 1024 * 1024 * 1024),
+    ("PiB", 1024 * 1024 * 1024 * 1024 * 1024),
+];
+
+#[derive(Clone)]
+pub struct FormatFilesize;
+
+impl Command for FormatFilesize {
+    fn name(&self) -> &str {
+        "format filesize"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("format filesize")
+            .required(
+                "unit",
+                SyntaxShape::String,
+                "the unit to show sizes in, e.g. KB or MiB",
+            )
+            .rest(
+                "rest",
+                SyntaxShape::CellPath,
+                "optionally format sizes by column paths",
+            )
+            .category(Category::Strings)
+    }
+
+    fn usage(&self) -> &str {
+        "Format filesizes in a fixed unit, whatever their magnitude."
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        format_in_units(
+            engine_state,
+            stack,
+            call,
+            input,
+            UNITS,
+            |value| match value {
+                Value::Filesize { val, .. } => Some(*val),
+                _ => None,
+            },
+            "filesizes",
+        )
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![
+            Example {
+                description: "Show a size in mebibytes",
+                example: "3mib | format filesize MiB",
+                result: Some(Value::test_string("3 MiB")),
+            },
+            Example {
+                description: "Show a size in kilobytes, with up to two decimals",
+                example: "1500b | format filesize kb",
+                result: Some(Value::test_string("1.5 KB")),
+            },
+            Example {
+                description: "Show the sizes of the files in a listing in kilobytes",
+                example: "ls | format filesize KB size",
+                result: None,
+            },
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_examples() {
+        use crate::test_examples;
+
+        test_examples(FormatFilesize {})
+    }
+}
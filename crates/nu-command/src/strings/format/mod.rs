@@ -1,3 +1,114 @@
 pub mod command;
+mod duration;
+mod filesize;
 
 pub use command::Format;
+pub use duration::FormatDuration;
+pub use filesize::FormatFilesize;
+
+use nu_engine::CallExt;
+use nu_protocol::ast::{Call, CellPath};
+use nu_protocol::engine::{EngineState, Stack};
+use nu_protocol::{PipelineData, ShellError, Span, Spanned, Value};
+
+/// Run a `format <type>` command: format the input, or the values at the column paths given after
+/// the unit, in the unit named by the first argument. `amount` takes the number out of a value of
+/// the command's type, in the base unit of `units`, and `type_name` names that type in errors.
+fn format_in_units(
+    engine_state: &EngineState,
+    stack: &mut Stack,
+    call: &Call,
+    input: PipelineData,
+    units: &'static [(&'static str, i64)],
+    amount: fn(&Value) -> Option<i64>,
+    type_name: &'static str,
+) -> Result<PipelineData, ShellError> {
+    let head = call.head;
+    let unit: Spanned<String> = call.req(engine_state, stack, 0)?;
+    let unit = find_unit(&unit, units)?;
+    let column_paths: Vec<CellPath> = call.rest(engine_state, stack, 1)?;
+
+    let action = move |input: &Value| format_value(input, amount, type_name, unit, head);
+
+    input.map(
+        move |v| {
+            if column_paths.is_empty() {
+                action(&v)
+            } else {
+                let mut ret = v;
+                for path in &column_paths {
+                    let r = ret.update_cell_path(&path.members, Box::new(action));
+                    if let Err(error) = r {
+                        return Value::Error { error };
+                    }
+                }
+                ret
+            }
+        },
+        engine_state.ctrlc.clone(),
+    )
+}
+
+fn format_value(
+    input: &Value,
+    amount: fn(&Value) -> Option<i64>,
+    type_name: &str,
+    unit: (&str, i64),
+    head: Span,
+) -> Value {
+    match amount(input) {
+        Some(val) => Value::String {
+            val: format_in_unit(val, unit),
+            span: head,
+        },
+        None => Value::Error {
+            error: ShellError::UnsupportedInput(
+                format!(
+                    "Input's type is {}. This command only works with {}.",
+                    input.get_type(),
+                    type_name
+                ),
+                head,
+            ),
+        },
+    }
+}
+
+/// Look up `unit` (ignoring case) in a table of unit names and their size in the base unit
+fn find_unit(
+    unit: &Spanned<String>,
+    units: &[(&'static str, i64)],
+) -> Result<(&'static str, i64), ShellError> {
+    units
+        .iter()
+        .find(|(name, _)| name.eq_ignore_ascii_case(&unit.item))
+        .copied()
+        .ok_or_else(|| {
+            ShellError::SpannedLabeledError(
+                "Unknown unit".into(),
+                format!(
+                    "expected one of: {}",
+                    units
+                        .iter()
+                        .map(|(name, _)| *name)
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ),
+                unit.span,
+            )
+        })
+}
+
+/// Render `val` in a fixed unit. Whole amounts are exact; the rest keep two decimal places.
+fn format_in_unit(val: i64, (name, size): (&str, i64)) -> String {
+    if val % size == 0 {
+        format!("{} {}", val / size, name)
+    } else {
+        let amount = format!("{:.2}", val as f64 / size as f64);
+        format!(
+            "{} {}",
+            amount.trim_end_matches('0').trim_end_matches('.'),
+            name
+        )
+    }
+}
@@ -10,7 +10,7 @@ use std::sync::Arc;
 
 struct Arguments {
     all: bool,
-    find: String,
+    find: Regex,
     replace: String,
     column_paths: Vec<CellPath>,
 }
@@ -20,19 +20,24 @@ pub struct SubCommand;
 
 impl Command for SubCommand {
     fn name(&self) -> &str {
-        "str find-replace"
+        "str replace"
     }
 
     fn signature(&self) -> Signature {
-        Signature::build("str find-replace")
-            .required("find", SyntaxShape::String, "the pattern to find")
-            .required("replace", SyntaxShape::String, "the replacement pattern")
+        Signature::build("str replace")
+            .required("find", SyntaxShape::String, "the text or pattern to find")
+            .required("replace", SyntaxShape::String, "the replacement text")
             .rest(
                 "rest",
                 SyntaxShape::CellPath,
                 "optionally find and replace text by column paths",
             )
             .switch("all", "replace all occurrences of find string", Some('a'))
+            .switch(
+                "regex",
+                "find a regular expression, and allow $1 or ${name} in the replacement to refer to capture groups",
+                Some('r'),
+            )
             .category(Category::Strings)
     }
 
@@ -40,6 +45,10 @@ impl Command for SubCommand {
         "finds and replaces text"
     }
 
+    fn extra_usage(&self) -> &str {
+        "Only the first occurrence is replaced unless --all is given. Without --regex the text is found and replaced as is."
+    }
+
     fn run(
         &self,
         engine_state: &EngineState,
@@ -47,14 +56,15 @@ impl Command for SubCommand {
         call: &Call,
         input: PipelineData,
     ) -> Result<PipelineData, ShellError> {
-        operate(engine_state, stack, call, input)
+        let regex = call.has_flag("regex");
+        operate(engine_state, stack, call, input, regex)
     }
 
     fn examples(&self) -> Vec<Example> {
         vec![
             Example {
                 description: "Find and replace contents with capture group",
-                example: "'my_library.rb' | str find-replace '(.+).rb' '$1.nu'",
+                example: "'my_library.rb' | str replace -r '(.+).rb' '$1.nu'",
                 result: Some(Value::String {
                     val: "my_library.nu".to_string(),
                     span: Span::test_data(),
@@ -62,7 +72,7 @@ impl Command for SubCommand {
             },
             Example {
                 description: "Find and replace all occurrences of find string",
-                example: "'abc abc abc' | str find-replace -a 'b' 'z'",
+                example: "'abc abc abc' | str replace -a 'b' 'z'",
                 result: Some(Value::String {
                     val: "azc azc azc".to_string(),
                     span: Span::test_data(),
@@ -70,8 +80,7 @@ impl Command for SubCommand {
             },
             Example {
                 description: "Find and replace all occurrences of find string in table",
-                example:
-                    "[[ColA ColB ColC]; [abc abc ads]] | str find-replace -a 'b' 'z' ColA ColC",
+                example: "[[ColA ColB ColC]; [abc abc ads]] | str replace -a 'b' 'z' ColA ColC",
                 result: Some(Value::List {
                     vals: vec![Value::Record {
                         cols: vec!["ColA".to_string(), "ColB".to_string(), "ColC".to_string()],
@@ -94,6 +103,76 @@ impl Command for SubCommand {
                     span: Span::test_data(),
                 }),
             },
+            Example {
+                description: "Find and replace text that would be special in a regex",
+                example: "'1.5 + 1.5' | str replace -a '.5' ',5'",
+                result: Some(Value::String {
+                    val: "1,5 + 1,5".to_string(),
+                    span: Span::test_data(),
+                }),
+            },
+        ]
+    }
+}
+
+/// The name `str replace` had before it found text literally, kept so older scripts still work
+#[derive(Clone)]
+pub struct FindReplace;
+
+impl Command for FindReplace {
+    fn name(&self) -> &str {
+        "str find-replace"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("str find-replace")
+            .required("find", SyntaxShape::String, "the pattern to find")
+            .required("replace", SyntaxShape::String, "the replacement pattern")
+            .rest(
+                "rest",
+                SyntaxShape::CellPath,
+                "optionally find and replace text by column paths",
+            )
+            .switch("all", "replace all occurrences of find string", Some('a'))
+            .category(Category::Strings)
+    }
+
+    fn usage(&self) -> &str {
+        "finds and replaces text using a regular expression"
+    }
+
+    fn extra_usage(&self) -> &str {
+        "This is `str replace --regex`, kept for scripts written before `str replace`."
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        operate(engine_state, stack, call, input, true)
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![
+            Example {
+                description: "Find and replace contents with capture group",
+                example: "'my_library.rb' | str find-replace '(.+).rb' '$1.nu'",
+                result: Some(Value::String {
+                    val: "my_library.nu".to_string(),
+                    span: Span::test_data(),
+                }),
+            },
+            Example {
+                description: "Find and replace all occurrences of find string",
+                example: "'abc abc abc' | str find-replace -a 'b' 'z'",
+                result: Some(Value::String {
+                    val: "azc azc azc".to_string(),
+                    span: Span::test_data(),
+                }),
+            },
         ]
     }
 }
@@ -103,14 +182,30 @@ fn operate(
     stack: &mut Stack,
     call: &Call,
     input: PipelineData,
+    regex: bool,
 ) -> Result<PipelineData, ShellError> {
     let find: Spanned<String> = call.req(engine_state, stack, 0)?;
     let replace: Spanned<String> = call.req(engine_state, stack, 1)?;
 
+    // Literal text is escaped, and `$` in the replacement doubled, so nothing in either is special
+    let (pattern, replace) = if regex {
+        (find.item, replace.item)
+    } else {
+        (regex::escape(&find.item), replace.item.replace('$', "$$"))
+    };
+
+    let find = Regex::new(&pattern).map_err(|err| {
+        ShellError::SpannedLabeledError(
+            "Couldn't parse the regex".into(),
+            err.to_string(),
+            find.span,
+        )
+    })?;
+
     let options = Arc::new(Arguments {
         all: call.has_flag("all"),
-        find: find.item,
-        replace: replace.item,
+        find,
+        replace,
         column_paths: call.rest(engine_state, stack, 2)?,
     });
     let head = call.head;
@@ -137,8 +232,6 @@ fn operate(
     )
 }
 
-struct FindReplace<'a>(&'a str, &'a str);
-
 fn action(
     input: &Value,
     Arguments {
@@ -148,27 +241,15 @@ fn action(
 ) -> Value {
     match input {
         Value::String { val, .. } => {
-            let FindReplace(find, replacement) = FindReplace(find, replace);
-            let regex = Regex::new(find);
-
-            match regex {
-                Ok(re) => {
-                    if *all {
-                        Value::String {
-                            val: re.replace_all(val, replacement).to_string(),
-                            span: head,
-                        }
-                    } else {
-                        Value::String {
-                            val: re.replace(val, replacement).to_string(),
-                            span: head,
-                        }
-                    }
-                }
-                Err(_) => Value::String {
-                    val: val.to_string(),
-                    span: head,
-                },
+            let val = if *all {
+                find.replace_all(val, replace.as_str())
+            } else {
+                find.replace(val, replace.as_str())
+            };
+
+            Value::String {
+                val: val.to_string(),
+                span: head,
             }
         }
         other => Value::Error {
@@ -186,7 +267,7 @@ fn action(
 #[cfg(test)]
 mod tests {
     use super::*;
-    use super::{action, Arguments, SubCommand};
+    use super::{action, Arguments, FindReplace, SubCommand};
 
     #[test]
     fn test_examples() {
@@ -195,6 +276,13 @@ mod tests {
         test_examples(SubCommand {})
     }
 
+    #[test]
+    fn test_find_replace_examples() {
+        use crate::test_examples;
+
+        test_examples(FindReplace {})
+    }
+
     #[test]
     fn can_have_capture_groups() {
         let word = Value::String {
@@ -203,7 +291,7 @@ mod tests {
         };
 
         let options = Arguments {
-            find: String::from("Cargo.(.+)"),
+            find: Regex::new("Cargo.(.+)").expect("valid regex"),
             replace: String::from("Carga.$1"),
             column_paths: vec![],
             all: false,
@@ -1,4 +1,5 @@
 mod benchmark;
+mod process_group;
 mod ps;
 mod run_external;
 mod sys;
@@ -0,0 +1,155 @@
+//! Every external runs in a process group of its own, so ctrl-c reaches the processes it
+//! started as well as the external itself.
+//!
+//! On unix the group is given the terminal while it runs, the way other shells do it, so that
+//! programs that read the terminal keep working and ctrl-c typed into it goes to the group. On
+//! Windows the group is created without ctrl-c, and the shell sends it a ctrl-break instead.
+
+use std::process::Command;
+
+#[cfg(unix)]
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// How many externals have been given the terminal and are still running
+#[cfg(unix)]
+static TERMINAL_LOANS: AtomicUsize = AtomicUsize::new(0);
+
+/// Make the process started by `process` the leader of a new process group
+#[cfg(unix)]
+pub(crate) fn prepare(process: &mut Command) {
+    use std::os::unix::process::CommandExt;
+
+    // Safety: setpgid is async-signal-safe, so it can run between fork and exec
+    unsafe {
+        process.pre_exec(|| {
+            if libc::setpgid(0, 0) == 0 {
+                Ok(())
+            } else {
+                Err(std::io::Error::last_os_error())
+            }
+        });
+    }
+}
+
+#[cfg(windows)]
+pub(crate) fn prepare(process: &mut Command) {
+    use std::os::windows::process::CommandExt;
+
+    process.creation_flags(winapi::um::winbase::CREATE_NEW_PROCESS_GROUP);
+}
+
+#[cfg(not(any(unix, windows)))]
+pub(crate) fn prepare(_process: &mut Command) {}
+
+/// The terminal while it is lent to the group of an external. Dropping it gives the terminal
+/// back to the shell.
+pub(crate) struct Terminal {
+    #[cfg(unix)]
+    lent: Option<(libc::pid_t, libc::pid_t)>,
+}
+
+/// Give the terminal to the group of the external with `pid`, if the terminal is the shell's
+/// or already lent to another external of the same pipeline
+#[cfg(unix)]
+pub(crate) fn lend_terminal(pid: u32) -> Terminal {
+    let group = pid as libc::pid_t;
+
+    // Safety: these only take and return plain integers
+    unsafe {
+        // The child may not have got to its own setpgid yet
+        libc::setpgid(group, group);
+
+        let shell = libc::getpgrp();
+        let foreground = libc::tcgetpgrp(libc::STDIN_FILENO);
+        let ours = foreground == shell || TERMINAL_LOANS.load(Ordering::SeqCst) > 0;
+
+        if libc::isatty(libc::STDIN_FILENO) == 1 && ours {
+            // Taking the terminal back from a background group would stop the shell otherwise
+            libc::signal(libc::SIGTTOU, libc::SIG_IGN);
+
+            if libc::tcsetpgrp(libc::STDIN_FILENO, group) == 0 {
+                TERMINAL_LOANS.fetch_add(1, Ordering::SeqCst);
+                return Terminal {
+                    lent: Some((shell, group)),
+                };
+            }
+        }
+    }
+
+    Terminal { lent: None }
+}
+
+#[cfg(not(unix))]
+pub(crate) fn lend_terminal(_pid: u32) -> Terminal {
+    Terminal {}
+}
+
+#[cfg(unix)]
+impl Drop for Terminal {
+    fn drop(&mut self) {
+        if let Some((shell, group)) = self.lent {
+            TERMINAL_LOANS.fetch_sub(1, Ordering::SeqCst);
+
+            // Safety: these only take and return plain integers
+            unsafe {
+                // A later external of the pipeline may have the terminal by now
+                if libc::tcgetpgrp(libc::STDIN_FILENO) == group {
+                    libc::tcsetpgrp(libc::STDIN_FILENO, shell);
+                }
+            }
+        }
+    }
+}
+
+/// Ask the group of the external with `pid` to stop, as ctrl-c typed into its terminal would
+#[cfg(unix)]
+pub(crate) fn interrupt(pid: u32) {
+    signal(pid, libc::SIGINT)
+}
+
+#[cfg(windows)]
+pub(crate) fn interrupt(pid: u32) {
+    // Safety: this only takes plain integers
+    unsafe {
+        winapi::um::wincon::GenerateConsoleCtrlEvent(winapi::um::wincon::CTRL_BREAK_EVENT, pid);
+    }
+}
+
+#[cfg(not(any(unix, windows)))]
+pub(crate) fn interrupt(_pid: u32) {}
+
+/// Stop the group of the external with `pid` without giving it a chance to clean up
+#[cfg(unix)]
+pub(crate) fn kill(pid: u32) {
+    signal(pid, libc::SIGKILL)
+}
+
+#[cfg(windows)]
+pub(crate) fn kill(pid: u32) {
+    use winapi::um::handleapi::CloseHandle;
+    use winapi::um::processthreadsapi::{OpenProcess, TerminateProcess};
+    use winapi::um::winnt::PROCESS_TERMINATE;
+
+    // Safety: the handle is checked before it's used and closed after
+    unsafe {
+        let handle = OpenProcess(PROCESS_TERMINATE, 0, pid);
+        if !handle.is_null() {
+            TerminateProcess(handle, 1);
+            CloseHandle(handle);
+        }
+    }
+}
+
+#[cfg(not(any(unix, windows)))]
+pub(crate) fn kill(_pid: u32) {}
+
+#[cfg(unix)]
+fn signal(pid: u32, signal: libc::c_int) {
+    // Safety: these only take plain integers
+    unsafe {
+        // An external that didn't get a group of its own is signalled by itself
+        if libc::killpg(pid as libc::pid_t, signal) != 0 {
+            libc::kill(pid as libc::pid_t, signal);
+        }
+    }
+}
@@ -1,8 +1,9 @@
 use std::collections::HashMap;
-use std::io::{BufRead, BufReader, Write};
-use std::process::{Command as CommandSys, Stdio};
-use std::sync::atomic::Ordering;
+use std::io::{BufRead, BufReader};
+use std::process::{Child, Command as CommandSys, ExitStatus, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::mpsc;
+use std::time::{Duration, Instant};
 
 use nu_engine::env_to_strings;
 use nu_protocol::engine::{EngineState, Stack};
@@ -14,6 +15,8 @@ use itertools::Itertools;
 use nu_engine::CallExt;
 use regex::Regex;
 
+use super::process_group;
+
 const OUTPUT_BUFFER_SIZE: usize = 1024;
 
 #[derive(Clone)]
@@ -89,7 +92,7 @@ impl Command for External {
             env_vars: env_vars_str,
             call,
         };
-        command.run_with_input(engine_state, input, config)
+        command.run_with_input(engine_state, stack, input, config)
     }
 }
 
@@ -105,6 +108,7 @@ impl<'call> ExternalCommand<'call> {
     pub fn run_with_input(
         &self,
         engine_state: &EngineState,
+        stack: &mut Stack,
         input: PipelineData,
         config: Config,
     ) -> Result<PipelineData, ShellError> {
@@ -142,6 +146,8 @@ impl<'call> ExternalCommand<'call> {
             process.stdin(Stdio::piped());
         }
 
+        process_group::prepare(&mut process);
+
         match process.spawn() {
             Err(err) => Err(ShellError::ExternalCommand(
                 "can't run executable".to_string(),
@@ -149,32 +155,13 @@ impl<'call> ExternalCommand<'call> {
                 self.name.span,
             )),
             Ok(mut child) => {
+                let terminal = process_group::lend_terminal(child.id());
+
                 // if there is a string or a stream, that is sent to the pipe std
                 if let Some(mut stdin_write) = child.stdin.take() {
                     std::thread::spawn(move || {
-                        for value in input.into_iter() {
-                            match value {
-                                Value::String { val, span: _ } => {
-                                    if stdin_write.write(val.as_bytes()).is_err() {
-                                        return Ok(());
-                                    }
-                                }
-                                Value::Binary { val, span: _ } => {
-                                    if stdin_write.write(&val).is_err() {
-                                        return Ok(());
-                                    }
-                                }
-                                x => {
-                                    if stdin_write
-                                        .write(x.into_string(", ", &config).as_bytes())
-                                        .is_err()
-                                    {
-                                        return Err(());
-                                    }
-                                }
-                            }
-                        }
-                        Ok(())
+                        // The external may stop reading early, which only ends the writing
+                        let _ = input.drain_to_writer(&mut stdin_write, &config);
                     });
                 }
 
@@ -182,6 +169,8 @@ impl<'call> ExternalCommand<'call> {
                 let span = self.name.span;
                 let output_ctrlc = ctrlc.clone();
                 let (tx, rx) = mpsc::channel();
+                let (exit_code_tx, exit_code_rx) = mpsc::channel();
+                stack.set_pending_exit_code(exit_code_rx);
 
                 std::thread::spawn(move || {
                     // If this external is not the last expression, then its output is piped to a channel
@@ -225,13 +214,26 @@ impl<'call> ExternalCommand<'call> {
                         }
                     }
 
-                    match child.wait() {
+                    let status = wait_interruptible(child, ctrlc.as_deref());
+                    drop(terminal);
+
+                    match status {
                         Err(err) => Err(ShellError::ExternalCommand(
                             "External command exited with error".into(),
                             err.to_string(),
                             span,
                         )),
-                        Ok(_) => Ok(()),
+                        Ok(status) => {
+                            // While the external has the terminal, ctrl-c only reaches its group
+                            if interrupted_by_ctrlc(&status) {
+                                if let Some(ctrlc) = &ctrlc {
+                                    ctrlc.store(true, Ordering::SeqCst);
+                                }
+                            }
+
+                            let _ = exit_code_tx.send(exit_code(status));
+                            Ok(())
+                        }
                     }
                 });
                 let receiver = ChannelReceiver::new(rx);
@@ -342,6 +344,80 @@ fn shell_arg_escape(arg: &str) -> String {
     }
 }
 
+/// The exit code of a finished process. On unix, a process killed by a signal has no exit code
+/// and reports 128 plus the signal number, the way other shells do
+fn exit_code(status: ExitStatus) -> i64 {
+    if let Some(code) = status.code() {
+        return code as i64;
+    }
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::ExitStatusExt;
+
+        if let Some(signal) = status.signal() {
+            return 128 + signal as i64;
+        }
+    }
+
+    1
+}
+
+/// How long an interrupted external gets to clean up before its group is killed
+const INTERRUPT_GRACE: Duration = Duration::from_secs(1);
+
+/// How often the shell checks for ctrl-c while an external runs
+const CTRLC_CHECK: Duration = Duration::from_millis(100);
+
+/// Wait for the child to exit. The terminal sends ctrl-c to the child as well, but a child that
+/// handles or ignores it would keep the shell waiting, so it is killed once ctrl-c was pressed.
+///
+/// The child is only polled with `try_wait`, never reaped behind our back, so until its exit has
+/// been seen its pid (and process group) still belong to it and can safely be signalled
+fn wait_interruptible(mut child: Child, ctrlc: Option<&AtomicBool>) -> std::io::Result<ExitStatus> {
+    let ctrlc = match ctrlc {
+        Some(ctrlc) => ctrlc,
+        None => return child.wait(),
+    };
+
+    loop {
+        if let Some(status) = child.try_wait()? {
+            return Ok(status);
+        }
+        if ctrlc.load(Ordering::SeqCst) {
+            break;
+        }
+        std::thread::sleep(CTRLC_CHECK);
+    }
+
+    process_group::interrupt(child.id());
+    let deadline = Instant::now() + INTERRUPT_GRACE;
+    while Instant::now() < deadline {
+        if let Some(status) = child.try_wait()? {
+            return Ok(status);
+        }
+        std::thread::sleep(CTRLC_CHECK);
+    }
+
+    if let Some(status) = child.try_wait()? {
+        return Ok(status);
+    }
+    process_group::kill(child.id());
+    child.wait()
+}
+
+#[cfg(unix)]
+fn interrupted_by_ctrlc(status: &ExitStatus) -> bool {
+    use std::os::unix::process::ExitStatusExt;
+
+    status.signal() == Some(libc::SIGINT)
+}
+
+#[cfg(not(unix))]
+fn interrupted_by_ctrlc(_status: &ExitStatus) -> bool {
+    false
+}
+
 fn trim_enclosing_quotes(input: &str) -> String {
     let mut chars = input.chars();
 
@@ -374,3 +450,52 @@ impl Iterator for ChannelReceiver {
         }
     }
 }
+
+#[cfg(all(test, unix))]
+mod test {
+    use super::*;
+
+    fn spawn(process: &mut CommandSys) -> Child {
+        process_group::prepare(process);
+        process.spawn().expect("test programs should be available")
+    }
+
+    #[test]
+    fn ctrlc_interrupts_waiting_for_child() {
+        let child = spawn(CommandSys::new("sleep").arg("10"));
+        let ctrlc = AtomicBool::new(true);
+
+        let start = Instant::now();
+        let status = wait_interruptible(child, Some(&ctrlc)).expect("child can be waited on");
+
+        assert!(!status.success());
+        assert!(interrupted_by_ctrlc(&status));
+        assert!(start.elapsed() < Duration::from_secs(5));
+    }
+
+    #[test]
+    fn ctrlc_interrupts_the_whole_group() {
+        let marker = std::env::temp_dir().join(format!("nu-group-marker-{}", std::process::id()));
+        let _ = std::fs::remove_file(&marker);
+
+        // The grandchild is what creates the marker, so killing only the child isn't enough
+        let script = format!("sh -c 'sleep 2; touch {}'; true", marker.display());
+        let child = spawn(CommandSys::new("sh").arg("-c").arg(script));
+        let ctrlc = AtomicBool::new(true);
+
+        wait_interruptible(child, Some(&ctrlc)).expect("child can be waited on");
+        std::thread::sleep(Duration::from_secs(3));
+
+        assert!(!marker.exists());
+    }
+
+    #[test]
+    fn finished_child_is_waited_on() {
+        let child = spawn(&mut CommandSys::new("true"));
+        let ctrlc = AtomicBool::new(false);
+
+        let status = wait_interruptible(child, Some(&ctrlc)).expect("child can be waited on");
+
+        assert!(status.success());
+    }
+}
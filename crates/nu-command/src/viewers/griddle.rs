@@ -6,8 +6,8 @@ use nu_engine::CallExt;
 use nu_protocol::{
     ast::{Call, PathMember},
     engine::{Command, EngineState, Stack},
-    Category, Config, IntoPipelineData, PipelineData, ShellError, Signature, Span, SyntaxShape,
-    Value,
+    Category, Config, DataSource, Example, IntoPipelineData, PipelineData, PipelineMetadata,
+    ShellError, Signature, Span, Spanned, SyntaxShape, Value,
 };
 use nu_term_grid::grid::{Alignment, Cell, Direction, Filling, Grid, GridOptions};
 use terminal_size::{Height, Width};
@@ -29,26 +29,50 @@ impl Command for Griddle {
             .named(
                 "width",
                 SyntaxShape::Int,
-                "number of columns wide",
+                "number of terminal columns wide (defaults to the terminal width)",
                 Some('w'),
             )
-            .switch("color", "draw output with color", Some('c'))
+            .switch(
+                "color",
+                "color entries by file type when the input comes from ls",
+                Some('c'),
+            )
             .named(
                 "separator",
                 SyntaxShape::String,
-                "character to separate grid with",
+                "string to separate grid columns with (defaults to two spaces)",
                 Some('s'),
             )
+            .named(
+                "column",
+                SyntaxShape::String,
+                "column of a table to lay out (defaults to 'name')",
+                None,
+            )
             .category(Category::Viewers)
     }
 
     fn extra_usage(&self) -> &str {
-        r#"grid was built to give a concise gridded layout for ls. however,
-it determines what to put in the grid by looking for a column named
-'name'. this works great for tables and records but for lists we
-need to do something different. such as with '[one two three] | grid'
-it creates a fake column called 'name' for these values so that it
-prints out the list properly."#
+        r#"grid was built to give a concise gridded layout for ls. for tables and
+records it lays out the values of the 'name' column, or of the column
+given with --column. a plain list like '[one two three] | grid' is laid
+out value by value. entries run top to bottom, using as many columns as
+fit in the width, like coreutils ls."#
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![
+            Example {
+                description: "Lay out a list in a grid 20 characters wide",
+                example: "[one two three four five] | grid --width 20",
+                result: Some(Value::test_string("one  three  five\ntwo  four\n")),
+            },
+            Example {
+                description: "Lay out the values of the 'lang' column",
+                example: "[[lang]; [rust] [nu]] | grid --column lang --separator ' | '",
+                result: Some(Value::test_string("rust | nu\n")),
+            },
+        ]
     }
 
     fn run(
@@ -58,76 +82,74 @@ prints out the list properly."#
         call: &Call,
         input: PipelineData,
     ) -> Result<nu_protocol::PipelineData, nu_protocol::ShellError> {
-        let width_param: Option<String> = call.get_flag(engine_state, stack, "width")?;
+        let width_param: Option<Spanned<i64>> = call.get_flag(engine_state, stack, "width")?;
         let color_param: bool = call.has_flag("color");
         let separator_param: Option<String> = call.get_flag(engine_state, stack, "separator")?;
+        let column_param: Option<String> = call.get_flag(engine_state, stack, "column")?;
         let config = stack.get_config().unwrap_or_default();
-        let env_str = match stack.get_env_var(engine_state, "LS_COLORS") {
-            Some(v) => Some(env_to_string("LS_COLORS", v, engine_state, stack, &config)?),
-            None => None,
-        };
-        let use_grid_icons = config.use_grid_icons;
-
-        match input {
-            PipelineData::Value(Value::List { vals, .. }, ..) => {
-                // dbg!("value::list");
-                let data = convert_to_list(vals, &config, call.head);
-                if let Some(items) = data {
-                    Ok(create_grid_output(
-                        items,
-                        call,
-                        width_param,
-                        color_param,
-                        separator_param,
-                        env_str,
-                        use_grid_icons,
-                    )?)
-                } else {
-                    Ok(PipelineData::new(call.head))
-                }
+
+        let width = match width_param {
+            Some(Spanned { item, span }) if item <= 0 => {
+                return Err(ShellError::UnsupportedInput(
+                    "width must be a positive number".into(),
+                    span,
+                ))
             }
-            PipelineData::ListStream(stream, ..) => {
-                // dbg!("value::stream");
-                let data = convert_to_list(stream, &config, call.head);
-                if let Some(items) = data {
-                    Ok(create_grid_output(
-                        items,
-                        call,
-                        width_param,
-                        color_param,
-                        separator_param,
-                        env_str,
-                        use_grid_icons,
-                    )?)
-                } else {
-                    // dbg!(data);
-                    Ok(PipelineData::new(call.head))
-                }
+            Some(Spanned { item, .. }) => item as usize,
+            None => match terminal_size::terminal_size() {
+                Some((Width(w), Height(_h))) => w as usize,
+                None => 80,
+            },
+        };
+        let separator = separator_param.unwrap_or_else(|| "  ".to_string());
+        let column = column_param.unwrap_or_else(|| "name".to_string());
+
+        let (names, metadata) = match input {
+            PipelineData::Value(Value::List { vals, .. }, metadata) => {
+                (grid_names(vals, &column, &config, call.head), metadata)
             }
-            PipelineData::Value(Value::Record { cols, vals, .. }, ..) => {
-                // dbg!("value::record");
-                let mut items = vec![];
-
-                for (i, (c, v)) in cols.into_iter().zip(vals.into_iter()).enumerate() {
-                    items.push((i, c, v.into_string(", ", &config)))
-                }
-
-                Ok(create_grid_output(
-                    items,
-                    call,
-                    width_param,
-                    color_param,
-                    separator_param,
-                    env_str,
-                    use_grid_icons,
-                )?)
+            PipelineData::ListStream(stream, metadata) => {
+                (grid_names(stream, &column, &config, call.head), metadata)
             }
-            x => {
-                // dbg!("other value");
-                // dbg!(x.get_type());
-                Ok(x)
+            PipelineData::Value(record @ Value::Record { .. }, metadata) => {
+                (grid_names([record], &column, &config, call.head), metadata)
             }
+            x => return Ok(x),
+        };
+
+        // Only ls output is known to hold paths, so that's the only input worth coloring
+        let from_ls = matches!(
+            metadata,
+            Some(PipelineMetadata {
+                data_source: DataSource::Ls
+            })
+        );
+
+        let cells = if color_param && from_ls {
+            let ls_colors = match stack.get_env_var(engine_state, "LS_COLORS") {
+                Some(v) => LsColors::from_string(&env_to_string(
+                    "LS_COLORS",
+                    v,
+                    engine_state,
+                    stack,
+                    &config,
+                )?),
+                None => LsColors::default(),
+            };
+
+            names
+                .into_iter()
+                .map(|name| colorize(name, &ls_colors, config.use_grid_icons, call.head))
+                .collect::<Result<Vec<_>, _>>()?
+        } else {
+            names
+        };
+
+        Ok(Value::String {
+            val: grid_layout(cells, width, &separator),
+            span: call.head,
         }
+        .into_pipeline_data())
     }
 }
 
@@ -139,165 +161,174 @@ fn strip_ansi(astring: &str) -> String {
     }
 }
 
-fn create_grid_output(
-    items: Vec<(usize, String, String)>,
-    call: &Call,
-    width_param: Option<String>,
-    color_param: bool,
-    separator_param: Option<String>,
-    env_str: Option<String>,
+fn colorize(
+    name: String,
+    ls_colors: &LsColors,
     use_grid_icons: bool,
-) -> Result<PipelineData, ShellError> {
-    let ls_colors = match env_str {
-        Some(s) => LsColors::from_string(&s),
-        None => LsColors::default(),
-    };
+    head: Span,
+) -> Result<String, ShellError> {
+    if use_grid_icons {
+        let no_ansi = strip_ansi(&name);
+        let path = std::path::Path::new(&no_ansi);
+        let icon = icon_for_file(path, head)?;
+        let ls_colors_style = ls_colors.style_for_path(path);
 
-    let cols = if let Some(col) = width_param {
-        col.parse::<u16>().unwrap_or(80)
-    } else if let Some((Width(w), Height(_h))) = terminal_size::terminal_size() {
-        w
-    } else {
-        80u16
-    };
-    let sep = if let Some(separator) = separator_param {
-        separator
+        let icon_style = match ls_colors_style {
+            Some(c) => c.to_crossterm_style(),
+            None => crossterm::style::ContentStyle::default(),
+        };
+
+        let ansi_style = ls_colors_style
+            .map(Style::to_crossterm_style)
+            .unwrap_or_default();
+
+        Ok(format!(
+            "{} {}",
+            icon_style.apply(icon),
+            ansi_style.apply(name)
+        ))
     } else {
-        " │ ".to_string()
-    };
+        let style = ls_colors.style_for_path(name.clone());
+        let ansi_style = style.map(Style::to_crossterm_style).unwrap_or_default();
+
+        Ok(ansi_style.apply(name).to_string())
+    }
+}
 
+/// Packs the cells top to bottom into as few lines as fit in `width`,
+/// falling back to one cell per line when a single cell is already too wide
+fn grid_layout(cells: Vec<String>, width: usize, separator: &str) -> String {
+    let cell_count = cells.len();
     let mut grid = Grid::new(GridOptions {
         direction: Direction::TopToBottom,
-        filling: Filling::Text(sep),
+        filling: Filling::Text(separator.to_string()),
     });
 
-    for (_row_index, header, value) in items {
-        // only output value if the header name is 'name'
-        if header == "name" {
-            if color_param {
-                if use_grid_icons {
-                    let no_ansi = strip_ansi(&value);
-                    let path = std::path::Path::new(&no_ansi);
-                    let icon = icon_for_file(path, call.head)?;
-                    let ls_colors_style = ls_colors.style_for_path(path);
-                    // eprintln!("ls_colors_style: {:?}", &ls_colors_style);
-
-                    let icon_style = match ls_colors_style {
-                        Some(c) => c.to_crossterm_style(),
-                        None => crossterm::style::ContentStyle::default(),
-                    };
-                    // eprintln!("icon_style: {:?}", &icon_style);
-
-                    let ansi_style = ls_colors_style
-                        .map(Style::to_crossterm_style)
-                        .unwrap_or_default();
-                    // eprintln!("ansi_style: {:?}", &ansi_style);
-
-                    let item = format!("{} {}", icon_style.apply(icon), ansi_style.apply(value));
-
-                    let mut cell = Cell::from(item);
-                    cell.alignment = Alignment::Left;
-                    grid.add(cell);
-                } else {
-                    let style = ls_colors.style_for_path(value.clone());
-                    let ansi_style = style.map(Style::to_crossterm_style).unwrap_or_default();
-                    let mut cell = Cell::from(ansi_style.apply(value).to_string());
-                    cell.alignment = Alignment::Left;
-                    grid.add(cell);
-                }
-            } else {
-                let mut cell = Cell::from(value);
-                cell.alignment = Alignment::Left;
-                grid.add(cell);
-            }
-        }
+    for contents in cells {
+        let mut cell = Cell::from(contents);
+        cell.alignment = Alignment::Left;
+        grid.add(cell);
     }
 
-    Ok(
-        if let Some(grid_display) = grid.fit_into_width(cols as usize) {
-            Value::String {
-                val: grid_display.to_string(),
-                span: call.head,
-            }
-        } else {
-            Value::String {
-                val: format!("Couldn't fit grid into {} columns!", cols),
-                span: call.head,
-            }
-        }
-        .into_pipeline_data(),
-    )
+    let display = grid
+        .fit_into_width(width)
+        .unwrap_or_else(|| grid.fit_into_columns(1));
+    let rows = display.row_count();
+    let columns = if rows == 0 {
+        0
+    } else {
+        (cell_count + rows - 1) / rows
+    };
+
+    // Lines that run out before the last column still end in a separator and padding
+    display
+        .to_string()
+        .lines()
+        .enumerate()
+        .map(|(row, line)| {
+            let line = if columns > 1 && row + rows * (columns - 1) >= cell_count {
+                line.strip_suffix(separator).unwrap_or(line)
+            } else {
+                line
+            };
+
+            format!("{}\n", line.trim_end())
+        })
+        .collect()
 }
 
-fn convert_to_list(
+/// The values to lay out: `column` of each record, or the value itself for anything else
+fn grid_names(
     iter: impl IntoIterator<Item = Value>,
+    column: &str,
     config: &Config,
     head: Span,
-) -> Option<Vec<(usize, String, String)>> {
-    let mut iter = iter.into_iter().peekable();
+) -> Vec<String> {
+    iter.into_iter()
+        .filter_map(|item| match item {
+            Value::Record { .. } => item
+                .follow_cell_path(&[PathMember::String {
+                    val: column.into(),
+                    span: head,
+                }])
+                .ok()
+                .map(|value| value.into_string(", ", config)),
+            other => Some(other.into_string(", ", config)),
+        })
+        .collect()
+}
 
-    if let Some(first) = iter.peek() {
-        let mut headers = first.columns();
+#[cfg(test)]
+mod test {
+    use super::*;
 
-        if !headers.is_empty() {
-            headers.insert(0, "#".into());
-        }
+    fn cells(names: &[&str]) -> Vec<String> {
+        names.iter().map(|name| name.to_string()).collect()
+    }
 
-        let mut data = vec![];
+    #[test]
+    fn test_examples() {
+        use crate::test_examples;
 
-        for (row_num, item) in iter.enumerate() {
-            let mut row = vec![row_num.to_string()];
+        test_examples(Griddle {})
+    }
 
-            if headers.is_empty() {
-                row.push(item.into_string(", ", config))
-            } else {
-                for header in headers.iter().skip(1) {
-                    let result = match item {
-                        Value::Record { .. } => {
-                            item.clone().follow_cell_path(&[PathMember::String {
-                                val: header.into(),
-                                span: head,
-                            }])
-                        }
-                        _ => Ok(item.clone()),
-                    };
-
-                    match result {
-                        Ok(value) => row.push(value.into_string(", ", config)),
-                        Err(_) => row.push(String::new()),
-                    }
-                }
-            }
+    #[test]
+    fn fits_on_one_line_when_wide_enough() {
+        let output = grid_layout(cells(&["a", "bb", "ccc", "dddd", "e"]), 80, "  ");
 
-            data.push(row);
-        }
+        assert_eq!(output, "a  bb  ccc  dddd  e\n");
+    }
 
-        let mut h: Vec<String> = headers.into_iter().collect();
+    #[test]
+    fn balances_columns_top_to_bottom() {
+        let output = grid_layout(cells(&["a", "bb", "ccc", "dddd", "e"]), 10, "  ");
 
-        // This is just a list
-        if h.is_empty() {
-            // let's fake the header
-            h.push("#".to_string());
-            h.push("name".to_string());
-        }
+        assert_eq!(output, "a    dddd\nbb   e\nccc\n");
+    }
 
-        // this tuple is (row_index, header_name, value)
-        let mut interleaved = vec![];
-        for (i, v) in data.into_iter().enumerate() {
-            for (n, s) in v.into_iter().enumerate() {
-                if h.len() == 1 {
-                    // always get the 1th element since this is a simple list
-                    // and we hacked the header above because it was empty
-                    // 0th element is an index, 1th element is the value
-                    interleaved.push((i, h[1].clone(), s))
-                } else {
-                    interleaved.push((i, h[n].clone(), s))
-                }
-            }
-        }
+    #[test]
+    fn counts_the_separator_width() {
+        let output = grid_layout(cells(&["a", "bb", "ccc", "dddd", "e"]), 11, " | ");
 
-        Some(interleaved)
-    } else {
-        None
+        assert_eq!(output, "a   | dddd\nbb  | e\nccc\n");
+    }
+
+    #[test]
+    fn falls_back_to_one_column_when_a_cell_is_too_wide() {
+        let output = grid_layout(cells(&["a", "bb", "ccc", "dddd"]), 3, "  ");
+
+        assert_eq!(output, "a\nbb\nccc\ndddd\n");
+    }
+
+    #[test]
+    fn ignores_ansi_when_measuring() {
+        let output = grid_layout(
+            vec!["\u{1b}[31ma\u{1b}[0m".to_string(), "b".to_string()],
+            4,
+            "  ",
+        );
+
+        assert_eq!(output, "\u{1b}[31ma\u{1b}[0m  b\n");
+    }
+
+    #[test]
+    fn picks_the_requested_column() {
+        let record = |name: &str, kind: &str| Value::Record {
+            cols: vec!["name".into(), "type".into()],
+            vals: vec![Value::test_string(name), Value::test_string(kind)],
+            span: Span::test_data(),
+        };
+        let rows = vec![record("a.txt", "file"), record("src", "dir")];
+        let config = Config::default();
+
+        assert_eq!(
+            grid_names(rows.clone(), "name", &config, Span::test_data()),
+            vec!["a.txt", "src"]
+        );
+        assert_eq!(
+            grid_names(rows, "type", &config, Span::test_data()),
+            vec!["file", "dir"]
+        );
     }
 }
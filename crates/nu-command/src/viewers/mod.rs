@@ -1,5 +1,6 @@
 mod griddle;
 mod icons;
+mod pager;
 mod table;
 
 pub use griddle::Griddle;
@@ -0,0 +1,355 @@
+use crossterm::cursor::{Hide, MoveTo, Show};
+use crossterm::event::{Event, KeyCode, KeyEvent, KeyModifiers};
+use crossterm::style::{Attribute, Print, SetAttribute};
+use crossterm::terminal::{self, Clear, ClearType, EnterAlternateScreen, LeaveAlternateScreen};
+use crossterm::tty::IsTty;
+use crossterm::{execute, queue};
+use nu_protocol::{PipelineData, ShellError, StringStream, Value};
+use std::io::Write;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+type Chunks = Box<dyn Iterator<Item = Result<String, ShellError>>>;
+
+/// Show a rendered table a screen at a time, the way `less` would.
+///
+/// Output that fits on the screen, or that isn't going to a terminal, is given back as it is.
+/// Otherwise the pager takes over the screen, and quitting it still runs the rest of the
+/// pipeline, it just isn't shown.
+pub fn page(
+    output: PipelineData,
+    ctrlc: Option<Arc<AtomicBool>>,
+) -> Result<PipelineData, ShellError> {
+    if !std::io::stdin().is_tty() || !std::io::stdout().is_tty() {
+        return Ok(output);
+    }
+
+    let (chunks, span) = match output {
+        PipelineData::Value(Value::String { val, span }, ..) => {
+            (Box::new(std::iter::once(Ok(val))) as Chunks, span)
+        }
+        PipelineData::StringStream(stream, span, ..) => (Box::new(stream) as Chunks, span),
+        output => return Ok(output),
+    };
+
+    let rows = screen_rows().map_err(io_error)?;
+    let mut lines = Lines::new(chunks);
+    lines.fill(rows)?;
+
+    if lines.exhausted && lines.lines.len() < rows {
+        return Ok(PipelineData::StringStream(
+            StringStream::from_stream(lines.chunks.into_iter().map(Ok), ctrlc),
+            span,
+            None,
+        ));
+    }
+
+    {
+        let _screen = Screen::enter().map_err(io_error)?;
+        show(&mut lines, &ctrlc)?;
+    }
+
+    // The rest of the pipeline still runs, its output just isn't shown
+    for chunk in lines.source {
+        chunk?;
+    }
+
+    Ok(PipelineData::new(span))
+}
+
+/// The lines of the table read so far, read from the table as the pager needs them
+struct Lines<I> {
+    source: I,
+    chunks: Vec<String>,
+    lines: Vec<String>,
+    exhausted: bool,
+}
+
+impl<I: Iterator<Item = Result<String, ShellError>>> Lines<I> {
+    fn new(source: I) -> Self {
+        Lines {
+            source,
+            chunks: vec![],
+            lines: vec![],
+            exhausted: false,
+        }
+    }
+
+    /// Read from the table until there are at least `count` lines or the table has ended
+    fn fill(&mut self, count: usize) -> Result<(), ShellError> {
+        while !self.exhausted && self.lines.len() < count {
+            match self.source.next() {
+                Some(chunk) => {
+                    let chunk = chunk?;
+                    self.lines.extend(chunk.lines().map(String::from));
+                    self.chunks.push(chunk);
+                }
+                None => self.exhausted = true,
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Read the rest of the table, a few lines at a time so that `stop` can end it early. A
+    /// table can be endless, so jumping to the end has to be something the user can cancel
+    fn fill_until(&mut self, mut stop: impl FnMut() -> bool) -> Result<(), ShellError> {
+        while !self.exhausted && !stop() {
+            self.fill(self.lines.len().saturating_add(SEEK_LINES))?;
+        }
+
+        Ok(())
+    }
+}
+
+/// How many lines are read between checks for a key press while jumping to the end
+const SEEK_LINES: usize = 1000;
+
+/// What a key does in the pager
+#[derive(Debug, PartialEq)]
+enum Action {
+    Quit,
+    Interrupt,
+    Down(usize),
+    Up(usize),
+    Top,
+    Bottom,
+    Nothing,
+}
+
+fn action(key: KeyEvent, page: usize) -> Action {
+    let control = key.modifiers.contains(KeyModifiers::CONTROL);
+
+    match key.code {
+        KeyCode::Char('c') if control => Action::Interrupt,
+        KeyCode::Char('q') | KeyCode::Esc => Action::Quit,
+        KeyCode::Char('j') | KeyCode::Down | KeyCode::Enter => Action::Down(1),
+        KeyCode::Char('k') | KeyCode::Up => Action::Up(1),
+        KeyCode::Char(' ') | KeyCode::Char('f') | KeyCode::PageDown => Action::Down(page),
+        KeyCode::Char('b') | KeyCode::PageUp => Action::Up(page),
+        KeyCode::Char('g') | KeyCode::Home => Action::Top,
+        KeyCode::Char('G') | KeyCode::End => Action::Bottom,
+        _ => Action::Nothing,
+    }
+}
+
+/// The first line shown when the screen starts at `top`, so that the screen isn't scrolled past
+/// the end of the table
+fn clamp_top<I>(lines: &Lines<I>, top: usize, page: usize) -> usize {
+    if lines.exhausted {
+        top.min(lines.lines.len().saturating_sub(page))
+    } else {
+        top
+    }
+}
+
+fn show<I: Iterator<Item = Result<String, ShellError>>>(
+    lines: &mut Lines<I>,
+    ctrlc: &Option<Arc<AtomicBool>>,
+) -> Result<(), ShellError> {
+    let mut stdout = std::io::stdout();
+    let mut top = 0;
+    // A key pressed while jumping to the end, handled once the jump has stopped
+    let mut pending = None;
+
+    loop {
+        // The last row of the screen is for the status
+        let page = screen_rows().map_err(io_error)?.saturating_sub(1).max(1);
+        lines.fill(top.saturating_add(page))?;
+        top = clamp_top(lines, top, page);
+
+        let end = lines.lines.len().min(top + page);
+        let status = if lines.exhausted && end == lines.lines.len() {
+            "(END)"
+        } else {
+            ":"
+        };
+        draw(&mut stdout, &lines.lines[top..end], status, page).map_err(io_error)?;
+
+        let key = match pending.take() {
+            Some(key) => key,
+            None => match crossterm::event::read().map_err(io_error)? {
+                Event::Key(key) => key,
+                // A resize is drawn again at the top of the loop
+                _ => continue,
+            },
+        };
+
+        match action(key, page) {
+            Action::Quit => return Ok(()),
+            Action::Interrupt => {
+                if let Some(ctrlc) = ctrlc {
+                    ctrlc.store(true, Ordering::SeqCst);
+                }
+                return Ok(());
+            }
+            Action::Down(rows) => top = top.saturating_add(rows),
+            Action::Up(rows) => top = top.saturating_sub(rows),
+            Action::Top => top = 0,
+            Action::Bottom => {
+                let mut failure = None;
+                lines.fill_until(|| {
+                    if is_interrupted(ctrlc) {
+                        return true;
+                    }
+                    match pressed_key() {
+                        Ok(key) => {
+                            pending = key;
+                            pending.is_some()
+                        }
+                        Err(err) => {
+                            failure = Some(err);
+                            true
+                        }
+                    }
+                })?;
+                if let Some(err) = failure {
+                    return Err(io_error(err));
+                }
+                top = lines.lines.len().saturating_sub(page);
+            }
+            Action::Nothing => {}
+        }
+    }
+}
+
+fn is_interrupted(ctrlc: &Option<Arc<AtomicBool>>) -> bool {
+    matches!(ctrlc, Some(ctrlc) if ctrlc.load(Ordering::SeqCst))
+}
+
+/// A key that was already pressed, without waiting for one
+fn pressed_key() -> crossterm::Result<Option<KeyEvent>> {
+    while crossterm::event::poll(Duration::ZERO)? {
+        if let Event::Key(key) = crossterm::event::read()? {
+            return Ok(Some(key));
+        }
+    }
+
+    Ok(None)
+}
+
+fn draw(
+    stdout: &mut impl Write,
+    lines: &[String],
+    status: &str,
+    page: usize,
+) -> crossterm::Result<()> {
+    queue!(stdout, Clear(ClearType::All), MoveTo(0, 0))?;
+    for line in lines {
+        // Raw mode doesn't go back to the start of the line by itself
+        queue!(stdout, Print(line), Print("\r\n"))?;
+    }
+    queue!(
+        stdout,
+        MoveTo(0, page as u16),
+        SetAttribute(Attribute::Reverse),
+        Print(status),
+        SetAttribute(Attribute::Reset)
+    )?;
+
+    stdout.flush()
+}
+
+fn screen_rows() -> crossterm::Result<usize> {
+    terminal::size().map(|(_, rows)| rows as usize)
+}
+
+fn io_error(err: crossterm::ErrorKind) -> ShellError {
+    ShellError::IOError(err.to_string())
+}
+
+/// The pager's own screen, left again when this is dropped
+struct Screen;
+
+impl Screen {
+    fn enter() -> crossterm::Result<Self> {
+        terminal::enable_raw_mode()?;
+        execute!(std::io::stdout(), EnterAlternateScreen, Hide)?;
+
+        Ok(Screen)
+    }
+}
+
+impl Drop for Screen {
+    fn drop(&mut self) {
+        let _ = execute!(std::io::stdout(), Show, LeaveAlternateScreen);
+        let _ = terminal::disable_raw_mode();
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    fn numbered(count: usize) -> impl Iterator<Item = Result<String, ShellError>> {
+        (0..count).map(|line| Ok(format!("{}\n", line)))
+    }
+
+    #[test]
+    fn lines_are_read_as_needed() {
+        let mut lines = Lines::new(numbered(100));
+
+        lines.fill(10).expect("lines can be read");
+
+        assert_eq!(lines.lines.len(), 10);
+        assert!(!lines.exhausted);
+        assert_eq!(
+            lines.source.next().expect("rest is unread").ok(),
+            Some("10\n".to_string())
+        );
+    }
+
+    #[test]
+    fn chunks_are_split_into_lines() {
+        let mut lines = Lines::new(vec![Ok("a\nb\n".to_string()), Ok("c".to_string())].into_iter());
+
+        lines.fill(usize::MAX).expect("lines can be read");
+
+        assert_eq!(lines.lines, vec!["a", "b", "c"]);
+        assert!(lines.exhausted);
+    }
+
+    #[test]
+    fn jumping_to_the_end_of_an_endless_table_can_stop() {
+        let mut lines = Lines::new((0..).map(|line| Ok(format!("{}\n", line))));
+        let mut checks = 0;
+
+        lines
+            .fill_until(|| {
+                checks += 1;
+                checks > 3
+            })
+            .expect("lines can be read");
+
+        assert!(!lines.exhausted);
+        assert_eq!(lines.lines.len(), 3 * SEEK_LINES);
+    }
+
+    #[test]
+    fn screen_stops_at_the_end() {
+        let mut lines = Lines::new(numbered(30));
+        lines.fill(usize::MAX).expect("lines can be read");
+
+        assert_eq!(clamp_top(&lines, usize::MAX, 10), 20);
+        assert_eq!(clamp_top(&lines, 5, 10), 5);
+        assert_eq!(clamp_top(&lines, 5, 50), 0);
+    }
+
+    #[test]
+    fn keys_move_like_less() {
+        assert_eq!(action(key(KeyCode::Char('q')), 10), Action::Quit);
+        assert_eq!(action(key(KeyCode::Char(' ')), 10), Action::Down(10));
+        assert_eq!(action(key(KeyCode::Char('j')), 10), Action::Down(1));
+        assert_eq!(action(key(KeyCode::Char('b')), 10), Action::Up(10));
+        assert_eq!(action(key(KeyCode::Char('G')), 10), Action::Bottom);
+        assert_eq!(
+            action(KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL), 10),
+            Action::Interrupt
+        );
+    }
+}
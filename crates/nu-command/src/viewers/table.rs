@@ -1,7 +1,9 @@
+use crossterm::tty::IsTty;
 use lscolors::{LsColors, Style};
 use nu_color_config::{get_color_config, style_primitive};
 use nu_engine::column::get_columns;
 use nu_engine::{env_to_string, CallExt};
+use nu_pretty_hex::HexConfig;
 use nu_protocol::ast::{Call, PathMember};
 use nu_protocol::engine::{Command, EngineState, Stack};
 use nu_protocol::{
@@ -9,6 +11,8 @@ use nu_protocol::{
     Signature, Span, StringStream, SyntaxShape, Value, ValueStream,
 };
 use nu_table::{StyledString, TextStyle, Theme};
+
+use super::pager;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use std::time::Instant;
@@ -16,6 +20,7 @@ use terminal_size::{Height, Width};
 
 const STREAM_PAGE_SIZE: usize = 1000;
 const STREAM_TIMEOUT_CHECK_INTERVAL: usize = 100;
+const BINARY_DISPLAY_LIMIT: usize = 1024;
 
 #[derive(Clone)]
 pub struct Table;
@@ -38,6 +43,16 @@ impl Command for Table {
                 "row number to start viewing from",
                 Some('n'),
             )
+            .switch(
+                "expand",
+                "show every cell in full, ignoring $config.table_max_cell_width, and nested records and lists inline",
+                Some('e'),
+            )
+            .switch(
+                "pager",
+                "show a table that doesn't fit on the screen a screen at a time, like less",
+                Some('p'),
+            )
             .category(Category::Viewers)
     }
 
@@ -48,205 +63,368 @@ impl Command for Table {
         call: &Call,
         input: PipelineData,
     ) -> Result<nu_protocol::PipelineData, nu_protocol::ShellError> {
-        let head = call.head;
-        let ctrlc = engine_state.ctrlc.clone();
         let config = stack.get_config().unwrap_or_default();
-        let color_hm = get_color_config(&config);
-        let start_num: Option<i64> = call.get_flag(engine_state, stack, "start_number")?;
-        let row_offset = start_num.unwrap_or_default() as usize;
+        let output = render(engine_state, stack, call, input)?;
 
-        let term_width = if let Some((Width(w), Height(_h))) = terminal_size::terminal_size() {
-            (w - 1) as usize
+        if call.has_flag("pager") || config.table_pager {
+            pager::page(output, engine_state.ctrlc.clone())
         } else {
-            80usize
-        };
+            Ok(output)
+        }
+    }
+}
 
-        match input {
-            PipelineData::ByteStream(stream, ..) => Ok(PipelineData::StringStream(
+fn render(
+    engine_state: &EngineState,
+    stack: &mut Stack,
+    call: &Call,
+    input: PipelineData,
+) -> Result<PipelineData, ShellError> {
+    let head = call.head;
+    let ctrlc = engine_state.ctrlc.clone();
+    let config = stack.get_config().unwrap_or_default();
+    let color_hm = get_color_config(&config);
+    let start_num: Option<i64> = call.get_flag(engine_state, stack, "start_number")?;
+    let row_offset = start_num.unwrap_or_default() as usize;
+    let max_rows = max_rows(&config);
+    let expand = call.has_flag("expand");
+
+    let term_width = if let Some((Width(w), Height(_h))) = terminal_size::terminal_size() {
+        (w - 1) as usize
+    } else {
+        80usize
+    };
+
+    match input {
+        PipelineData::ByteStream(stream, ..) => {
+            // Addresses carry on from one chunk to the next so the dump reads as one
+            let mut address_offset = 0;
+
+            Ok(PipelineData::StringStream(
                 StringStream::from_stream(
                     stream.map(move |x| {
-                        Ok(if x.iter().all(|x| x.is_ascii()) {
-                            format!("{}", String::from_utf8_lossy(&x?))
+                        let x = x?;
+                        let output = if x.iter().all(|x| x.is_ascii()) {
+                            format!("{}", String::from_utf8_lossy(&x))
                         } else {
-                            format!("{}\n", nu_pretty_hex::pretty_hex(&x?))
-                        })
+                            let cfg = HexConfig {
+                                title: address_offset == 0,
+                                address_offset,
+                                ..HexConfig::default()
+                            };
+                            format!("{}\n", nu_pretty_hex::config_hex(&x, cfg))
+                        };
+                        address_offset += x.len();
+
+                        Ok(output)
                     }),
                     ctrlc,
                 ),
                 head,
                 None,
-            )),
-            PipelineData::Value(Value::Binary { val, .. }, ..) => Ok(PipelineData::StringStream(
-                StringStream::from_stream(
-                    vec![Ok(
-                        if val.iter().all(|x| {
-                            *x < 128 && (*x >= b' ' || *x == b'\t' || *x == b'\r' || *x == b'\n')
-                        }) {
-                            format!("{}", String::from_utf8_lossy(&val))
-                        } else {
-                            format!("{}\n", nu_pretty_hex::pretty_hex(&val))
-                        },
-                    )]
-                    .into_iter(),
-                    ctrlc,
-                ),
-                head,
-                None,
-            )),
-            PipelineData::Value(Value::List { vals, .. }, ..) => {
-                let table = convert_to_table(row_offset, &vals, ctrlc, &config, call.head)?;
-
-                if let Some(table) = table {
-                    let result = nu_table::draw_table(&table, term_width, &color_hm, &config);
+            ))
+        }
+        PipelineData::Value(Value::Binary { val, .. }, ..) => Ok(PipelineData::StringStream(
+            StringStream::from_stream(
+                vec![Ok(
+                    if val.iter().all(|x| {
+                        *x < 128 && (*x >= b' ' || *x == b'\t' || *x == b'\r' || *x == b'\n')
+                    }) {
+                        format!("{}", String::from_utf8_lossy(&val))
+                    } else {
+                        format!("{}\n", hex_dump(&val))
+                    },
+                )]
+                .into_iter(),
+                ctrlc,
+            ),
+            head,
+            None,
+        )),
+        PipelineData::Value(Value::List { vals, .. }, ..) => {
+            let shown = max_rows.map_or(vals.len(), |max| vals.len().min(max));
+            let table = convert_to_table(
+                row_offset,
+                &vals[..shown],
+                ctrlc,
+                &config,
+                call.head,
+                expand,
+            )?;
+
+            if let Some(table) = table {
+                let mut result = nu_table::draw_table(&table, term_width, &color_hm, &config);
+                if shown < vals.len() {
+                    result = format!(
+                        "{}\n{}",
+                        result.trim_end_matches('\n'),
+                        more_rows_note(vals.len() - shown)
+                    );
+                }
 
-                    Ok(Value::String {
-                        val: result,
-                        span: call.head,
-                    }
-                    .into_pipeline_data())
-                } else {
-                    Ok(PipelineData::new(call.head))
+                Ok(Value::String {
+                    val: result,
+                    span: call.head,
                 }
+                .into_pipeline_data())
+            } else {
+                Ok(PipelineData::new(call.head))
             }
-            PipelineData::ListStream(stream, metadata) => {
-                let stream = match metadata {
-                    Some(PipelineMetadata {
-                        data_source: DataSource::Ls,
-                    }) => {
-                        let config = config.clone();
-                        let ctrlc = ctrlc.clone();
-
-                        let ls_colors = match stack.get_env_var(engine_state, "LS_COLORS") {
-                            Some(v) => LsColors::from_string(&env_to_string(
-                                "LS_COLORS",
-                                v,
-                                engine_state,
-                                stack,
-                                &config,
-                            )?),
-                            None => LsColors::default(),
-                        };
+        }
+        PipelineData::ListStream(stream, metadata) => {
+            let stream = match metadata {
+                Some(PipelineMetadata {
+                    data_source: DataSource::Ls,
+                }) => {
+                    let config = config.clone();
+                    let ctrlc = ctrlc.clone();
+
+                    let ls_colors = match stack.get_env_var(engine_state, "LS_COLORS") {
+                        Some(v) => LsColors::from_string(&env_to_string(
+                            "LS_COLORS",
+                            v,
+                            engine_state,
+                            stack,
+                            &config,
+                        )?),
+                        None => LsColors::default(),
+                    };
 
-                        ValueStream::from_stream(
-                            stream.map(move |mut x| match &mut x {
-                                Value::Record { cols, vals, .. } => {
-                                    let mut idx = 0;
-
-                                    while idx < cols.len() {
-                                        if cols[idx] == "name" {
-                                            if let Some(Value::String { val: path, span }) =
-                                                vals.get(idx)
-                                            {
-                                                match std::fs::symlink_metadata(&path) {
-                                                    Ok(metadata) => {
-                                                        let style = ls_colors
-                                                            .style_for_path_with_metadata(
-                                                                path.clone(),
-                                                                Some(&metadata),
-                                                            );
-                                                        let ansi_style = style
-                                                            .map(Style::to_crossterm_style)
-                                                            .unwrap_or_default();
-                                                        let use_ls_colors = config.use_ls_colors;
-
-                                                        if use_ls_colors {
-                                                            vals[idx] = Value::String {
-                                                                val: ansi_style
-                                                                    .apply(path)
-                                                                    .to_string(),
-                                                                span: *span,
-                                                            };
-                                                        }
+                    ValueStream::from_stream(
+                        stream.map(move |mut x| match &mut x {
+                            Value::Record { cols, vals, .. } => {
+                                let mut idx = 0;
+
+                                while idx < cols.len() {
+                                    if cols[idx] == "name" {
+                                        if let Some(Value::String { val: path, span }) =
+                                            vals.get(idx)
+                                        {
+                                            match std::fs::symlink_metadata(&path) {
+                                                Ok(metadata) => {
+                                                    let style = ls_colors
+                                                        .style_for_path_with_metadata(
+                                                            path.clone(),
+                                                            Some(&metadata),
+                                                        );
+                                                    let ansi_style = style
+                                                        .map(Style::to_crossterm_style)
+                                                        .unwrap_or_default();
+                                                    let use_ls_colors = config.use_ls_colors;
+
+                                                    if use_ls_colors {
+                                                        vals[idx] = Value::String {
+                                                            val: ansi_style.apply(path).to_string(),
+                                                            span: *span,
+                                                        };
                                                     }
-                                                    Err(_) => {
-                                                        let style =
-                                                            ls_colors.style_for_path(path.clone());
-                                                        let ansi_style = style
-                                                            .map(Style::to_crossterm_style)
-                                                            .unwrap_or_default();
-                                                        let use_ls_colors = config.use_ls_colors;
-
-                                                        if use_ls_colors {
-                                                            vals[idx] = Value::String {
-                                                                val: ansi_style
-                                                                    .apply(path)
-                                                                    .to_string(),
-                                                                span: *span,
-                                                            };
-                                                        }
+                                                }
+                                                Err(_) => {
+                                                    let style =
+                                                        ls_colors.style_for_path(path.clone());
+                                                    let ansi_style = style
+                                                        .map(Style::to_crossterm_style)
+                                                        .unwrap_or_default();
+                                                    let use_ls_colors = config.use_ls_colors;
+
+                                                    if use_ls_colors {
+                                                        vals[idx] = Value::String {
+                                                            val: ansi_style.apply(path).to_string(),
+                                                            span: *span,
+                                                        };
                                                     }
                                                 }
                                             }
                                         }
-
-                                        idx += 1;
                                     }
 
-                                    x
+                                    idx += 1;
                                 }
-                                _ => x,
-                            }),
-                            ctrlc,
-                        )
-                    }
-                    _ => stream,
-                };
-
-                let head = call.head;
 
-                Ok(PipelineData::StringStream(
-                    StringStream::from_stream(
-                        PagingTableCreator {
-                            row_offset,
-                            config,
-                            ctrlc: ctrlc.clone(),
-                            head,
-                            stream,
-                        },
+                                x
+                            }
+                            _ => x,
+                        }),
                         ctrlc,
-                    ),
-                    head,
-                    None,
-                ))
-            }
-            PipelineData::Value(Value::Record { cols, vals, .. }, ..) => {
-                let mut output = vec![];
-
-                for (c, v) in cols.into_iter().zip(vals.into_iter()) {
-                    output.push(vec![
-                        StyledString {
-                            contents: c,
-                            style: TextStyle::default_field(),
-                        },
-                        StyledString {
-                            contents: v.into_abbreviated_string(&config),
-                            style: TextStyle::default(),
+                    )
+                }
+                Some(PipelineMetadata {
+                    data_source:
+                        DataSource::Calendar {
+                            today: Some((row, column)),
                         },
-                    ])
+                }) if config.use_ansi_coloring && std::io::stdout().is_tty() => {
+                    let mut index = 0;
+                    let highlight_config = config.clone();
+
+                    ValueStream::from_stream(
+                        stream.map(move |x| {
+                            let x = if index == row {
+                                highlight_cell(x, &column, &highlight_config)
+                            } else {
+                                x
+                            };
+                            index += 1;
+                            x
+                        }),
+                        ctrlc.clone(),
+                    )
                 }
+                _ => stream,
+            };
 
-                let table = nu_table::Table {
-                    headers: vec![],
-                    data: output,
-                    theme: load_theme_from_config(&config),
-                };
+            let head = call.head;
 
-                let result = nu_table::draw_table(&table, term_width, &color_hm, &config);
+            Ok(PipelineData::StringStream(
+                StringStream::from_stream(
+                    PagingTableCreator {
+                        row_offset,
+                        config,
+                        ctrlc: ctrlc.clone(),
+                        head,
+                        stream,
+                        max_rows,
+                        rows_shown: 0,
+                        done: false,
+                        expand,
+                    },
+                    ctrlc,
+                ),
+                head,
+                None,
+            ))
+        }
+        PipelineData::Value(Value::Record { cols, vals, .. }, ..) => {
+            let max_cell_width = max_cell_width(&config, expand);
+            let depth = collapse_depth(&config, expand);
+            let mut output = vec![];
+
+            for (c, v) in cols.into_iter().zip(vals.into_iter()) {
+                output.push(vec![
+                    StyledString {
+                        contents: c,
+                        style: TextStyle::default_field(),
+                    },
+                    StyledString {
+                        contents: truncate_cell(
+                            v.into_nested_string(&config, depth),
+                            max_cell_width,
+                        ),
+                        style: TextStyle::default(),
+                    },
+                ])
+            }
 
-                Ok(Value::String {
-                    val: result,
-                    span: call.head,
-                }
-                .into_pipeline_data())
+            let table = nu_table::Table {
+                headers: vec![],
+                data: output,
+                theme: load_theme_from_config(&config),
+            };
+
+            let result = nu_table::draw_table(&table, term_width, &color_hm, &config);
+
+            Ok(Value::String {
+                val: result,
+                span: call.head,
             }
-            PipelineData::Value(Value::Error { error }, ..) => Err(error),
-            PipelineData::Value(Value::CustomValue { val, span }, ..) => {
-                let base_pipeline = val.to_base_value(span)?.into_pipeline_data();
-                self.run(engine_state, stack, call, base_pipeline)
+            .into_pipeline_data())
+        }
+        PipelineData::Value(Value::Error { error }, ..) => Err(error),
+        PipelineData::Value(Value::CustomValue { val, span }, ..) => {
+            let base_pipeline = val.to_base_value(span)?.into_pipeline_data();
+            render(engine_state, stack, call, base_pipeline)
+        }
+        x => Ok(x),
+    }
+}
+
+/// Show the value in `column` of a row in reverse video, to mark today in a calendar
+fn highlight_cell(row: Value, column: &str, config: &Config) -> Value {
+    match row {
+        Value::Record {
+            cols,
+            mut vals,
+            span,
+        } => {
+            if let Some(index) = cols.iter().position(|col| col == column) {
+                let val = &vals[index];
+                if let Ok(val_span) = val.span() {
+                    let text = val.into_abbreviated_string(config);
+                    vals[index] = Value::String {
+                        val: nu_ansi_term::Style::new().reverse().paint(text).to_string(),
+                        span: val_span,
+                    };
+                }
             }
-            x => Ok(x),
+
+            Value::Record { cols, vals, span }
         }
+        row => row,
+    }
+}
+
+/// Hex dump of a binary value, cut short after `BINARY_DISPLAY_LIMIT` bytes with a note saying
+/// how much was left out
+fn hex_dump(val: &[u8]) -> String {
+    let shown = val.len().min(BINARY_DISPLAY_LIMIT);
+    let cfg = HexConfig {
+        length: Some(shown),
+        ..HexConfig::default()
+    };
+
+    let mut output = nu_pretty_hex::config_hex(&val, cfg);
+    if shown < val.len() {
+        output.push_str(&format!(
+            "\n... {} more bytes ({} total)",
+            val.len() - shown,
+            val.len()
+        ));
     }
+
+    output
+}
+
+/// `$config.table_max_rows`, where 0 or less means every row is shown
+fn max_rows(config: &Config) -> Option<usize> {
+    if config.table_max_rows > 0 {
+        Some(config.table_max_rows as usize)
+    } else {
+        None
+    }
+}
+
+/// `$config.table_max_cell_width`, unless `--expand` asks for cells in full
+fn max_cell_width(config: &Config, expand: bool) -> Option<usize> {
+    if !expand && config.table_max_cell_width > 0 {
+        Some(config.table_max_cell_width as usize)
+    } else {
+        None
+    }
+}
+
+/// How many levels of nesting `--expand` shows inline, at least
+const EXPANDED_DEPTH: usize = 8;
+
+/// `$config.table_collapse_depth`, raised to `EXPANDED_DEPTH` by `--expand`
+fn collapse_depth(config: &Config, expand: bool) -> usize {
+    let depth = config.table_collapse_depth.max(0) as usize;
+    if expand {
+        depth.max(EXPANDED_DEPTH)
+    } else {
+        depth
+    }
+}
+
+fn truncate_cell(contents: String, max_cell_width: Option<usize>) -> String {
+    match max_cell_width {
+        Some(width) => nu_table::truncate_to_width(&contents, width),
+        None => contents,
+    }
+}
+
+/// The line under a table that was cut short by `table_max_rows`
+fn more_rows_note(hidden: usize) -> String {
+    format!("... and {} more rows", hidden)
 }
 
 fn convert_to_table(
@@ -255,14 +433,18 @@ fn convert_to_table(
     ctrlc: Option<Arc<AtomicBool>>,
     config: &Config,
     head: Span,
+    expand: bool,
 ) -> Result<Option<nu_table::Table>, ShellError> {
     let mut headers = get_columns(input);
     let mut input = input.iter().peekable();
     let color_hm = get_color_config(config);
     let float_precision = config.float_precision as usize;
+    let show_index = config.table_index;
+    let max_cell_width = max_cell_width(config, expand);
+    let depth = collapse_depth(config, expand);
 
     if input.peek().is_some() {
-        if !headers.is_empty() {
+        if !headers.is_empty() && show_index {
             headers.insert(0, "#".into());
         }
 
@@ -275,20 +457,24 @@ fn convert_to_table(
                     return Ok(None);
                 }
             }
-            if let Value::Error { error } = item {
+            // Shown as a cell, an error would lose its spans
+            if let Some(error) = item.find_error() {
                 return Err(error.clone());
             }
             // String1 = datatype, String2 = value as string
-            let mut row: Vec<(String, String)> =
-                vec![("string".to_string(), (row_num + row_offset).to_string())];
+            let mut row: Vec<(String, String)> = if show_index {
+                vec![("string".to_string(), (row_num + row_offset).to_string())]
+            } else {
+                vec![]
+            };
 
             if headers.is_empty() {
                 row.push((
                     item.get_type().to_string(),
-                    item.into_abbreviated_string(config),
+                    item.into_nested_string(config, depth),
                 ))
             } else {
-                for header in headers.iter().skip(1) {
+                for header in headers.iter().skip(if show_index { 1 } else { 0 }) {
                     let result = match item {
                         Value::Record { .. } => {
                             item.clone().follow_cell_path(&[PathMember::String {
@@ -302,7 +488,7 @@ fn convert_to_table(
                     match result {
                         Ok(value) => row.push((
                             (&value.get_type()).to_string(),
-                            value.into_abbreviated_string(config),
+                            value.into_nested_string(config, depth),
                         )),
                         Err(_) => row.push(("empty".to_string(), "❎".into())),
                     }
@@ -329,7 +515,7 @@ fn convert_to_table(
                     x.into_iter()
                         .enumerate()
                         .map(|(col, y)| {
-                            if col == 0 {
+                            if col == 0 && show_index {
                                 StyledString {
                                     contents: y.1,
                                     style: TextStyle {
@@ -345,12 +531,12 @@ fn convert_to_table(
                                         Err(e) => e.to_string(),
                                     };
                                 StyledString {
-                                    contents: precise_number,
+                                    contents: truncate_cell(precise_number, max_cell_width),
                                     style: style_primitive(&y.0, &color_hm),
                                 }
                             } else {
                                 StyledString {
-                                    contents: y.1,
+                                    contents: truncate_cell(y.1, max_cell_width),
                                     style: style_primitive(&y.0, &color_hm),
                                 }
                             }
@@ -385,12 +571,36 @@ struct PagingTableCreator {
     ctrlc: Option<Arc<AtomicBool>>,
     config: Config,
     row_offset: usize,
+    max_rows: Option<usize>,
+    rows_shown: usize,
+    done: bool,
+    expand: bool,
 }
 
 impl Iterator for PagingTableCreator {
     type Item = Result<String, ShellError>;
 
     fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        let page_size = match self.max_rows {
+            Some(max_rows) if self.rows_shown >= max_rows => {
+                // The rest isn't shown, but it's still run and counted for the note
+                self.done = true;
+                let hidden = self.stream.by_ref().count();
+
+                return if hidden > 0 {
+                    Some(Ok(more_rows_note(hidden)))
+                } else {
+                    None
+                };
+            }
+            Some(max_rows) => STREAM_PAGE_SIZE.min(max_rows - self.rows_shown),
+            None => STREAM_PAGE_SIZE,
+        };
+
         let mut batch = vec![];
 
         let start_time = Instant::now();
@@ -411,7 +621,7 @@ impl Iterator for PagingTableCreator {
                 }
             }
 
-            if idx == STREAM_PAGE_SIZE {
+            if idx == page_size {
                 break;
             }
 
@@ -436,8 +646,10 @@ impl Iterator for PagingTableCreator {
             self.ctrlc.clone(),
             &self.config,
             self.head,
+            self.expand,
         );
         self.row_offset += idx;
+        self.rows_shown += idx;
 
         match table {
             Ok(Some(table)) => {
@@ -461,7 +673,214 @@ fn load_theme_from_config(config: &Config) -> Theme {
         "rounded" => nu_table::Theme::rounded(),
         "reinforced" => nu_table::Theme::reinforced(),
         "heavy" => nu_table::Theme::heavy(),
+        "markdown" => nu_table::Theme::markdown(),
         "none" => nu_table::Theme::none(),
         _ => nu_table::Theme::rounded(),
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    // The test data only has ASCII and CJK ideographs, which take two columns
+    fn display_width(line: &str) -> usize {
+        line.chars()
+            .map(|c| {
+                if ('\u{4e00}'..='\u{9fff}').contains(&c) {
+                    2
+                } else {
+                    1
+                }
+            })
+            .sum()
+    }
+
+    fn render(config: &Config, expand: bool) -> String {
+        let span = Span::test_data();
+        let rows = vec![
+            Value::Record {
+                cols: vec!["name".into(), "note".into()],
+                vals: vec![
+                    Value::string("nu", span),
+                    Value::string("a rather long note that goes on", span),
+                ],
+                span,
+            },
+            Value::Record {
+                cols: vec!["name".into(), "note".into()],
+                vals: vec![Value::string("数据", span), Value::string("表格", span)],
+                span,
+            },
+        ];
+
+        let table = convert_to_table(0, &rows, None, config, span, expand)
+            .expect("rows can be converted")
+            .expect("rows give a table");
+        let color_hm = get_color_config(config);
+
+        nu_table::draw_table(&table, 80, &color_hm, config)
+    }
+
+    fn no_color_config() -> Config {
+        Config {
+            use_ansi_coloring: false,
+            table_mode: "basic".into(),
+            ..Config::default()
+        }
+    }
+
+    #[test]
+    fn index_column_can_be_hidden() {
+        let mut config = no_color_config();
+        assert!(render(&config, false).contains(" # "));
+
+        config.table_index = false;
+        let output = render(&config, false);
+        assert!(!output.contains(" # "));
+        assert!(output.lines().any(|line| line.starts_with("| nu ")));
+    }
+
+    #[test]
+    fn wide_characters_keep_columns_aligned() {
+        let output = render(&no_color_config(), false);
+        let widths: Vec<usize> = output
+            .lines()
+            .map(display_width)
+            .filter(|width| *width > 0)
+            .collect();
+
+        assert!(widths.windows(2).all(|pair| pair[0] == pair[1]));
+    }
+
+    #[test]
+    fn long_cells_are_truncated_unless_expanded() {
+        let mut config = no_color_config();
+        config.table_max_cell_width = 10;
+
+        let output = render(&config, false);
+        assert!(output.contains("a rather …"));
+        assert!(!output.contains("goes on"));
+
+        let output = render(&config, true);
+        assert!(output.contains("a rather long note that goes on"));
+    }
+
+    #[test]
+    fn markdown_mode() {
+        let mut config = no_color_config();
+        config.table_mode = "markdown".into();
+        config.table_index = false;
+
+        let output = render(&config, false);
+        let lines: Vec<&str> = output.lines().collect();
+
+        assert!(lines[0].starts_with("| name"));
+        assert!(lines[1].starts_with("|-"));
+        assert!(lines[1].chars().all(|c| c == '|' || c == '-'));
+        assert!(lines[2].starts_with("| nu"));
+    }
+
+    #[test]
+    fn truncation_counts_display_width() {
+        assert_eq!(nu_table::truncate_to_width("short", 10), "short");
+        assert_eq!(nu_table::truncate_to_width("abcdefgh", 5), "abcd…");
+        assert_eq!(nu_table::truncate_to_width("数据表格", 5), "数据…");
+    }
+
+    #[test]
+    fn max_rows_from_config() {
+        let mut config = Config::default();
+        assert_eq!(max_rows(&config), None);
+
+        config.table_max_rows = 20;
+        assert_eq!(max_rows(&config), Some(20));
+
+        config.table_max_rows = -1;
+        assert_eq!(max_rows(&config), None);
+    }
+
+    fn nested_row() -> Value {
+        let span = Span::test_data();
+        Value::Record {
+            cols: vec!["name".into(), "meta".into()],
+            vals: vec![
+                Value::string("nu", span),
+                Value::Record {
+                    cols: vec!["tags".into(), "owner".into()],
+                    vals: vec![
+                        Value::List {
+                            vals: vec![Value::string("shell", span), Value::string("rust", span)],
+                            span,
+                        },
+                        Value::Record {
+                            cols: vec!["login".into()],
+                            vals: vec![Value::string("jt", span)],
+                            span,
+                        },
+                    ],
+                    span,
+                },
+            ],
+            span,
+        }
+    }
+
+    fn render_nested(config: &Config, expand: bool) -> String {
+        let span = Span::test_data();
+        let table = convert_to_table(0, &[nested_row()], None, config, span, expand)
+            .expect("rows can be converted")
+            .expect("rows give a table");
+
+        nu_table::draw_table(&table, 200, &get_color_config(config), config)
+    }
+
+    #[test]
+    fn nested_values_collapse_by_depth() {
+        let mut config = no_color_config();
+        assert!(render_nested(&config, false).contains("| {record 2 fields} |"));
+
+        config.table_collapse_depth = 1;
+        assert!(render_nested(&config, false)
+            .contains("| {tags: [list 2 items], owner: {record 1 field}} |"));
+
+        config.table_collapse_depth = 2;
+        assert!(
+            render_nested(&config, false).contains("| {tags: [shell, rust], owner: {login: jt}} |")
+        );
+    }
+
+    #[test]
+    fn expand_shows_nested_values_inline() {
+        let config = no_color_config();
+        assert!(render_nested(&config, true).contains("{tags: [shell, rust], owner: {login: jt}}"));
+    }
+
+    #[test]
+    fn deep_nesting_stops_at_the_depth() {
+        let span = Span::test_data();
+        let mut value = Value::test_int(0);
+        for _ in 0..1000 {
+            value = Value::List {
+                vals: vec![value],
+                span,
+            };
+        }
+
+        let config = Config::default();
+        assert_eq!(value.into_nested_string(&config, 2), "[[[list 1 item]]]");
+        assert_eq!(collapse_depth(&config, true), EXPANDED_DEPTH);
+    }
+
+    #[test]
+    fn hex_dump_is_truncated() {
+        let short = hex_dump(&[0xde, 0xad, 0xbe, 0xef]);
+        assert!(!short.contains("more bytes"));
+
+        let long = hex_dump(&vec![0xff; BINARY_DISPLAY_LIMIT + 10]);
+        assert!(long.ends_with(&format!(
+            "... 10 more bytes ({} total)",
+            BINARY_DISPLAY_LIMIT + 10
+        )));
+    }
+}
@@ -1,5 +1,5 @@
 use itertools::Itertools;
-use nu_protocol::{engine::EngineState, Example, Signature, Span, Value};
+use nu_protocol::{engine::EngineState, Example, Signature, Span, Type, Value};
 use std::collections::HashMap;
 
 const COMMANDS_DOCS_DIR: &str = "docs/commands";
@@ -168,6 +168,13 @@ pub fn get_documentation(
 
     long_desc.push_str(&format!("Usage:\n  > {}\n", sig.call_signature()));
 
+    if sig.input_type != Type::Unknown || sig.output_type != Type::Unknown {
+        long_desc.push_str(&format!(
+            "\nType:\n  {} -> {}\n",
+            sig.input_type, sig.output_type
+        ));
+    }
+
     if !subcommands.is_empty() {
         long_desc.push_str("\nSubcommands:\n");
         subcommands.sort();
@@ -181,17 +188,23 @@ pub fn get_documentation(
     {
         long_desc.push_str("\nParameters:\n");
         for positional in &sig.required_positional {
-            long_desc.push_str(&format!("  {}: {}\n", positional.name, positional.desc));
+            long_desc.push_str(&format!(
+                "  {} <{}>: {}\n",
+                positional.name, positional.shape, positional.desc
+            ));
         }
         for positional in &sig.optional_positional {
             long_desc.push_str(&format!(
-                "  (optional) {}: {}\n",
-                positional.name, positional.desc
+                "  (optional) {} <{}>: {}\n",
+                positional.name, positional.shape, positional.desc
             ));
         }
 
         if let Some(rest_positional) = &sig.rest_positional {
-            long_desc.push_str(&format!("  ...args: {}\n", rest_positional.desc));
+            long_desc.push_str(&format!(
+                "  ...{} <{}>: {}\n",
+                rest_positional.name, rest_positional.shape, rest_positional.desc
+            ));
         }
     }
     if !sig.named.is_empty() {
@@ -225,71 +238,19 @@ fn get_flags_section(signature: &Signature) -> String {
     let mut long_desc = String::new();
     long_desc.push_str("\nFlags:\n");
     for flag in &signature.named {
-        let msg = if let Some(arg) = &flag.arg {
-            if let Some(short) = flag.short {
-                if flag.required {
-                    format!(
-                        "  -{}{} (required parameter) {:?} {}\n",
-                        short,
-                        if !flag.long.is_empty() {
-                            format!(", --{}", flag.long)
-                        } else {
-                            "".into()
-                        },
-                        arg,
-                        flag.desc
-                    )
-                } else {
-                    format!(
-                        "  -{}{} {:?} {}\n",
-                        short,
-                        if !flag.long.is_empty() {
-                            format!(", --{}", flag.long)
-                        } else {
-                            "".into()
-                        },
-                        arg,
-                        flag.desc
-                    )
-                }
-            } else if flag.required {
-                format!(
-                    "  --{} (required parameter) {:?} {}\n",
-                    flag.long, arg, flag.desc
-                )
-            } else {
-                format!("  --{} {:?} {}\n", flag.long, arg, flag.desc)
-            }
-        } else if let Some(short) = flag.short {
-            if flag.required {
-                format!(
-                    "  -{}{} (required parameter) {}\n",
-                    short,
-                    if !flag.long.is_empty() {
-                        format!(", --{}", flag.long)
-                    } else {
-                        "".into()
-                    },
-                    flag.desc
-                )
-            } else {
-                format!(
-                    "  -{}{} {}\n",
-                    short,
-                    if !flag.long.is_empty() {
-                        format!(", --{}", flag.long)
-                    } else {
-                        "".into()
-                    },
-                    flag.desc
-                )
-            }
-        } else if flag.required {
-            format!("  --{} (required parameter) {}\n", flag.long, flag.desc)
-        } else {
-            format!("  --{} {}\n", flag.long, flag.desc)
+        let mut names = match flag.short {
+            Some(short) if flag.long.is_empty() => format!("-{}", short),
+            Some(short) => format!("-{}, --{}", short, flag.long),
+            None => format!("--{}", flag.long),
         };
-        long_desc.push_str(&msg);
+        if let Some(arg) = &flag.arg {
+            names.push_str(&format!(" <{}>", arg));
+        }
+        if flag.required {
+            names.push_str(" (required parameter)");
+        }
+
+        long_desc.push_str(&format!("  {} {}\n", names, flag.desc));
     }
     long_desc
 }
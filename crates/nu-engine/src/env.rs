@@ -11,6 +11,15 @@ const ENV_SEP: &str = ";";
 #[cfg(not(windows))]
 const ENV_SEP: &str = ":";
 
+/// PATH is kept as a list inside the engine. Windows spells it `Path` and doesn't care about case.
+fn is_path_var(name: &str) -> bool {
+    if cfg!(windows) {
+        name.eq_ignore_ascii_case("path")
+    } else {
+        name == "PATH"
+    }
+}
+
 /// Translate environment variables from Strings to Values. Requires config to be already set up in
 /// case the user defined custom env conversions in config.nu.
 ///
@@ -64,10 +73,10 @@ pub fn convert_env_values(
                     });
                 }
             } else {
-                new_scope.insert(name.to_string(), val.clone());
+                new_scope.insert(name.to_string(), default_from_string(name, val));
             }
         } else {
-            new_scope.insert(name.to_string(), val.clone());
+            new_scope.insert(name.to_string(), default_from_string(name, val));
         }
     }
 
@@ -78,6 +87,37 @@ pub fn convert_env_values(
     error
 }
 
+/// Built-in conversion used when the config doesn't define `from_string` for a variable
+fn default_from_string(name: &str, val: &Value) -> Value {
+    match val {
+        Value::String { val, span } if is_path_var(name) => Value::List {
+            vals: val
+                .split(ENV_SEP)
+                .map(|path| Value::String {
+                    val: path.to_string(),
+                    span: *span,
+                })
+                .collect(),
+            span: *span,
+        },
+        _ => val.clone(),
+    }
+}
+
+/// Built-in conversion used when the config doesn't define `to_string` for a variable
+fn default_to_string(name: &str, value: Value, config: &Config) -> String {
+    match value {
+        Value::List { vals, .. } if is_path_var(name) => vals
+            .into_iter()
+            .map(|path| path.into_string("", config))
+            .collect::<Vec<_>>()
+            .join(ENV_SEP),
+        // Do not fail here. Must succeed, otherwise setting a non-string env var would constantly
+        // throw errors when running externals etc.
+        value => value.into_string(ENV_SEP, config),
+    }
+}
+
 /// Translate one environment variable from Value to String
 pub fn env_to_string(
     env_name: &str,
@@ -108,14 +148,10 @@ pub fn env_to_string(
                 Err(ShellError::MissingParameter("block input".into(), to_span))
             }
         } else {
-            // Do not fail here. Must succeed, otherwise setting a non-string env var would constantly
-            // throw errors when running externals etc.
-            Ok(value.into_string(ENV_SEP, config))
+            Ok(default_to_string(env_name, value, config))
         }
     } else {
-        // Do not fail here. Must succeed, otherwise setting a non-string env var would constantly
-        // throw errors when running externals etc.
-        Ok(value.into_string(ENV_SEP, config))
+        Ok(default_to_string(env_name, value, config))
     }
 }
 
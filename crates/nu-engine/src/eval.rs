@@ -24,6 +24,27 @@ pub fn eval_operator(op: &Expression) -> Result<Operator, ShellError> {
     }
 }
 
+/// Point an operator's error at its operands as they are written, rather than at wherever their
+/// values came from, such as a variable's definition or the command that made a table
+fn with_operand_spans(error: ShellError, (lhs_span, rhs_span): (Span, Span)) -> ShellError {
+    match error {
+        ShellError::OperatorMismatch {
+            op_span,
+            lhs_ty,
+            rhs_ty,
+            ..
+        } => ShellError::OperatorMismatch {
+            op_span,
+            lhs_ty,
+            lhs_span,
+            rhs_ty,
+            rhs_span,
+        },
+        ShellError::DivisionByZero(op_span, _) => ShellError::DivisionByZero(op_span, rhs_span),
+        error => error,
+    }
+}
+
 fn eval_call(
     engine_state: &EngineState,
     caller_stack: &mut Stack,
@@ -186,6 +207,10 @@ pub fn eval_expression(
             val: *i,
             span: expr.span,
         }),
+        Expr::Binary(b) => Ok(Value::Binary {
+            val: b.clone(),
+            span: expr.span,
+        }),
         Expr::Float(f) => Ok(Value::Float {
             val: *f,
             span: expr.span,
@@ -257,11 +282,12 @@ pub fn eval_expression(
         Expr::Operator(_) => Ok(Value::Nothing { span: expr.span }),
         Expr::BinaryOp(lhs, op, rhs) => {
             let op_span = op.span;
+            let operand_spans = (lhs.span, rhs.span);
             let lhs = eval_expression(engine_state, stack, lhs)?;
             let op = eval_operator(op)?;
             let rhs = eval_expression(engine_state, stack, rhs)?;
 
-            match op {
+            let result = match op {
                 Operator::Plus => lhs.add(op_span, &rhs),
                 Operator::Minus => lhs.sub(op_span, &rhs),
                 Operator::Multiply => lhs.mul(op_span, &rhs),
@@ -274,13 +300,20 @@ pub fn eval_expression(
                 Operator::NotEqual => lhs.ne(op_span, &rhs),
                 Operator::In => lhs.r#in(op_span, &rhs),
                 Operator::NotIn => lhs.not_in(op_span, &rhs),
-                Operator::Contains => lhs.contains(op_span, &rhs),
-                Operator::NotContains => lhs.not_contains(op_span, &rhs),
+                Operator::RegexMatch => lhs.regex_match(op_span, &rhs, false),
+                Operator::NotRegexMatch => lhs.regex_match(op_span, &rhs, true),
                 Operator::Modulo => lhs.modulo(op_span, &rhs),
                 Operator::And => lhs.and(op_span, &rhs),
                 Operator::Or => lhs.or(op_span, &rhs),
                 Operator::Pow => lhs.pow(op_span, &rhs),
-            }
+                Operator::BitOr => lhs.bit_or(op_span, &rhs),
+                Operator::BitXor => lhs.bit_xor(op_span, &rhs),
+                Operator::BitAnd => lhs.bit_and(op_span, &rhs),
+                Operator::ShiftLeft => lhs.bit_shl(op_span, &rhs),
+                Operator::ShiftRight => lhs.bit_shr(op_span, &rhs),
+            };
+
+            result.map_err(|err| with_operand_spans(err, operand_spans))
         }
         Expr::Subexpression(block_id) => {
             let block = engine_state.get_block(*block_id);
@@ -410,6 +443,11 @@ pub fn eval_block(
     for (stmt_idx, stmt) in block.stmts.iter().enumerate() {
         if let Statement::Pipeline(pipeline) = stmt {
             for (i, elem) in pipeline.expressions.iter().enumerate() {
+                // `break` or `continue` skips the rest of the loop body
+                if stack.loop_control_raised() {
+                    return Ok(PipelineData::new(elem.span));
+                }
+
                 match elem {
                     Expression {
                         expr: Expr::Call(call),
@@ -491,7 +529,10 @@ pub fn eval_block(
                 }
             }
 
-            input = PipelineData::new(Span { start: 0, end: 0 })
+            input = PipelineData::new(Span { start: 0, end: 0 });
+
+            // The statement's output is drained, so an external it ran has finished
+            stack.update_last_exit_code();
         }
     }
 
@@ -507,6 +548,10 @@ pub fn eval_subexpression(
     for stmt in block.stmts.iter() {
         if let Statement::Pipeline(pipeline) = stmt {
             for elem in pipeline.expressions.iter() {
+                if stack.loop_control_raised() {
+                    return Ok(PipelineData::new(elem.span));
+                }
+
                 match elem {
                     Expression {
                         expr: Expr::Call(call),
@@ -547,6 +592,7 @@ pub fn eval_variable(
             config_path.push("nushell");
 
             let mut history_path = config_path.clone();
+            let mut env_path = config_path.clone();
             let mut keybinding_path = config_path.clone();
 
             history_path.push("history.txt");
@@ -557,7 +603,11 @@ pub fn eval_variable(
                 span,
             });
 
-            config_path.push("config.nu");
+            // The config given with --config, or where the default one lives
+            match &engine_state.config_path {
+                Some(path) => config_path = path.clone(),
+                None => config_path.push("config.nu"),
+            }
 
             output_cols.push("config-path".into());
             output_vals.push(Value::String {
@@ -565,6 +615,18 @@ pub fn eval_variable(
                 span,
             });
 
+            // The env file given with --env-config, or where the default one lives
+            match &engine_state.env_path {
+                Some(path) => env_path = path.clone(),
+                None => env_path.push("env.nu"),
+            }
+
+            output_cols.push("env-path".into());
+            output_vals.push(Value::String {
+                val: env_path.to_string_lossy().to_string(),
+                span,
+            });
+
             // TODO: keybindings don't exist yet but lets add a file
             // path for them to be stored in. It doesn't have to be yml.
             keybinding_path.push("keybindings.yml");
@@ -611,6 +673,34 @@ pub fn eval_variable(
             })
         }
 
+        output_cols.push("loglevel".into());
+        output_vals.push(Value::String {
+            val: stack.get_config().unwrap_or_default().log_level,
+            span,
+        });
+
+        output_cols.push("pid".into());
+        output_vals.push(Value::Int {
+            val: std::process::id() as i64,
+            span,
+        });
+
+        if let Ok(current_exe) = std::env::current_exe() {
+            output_cols.push("current-exe".into());
+            output_vals.push(Value::String {
+                val: current_exe.to_string_lossy().to_string(),
+                span,
+            })
+        }
+
+        if engine_state.startup_time >= 0 {
+            output_cols.push("startup-time".into());
+            output_vals.push(Value::Duration {
+                val: engine_state.startup_time,
+                span,
+            })
+        }
+
         Ok(Value::Record {
             cols: output_cols,
             vals: output_vals,
@@ -623,8 +713,8 @@ pub fn eval_variable(
         let mut var_names = vec![];
         let mut var_types = vec![];
         let mut commands = vec![];
-        let mut aliases = vec![];
-        let mut overlays = vec![];
+        let mut aliases = HashMap::new();
+        let mut overlays = HashMap::new();
 
         for frame in &engine_state.scope {
             for var in &frame.vars {
@@ -635,6 +725,11 @@ pub fn eval_variable(
             }
 
             for command in &frame.decls {
+                // Skip commands hidden or shadowed by a later definition of the same name
+                if engine_state.find_decl(command.0) != Some(*command.1) {
+                    continue;
+                }
+
                 let mut cols = vec![];
                 let mut vals = vec![];
 
@@ -828,18 +923,13 @@ pub fn eval_variable(
                 commands.push(Value::Record { cols, vals, span })
             }
 
-            for alias in &frame.aliases {
-                aliases.push(Value::String {
-                    val: String::from_utf8_lossy(alias.0).to_string(),
-                    span,
-                });
+            // Later frames overwrite what earlier ones defined under the same name
+            for (name, expansion) in &frame.aliases {
+                aliases.insert(name, expansion);
             }
 
-            for overlay in &frame.overlays {
-                overlays.push(Value::String {
-                    val: String::from_utf8_lossy(overlay.0).to_string(),
-                    span,
-                });
+            for (name, overlay_id) in &frame.overlays {
+                overlays.insert(name, *overlay_id);
             }
         }
 
@@ -873,17 +963,70 @@ pub fn eval_variable(
             span,
         });
 
-        aliases.sort_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+        let mut aliases = aliases.into_iter().collect::<Vec<_>>();
+        aliases.sort_by(|a, b| a.0.cmp(b.0));
         output_cols.push("aliases".to_string());
         output_vals.push(Value::List {
-            vals: aliases,
+            vals: aliases
+                .into_iter()
+                .map(|(name, expansion)| Value::Record {
+                    cols: vec!["name".into(), "expansion".into()],
+                    vals: vec![
+                        Value::string(String::from_utf8_lossy(name), span),
+                        Value::string(
+                            expansion
+                                .iter()
+                                .map(|part| {
+                                    String::from_utf8_lossy(engine_state.get_span_contents(part))
+                                })
+                                .collect::<Vec<_>>()
+                                .join(" "),
+                            span,
+                        ),
+                    ],
+                    span,
+                })
+                .collect(),
             span,
         });
 
-        overlays.sort_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+        let mut overlays = overlays.into_iter().collect::<Vec<_>>();
+        overlays.sort_by(|a, b| a.0.cmp(b.0));
         output_cols.push("overlays".to_string());
         output_vals.push(Value::List {
-            vals: overlays,
+            vals: overlays
+                .iter()
+                .map(|(name, _)| Value::string(String::from_utf8_lossy(name), span))
+                .collect(),
+            span,
+        });
+
+        // Modules are the overlays again, along with what each one exports
+        output_cols.push("modules".to_string());
+        output_vals.push(Value::List {
+            vals: overlays
+                .into_iter()
+                .map(|(name, overlay_id)| {
+                    let overlay = engine_state.get_overlay(overlay_id);
+                    let names = |names: Vec<&Vec<u8>>| Value::List {
+                        vals: names
+                            .into_iter()
+                            .map(|name| Value::string(String::from_utf8_lossy(name), span))
+                            .collect(),
+                        span,
+                    };
+
+                    Value::Record {
+                        cols: vec!["name".into(), "commands".into(), "env_vars".into()],
+                        vals: vec![
+                            Value::string(String::from_utf8_lossy(name), span),
+                            names(overlay.decls.keys().collect()),
+                            names(overlay.env_vars.keys().collect()),
+                        ],
+                        span,
+                    }
+                })
+                .collect(),
             span,
         });
 
@@ -0,0 +1,158 @@
+use nu_protocol::engine::{EngineState, Stack};
+use nu_protocol::{BlockId, PipelineData, Span, Value, ValueStream, CONFIG_VARIABLE_ID};
+
+use crate::eval_block;
+
+/// Hand back the output of `command` unchanged, running the hook `$config.hooks` has for it once
+/// the output has been read. The hook is called with the command name and a summary record
+/// holding the number of rows. A stream is counted as it passes through, so the hook runs when
+/// the stream ends, or when whatever reads it stops early.
+///
+/// Hooks only observe: a hook that fails is reported on stderr and the command carries on.
+pub fn observe_with_hook(
+    engine_state: &EngineState,
+    stack: &Stack,
+    command: &str,
+    output: PipelineData,
+    span: Span,
+) -> PipelineData {
+    let block_id = match stack.get_config() {
+        Ok(config) => match config.hooks.get(command) {
+            Some((block_id, _)) => *block_id,
+            None => return output,
+        },
+        Err(_) => return output,
+    };
+
+    let hook = Hook {
+        engine_state: engine_state.clone(),
+        stack: stack.clone(),
+        command: command.to_string(),
+        block_id,
+        span,
+    };
+
+    match output {
+        PipelineData::ListStream(stream, metadata) => PipelineData::ListStream(
+            ValueStream {
+                stream: Box::new(ObservedStream {
+                    stream: stream.stream,
+                    hook: Some(hook),
+                    count: 0,
+                }),
+                ctrlc: stream.ctrlc,
+            },
+            metadata,
+        ),
+        PipelineData::Value(value, metadata) => {
+            let count = match &value {
+                Value::List { vals, .. } => vals.len(),
+                Value::Nothing { .. } => 0,
+                _ => 1,
+            };
+            hook.run(Some(count));
+
+            PipelineData::Value(value, metadata)
+        }
+        // Text and bytes aren't rows, so there is nothing to count
+        output => {
+            hook.run(None);
+
+            output
+        }
+    }
+}
+
+struct Hook {
+    engine_state: EngineState,
+    stack: Stack,
+    command: String,
+    block_id: BlockId,
+    span: Span,
+}
+
+impl Hook {
+    fn run(self, count: Option<usize>) {
+        let span = self.span;
+        let block = self.engine_state.get_block(self.block_id);
+        let mut stack = self.stack.gather_captures(&block.captures);
+
+        // A hook that runs the command it's watching would otherwise call itself forever
+        if let Ok(Value::Record { cols, vals, span }) = stack.get_var(CONFIG_VARIABLE_ID) {
+            let (cols, vals) = cols
+                .into_iter()
+                .zip(vals)
+                .filter(|(col, _)| col != "hooks")
+                .unzip();
+            stack.add_var(CONFIG_VARIABLE_ID, Value::Record { cols, vals, span });
+        }
+
+        let summary = Value::Record {
+            cols: vec!["count".into()],
+            vals: vec![match count {
+                Some(count) => Value::Int {
+                    val: count as i64,
+                    span,
+                },
+                None => Value::Nothing { span },
+            }],
+            span,
+        };
+        let args = [Value::string(self.command.clone(), span), summary];
+
+        for (param, arg) in block.signature.required_positional.iter().zip(args) {
+            if let Some(var_id) = param.var_id {
+                stack.add_var(var_id, arg);
+            }
+        }
+
+        let result = eval_block(
+            &self.engine_state,
+            &mut stack,
+            block,
+            PipelineData::new(span),
+        )
+        .map(|output| output.into_value(span));
+
+        match result {
+            Ok(Value::Error { error }) | Err(error) => {
+                eprintln!("$config.hooks.{} failed: {}", self.command, error)
+            }
+            Ok(_) => {}
+        }
+    }
+}
+
+/// Passes a stream through, counting its rows for the hook
+struct ObservedStream {
+    stream: Box<dyn Iterator<Item = Value> + Send + 'static>,
+    hook: Option<Hook>,
+    count: usize,
+}
+
+impl Iterator for ObservedStream {
+    type Item = Value;
+
+    fn next(&mut self) -> Option<Value> {
+        match self.stream.next() {
+            Some(value) => {
+                self.count += 1;
+                Some(value)
+            }
+            None => {
+                if let Some(hook) = self.hook.take() {
+                    hook.run(Some(self.count));
+                }
+                None
+            }
+        }
+    }
+}
+
+impl Drop for ObservedStream {
+    fn drop(&mut self) {
+        if let Some(hook) = self.hook.take() {
+            hook.run(Some(self.count));
+        }
+    }
+}
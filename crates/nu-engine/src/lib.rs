@@ -3,9 +3,11 @@ pub mod column;
 mod documentation;
 pub mod env;
 mod eval;
+mod hooks;
 
 pub use call_ext::CallExt;
 pub use column::get_columns;
 pub use documentation::{generate_docs, get_brief_help, get_documentation, get_full_help};
 pub use env::*;
-pub use eval::{eval_block, eval_expression, eval_operator};
+pub use eval::{eval_block, eval_expression, eval_operator, eval_subexpression};
+pub use hooks::observe_with_hook;
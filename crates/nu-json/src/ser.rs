@@ -797,7 +797,8 @@ where
     let mut start = 0;
 
     for (i, byte) in bytes.iter().enumerate() {
-        let escaped = match *byte {
+        let unicode;
+        let escaped: &[u8] = match *byte {
             b'"' => b"\\\"",
             b'\\' => b"\\\\",
             b'\x08' => b"\\b",
@@ -805,6 +806,11 @@ where
             b'\n' => b"\\n",
             b'\r' => b"\\r",
             b'\t' => b"\\t",
+            // Any other control character would make the output invalid JSON
+            b'\x00'..=b'\x1f' | b'\x7f' => {
+                unicode = format!("\\u{:04x}", byte);
+                unicode.as_bytes()
+            }
             _ => {
                 continue;
             }
@@ -828,52 +834,17 @@ where
 }
 
 /// Serializes and escapes a `&str` into a Hjson string.
+///
+/// Strings are always written quoted and escaped, never in the multiline format, so the output
+/// stays valid JSON and keeps every character, including newlines and carriage returns.
 #[inline]
 pub fn quote_str<W, F>(wr: &mut W, formatter: &mut F, value: &str) -> Result<()>
 where
     W: io::Write,
     F: Formatter,
 {
-    lazy_static! {
-        // NEEDS_ESCAPE tests if the string can be written without escapes
-        static ref NEEDS_ESCAPE: Regex = Regex::new("[\\\\\"\x00-\x1f\x7f-\u{9f}\u{00ad}\u{0600}-\u{0604}\u{070f}\u{17b4}\u{17b5}\u{200c}-\u{200f}\u{2028}-\u{202f}\u{2060}-\u{206f}\u{feff}\u{fff0}-\u{ffff}]").expect("Internal error: json parsing");
-        // NEEDS_QUOTES tests if the string can be written as a quoteless string (includes needsEscape but without \\ and \")
-        static ref NEEDS_QUOTES: Regex = Regex::new("^\\s|^\"|^'''|^#|^/\\*|^//|^\\{|^\\}|^\\[|^\\]|^:|^,|\\s$|[\x00-\x1f\x7f-\u{9f}\u{00ad}\u{0600}-\u{0604}\u{070f}\u{17b4}\u{17b5}\u{200c}-\u{200f}\u{2028}-\u{202f}\u{2060}-\u{206f}\u{feff}\u{fff0}-\u{ffff}]").expect("Internal error: json parsing");
-        // NEEDS_ESCAPEML tests if the string can be written as a multiline string (includes needsEscape but without \n, \r, \\ and \")
-        static ref NEEDS_ESCAPEML: Regex = Regex::new("'''|[\x00-\x09\x0b\x0c\x0e-\x1f\x7f-\u{9f}\u{00ad}\u{0600}-\u{0604}\u{070f}\u{17b4}\u{17b5}\u{200c}-\u{200f}\u{2028}-\u{202f}\u{2060}-\u{206f}\u{feff}\u{fff0}-\u{ffff}]").expect("Internal error: json parsing");
-        // starts with a keyword and optionally is followed by a comment
-        static ref STARTS_WITH_KEYWORD: Regex = Regex::new(r#"^(true|false|null)\s*((,|\]|\}|#|//|/\*).*)?$"#).expect("Internal error: json parsing");
-    }
-
-    if value.is_empty() {
-        formatter.start_value(wr)?;
-        return escape_bytes(wr, value.as_bytes());
-    }
-
-    // Check if we can insert this string without quotes
-    // see hjson syntax (must not parse as true, false, null or number)
-
-    //let mut pn = ParseNumber::new(value.bytes());
-    //let is_number = pn.parse(true).is_ok();
-
-    if true {
-        // is_number || NEEDS_QUOTES.is_match(value) || STARTS_WITH_KEYWORD.is_match(value) {
-        // First check if the string can be expressed in multiline format or
-        // we must replace the offending characters with safe escape sequences.
-
-        if NEEDS_ESCAPE.is_match(value) && !NEEDS_ESCAPEML.is_match(value)
-        /* && !isRootObject */
-        {
-            ml_str(wr, formatter, value)
-        } else {
-            formatter.start_value(wr)?;
-            escape_bytes(wr, value.as_bytes())
-        }
-    } else {
-        // without quotes
-        formatter.start_value(wr)?;
-        wr.write_all(value.as_bytes()).map_err(From::from)
-    }
+    formatter.start_value(wr)?;
+    escape_bytes(wr, value.as_bytes())
 }
 
 /// Serializes and escapes a `&str` into a multiline Hjson string.
@@ -53,6 +53,19 @@ pub enum ParseError {
         Type,
     ),
 
+    #[error("Pipeline stages don't fit together.")]
+    #[diagnostic(
+        code(nu::parser::pipeline_type_mismatch),
+        url(docsrs),
+        help("The command on the right takes {3} from the pipeline, not {1}. Pick out a column first, or check the order of the commands.")
+    )]
+    PipelineTypeMismatch(
+        #[label("this gives out {1}")] Span,
+        Type,
+        #[label("which this can't take")] Span,
+        Type,
+    ),
+
     #[error("Expected keyword.")]
     #[diagnostic(code(nu::parser::expected_keyword), url(docsrs))]
     ExpectedKeyword(String, #[label("expected {0}")] Span),
@@ -75,6 +88,14 @@ pub enum ParseError {
     )]
     StatementInPipeline(String, #[label("not allowed in pipeline")] Span),
 
+    #[error("Loop control outside of a loop.")]
+    #[diagnostic(
+        code(nu::parser::outside_of_loop),
+        url(docsrs),
+        help("'{0}' can only be used in the body of a 'for' or 'while' loop.")
+    )]
+    OutsideOfLoop(String, #[label("{0} outside of a loop")] Span),
+
     #[error("Incorrect value")]
     #[diagnostic(code(nu::parser::incorrect_value), url(docsrs), help("{2}"))]
     IncorrectValue(String, #[label("unexpected {0}")] Span, String),
@@ -131,9 +152,9 @@ pub enum ParseError {
     #[diagnostic(code(nu::parser::missing_flag_param), url(docsrs))]
     MissingFlagParam(String, #[label = "flag missing {0} argument"] Span),
 
-    #[error("Batches of short flags can't take arguments.")]
+    #[error("Only the last flag of a batch of short flags can take an argument.")]
     #[diagnostic(code(nu::parser::short_flag_arg_cant_take_arg), url(docsrs))]
-    ShortFlagBatchCantTakeArg(#[label = "short flag batches can't take args"] Span),
+    ShortFlagBatchCantTakeArg(#[label = "flag takes an argument but isn't last"] Span),
 
     #[error("Missing required positional argument.")]
     #[diagnostic(code(nu::parser::missing_positional), url(docsrs), help("Usage: {2}"))]
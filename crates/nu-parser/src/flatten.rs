@@ -11,6 +11,7 @@ pub enum FlatShape {
     Bool,
     Int,
     Float,
+    Binary,
     Range,
     InternalCall,
     External,
@@ -28,6 +29,7 @@ pub enum FlatShape {
     GlobPattern,
     Variable,
     Flag,
+    CellPath,
     Custom(String),
 }
 
@@ -39,6 +41,7 @@ impl Display for FlatShape {
             FlatShape::Bool => write!(f, "flatshape_bool"),
             FlatShape::Int => write!(f, "flatshape_int"),
             FlatShape::Float => write!(f, "flatshape_float"),
+            FlatShape::Binary => write!(f, "flatshape_binary"),
             FlatShape::Range => write!(f, "flatshape_range"),
             FlatShape::InternalCall => write!(f, "flatshape_internalcall"),
             FlatShape::External => write!(f, "flatshape_external"),
@@ -48,7 +51,7 @@ impl Display for FlatShape {
             FlatShape::Signature => write!(f, "flatshape_signature"),
             FlatShape::String => write!(f, "flatshape_string"),
             FlatShape::StringInterpolation => write!(f, "flatshape_string_interpolation"),
-            FlatShape::List => write!(f, "flatshape_string_interpolation"),
+            FlatShape::List => write!(f, "flatshape_list"),
             FlatShape::Table => write!(f, "flatshape_table"),
             FlatShape::Record => write!(f, "flatshape_record"),
             FlatShape::Block => write!(f, "flatshape_block"),
@@ -56,6 +59,7 @@ impl Display for FlatShape {
             FlatShape::GlobPattern => write!(f, "flatshape_globpattern"),
             FlatShape::Variable => write!(f, "flatshape_variable"),
             FlatShape::Flag => write!(f, "flatshape_flag"),
+            FlatShape::CellPath => write!(f, "flatshape_cellpath"),
             FlatShape::Custom(_) => write!(f, "flatshape_custom"),
         }
     }
@@ -140,12 +144,19 @@ pub fn flatten_expression(
         Expr::Call(call) => {
             let mut output = vec![(call.head, FlatShape::InternalCall)];
 
+            let signature = working_set.get_decl(call.decl_id).signature();
+
             let mut args = vec![];
             for positional in &call.positional {
                 args.extend(flatten_expression(working_set, positional));
             }
             for named in &call.named {
-                args.push((named.0.span, FlatShape::Flag));
+                // Flags the command doesn't know are kept by the parser, but shown as garbage
+                if signature.get_long_flag(&named.0.item).is_some() {
+                    args.push((named.0.span, FlatShape::Flag));
+                } else {
+                    args.push((named.0.span, FlatShape::Garbage));
+                }
                 if let Some(expr) = &named.1 {
                     args.extend(flatten_expression(working_set, expr));
                 }
@@ -202,6 +213,9 @@ pub fn flatten_expression(
         Expr::Float(_) => {
             vec![(expr.span, FlatShape::Float)]
         }
+        Expr::Binary(_) => {
+            vec![(expr.span, FlatShape::Binary)]
+        }
         Expr::ValueWithUnit(x, unit) => {
             let mut output = flatten_expression(working_set, x);
             output.push((unit.span, FlatShape::String));
@@ -212,8 +226,9 @@ pub fn flatten_expression(
             let mut output = vec![];
             for path_element in &cell_path.members {
                 match path_element {
-                    PathMember::String { span, .. } => output.push((*span, FlatShape::String)),
-                    PathMember::Int { span, .. } => output.push((*span, FlatShape::Int)),
+                    PathMember::String { span, .. } | PathMember::Int { span, .. } => {
+                        output.push((*span, FlatShape::CellPath))
+                    }
                 }
             }
             output
@@ -223,8 +238,9 @@ pub fn flatten_expression(
             output.extend(flatten_expression(working_set, &cell_path.head));
             for path_element in &cell_path.tail {
                 match path_element {
-                    PathMember::String { span, .. } => output.push((*span, FlatShape::String)),
-                    PathMember::Int { span, .. } => output.push((*span, FlatShape::Int)),
+                    PathMember::String { span, .. } | PathMember::Int { span, .. } => {
+                        output.push((*span, FlatShape::CellPath))
+                    }
                 }
             }
             output
@@ -0,0 +1,86 @@
+use nu_protocol::ast::{Call, Expr, Expression};
+use nu_protocol::engine::{Command, EngineState, Stack};
+use nu_protocol::{PipelineData, ShellError, Signature, Span, Type};
+
+/// An external command with a signature declared through `extern`
+///
+/// Calls to it are parsed and checked like calls to an internal command, then handed to
+/// `run_external` with the flags and arguments turned back into command line words.
+#[derive(Clone)]
+pub struct KnownExternal {
+    pub signature: Box<Signature>,
+}
+
+impl Command for KnownExternal {
+    fn name(&self) -> &str {
+        &self.signature.name
+    }
+
+    fn signature(&self) -> Signature {
+        *self.signature.clone()
+    }
+
+    fn usage(&self) -> &str {
+        &self.signature.usage
+    }
+
+    fn is_builtin(&self) -> bool {
+        false
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let decl_id = engine_state
+            .find_decl(b"run_external")
+            .ok_or(ShellError::ExternalNotSupported(call.head))?;
+
+        let command = engine_state.get_decl(decl_id);
+
+        let mut extern_call = Call::new();
+        extern_call.head = call.head;
+
+        // `extern "git push"` runs `git` with `push` as its first argument
+        for word in self.name().split(' ') {
+            extern_call
+                .positional
+                .push(string_expression(word.to_string(), call.head));
+        }
+
+        // Flags are passed in their long form, and everything stays in the order it was written
+        let mut args: Vec<(usize, Vec<Expression>)> = call
+            .named
+            .iter()
+            .map(|(flag, value)| {
+                let mut words = vec![string_expression(format!("--{}", flag.item), flag.span)];
+                words.extend(value.iter().cloned());
+                (flag.span.start, words)
+            })
+            .chain(
+                call.positional
+                    .iter()
+                    .map(|arg| (arg.span.start, vec![arg.clone()])),
+            )
+            .collect();
+        args.sort_by_key(|(start, _)| *start);
+
+        extern_call
+            .positional
+            .extend(args.into_iter().flat_map(|(_, words)| words));
+
+        command.run(engine_state, stack, &extern_call, input)
+    }
+}
+
+fn string_expression(val: String, span: Span) -> Expression {
+    Expression {
+        expr: Expr::String(val),
+        span,
+        ty: Type::String,
+        custom_completion: None,
+    }
+}
@@ -1,5 +1,6 @@
 mod errors;
 mod flatten;
+mod known_external;
 mod lex;
 mod lite_parse;
 mod parse_keywords;
@@ -10,12 +11,14 @@ pub use errors::ParseError;
 pub use flatten::{
     flatten_block, flatten_expression, flatten_pipeline, flatten_statement, FlatShape,
 };
+pub use known_external::KnownExternal;
 pub use lex::{lex, Token, TokenContents};
 pub use lite_parse::{lite_parse, LiteBlock};
 pub use parse_keywords::{
     parse_alias, parse_def, parse_def_predecl, parse_let, parse_module, parse_use,
 };
 pub use parser::{find_captures_in_expr, parse, trim_quotes, Import};
+pub use type_check::check_pipeline_types;
 
 #[cfg(feature = "plugin")]
 pub use parse_keywords::parse_register;
@@ -107,18 +107,21 @@ pub fn lite_parse(tokens: &[Token]) -> (LiteBlock, Option<ParseError>) {
                 last_token = TokenContents::Pipe;
             }
             TokenContents::Eol => {
-                if last_token != TokenContents::Pipe {
-                    if !curr_command.is_empty() {
-                        curr_pipeline.push(curr_command);
+                // A trailing `|` carries the pipeline on to the next line, past any blank lines
+                if last_token == TokenContents::Pipe {
+                    continue;
+                }
 
-                        curr_command = LiteCommand::new();
-                    }
+                if !curr_command.is_empty() {
+                    curr_pipeline.push(curr_command);
 
-                    if !curr_pipeline.is_empty() {
-                        block.push(curr_pipeline);
+                    curr_command = LiteCommand::new();
+                }
 
-                        curr_pipeline = LiteStatement::new();
-                    }
+                if !curr_pipeline.is_empty() {
+                    block.push(curr_pipeline);
+
+                    curr_pipeline = LiteStatement::new();
                 }
 
                 if last_token == TokenContents::Eol {
@@ -144,6 +147,11 @@ pub fn lite_parse(tokens: &[Token]) -> (LiteBlock, Option<ParseError>) {
                 last_token = TokenContents::Semicolon;
             }
             TokenContents::Comment => {
+                // `ls | # note` still continues on the next line
+                if last_token == TokenContents::Pipe {
+                    continue;
+                }
+
                 // Comment is beside something
                 if last_token != TokenContents::Eol {
                     curr_command.comments.push(token.span);
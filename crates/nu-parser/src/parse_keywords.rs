@@ -10,12 +10,13 @@ use nu_protocol::{
 use std::collections::HashSet;
 
 use crate::{
+    known_external::KnownExternal,
     lex, lite_parse,
     lite_parse::LiteCommand,
     parser::{
         check_call, check_name, find_captures_in_block, garbage, garbage_statement, parse,
-        parse_block_expression, parse_internal_call, parse_multispan_value, parse_signature,
-        parse_string, parse_var_with_opt_type, trim_quotes,
+        parse_block_expression, parse_call, parse_internal_call, parse_multispan_value,
+        parse_signature, parse_string, parse_var_with_opt_type, trim_quotes,
     },
     ParseError,
 };
@@ -53,6 +54,23 @@ pub fn parse_def_predecl(working_set: &mut StateWorkingSet, spans: &[Span]) -> O
                 return Some(ParseError::DuplicateCommandDef(spans[1]));
             }
         }
+    } else if name == b"extern" && spans.len() == 3 {
+        let (name_expr, ..) = parse_string(working_set, spans[1]);
+        let name = name_expr.as_string();
+
+        working_set.enter_scope();
+        let (sig, ..) = parse_signature(working_set, spans[2]);
+        let signature = sig.as_signature();
+        working_set.exit_scope();
+
+        if let (Some(name), Some(mut signature)) = (name, signature) {
+            signature.name = name;
+            let decl = KnownExternal { signature };
+
+            if working_set.add_predecl(Box::new(decl)).is_some() {
+                return Some(ParseError::DuplicateCommandDef(spans[1]));
+            }
+        }
     }
 
     None
@@ -88,10 +106,17 @@ pub fn parse_for(
             )
         }
         Some(decl_id) => {
+            let body = loop_body_span(working_set, spans);
+            working_set.loop_bodies.extend(body);
+
             working_set.enter_scope();
             let (call, mut err) = parse_internal_call(working_set, spans[0], &spans[1..], decl_id);
             working_set.exit_scope();
 
+            if body.is_some() {
+                working_set.loop_bodies.pop();
+            }
+
             let call_span = span(spans);
             let decl = working_set.get_decl(decl_id);
             let sig = decl.signature();
@@ -174,6 +199,70 @@ pub fn parse_for(
     )
 }
 
+/// `if` is parsed like any other call, then its condition is checked to be a bool wherever its
+/// type is already known. An `else if` branch goes through here again, so the whole chain is checked.
+pub fn parse_if(
+    working_set: &mut StateWorkingSet,
+    spans: &[Span],
+    expand_aliases: bool,
+) -> (Expression, Option<ParseError>) {
+    let (expr, err) = parse_call(working_set, spans, expand_aliases, spans[0]);
+
+    if let Expr::Call(call) = &expr.expr {
+        if let Some(cond) = call.positional.get(0) {
+            if !matches!(cond.ty, Type::Bool | Type::Unknown) {
+                let mismatch = ParseError::TypeMismatch(Type::Bool, cond.ty.clone(), cond.span);
+                return (expr, err.or(Some(mismatch)));
+            }
+        }
+    }
+
+    (expr, err)
+}
+
+pub fn parse_while(
+    working_set: &mut StateWorkingSet,
+    spans: &[Span],
+    expand_aliases: bool,
+) -> (Expression, Option<ParseError>) {
+    let body = loop_body_span(working_set, spans);
+    working_set.loop_bodies.extend(body);
+
+    let output = parse_call(working_set, spans, expand_aliases, spans[0]);
+
+    if body.is_some() {
+        working_set.loop_bodies.pop();
+    }
+
+    output
+}
+
+/// The body of a `for` or `while` call is its last block. Flags can come after it, but they
+/// aren't blocks.
+fn loop_body_span(working_set: &StateWorkingSet, spans: &[Span]) -> Option<Span> {
+    spans
+        .iter()
+        .rev()
+        .find(|span| working_set.get_span_contents(**span).starts_with(b"{"))
+        .copied()
+}
+
+/// `break` and `continue` are parsed like any other call, but only inside the body of a loop
+pub fn parse_loop_control(
+    working_set: &mut StateWorkingSet,
+    spans: &[Span],
+    expand_aliases: bool,
+) -> (Expression, Option<ParseError>) {
+    let (expr, err) = parse_call(working_set, spans, expand_aliases, spans[0]);
+
+    if working_set.loop_depth == 0 {
+        let name = String::from_utf8_lossy(working_set.get_span_contents(spans[0])).to_string();
+        (expr, Some(ParseError::OutsideOfLoop(name, spans[0])))
+    } else {
+        (expr, err)
+    }
+}
+
 fn build_usage(working_set: &StateWorkingSet, spans: &[Span]) -> String {
     let mut usage = String::new();
 
@@ -259,10 +348,15 @@ pub fn parse_def(
             )
         }
         Some(decl_id) => {
+            // A loop around the definition doesn't reach into the body of the command
+            let loop_depth = std::mem::take(&mut working_set.loop_depth);
+
             working_set.enter_scope();
             let (call, mut err) = parse_internal_call(working_set, spans[0], &spans[1..], decl_id);
             working_set.exit_scope();
 
+            working_set.loop_depth = loop_depth;
+
             let call_span = span(spans);
             let decl = working_set.get_decl(decl_id);
             let sig = decl.signature();
@@ -367,6 +461,90 @@ pub fn parse_def(
     )
 }
 
+pub fn parse_extern(
+    working_set: &mut StateWorkingSet,
+    lite_command: &LiteCommand,
+) -> (Statement, Option<ParseError>) {
+    let spans = &lite_command.parts[..];
+
+    let usage = build_usage(working_set, &lite_command.comments);
+
+    let (call, call_span) = match working_set.find_decl(b"extern") {
+        None => {
+            return (
+                garbage_statement(spans),
+                Some(ParseError::UnknownState(
+                    "internal error: extern declaration not found".into(),
+                    span(spans),
+                )),
+            )
+        }
+        Some(decl_id) => {
+            working_set.enter_scope();
+            let (call, mut err) = parse_internal_call(working_set, spans[0], &spans[1..], decl_id);
+            working_set.exit_scope();
+
+            let call_span = span(spans);
+            let sig = working_set.get_decl(decl_id).signature();
+
+            err = check_call(call_span, &sig, &call).or(err);
+            if err.is_some() || call.has_flag("help") {
+                return (
+                    Statement::Pipeline(Pipeline::from_vec(vec![Expression {
+                        expr: Expr::Call(call),
+                        span: call_span,
+                        ty: Type::Unknown,
+                        custom_completion: None,
+                    }])),
+                    err,
+                );
+            }
+
+            (call, call_span)
+        }
+    };
+
+    let name_expr = call.positional.get(0).expect("extern call already checked");
+    let sig = call.positional.get(1).expect("extern call already checked");
+
+    let mut error = None;
+    if let (Some(name), Some(mut signature)) = (&name_expr.as_string(), sig.as_signature()) {
+        if let Some(decl_id) = working_set.find_decl(name.as_bytes()) {
+            signature.name = name.clone();
+            signature.usage = usage;
+
+            *working_set.get_decl_mut(decl_id) = Box::new(KnownExternal { signature });
+        } else {
+            error = error.or_else(|| {
+                Some(ParseError::InternalError(
+                    "Predeclaration failed to add declaration".into(),
+                    spans[1],
+                ))
+            });
+        }
+
+        // It's OK if it returns None: The decl was already merged in previous parse pass.
+        working_set.merge_predecl(name.as_bytes());
+    } else {
+        error = error.or_else(|| {
+            Some(ParseError::UnknownState(
+                "Could not get string from string expression".into(),
+                name_expr.span,
+            ))
+        });
+    }
+
+    (
+        Statement::Pipeline(Pipeline::from_vec(vec![Expression {
+            expr: Expr::Call(call),
+            span: call_span,
+            ty: Type::Unknown,
+            custom_completion: None,
+        }])),
+        error,
+    )
+}
+
 pub fn parse_alias(
     working_set: &mut StateWorkingSet,
     spans: &[Span],
@@ -1286,8 +1464,10 @@ pub fn parse_let(
 
                         let rhs_type = rvalue.ty.clone();
 
+                        // A type written on the variable, as in `let x: binary = ...`, stands
+                        // when the type of the value isn't known until it runs
                         if let Some(var_id) = var_id {
-                            if var_id != CONFIG_VARIABLE_ID {
+                            if var_id != CONFIG_VARIABLE_ID && rhs_type != Type::Unknown {
                                 working_set.set_variable_type(var_id, rhs_type);
                             }
                         }
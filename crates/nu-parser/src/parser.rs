@@ -1,7 +1,7 @@
 use crate::{
     lex, lite_parse,
     lite_parse::LiteCommand,
-    parse_keywords::{parse_for, parse_source},
+    parse_keywords::{parse_for, parse_if, parse_loop_control, parse_source, parse_while},
     type_check::{math_result_type, type_compatible},
     LiteBlock, ParseError, Token, TokenContents,
 };
@@ -18,7 +18,8 @@ use nu_protocol::{
 };
 
 use crate::parse_keywords::{
-    parse_alias, parse_def, parse_def_predecl, parse_hide, parse_let, parse_module, parse_use,
+    parse_alias, parse_def, parse_def_predecl, parse_extern, parse_hide, parse_let, parse_module,
+    parse_use,
 };
 
 use log::trace;
@@ -253,7 +254,11 @@ fn parse_long_flag(
     spans: &[Span],
     spans_idx: &mut usize,
     sig: &Signature,
-) -> (Option<String>, Option<Expression>, Option<ParseError>) {
+) -> (
+    Option<Spanned<String>>,
+    Option<Expression>,
+    Option<ParseError>,
+) {
     let arg_span = spans[*spans_idx];
     let arg_contents = working_set.get_span_contents(arg_span);
 
@@ -262,24 +267,37 @@ fn parse_long_flag(
         let split: Vec<_> = arg_contents.split(|x| *x == b'=').collect();
         let long_name = String::from_utf8(split[0].into());
         if let Ok(long_name) = long_name {
+            // With --flag=value, the flag is only the part before the '='
+            let name_span = if split.len() > 1 {
+                Span {
+                    start: arg_span.start,
+                    end: arg_span.start + long_name.len(),
+                }
+            } else {
+                arg_span
+            };
             let long_name = long_name[2..].to_string();
+            let spanned_name = Spanned {
+                item: long_name.clone(),
+                span: name_span,
+            };
             if let Some(flag) = sig.get_long_flag(&long_name) {
                 if let Some(arg_shape) = &flag.arg {
                     if split.len() > 1 {
                         // and we also have the argument
                         let mut span = arg_span;
-                        span.start += long_name.len() + 1; //offset by long flag and '='
+                        span.start = name_span.end + 1; //offset by '--', the long flag and '='
                         let (arg, err) = parse_value(working_set, span, arg_shape);
 
-                        (Some(long_name), Some(arg), err)
+                        (Some(spanned_name), Some(arg), err)
                     } else if let Some(arg) = spans.get(*spans_idx + 1) {
                         let (arg, err) = parse_value(working_set, *arg, arg_shape);
 
                         *spans_idx += 1;
-                        (Some(long_name), Some(arg), err)
+                        (Some(spanned_name), Some(arg), err)
                     } else {
                         (
-                            Some(long_name),
+                            Some(spanned_name),
                             None,
                             Some(ParseError::MissingFlagParam(
                                 arg_shape.to_string(),
@@ -289,21 +307,28 @@ fn parse_long_flag(
                     }
                 } else {
                     // A flag with no argument
-                    (Some(long_name), None, None)
+                    (Some(spanned_name), None, None)
                 }
             } else {
                 (
-                    Some(long_name.clone()),
+                    Some(spanned_name),
                     None,
                     Some(ParseError::UnknownFlag(
                         sig.name.clone(),
-                        long_name.clone(),
-                        arg_span,
+                        long_name,
+                        name_span,
                     )),
                 )
             }
         } else {
-            (Some("--".into()), None, Some(ParseError::NonUtf8(arg_span)))
+            (
+                Some(Spanned {
+                    item: "--".into(),
+                    span: arg_span,
+                }),
+                None,
+                Some(ParseError::NonUtf8(arg_span)),
+            )
         }
     } else {
         (None, None, None)
@@ -316,7 +341,7 @@ fn parse_short_flags(
     spans_idx: &mut usize,
     positional_idx: usize,
     sig: &Signature,
-) -> (Option<Vec<Flag>>, Option<ParseError>) {
+) -> (Option<Vec<(Flag, Span)>>, Option<ParseError>) {
     let mut error = None;
     let arg_span = spans[*spans_idx];
 
@@ -334,11 +359,20 @@ fn parse_short_flags(
                 end: orig.start + 1 + short_flag.0 + 1,
             };
             if let Some(flag) = sig.get_short_flag(short_flag_char) {
-                // If we require an arg and are in a batch of short flags, error
-                if !found_short_flags.is_empty() && flag.arg.is_some() {
+                // In a batch of short flags, only the last one can take an arg
+                if short_flag.0 + 1 < short_flags.len() && flag.arg.is_some() {
                     error = error.or(Some(ParseError::ShortFlagBatchCantTakeArg(short_flag_span)))
                 }
-                found_short_flags.push(flag);
+                // The first flag of the batch gets the dash too
+                let flag_span = if short_flag.0 == 0 {
+                    Span {
+                        start: orig.start,
+                        end: short_flag_span.end,
+                    }
+                } else {
+                    short_flag_span
+                };
+                found_short_flags.push((flag, flag_span));
             } else {
                 unmatched_short_flags.push(short_flag_span);
             }
@@ -625,13 +659,7 @@ pub fn parse_internal_call(
         if let Some(long_name) = long_name {
             // We found a long flag, like --bar
             error = error.or(err);
-            call.named.push((
-                Spanned {
-                    item: long_name,
-                    span: arg_span,
-                },
-                arg,
-            ));
+            call.named.push((long_name, arg));
             spans_idx += 1;
             continue;
         }
@@ -647,7 +675,7 @@ pub fn parse_internal_call(
 
         if let Some(short_flags) = short_flags {
             error = error.or(err);
-            for flag in short_flags {
+            for (flag, flag_span) in short_flags {
                 if let Some(arg_shape) = flag.arg {
                     if let Some(arg) = spans.get(spans_idx + 1) {
                         let (arg, err) = parse_value(working_set, *arg, &arg_shape);
@@ -656,7 +684,7 @@ pub fn parse_internal_call(
                         call.named.push((
                             Spanned {
                                 item: flag.long.clone(),
-                                span: spans[spans_idx],
+                                span: flag_span,
                             },
                             Some(arg),
                         ));
@@ -673,7 +701,7 @@ pub fn parse_internal_call(
                     call.named.push((
                         Spanned {
                             item: flag.long.clone(),
-                            span: spans[spans_idx],
+                            span: flag_span,
                         },
                         None,
                     ));
@@ -1962,6 +1990,7 @@ pub fn parse_shape_name(
 ) -> (SyntaxShape, Option<ParseError>) {
     let result = match bytes {
         b"any" => SyntaxShape::Any,
+        b"binary" => SyntaxShape::Binary,
         b"block" => SyntaxShape::Block(None), //FIXME: Blocks should have known output types
         b"cell-path" => SyntaxShape::CellPath,
         b"duration" => SyntaxShape::Duration,
@@ -2214,12 +2243,17 @@ pub fn parse_row_condition(
     spans: &[Span],
 ) -> (Expression, Option<ParseError>) {
     let var_id = working_set.add_variable(b"$it".to_vec(), Type::Unknown);
-    let (expression, err) = parse_math_expression(working_set, spans, Some(var_id));
+    let (mut expression, err) = parse_math_expression(working_set, spans, Some(var_id));
     let span = span(spans);
 
     let block_id = match expression.expr {
         Expr::Block(block_id) => block_id,
         _ => {
+            // Inside a row condition `$in` is the row being tested, the same as `$it`
+            if expression.has_in_variable(working_set) {
+                expression.replace_in_variable(working_set, var_id);
+            }
+
             // We have an expression, so let's convert this into a block.
             let mut block = Block::new();
             let mut pipeline = Pipeline::new();
@@ -2645,27 +2679,30 @@ pub fn parse_list_expression(
 
     let mut contained_type: Option<Type> = None;
 
-    if !output.block.is_empty() {
-        for arg in &output.block[0].commands {
-            let mut spans_idx = 0;
+    // A list spread over several lines lite-parses as one statement per line
+    for arg in output
+        .block
+        .iter()
+        .flat_map(|statement| statement.commands.iter())
+    {
+        let mut spans_idx = 0;
 
-            while spans_idx < arg.parts.len() {
-                let (arg, err) =
-                    parse_multispan_value(working_set, &arg.parts, &mut spans_idx, element_shape);
-                error = error.or(err);
+        while spans_idx < arg.parts.len() {
+            let (arg, err) =
+                parse_multispan_value(working_set, &arg.parts, &mut spans_idx, element_shape);
+            error = error.or(err);
 
-                if let Some(ref ctype) = contained_type {
-                    if *ctype != arg.ty {
-                        contained_type = Some(Type::Unknown);
-                    }
-                } else {
-                    contained_type = Some(arg.ty.clone());
+            if let Some(ref ctype) = contained_type {
+                if *ctype != arg.ty {
+                    contained_type = Some(Type::Unknown);
                 }
+            } else {
+                contained_type = Some(arg.ty.clone());
+            }
 
-                args.push(arg);
+            args.push(arg);
 
-                spans_idx += 1;
-            }
+            spans_idx += 1;
         }
     }
 
@@ -2684,6 +2721,77 @@ pub fn parse_list_expression(
     )
 }
 
+/// Parse a binary literal such as `0x[de ad be ef]`. Whitespace between the hex digits is
+/// ignored, and an odd number of digits is padded with a leading zero.
+pub fn parse_binary(
+    working_set: &mut StateWorkingSet,
+    span: Span,
+) -> (Expression, Option<ParseError>) {
+    let bytes = working_set.get_span_contents(span);
+
+    let inner = match bytes
+        .strip_prefix(b"0x[")
+        .and_then(|inner| inner.strip_suffix(b"]"))
+    {
+        Some(inner) => inner,
+        None => {
+            return (
+                garbage(span),
+                Some(ParseError::Unclosed(
+                    "]".into(),
+                    Span {
+                        start: span.end,
+                        end: span.end,
+                    },
+                )),
+            )
+        }
+    };
+
+    let mut digits = vec![];
+    for (offset, c) in inner.iter().enumerate() {
+        if c.is_ascii_whitespace() {
+            continue;
+        }
+
+        match (*c as char).to_digit(16) {
+            Some(digit) => digits.push(digit as u8),
+            None => {
+                let start = span.start + 3 + offset;
+                return (
+                    garbage(span),
+                    Some(ParseError::Expected(
+                        "hex digit".into(),
+                        Span {
+                            start,
+                            end: start + 1,
+                        },
+                    )),
+                );
+            }
+        }
+    }
+
+    if digits.len() % 2 == 1 {
+        digits.insert(0, 0);
+    }
+
+    let binary = digits
+        .chunks(2)
+        .map(|pair| (pair[0] << 4) | pair[1])
+        .collect();
+
+    (
+        Expression {
+            expr: Expr::Binary(binary),
+            span,
+            ty: Type::Binary,
+            custom_completion: None,
+        },
+        None,
+    )
+}
+
 pub fn parse_table_expression(
     working_set: &mut StateWorkingSet,
     original_span: Span,
@@ -2710,11 +2818,16 @@ pub fn parse_table_expression(
     let (output, err) = lex(source, start, &[b'\n', b'\r', b','], &[], true);
     error = error.or(err);
 
+    // Only a `;` after the header row turns the list into a table literal: `[[a b]; [1 2]]`
+    let is_table = output
+        .iter()
+        .any(|token| token.contents == TokenContents::Semicolon);
+
     let (output, err) = lite_parse(&output);
     error = error.or(err);
 
-    match output.block.len() {
-        0 => (
+    if output.block.is_empty() {
+        return (
             Expression {
                 expr: Expr::List(vec![]),
                 span: original_span,
@@ -2722,81 +2835,96 @@ pub fn parse_table_expression(
                 custom_completion: None,
             },
             None,
-        ),
-        1 => {
-            // List
-            parse_list_expression(working_set, original_span, &SyntaxShape::Any)
-        }
-        _ => {
-            let mut table_headers = vec![];
+        );
+    }
 
-            let (headers, err) = parse_value(
-                working_set,
-                output.block[0].commands[0].parts[0],
-                &SyntaxShape::List(Box::new(SyntaxShape::Any)),
-            );
-            error = error.or(err);
+    if !is_table {
+        return parse_list_expression(working_set, original_span, &SyntaxShape::Any);
+    }
 
-            if let Expression {
-                expr: Expr::List(headers),
-                ..
-            } = headers
-            {
-                table_headers = headers;
-            }
+    let mut table_headers = vec![];
 
-            let mut rows = vec![];
-            for part in &output.block[1].commands[0].parts {
-                let (values, err) = parse_value(
-                    working_set,
-                    *part,
-                    &SyntaxShape::List(Box::new(SyntaxShape::Any)),
-                );
-                error = error.or(err);
-                if let Expression {
-                    expr: Expr::List(values),
-                    span,
-                    ..
-                } = values
-                {
-                    match values.len().cmp(&table_headers.len()) {
-                        std::cmp::Ordering::Less => {
-                            error = error.or_else(|| {
-                                Some(ParseError::MissingColumns(table_headers.len(), span))
-                            })
-                        }
-                        std::cmp::Ordering::Equal => {}
-                        std::cmp::Ordering::Greater => {
-                            error = error.or_else(|| {
-                                Some(ParseError::ExtraColumns(
-                                    table_headers.len(),
-                                    values[table_headers.len()].span,
-                                ))
-                            })
-                        }
-                    }
+    let (headers, err) = parse_value(
+        working_set,
+        output.block[0].commands[0].parts[0],
+        &SyntaxShape::List(Box::new(SyntaxShape::Any)),
+    );
+    error = error.or(err);
+
+    if let Expression {
+        expr: Expr::List(headers),
+        ..
+    } = headers
+    {
+        table_headers = headers;
+    }
 
-                    rows.push(values);
+    // Rows may be spread over several lines, each of which lite-parses as its own statement
+    let mut rows = vec![];
+    for part in output.block[1..]
+        .iter()
+        .flat_map(|statement| statement.commands.iter())
+        .flat_map(|command| command.parts.iter())
+    {
+        let (values, err) = parse_value(
+            working_set,
+            *part,
+            &SyntaxShape::List(Box::new(SyntaxShape::Any)),
+        );
+        error = error.or(err);
+        if let Expression {
+            expr: Expr::List(values),
+            span,
+            ..
+        } = values
+        {
+            match values.len().cmp(&table_headers.len()) {
+                std::cmp::Ordering::Less => {
+                    error = error
+                        .or_else(|| Some(ParseError::MissingColumns(table_headers.len(), span)))
+                }
+                std::cmp::Ordering::Equal => {}
+                std::cmp::Ordering::Greater => {
+                    error =
+                        error.or_else(|| Some(ParseError::ExtraColumns(table_headers.len(), span)))
                 }
             }
 
-            (
-                Expression {
-                    expr: Expr::Table(table_headers, rows),
-                    span: original_span,
-                    ty: Type::Table,
-                    custom_completion: None,
-                },
-                error,
-            )
+            rows.push(values);
         }
     }
+
+    (
+        Expression {
+            expr: Expr::Table(table_headers, rows),
+            span: original_span,
+            ty: Type::Table,
+            custom_completion: None,
+        },
+        error,
+    )
 }
 
 pub fn parse_block_expression(
     working_set: &mut StateWorkingSet,
     shape: &SyntaxShape,
     span: Span,
+) -> (Expression, Option<ParseError>) {
+    if !working_set.loop_bodies.contains(&span) {
+        return parse_block_contents(working_set, shape, span);
+    }
+
+    working_set.loop_depth += 1;
+    let output = parse_block_contents(working_set, shape, span);
+    working_set.loop_depth -= 1;
+
+    output
+}
+
+fn parse_block_contents(
+    working_set: &mut StateWorkingSet,
+    shape: &SyntaxShape,
+    span: Span,
 ) -> (Expression, Option<ParseError>) {
     trace!("parsing: block expression");
 
@@ -2981,6 +3109,10 @@ pub fn parse_value(
                 Some(ParseError::Expected("non-block value".into(), span)),
             );
         }
+    } else if bytes.starts_with(b"0x[") && matches!(shape, SyntaxShape::Any | SyntaxShape::Binary) {
+        trace!("parsing: binary");
+
+        return parse_binary(working_set, span);
     } else if bytes.starts_with(b"[") {
         match shape {
             SyntaxShape::Any
@@ -3006,6 +3138,10 @@ pub fn parse_value(
         SyntaxShape::Int => parse_int(bytes, span),
         SyntaxShape::Duration => parse_duration(working_set, span),
         SyntaxShape::Filesize => parse_filesize(working_set, span),
+        SyntaxShape::Binary => (
+            Expression::garbage(span),
+            Some(ParseError::Expected("binary".into(), span)),
+        ),
         SyntaxShape::Range => parse_range(working_set, span),
         SyntaxShape::Filepath => parse_filepath(working_set, span),
         SyntaxShape::GlobPattern => parse_glob_pattern(working_set, span),
@@ -3135,8 +3271,8 @@ pub fn parse_operator(
         b"<=" => Operator::LessThanOrEqual,
         b">" => Operator::GreaterThan,
         b">=" => Operator::GreaterThanOrEqual,
-        b"=~" => Operator::Contains,
-        b"!~" => Operator::NotContains,
+        b"=~" => Operator::RegexMatch,
+        b"!~" => Operator::NotRegexMatch,
         b"+" => Operator::Plus,
         b"-" => Operator::Minus,
         b"*" => Operator::Multiply,
@@ -3147,6 +3283,11 @@ pub fn parse_operator(
         b"&&" => Operator::And,
         b"||" => Operator::Or,
         b"**" => Operator::Pow,
+        b"bit-or" => Operator::BitOr,
+        b"bit-xor" => Operator::BitXor,
+        b"bit-and" => Operator::BitAnd,
+        b"bit-shl" => Operator::ShiftLeft,
+        b"bit-shr" => Operator::ShiftRight,
         _ => {
             return (
                 garbage(span),
@@ -3184,7 +3325,6 @@ pub fn parse_math_expression(
     let mut expr_stack: Vec<Expression> = vec![];
 
     let mut idx = 0;
-    let mut last_prec = 1000000;
 
     let mut error = None;
     let (lhs, err) = parse_value(working_set, spans[0], &SyntaxShape::Any);
@@ -3214,9 +3354,16 @@ pub fn parse_math_expression(
         let (rhs, err) = parse_value(working_set, spans[idx], &SyntaxShape::Any);
         error = error.or(err);
 
-        if op_prec <= last_prec && expr_stack.len() > 1 {
-            // Collapse the right associated operations first
-            // so that we can get back to a stack with a lower precedence
+        // Collapse the right associated operations first so that we can get back to a stack
+        // with a lower precedence. Right associative operators (`**`) of the same precedence
+        // stay on the stack so they fold from the right.
+        while expr_stack.len() > 1 {
+            let last_prec = expr_stack[expr_stack.len() - 2].precedence();
+
+            if op_prec > last_prec || (op_prec == last_prec && op.is_right_associative()) {
+                break;
+            }
+
             let mut rhs = expr_stack
                 .pop()
                 .expect("internal error: expression stack empty");
@@ -3246,8 +3393,6 @@ pub fn parse_math_expression(
         expr_stack.push(op);
         expr_stack.push(rhs);
 
-        last_prec = op_prec;
-
         idx += 1;
     }
 
@@ -3357,6 +3502,10 @@ pub fn parse_expression(
                 parse_call(working_set, &spans[pos..], expand_aliases, spans[0]).0,
                 Some(ParseError::StatementInPipeline("def".into(), spans[0])),
             ),
+            b"extern" => (
+                parse_call(working_set, &spans[pos..], expand_aliases, spans[0]).0,
+                Some(ParseError::StatementInPipeline("extern".into(), spans[0])),
+            ),
             b"let" => (
                 parse_call(working_set, &spans[pos..], expand_aliases, spans[0]).0,
                 Some(ParseError::StatementInPipeline("let".into(), spans[0])),
@@ -3392,6 +3541,11 @@ pub fn parse_expression(
             ),
 
             b"for" => parse_for(working_set, spans),
+            b"if" => parse_if(working_set, &spans[pos..], expand_aliases),
+            b"while" => parse_while(working_set, &spans[pos..], expand_aliases),
+            b"break" | b"continue" => {
+                parse_loop_control(working_set, &spans[pos..], expand_aliases)
+            }
             _ => parse_call(working_set, &spans[pos..], expand_aliases, spans[0]),
         }
     };
@@ -3481,6 +3635,7 @@ pub fn parse_statement(
 
     match name {
         b"def" => parse_def(working_set, lite_command),
+        b"extern" => parse_extern(working_set, lite_command),
         b"let" => parse_let(working_set, &lite_command.parts),
         b"for" => {
             let (expr, err) = parse_for(working_set, &lite_command.parts);
@@ -3772,6 +3927,7 @@ pub fn find_captures_in_expr(
         }
         Expr::Filepath(_) => {}
         Expr::Float(_) => {}
+        Expr::Binary(_) => {}
         Expr::FullCellPath(cell_path) => {
             let result = find_captures_in_expr(working_set, &cell_path.head, seen, seen_decls);
             output.extend(&result);
@@ -1,6 +1,6 @@
 use crate::ParseError;
 use nu_protocol::{
-    ast::{Expr, Expression, Operator},
+    ast::{Block, Expr, Expression, Operator, Statement},
     engine::StateWorkingSet,
     Type,
 };
@@ -16,6 +16,90 @@ pub fn type_compatible(lhs: &Type, rhs: &Type) -> bool {
     }
 }
 
+/// Whether a stage giving out `output` can be piped into one that takes `input`. This is
+/// permissive: an unknown type on either side always fits.
+fn pipeline_type_compatible(output: &Type, input: &Type) -> bool {
+    match (output, input) {
+        (Type::Unknown, _) | (_, Type::Unknown) => true,
+        // A command that doesn't read its input can follow anything
+        (_, Type::Nothing) => true,
+        (Type::Table, Type::List(item)) | (Type::List(item), Type::Table) => {
+            matches!(**item, Type::Unknown | Type::Record(_))
+        }
+        (Type::ValueStream, Type::List(_)) | (Type::Range, Type::List(_)) => true,
+        (Type::List(output), Type::List(input)) => pipeline_type_compatible(output, input),
+        // Commands that work on a single value map over a list of them
+        (Type::List(item), input) => pipeline_type_compatible(item, input),
+        (output, input) => type_compatible(input, output),
+    }
+}
+
+/// Check that each stage of the pipelines in `block` can take what the stage before it gives
+/// out. The checks are separate from parsing so the caller decides whether a mismatch is a
+/// warning or an error.
+///
+/// Commands that don't declare types are never flagged. A command given column paths works on
+/// those columns of its input, so its declared input type isn't checked either.
+pub fn check_pipeline_types(working_set: &StateWorkingSet, block: &Block) -> Vec<ParseError> {
+    let mut errors = vec![];
+
+    for stmt in &block.stmts {
+        if let Statement::Pipeline(pipeline) = stmt {
+            for stages in pipeline.expressions.windows(2) {
+                if let Some(err) = check_stage(working_set, &stages[0], &stages[1]) {
+                    errors.push(err);
+                }
+            }
+        }
+    }
+
+    errors
+}
+
+fn check_stage(
+    working_set: &StateWorkingSet,
+    prev: &Expression,
+    next: &Expression,
+) -> Option<ParseError> {
+    let call = match &next.expr {
+        Expr::Call(call) => call,
+        _ => return None,
+    };
+
+    if call
+        .positional
+        .iter()
+        .any(|arg| matches!(arg.expr, Expr::CellPath(_)))
+    {
+        return None;
+    }
+
+    let input = working_set.get_decl(call.decl_id).signature().input_type;
+    let output = match &prev.expr {
+        Expr::Call(call) => working_set.get_decl(call.decl_id).signature().output_type,
+        _ => prev.ty.clone(),
+    };
+
+    if pipeline_type_compatible(&output, &input) {
+        None
+    } else {
+        Some(ParseError::PipelineTypeMismatch(
+            prev.span, output, next.span, input,
+        ))
+    }
+}
+
+/// Literal patterns on the right of `=~` and `!~` are checked while parsing, so a bad pattern is
+/// reported before anything runs
+fn check_regex_literal(rhs: &Expression) -> Option<ParseError> {
+    match &rhs.expr {
+        Expr::String(pattern) => regex::Regex::new(pattern)
+            .err()
+            .map(|e| ParseError::LabeledError("Invalid regex".into(), e.to_string(), rhs.span)),
+        _ => None,
+    }
+}
+
 pub fn math_result_type(
     _working_set: &StateWorkingSet,
     lhs: &mut Expression,
@@ -110,7 +194,7 @@ pub fn math_result_type(
                     )
                 }
             },
-            Operator::Divide | Operator::Modulo => match (&lhs.ty, &rhs.ty) {
+            Operator::Divide => match (&lhs.ty, &rhs.ty) {
                 (Type::Int, Type::Int) => (Type::Int, None),
                 (Type::Float, Type::Int) => (Type::Float, None),
                 (Type::Int, Type::Float) => (Type::Float, None),
@@ -134,6 +218,53 @@ pub fn math_result_type(
                     )
                 }
             },
+            Operator::Modulo => match (&lhs.ty, &rhs.ty) {
+                (Type::Int, Type::Int) => (Type::Int, None),
+                (Type::Float, Type::Int) => (Type::Float, None),
+                (Type::Int, Type::Float) => (Type::Float, None),
+                (Type::Float, Type::Float) => (Type::Float, None),
+                (Type::Filesize, Type::Filesize) => (Type::Filesize, None),
+                (Type::Duration, Type::Duration) => (Type::Duration, None),
+
+                (Type::Unknown, _) => (Type::Unknown, None),
+                (_, Type::Unknown) => (Type::Unknown, None),
+                _ => {
+                    *op = Expression::garbage(op.span);
+                    (
+                        Type::Unknown,
+                        Some(ParseError::UnsupportedOperation(
+                            op.span,
+                            lhs.span,
+                            lhs.ty.clone(),
+                            rhs.span,
+                            rhs.ty.clone(),
+                        )),
+                    )
+                }
+            },
+            Operator::BitOr
+            | Operator::BitXor
+            | Operator::BitAnd
+            | Operator::ShiftLeft
+            | Operator::ShiftRight => match (&lhs.ty, &rhs.ty) {
+                (Type::Int, Type::Int) => (Type::Int, None),
+
+                (Type::Unknown, _) => (Type::Unknown, None),
+                (_, Type::Unknown) => (Type::Unknown, None),
+                _ => {
+                    *op = Expression::garbage(op.span);
+                    (
+                        Type::Unknown,
+                        Some(ParseError::UnsupportedOperation(
+                            op.span,
+                            lhs.span,
+                            lhs.ty.clone(),
+                            rhs.span,
+                            rhs.ty.clone(),
+                        )),
+                    )
+                }
+            },
             Operator::And | Operator::Or => match (&lhs.ty, &rhs.ty) {
                 (Type::Bool, Type::Bool) => (Type::Bool, None),
 
@@ -251,8 +382,8 @@ pub fn math_result_type(
             },
             Operator::Equal => (Type::Bool, None),
             Operator::NotEqual => (Type::Bool, None),
-            Operator::Contains => match (&lhs.ty, &rhs.ty) {
-                (Type::String, Type::String) => (Type::Bool, None),
+            Operator::RegexMatch => match (&lhs.ty, &rhs.ty) {
+                (Type::String, Type::String) => (Type::Bool, check_regex_literal(rhs)),
                 (Type::Unknown, _) => (Type::Bool, None),
                 (_, Type::Unknown) => (Type::Bool, None),
                 _ => {
@@ -269,8 +400,8 @@ pub fn math_result_type(
                     )
                 }
             },
-            Operator::NotContains => match (&lhs.ty, &rhs.ty) {
-                (Type::String, Type::String) => (Type::Bool, None),
+            Operator::NotRegexMatch => match (&lhs.ty, &rhs.ty) {
+                (Type::String, Type::String) => (Type::Bool, check_regex_literal(rhs)),
                 (Type::Unknown, _) => (Type::Bool, None),
                 (_, Type::Unknown) => (Type::Bool, None),
                 _ => {
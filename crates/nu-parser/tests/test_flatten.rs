@@ -0,0 +1,111 @@
+use nu_parser::*;
+use nu_protocol::{
+    engine::{EngineState, StateWorkingSet},
+    Signature, SyntaxShape,
+};
+
+fn flatten(source: &[u8]) -> Vec<(String, FlatShape)> {
+    let engine_state = EngineState::new();
+    let mut working_set = StateWorkingSet::new(&engine_state);
+
+    let sig = Signature::build("foo")
+        .named("jazz", SyntaxShape::Int, "jazz!!", Some('j'))
+        .switch("loud", "loud!!", Some('l'))
+        .rest("rest", SyntaxShape::Any, "anything");
+    working_set.add_decl(sig.predeclare());
+
+    let (block, _) = parse(&mut working_set, None, source, true);
+
+    flatten_block(&working_set, &block)
+        .into_iter()
+        .map(|(span, shape)| {
+            (
+                String::from_utf8_lossy(working_set.get_span_contents(span)).to_string(),
+                shape,
+            )
+        })
+        .collect()
+}
+
+fn token(contents: &str, shape: FlatShape) -> (String, FlatShape) {
+    (contents.to_string(), shape)
+}
+
+#[test]
+fn flattens_call_with_flags_and_cell_path() {
+    assert_eq!(
+        flatten(br#"foo --jazz 3 -l $nu.cwd.0 "hi" 1.5"#),
+        vec![
+            token("foo", FlatShape::InternalCall),
+            token("--jazz", FlatShape::Flag),
+            token("3", FlatShape::Int),
+            token("-l", FlatShape::Flag),
+            token("$nu", FlatShape::Variable),
+            token("cwd", FlatShape::CellPath),
+            token("0", FlatShape::CellPath),
+            token(r#""hi""#, FlatShape::String),
+            token("1.5", FlatShape::Float),
+        ]
+    );
+}
+
+#[test]
+fn flattens_flag_with_equals_and_short_flag_batch() {
+    assert_eq!(
+        flatten(b"foo --jazz=3 -lj 4"),
+        vec![
+            token("foo", FlatShape::InternalCall),
+            token("--jazz", FlatShape::Flag),
+            token("3", FlatShape::Int),
+            token("-l", FlatShape::Flag),
+            token("j", FlatShape::Flag),
+            token("4", FlatShape::Int),
+        ]
+    );
+}
+
+#[test]
+fn flattens_string_interpolation() {
+    let flattened = flatten(br#"$"a($nu)""#);
+
+    assert_eq!(
+        flattened.first(),
+        Some(&token(r#"$""#, FlatShape::StringInterpolation))
+    );
+    assert_eq!(
+        flattened.last(),
+        Some(&token(r#"""#, FlatShape::StringInterpolation))
+    );
+    assert!(flattened.contains(&token("a", FlatShape::String)));
+    assert!(flattened.contains(&token("$nu", FlatShape::Variable)));
+}
+
+#[test]
+fn flattens_unknown_flag_as_garbage() {
+    assert_eq!(
+        flatten(b"foo --nope 1"),
+        vec![
+            token("foo", FlatShape::InternalCall),
+            token("--nope", FlatShape::Garbage),
+            token("1", FlatShape::Int),
+        ]
+    );
+}
+
+#[test]
+fn shapes_have_distinct_config_names() {
+    let shapes = [
+        FlatShape::Flag,
+        FlatShape::CellPath,
+        FlatShape::Variable,
+        FlatShape::StringInterpolation,
+        FlatShape::List,
+        FlatShape::Garbage,
+    ];
+
+    let mut names: Vec<String> = shapes.iter().map(|shape| shape.to_string()).collect();
+    names.sort();
+    names.dedup();
+
+    assert_eq!(names.len(), shapes.len());
+}
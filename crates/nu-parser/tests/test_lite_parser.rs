@@ -240,3 +240,62 @@ let b = 0
 
     Ok(())
 }
+
+#[test]
+fn pipeline_continues_after_trailing_pipe() -> Result<(), ParseError> {
+    // Code:
+    // ls |
+    //   where size > 10kb |
+    //   length
+    let input = b"ls |\n  where size > 10kb |\n  length";
+
+    let lite_block = lite_parse_helper(input)?;
+
+    assert_eq!(lite_block.block.len(), 1);
+    assert_eq!(lite_block.block[0].commands.len(), 3);
+    assert_eq!(lite_block.block[0].commands[2].parts.len(), 1);
+
+    Ok(())
+}
+
+#[test]
+fn pipeline_continues_past_blank_lines_and_comments() -> Result<(), ParseError> {
+    // Code:
+    // ls | # the files
+    //
+    //   length
+    let input = b"ls | # the files\n\n  length";
+
+    let lite_block = lite_parse_helper(input)?;
+
+    assert_eq!(lite_block.block.len(), 1);
+    assert_eq!(lite_block.block[0].commands.len(), 2);
+
+    Ok(())
+}
+
+#[test]
+fn pipe_in_comment_does_not_continue() -> Result<(), ParseError> {
+    // Code:
+    // ls # not a pipe |
+    // length
+    let input = b"ls # not a pipe |\nlength";
+
+    let lite_block = lite_parse_helper(input)?;
+
+    assert_eq!(lite_block.block.len(), 2);
+    assert_eq!(lite_block.block[0].commands.len(), 1);
+    assert_eq!(lite_block.block[1].commands.len(), 1);
+
+    Ok(())
+}
+
+#[test]
+fn trailing_pipe_at_end_of_input_is_incomplete() {
+    let input = b"ls |\n\n";
+
+    assert!(matches!(
+        lite_parse_helper(input),
+        Err(ParseError::UnexpectedEof(..))
+    ));
+}
@@ -134,6 +134,73 @@ pub fn parse_call_too_many_shortflag_args() {
     ));
 }
 
+#[test]
+pub fn parse_call_shortflag_batch_last_takes_arg() {
+    let engine_state = EngineState::new();
+    let mut working_set = StateWorkingSet::new(&engine_state);
+
+    let sig = Signature::build("foo")
+        .named("jazz", SyntaxShape::Int, "jazz!!", Some('j'))
+        .switch("math", "math!!", Some('m'));
+    working_set.add_decl(sig.predeclare());
+    let (block, err) = parse(&mut working_set, None, b"foo -mj 3", true);
+    assert!(err.is_none());
+
+    match &block[0] {
+        Statement::Pipeline(Pipeline { expressions }) => match &expressions[0].expr {
+            Expr::Call(call) => {
+                let names: Vec<_> = call
+                    .named
+                    .iter()
+                    .map(|(name, _)| working_set.get_span_contents(name.span).to_vec())
+                    .collect();
+                assert_eq!(names, vec![b"-m".to_vec(), b"j".to_vec()]);
+
+                assert!(call.named[0].1.is_none());
+                assert!(matches!(
+                    call.named[1].1,
+                    Some(Expression {
+                        expr: Expr::Int(3),
+                        ..
+                    })
+                ));
+            }
+            _ => panic!("not a call"),
+        },
+        _ => panic!("No match"),
+    }
+}
+
+#[test]
+pub fn parse_call_long_flag_with_equals() {
+    let engine_state = EngineState::new();
+    let mut working_set = StateWorkingSet::new(&engine_state);
+
+    let sig = Signature::build("foo").named("jazz", SyntaxShape::Int, "jazz!!", Some('j'));
+    working_set.add_decl(sig.predeclare());
+    let (block, err) = parse(&mut working_set, None, b"foo --jazz=42", true);
+    assert!(err.is_none());
+
+    match &block[0] {
+        Statement::Pipeline(Pipeline { expressions }) => match &expressions[0].expr {
+            Expr::Call(call) => {
+                let (name, arg) = &call.named[0];
+                assert_eq!(name.item, "jazz");
+                assert_eq!(working_set.get_span_contents(name.span), b"--jazz");
+                assert!(matches!(
+                    arg,
+                    Some(Expression {
+                        expr: Expr::Int(42),
+                        ..
+                    })
+                ));
+            }
+            _ => panic!("not a call"),
+        },
+        _ => panic!("No match"),
+    }
+}
+
 #[test]
 pub fn parse_call_unknown_shorthand() {
     let engine_state = EngineState::new();
@@ -145,6 +212,25 @@ pub fn parse_call_unknown_shorthand() {
     assert!(matches!(err, Some(ParseError::UnknownFlag(..))));
 }
 
+#[test]
+pub fn parse_call_unknown_flag_in_batch() {
+    let engine_state = EngineState::new();
+    let mut working_set = StateWorkingSet::new(&engine_state);
+
+    let sig = Signature::build("foo").switch("jazz", "jazz!!", Some('j'));
+    working_set.add_decl(sig.predeclare());
+    let (_, err) = parse(&mut working_set, None, b"foo -jx", true);
+
+    match err {
+        Some(ParseError::UnknownFlag(command, flag, span)) => {
+            assert_eq!(command, "foo");
+            assert_eq!(flag, "-x");
+            assert_eq!(working_set.get_span_contents(span), b"x");
+        }
+        err => panic!("expected an unknown flag, got {:?}", err),
+    }
+}
+
 #[test]
 pub fn parse_call_extra_positional() {
     let engine_state = EngineState::new();
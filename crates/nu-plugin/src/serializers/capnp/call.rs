@@ -10,18 +10,23 @@ pub(crate) fn serialize_call(
     head.set_start(call.head.start as u64);
     head.set_end(call.head.end as u64);
 
-    serialize_positional(&call.positional, builder.reborrow());
+    serialize_positional(&call.positional, builder.reborrow())?;
     serialize_named(&call.named, builder)?;
 
     Ok(())
 }
 
-fn serialize_positional(positional: &[Value], mut builder: evaluated_call::Builder) {
+fn serialize_positional(
+    positional: &[Value],
+    mut builder: evaluated_call::Builder,
+) -> Result<(), ShellError> {
     let mut positional_builder = builder.reborrow().init_positional(positional.len() as u32);
 
     for (index, value) in positional.iter().enumerate() {
-        value::serialize_value(value, positional_builder.reborrow().get(index as u32))
+        value::serialize_value(value, positional_builder.reborrow().get(index as u32))?;
     }
+
+    Ok(())
 }
 
 fn serialize_named(
@@ -42,7 +47,7 @@ fn serialize_named(
 
         if let Some(value) = expression {
             let value_builder = entry_builder.init_value();
-            value::serialize_value(value, value_builder);
+            value::serialize_value(value, value_builder)?;
         }
     }
 
@@ -36,7 +36,7 @@ pub fn encode_call(
                 .get_input()
                 .map_err(|e| ShellError::PluginFailedToEncode(e.to_string()))?;
 
-            value::serialize_value(&call_info.input, value_builder);
+            value::serialize_value(&call_info.input, value_builder)?;
         }
     };
 
@@ -116,7 +116,7 @@ pub fn encode_response(
         }
         PluginResponse::Value(val) => {
             let value_builder = builder.reborrow().init_value();
-            value::serialize_value(val, value_builder);
+            value::serialize_value(val, value_builder)?;
         }
     };
 
@@ -1,5 +1,5 @@
 use crate::plugin_capnp::{argument, flag, signature, Category as PluginCategory, Shape};
-use nu_protocol::{Category, Flag, PositionalArg, ShellError, Signature, SyntaxShape};
+use nu_protocol::{Category, Flag, PositionalArg, ShellError, Signature, SyntaxShape, Type};
 
 pub(crate) fn serialize_signature(signature: &Signature, mut builder: signature::Builder) {
     builder.set_name(signature.name.as_str());
@@ -176,6 +176,9 @@ pub(crate) fn deserialize_signature(reader: signature::Reader) -> Result<Signatu
         is_filter,
         creates_scope: false,
         category,
+        // Plugins don't declare pipeline types yet
+        input_type: Type::Unknown,
+        output_type: Type::Unknown,
     })
 }
 
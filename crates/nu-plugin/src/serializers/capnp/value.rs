@@ -1,7 +1,13 @@
 use crate::plugin_capnp::value;
 use nu_protocol::{ShellError, Span, Value};
 
-pub(crate) fn serialize_value(value: &Value, mut builder: value::Builder) {
+/// Writes a value into the capnp message. The schema only has the basic variants, so any other
+/// value is an error rather than being sent as something it isn't; the json encoding carries all
+/// of them.
+pub(crate) fn serialize_value(
+    value: &Value,
+    mut builder: value::Builder,
+) -> Result<(), ShellError> {
     let value_span = match value {
         Value::Nothing { span } => {
             builder.set_void(());
@@ -32,10 +38,10 @@ pub(crate) fn serialize_value(value: &Value, mut builder: value::Builder) {
                 .for_each(|(index, col)| cols_builder.set(index as u32, col.as_str()));
 
             let mut values_builder = record_builder.reborrow().init_vals(vals.len() as u32);
-            vals.iter().enumerate().for_each(|(index, value)| {
+            for (index, value) in vals.iter().enumerate() {
                 let inner_builder = values_builder.reborrow().get(index as u32);
-                serialize_value(value, inner_builder);
-            });
+                serialize_value(value, inner_builder)?;
+            }
 
             *span
         }
@@ -43,23 +49,26 @@ pub(crate) fn serialize_value(value: &Value, mut builder: value::Builder) {
             let mut list_builder = builder.reborrow().init_list(vals.len() as u32);
             for (index, value) in vals.iter().enumerate() {
                 let inner_builder = list_builder.reborrow().get(index as u32);
-                serialize_value(value, inner_builder);
+                serialize_value(value, inner_builder)?;
             }
 
             *span
         }
-        _ => {
+        value => {
             // If there is the need to pass other type of value to the plugin
             // we have to define the encoding for that object in this match
-
-            // FIXME: put this in a proper span
-            Span { start: 0, end: 0 }
+            return Err(ShellError::PluginFailedToEncode(format!(
+                "{} values can't be sent with the capnp encoding, register the plugin with -e json",
+                value.get_type()
+            )));
         }
     };
 
     let mut span = builder.reborrow().init_span();
     span.set_start(value_span.start as u64);
     span.set_end(value_span.end as u64);
+
+    Ok(())
 }
 
 pub(crate) fn deserialize_value(reader: value::Reader, head: Span) -> Result<Value, ShellError> {
@@ -133,7 +142,7 @@ mod tests {
 
         let mut builder = message.init_root::<value::Builder>();
 
-        serialize_value(value, builder.reborrow());
+        serialize_value(value, builder.reborrow())?;
 
         serialize::write_message(writer, &message)
             .map_err(|e| ShellError::PluginFailedToDecode(e.to_string()))
@@ -372,4 +381,21 @@ mod tests {
 
         assert_eq!(record, returned_record)
     }
+
+    #[test]
+    fn unsupported_value_is_an_error() {
+        let record = Value::Record {
+            cols: vec!["size".into()],
+            vals: vec![Value::Filesize {
+                val: 1024,
+                span: Span { start: 1, end: 5 },
+            }],
+            span: Span { start: 0, end: 10 },
+        };
+
+        let mut buffer: Vec<u8> = Vec::new();
+        let result = write_buffer(&record, &mut buffer);
+
+        assert!(matches!(result, Err(ShellError::PluginFailedToEncode(_))));
+    }
 }
@@ -45,7 +45,9 @@ impl PluginEncoder for JsonSerializer {
 mod tests {
     use super::*;
     use crate::protocol::{CallInfo, EvaluatedCall, LabeledError, PluginCall, PluginResponse};
-    use nu_protocol::{Signature, Span, Spanned, SyntaxShape, Value};
+    use nu_protocol::ast::{CellPath, PathMember, RangeInclusion};
+    use nu_protocol::{Range, Signature, Span, Spanned, SyntaxShape, Value};
+    use std::collections::HashMap;
 
     #[test]
     fn callinfo_round_trip_signature() {
@@ -281,4 +283,101 @@ mod tests {
             PluginResponse::Value(_) => panic!("returned wrong call type"),
         }
     }
+
+    #[test]
+    fn response_round_trip_every_value() {
+        let span = Span { start: 2, end: 30 };
+        let values = vec![
+            Value::Bool { val: true, span },
+            Value::Int { val: -10, span },
+            Value::Filesize { val: 1024, span },
+            Value::Duration {
+                val: 1_000_000_000,
+                span,
+            },
+            Value::Date {
+                val: chrono::DateTime::parse_from_rfc3339("2021-10-22T20:00:12+01:00")
+                    .expect("valid date"),
+                span,
+            },
+            Value::Range {
+                val: Box::new(Range {
+                    from: Value::Int { val: 1, span },
+                    incr: Value::Int { val: 2, span },
+                    to: Value::Int { val: 9, span },
+                    inclusion: RangeInclusion::RightExclusive,
+                }),
+                span,
+            },
+            Value::Float { val: 10.5, span },
+            Value::String {
+                val: "a string".into(),
+                span,
+            },
+            Value::Record {
+                cols: vec!["name".into(), "sizes".into()],
+                vals: vec![
+                    Value::String {
+                        val: "nu".into(),
+                        span,
+                    },
+                    Value::List {
+                        vals: vec![Value::Filesize { val: 1, span }],
+                        span,
+                    },
+                ],
+                span,
+            },
+            Value::List {
+                vals: vec![Value::Nothing { span }, Value::Int { val: 1, span }],
+                span,
+            },
+            Value::Block {
+                val: 3,
+                captures: HashMap::from([(0, Value::Bool { val: false, span })]),
+                span,
+            },
+            Value::Nothing { span },
+            Value::Error {
+                error: ShellError::UnsupportedInput("not here".into(), span),
+            },
+            Value::Binary {
+                val: vec![0, 1, 255],
+                span,
+            },
+            Value::CellPath {
+                val: CellPath {
+                    members: vec![
+                        PathMember::String {
+                            val: "name".into(),
+                            span,
+                        },
+                        PathMember::Int { val: 0, span },
+                    ],
+                },
+                span,
+            },
+        ];
+
+        let encoder = JsonSerializer {};
+        for value in values {
+            let response = PluginResponse::Value(Box::new(value.clone()));
+
+            let mut buffer: Vec<u8> = Vec::new();
+            encoder
+                .encode_response(&response, &mut buffer)
+                .expect("unable to serialize message");
+            let returned = encoder
+                .decode_response(&mut buffer.as_slice())
+                .expect("unable to deserialize message");
+
+            // Not every variant can be compared, so compare how they print instead
+            match returned {
+                PluginResponse::Value(returned_value) => {
+                    assert_eq!(format!("{:?}", value), format!("{:?}", returned_value))
+                }
+                _ => panic!("returned wrong call type"),
+            }
+        }
+    }
 }
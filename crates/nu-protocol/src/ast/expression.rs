@@ -29,8 +29,14 @@ impl Expression {
                     Operator::Pow => 100,
                     Operator::Multiply | Operator::Divide | Operator::Modulo => 95,
                     Operator::Plus | Operator::Minus => 90,
-                    Operator::NotContains
-                    | Operator::Contains
+                    // Bitwise operators bind tighter than comparisons, so that
+                    // `$mode bit-and 0o100 == 0o100` doesn't need parens
+                    Operator::ShiftLeft | Operator::ShiftRight => 85,
+                    Operator::BitAnd => 84,
+                    Operator::BitXor => 83,
+                    Operator::BitOr => 82,
+                    Operator::NotRegexMatch
+                    | Operator::RegexMatch
                     | Operator::LessThan
                     | Operator::LessThanOrEqual
                     | Operator::GreaterThan
@@ -47,6 +53,11 @@ impl Expression {
         }
     }
 
+    /// Whether a chain of this operator groups from the right, as in `2 ** 3 ** 2`
+    pub fn is_right_associative(&self) -> bool {
+        matches!(self.expr, Expr::Operator(Operator::Pow))
+    }
+
     pub fn as_block(&self) -> Option<BlockId> {
         match self.expr {
             Expr::Block(block_id) => Some(block_id),
@@ -156,6 +167,7 @@ impl Expression {
             Expr::ImportPattern(_) => false,
             Expr::Filepath(_) => false,
             Expr::Float(_) => false,
+            Expr::Binary(_) => false,
             Expr::FullCellPath(full_cell_path) => {
                 if full_cell_path.head.has_in_variable(working_set) {
                     return true;
@@ -309,6 +321,7 @@ impl Expression {
             }
             Expr::Filepath(_) => {}
             Expr::Float(_) => {}
+            Expr::Binary(_) => {}
             Expr::FullCellPath(full_cell_path) => {
                 full_cell_path
                     .head
@@ -11,8 +11,8 @@ pub enum Operator {
     GreaterThan,
     LessThanOrEqual,
     GreaterThanOrEqual,
-    Contains,
-    NotContains,
+    RegexMatch,
+    NotRegexMatch,
     Plus,
     Minus,
     Multiply,
@@ -23,6 +23,11 @@ pub enum Operator {
     And,
     Or,
     Pow,
+    BitOr,
+    BitXor,
+    BitAnd,
+    ShiftLeft,
+    ShiftRight,
 }
 
 impl Display for Operator {
@@ -32,8 +37,8 @@ impl Display for Operator {
             Operator::NotEqual => write!(f, "!="),
             Operator::LessThan => write!(f, "<"),
             Operator::GreaterThan => write!(f, ">"),
-            Operator::Contains => write!(f, "=~"),
-            Operator::NotContains => write!(f, "!~"),
+            Operator::RegexMatch => write!(f, "=~"),
+            Operator::NotRegexMatch => write!(f, "!~"),
             Operator::Plus => write!(f, "+"),
             Operator::Minus => write!(f, "-"),
             Operator::Multiply => write!(f, "*"),
@@ -44,6 +49,11 @@ impl Display for Operator {
             Operator::And => write!(f, "&&"),
             Operator::Or => write!(f, "||"),
             Operator::Pow => write!(f, "**"),
+            Operator::BitOr => write!(f, "bit-or"),
+            Operator::BitXor => write!(f, "bit-xor"),
+            Operator::BitAnd => write!(f, "bit-and"),
+            Operator::ShiftLeft => write!(f, "bit-shl"),
+            Operator::ShiftRight => write!(f, "bit-shr"),
             Operator::LessThanOrEqual => write!(f, "<="),
             Operator::GreaterThanOrEqual => write!(f, ">="),
         }
@@ -65,6 +65,19 @@ pub struct Config {
     pub log_level: String,
     pub menu_config: HashMap<String, Value>,
     pub keybindings: Vec<ParsedKeybinding>,
+    pub ls_newest_first: bool,
+    pub table_max_rows: i64,
+    /// Show tables that don't fit on the screen through the pager, as `table --pager` does
+    pub table_pager: bool,
+    pub table_index: bool,
+    pub table_max_cell_width: i64,
+    /// How many levels of records and lists inside a cell are shown inline before they collapse
+    /// into a summary like `{record 3 fields}`
+    pub table_collapse_depth: i64,
+    /// Blocks run after the command they're named after, see `nu_engine::observe_with_hook`
+    pub hooks: HashMap<String, (BlockId, Span)>,
+    /// Refuse to run pipelines whose stages have mismatched types, instead of warning
+    pub strict_pipeline_types: bool,
 }
 
 impl Default for Config {
@@ -86,6 +99,14 @@ impl Default for Config {
             log_level: String::new(),
             menu_config: HashMap::new(),
             keybindings: Vec::new(),
+            ls_newest_first: false,
+            table_max_rows: 0,
+            table_pager: false,
+            table_index: true,
+            table_max_cell_width: 0,
+            table_collapse_depth: 0,
+            hooks: HashMap::new(),
+            strict_pipeline_types: false,
         }
     }
 }
@@ -222,6 +243,41 @@ impl Value {
                             eprintln!("$config.max_history_size is not an integer")
                         }
                     }
+                    "table_max_rows" => {
+                        if let Ok(i) = value.as_i64() {
+                            config.table_max_rows = i;
+                        } else {
+                            eprintln!("$config.table_max_rows is not an integer")
+                        }
+                    }
+                    "table_pager" => {
+                        if let Ok(b) = value.as_bool() {
+                            config.table_pager = b;
+                        } else {
+                            eprintln!("$config.table_pager is not a bool")
+                        }
+                    }
+                    "table_index" => {
+                        if let Ok(b) = value.as_bool() {
+                            config.table_index = b;
+                        } else {
+                            eprintln!("$config.table_index is not a bool")
+                        }
+                    }
+                    "table_max_cell_width" => {
+                        if let Ok(i) = value.as_i64() {
+                            config.table_max_cell_width = i;
+                        } else {
+                            eprintln!("$config.table_max_cell_width is not an integer")
+                        }
+                    }
+                    "table_collapse_depth" => {
+                        if let Ok(i) = value.as_i64() {
+                            config.table_collapse_depth = i;
+                        } else {
+                            eprintln!("$config.table_collapse_depth is not an integer")
+                        }
+                    }
                     "log_level" => {
                         if let Ok(v) = value.as_string() {
                             config.log_level = v.to_lowercase();
@@ -243,6 +299,27 @@ impl Value {
                             eprintln!("$config.keybindings is not a valid keybindings list")
                         }
                     }
+                    "ls_newest_first" => {
+                        if let Ok(b) = value.as_bool() {
+                            config.ls_newest_first = b;
+                        } else {
+                            eprintln!("$config.ls_newest_first is not a bool")
+                        }
+                    }
+                    "hooks" => {
+                        if let Ok(hooks) = create_hooks(value) {
+                            config.hooks = hooks;
+                        } else {
+                            eprintln!("$config.hooks is not a record of blocks")
+                        }
+                    }
+                    "strict_pipeline_types" => {
+                        if let Ok(b) = value.as_bool() {
+                            config.strict_pipeline_types = b;
+                        } else {
+                            eprintln!("$config.strict_pipeline_types is not a bool")
+                        }
+                    }
                     x => {
                         eprintln!("$config.{} is an unknown config setting", x)
                     }
@@ -256,6 +333,16 @@ impl Value {
     }
 }
 
+fn create_hooks(value: &Value) -> Result<HashMap<String, (BlockId, Span)>, ShellError> {
+    let (commands, blocks) = value.as_record()?;
+
+    commands
+        .iter()
+        .zip(blocks)
+        .map(|(command, block)| Ok((command.clone(), (block.as_block()?, block.span()?))))
+        .collect()
+}
+
 fn create_map(value: &Value, config: &Config) -> Result<HashMap<String, Value>, ShellError> {
     let (cols, inner_vals) = value.as_record()?;
     let mut hm: HashMap<String, Value> = HashMap::new();
@@ -11,10 +11,7 @@ use std::{
 
 use crate::Value;
 
-use std::path::Path;
-
-#[cfg(feature = "plugin")]
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 // Tells whether a decl etc. is visible or not
 #[derive(Debug, Clone)]
@@ -145,6 +142,12 @@ pub struct EngineState {
     pub scope: im::Vector<ScopeFrame>,
     pub ctrlc: Option<Arc<AtomicBool>>,
     pub env_vars: im::HashMap<String, Value>,
+    /// The config file the shell was started with, if any
+    pub config_path: Option<PathBuf>,
+    /// The env file the shell was started with, if any
+    pub env_path: Option<PathBuf>,
+    /// Nanoseconds from the start of the process until it was ready to run code, -1 until then
+    pub startup_time: i64,
     #[cfg(feature = "plugin")]
     pub plugin_signatures: Option<PathBuf>,
 }
@@ -174,6 +177,9 @@ impl EngineState {
             scope: im::vector![ScopeFrame::new()],
             ctrlc: None,
             env_vars: im::HashMap::new(),
+            config_path: None,
+            env_path: None,
+            startup_time: -1,
             #[cfg(feature = "plugin")]
             plugin_signatures: None,
         }
@@ -439,6 +445,14 @@ impl EngineState {
         panic!("internal error: span missing in file contents cache")
     }
 
+    /// Like `get_span_contents`, for spans which may not come from any parsed source
+    pub fn find_span_contents(&self, span: &Span) -> Option<&[u8]> {
+        self.file_contents
+            .iter()
+            .find(|(_, start, finish)| span.start >= *start && span.end <= *finish)
+            .map(|(contents, start, _)| &contents[(span.start - start)..(span.end - start)])
+    }
+
     pub fn get_var(&self, var_id: VarId) -> &Type {
         self.vars
             .get(var_id)
@@ -597,6 +611,12 @@ impl Default for EngineState {
 pub struct StateWorkingSet<'a> {
     pub permanent_state: &'a EngineState,
     pub delta: StateDelta,
+    /// How many `for`/`while` bodies enclose what is being parsed, so `break` and `continue`
+    /// can be rejected outside of them
+    pub loop_depth: usize,
+    /// The spans of the bodies of the `for`/`while` calls being parsed. Only the block at one of
+    /// these raises `loop_depth`, not the condition or the list that is iterated over.
+    pub loop_bodies: Vec<Span>,
 }
 
 /// A delta (or change set) between the current global state and a possible future global state. Deltas
@@ -665,6 +685,8 @@ impl<'a> StateWorkingSet<'a> {
         Self {
             delta: StateDelta::new(),
             permanent_state,
+            loop_depth: 0,
+            loop_bodies: vec![],
         }
     }
 
@@ -1,7 +1,9 @@
 use std::collections::{HashMap, HashSet};
+use std::sync::mpsc::Receiver;
+use std::sync::{Arc, Mutex};
 
 use crate::engine::EngineState;
-use crate::{Config, ShellError, Value, VarId, CONFIG_VARIABLE_ID};
+use crate::{Config, ShellError, Span, Value, VarId, CONFIG_VARIABLE_ID};
 
 /// A runtime value stack used during evaluation
 ///
@@ -29,6 +31,41 @@ pub struct Stack {
     /// Tells which environment variables from engine state are hidden. We don't need to track the
     /// env vars in the stack since we can just delete them.
     pub env_hidden: HashSet<String>,
+    /// Exit status of the last external started on this stack, sent once the external has been
+    /// waited on
+    pub pending_exit_code: Option<Arc<Mutex<Receiver<i64>>>>,
+    /// Where `break` and `continue` tell the loop this stack runs the body of what to do. Only
+    /// loop bodies, and the `if` blocks inside them, have one.
+    pub loop_signal: Option<LoopSignal>,
+}
+
+/// What `break` or `continue` asks of the loop they're used in
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LoopControl {
+    Break,
+    Continue,
+}
+
+/// The loop control raised in a loop body, shared by the stacks of the blocks inside it. Once
+/// it's raised, the rest of the body is skipped, and the loop takes it after the iteration.
+#[derive(Debug, Clone, Default)]
+pub struct LoopSignal(Arc<Mutex<Option<LoopControl>>>);
+
+impl LoopSignal {
+    pub fn raise(&self, control: LoopControl) {
+        if let Ok(mut signal) = self.0.lock() {
+            *signal = Some(control);
+        }
+    }
+
+    pub fn is_raised(&self) -> bool {
+        self.0.lock().map_or(false, |signal| signal.is_some())
+    }
+
+    /// Take the raised control, so the next iteration starts afresh
+    pub fn take(&self) -> Option<LoopControl> {
+        self.0.lock().ok().and_then(|mut signal| signal.take())
+    }
 }
 
 impl Default for Stack {
@@ -43,6 +80,8 @@ impl Stack {
             vars: HashMap::new(),
             env_vars: vec![],
             env_hidden: HashSet::new(),
+            pending_exit_code: None,
+            loop_signal: None,
         }
     }
 
@@ -57,6 +96,43 @@ impl Stack {
         }
     }
 
+    /// Take a detached copy of the stack for speculative evaluation
+    ///
+    /// The snapshot sees the same variables and environment as this stack, but any mutation made
+    /// while evaluating against it (new variables, `let-env`, hidden env vars) stays in the
+    /// snapshot. Completions and other read-only evaluations should run against a snapshot.
+    pub fn snapshot(&self) -> Stack {
+        self.clone()
+    }
+
+    /// Throw away every change made to the stack since `snapshot` was taken
+    pub fn restore(&mut self, snapshot: Stack) {
+        *self = snapshot;
+    }
+
+    /// Remember the external that was just started, so its exit status can be recorded once the
+    /// statement it ran in has finished
+    pub fn set_pending_exit_code(&mut self, exit_code: Receiver<i64>) {
+        self.pending_exit_code = Some(Arc::new(Mutex::new(exit_code)));
+    }
+
+    /// Wait for the last external started on this stack and record its exit status in
+    /// `$env.LAST_EXIT_CODE`. Returns None if no external ran since the last update.
+    pub fn update_last_exit_code(&mut self) -> Option<i64> {
+        let pending = self.pending_exit_code.take()?;
+        let exit_code = pending.lock().ok()?.recv().ok()?;
+
+        self.add_env_var(
+            "LAST_EXIT_CODE".into(),
+            Value::Int {
+                val: exit_code,
+                span: Span { start: 0, end: 0 },
+            },
+        );
+
+        Some(exit_code)
+    }
+
     pub fn get_var(&self, var_id: VarId) -> Result<Value, ShellError> {
         if let Some(v) = self.vars.get(&var_id) {
             return Ok(v.clone());
@@ -83,6 +159,13 @@ impl Stack {
         }
     }
 
+    /// Whether `break` or `continue` was used in the loop body this stack is running
+    pub fn loop_control_raised(&self) -> bool {
+        self.loop_signal
+            .as_ref()
+            .map_or(false, LoopSignal::is_raised)
+    }
+
     pub fn captures_to_stack(&self, captures: &HashMap<VarId, Value>) -> Stack {
         let mut output = Stack::new();
 
@@ -1,3 +1,5 @@
+use std::io::Write;
+use std::path::PathBuf;
 use std::sync::{atomic::AtomicBool, Arc};
 
 use crate::{
@@ -49,6 +51,12 @@ pub struct PipelineMetadata {
 #[derive(Debug, Clone)]
 pub enum DataSource {
     Ls,
+    /// A calendar from `cal`, with the row and column of today's cell when it's in the calendar
+    Calendar {
+        today: Option<(usize, String)>,
+    },
+    /// The contents of a file, from `open`
+    FilePath(PathBuf),
 }
 
 impl PipelineData {
@@ -137,6 +145,56 @@ impl PipelineData {
         }
     }
 
+    /// Write the data to `writer` a piece at a time, as it comes in, so a stream is never
+    /// collected in memory. Binary is written as it is, text as UTF-8 and other values as their
+    /// string form. An error value in the data stops the writing and is returned.
+    pub fn drain_to_writer(
+        self,
+        writer: &mut impl Write,
+        config: &Config,
+    ) -> Result<(), ShellError> {
+        fn write_bytes(writer: &mut impl Write, bytes: &[u8]) -> Result<(), ShellError> {
+            writer
+                .write_all(bytes)
+                .map_err(|err| ShellError::IOError(err.to_string()))
+        }
+
+        fn write_value(
+            writer: &mut impl Write,
+            value: Value,
+            config: &Config,
+        ) -> Result<(), ShellError> {
+            match value {
+                Value::Binary { val, .. } => write_bytes(writer, &val),
+                Value::String { val, .. } => write_bytes(writer, val.as_bytes()),
+                Value::Error { error } => Err(error),
+                value => write_bytes(writer, value.into_string("", config).as_bytes()),
+            }
+        }
+
+        match self {
+            PipelineData::Value(value, ..) => write_value(writer, value, config),
+            PipelineData::ListStream(stream, ..) => {
+                for value in stream {
+                    write_value(writer, value, config)?;
+                }
+                Ok(())
+            }
+            PipelineData::StringStream(stream, ..) => {
+                for chunk in stream {
+                    write_bytes(writer, chunk?.as_bytes())?;
+                }
+                Ok(())
+            }
+            PipelineData::ByteStream(stream, ..) => {
+                for chunk in stream {
+                    write_bytes(writer, &chunk?)?;
+                }
+                Ok(())
+            }
+        }
+    }
+
     pub fn follow_cell_path(
         self,
         cell_path: &[PathMember],
@@ -263,7 +321,9 @@ impl PipelineData {
                 Ok(vals.into_iter().filter(f).into_pipeline_data(ctrlc))
             }
             PipelineData::ListStream(stream, ..) => Ok(stream.filter(f).into_pipeline_data(ctrlc)),
+            // Text is filtered a line at a time, however it was chunked on the way in
             PipelineData::StringStream(stream, span, ..) => Ok(stream
+                .into_lines()
                 .map(move |x| match x {
                     Ok(s) => Value::String { val: s, span },
                     Err(err) => Value::Error { error: err },
@@ -2,7 +2,7 @@ use miette::Diagnostic;
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
-use crate::{ast::Operator, Span, Type};
+use crate::{ast::Operator, Span, Type, Value};
 
 /// The fundamental error type for the evaluation engine. These cases represent different kinds of errors
 /// the evaluator might face, along with helpful spans to label. An error renderer will take this error value
@@ -109,7 +109,10 @@ pub enum ShellError {
 
     #[error("Division by zero.")]
     #[diagnostic(code(nu::shell::division_by_zero), url(docsrs))]
-    DivisionByZero(#[label("division by zero")] Span),
+    DivisionByZero(
+        #[label("division by zero")] Span,
+        #[label("divisor is zero")] Span,
+    ),
 
     #[error("Can't convert range to countable values")]
     #[diagnostic(code(nu::shell::range_to_countable), url(docsrs))]
@@ -134,6 +137,13 @@ pub enum ShellError {
         #[label = "value originates here"] Span,
     ),
 
+    #[error("Column already exists")]
+    #[diagnostic(code(nu::shell::column_already_exists), url(docsrs))]
+    ColumnAlreadyExists(
+        #[label = "column already exists"] Span,
+        #[label = "value originates here"] Span,
+    ),
+
     #[error("Not a list value")]
     #[diagnostic(code(nu::shell::not_a_list), url(docsrs))]
     NotAList(
@@ -258,6 +268,115 @@ pub enum ShellError {
     #[error("{0}")]
     #[diagnostic(help("{1}"))]
     LabeledError(String, String),
+
+    /// `break` or `continue` was run where there's no loop for it to control
+    #[error("{0} used outside of a loop")]
+    #[diagnostic(
+        code(nu::shell::loop_control_outside_loop),
+        url(docsrs),
+        help("break and continue only work in the body of a 'for' or 'while' loop, not in blocks passed to commands like 'each'")
+    )]
+    LoopControlOutsideLoop(String, #[label = "used outside of a loop"] Span),
+
+    /// An error rebuilt from its record form, keeping the kind of the error it came from
+    #[error("{msg}")]
+    #[diagnostic()]
+    ErrorValue {
+        kind: String,
+        msg: String,
+        label: String,
+        #[label("{label}")]
+        span: Span,
+    },
+}
+
+impl ShellError {
+    /// The record form of the error, with `type`, `kind`, `msg`, `label` and `span` columns.
+    ///
+    /// The span column holds the span of the first label, or `span` if the error has no label.
+    pub fn to_record(&self, span: Span) -> Value {
+        let kind = match self {
+            ShellError::ErrorValue { kind, .. } => kind.clone(),
+            _ => self
+                .code()
+                .map(|code| code.to_string())
+                .unwrap_or_else(|| "nu::shell::error".into()),
+        };
+
+        let first_label = self.labels().and_then(|mut labels| labels.next());
+
+        let (label, error_span) = match first_label {
+            Some(labeled) => (
+                labeled.label().unwrap_or_default().to_string(),
+                Span::new(labeled.offset(), labeled.offset() + labeled.len()),
+            ),
+            // Errors without a label usually carry their details as help text
+            None => (
+                self.help().map(|help| help.to_string()).unwrap_or_default(),
+                span,
+            ),
+        };
+
+        Value::Record {
+            cols: vec![
+                "type".into(),
+                "kind".into(),
+                "msg".into(),
+                "label".into(),
+                "span".into(),
+            ],
+            vals: vec![
+                Value::string("error", span),
+                Value::string(kind, span),
+                Value::string(self.to_string(), span),
+                Value::string(label, span),
+                Value::Record {
+                    cols: vec!["start".into(), "end".into()],
+                    vals: vec![
+                        Value::int(error_span.start as i64, span),
+                        Value::int(error_span.end as i64, span),
+                    ],
+                    span,
+                },
+            ],
+            span,
+        }
+    }
+
+    /// Get the error back out of a value so it can be raised again.
+    ///
+    /// Accepts either an error value or an error in its record form, as made by `to_record`.
+    pub fn from_value(value: &Value) -> Option<ShellError> {
+        match value {
+            Value::Error { error } => Some(error.clone()),
+            Value::Record { .. } => {
+                let string = |name: &str| {
+                    value
+                        .get_data_by_key(name)
+                        .and_then(|val| val.as_string().ok())
+                };
+
+                if string("type")? != "error" {
+                    return None;
+                }
+
+                let span = value.get_data_by_key("span")?;
+                let offset = |name: &str| {
+                    span.get_data_by_key(name)
+                        .and_then(|val| val.as_integer().ok())
+                        .map(|val| val as usize)
+                };
+
+                Some(ShellError::ErrorValue {
+                    kind: string("kind")?,
+                    msg: string("msg")?,
+                    label: string("label").unwrap_or_default(),
+                    span: Span::new(offset("start")?, offset("end")?),
+                })
+            }
+            _ => None,
+        }
+    }
 }
 
 impl From<std::io::Error> for ShellError {
@@ -8,6 +8,7 @@ use crate::engine::Stack;
 use crate::BlockId;
 use crate::PipelineData;
 use crate::SyntaxShape;
+use crate::Type;
 use crate::VarId;
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -51,6 +52,7 @@ pub enum Category {
     Viewers,
     Hash,
     Generators,
+    Bits,
     Custom(String),
 }
 
@@ -76,6 +78,7 @@ impl std::fmt::Display for Category {
             Category::Viewers => "viewers",
             Category::Hash => "hash",
             Category::Generators => "generators",
+            Category::Bits => "bits",
             Category::Custom(name) => name,
         };
 
@@ -96,6 +99,16 @@ pub struct Signature {
     pub creates_scope: bool,
     // Signature category used to classify commands stored in the list of declarations
     pub category: Category,
+    /// What the command expects to be piped in, checked by the parser. Unknown takes anything.
+    #[serde(default = "unknown_type")]
+    pub input_type: Type,
+    /// What the command hands to the next one in the pipeline
+    #[serde(default = "unknown_type")]
+    pub output_type: Type,
+}
+
+fn unknown_type() -> Type {
+    Type::Unknown
 }
 
 impl PartialEq for Signature {
@@ -134,6 +147,8 @@ impl Signature {
             is_filter: false,
             creates_scope: false,
             category: Category::Default,
+            input_type: Type::Unknown,
+            output_type: Type::Unknown,
         }
     }
     pub fn build(name: impl Into<String>) -> Signature {
@@ -146,6 +161,13 @@ impl Signature {
         self
     }
 
+    /// Declare what the command takes from the pipeline and what it gives out
+    pub fn input_output_type(mut self, input_type: Type, output_type: Type) -> Signature {
+        self.input_type = input_type;
+        self.output_type = output_type;
+        self
+    }
+
     /// Add a required positional argument to the signature
     pub fn required(
         mut self,
@@ -49,6 +49,9 @@ pub enum SyntaxShape {
     /// A table is allowed, eg `[first second]`
     List(Box<SyntaxShape>),
 
+    /// A binary literal is allowed, eg `0x[de ad be ef]`
+    Binary,
+
     /// A filesize value is allowed, eg `10kb`
     Filesize,
 
@@ -91,6 +94,7 @@ impl SyntaxShape {
     pub fn to_type(&self) -> Type {
         match self {
             SyntaxShape::Any => Type::Unknown,
+            SyntaxShape::Binary => Type::Binary,
             SyntaxShape::Block(_) => Type::Block,
             SyntaxShape::CellPath => Type::Unknown,
             SyntaxShape::Custom(custom, _) => custom.to_type(),
@@ -143,6 +147,7 @@ impl Display for SyntaxShape {
             SyntaxShape::Table => write!(f, "table"),
             SyntaxShape::List(x) => write!(f, "list<{}>", x),
             SyntaxShape::Record => write!(f, "record"),
+            SyntaxShape::Binary => write!(f, "binary"),
             SyntaxShape::Filesize => write!(f, "filesize"),
             SyntaxShape::Duration => write!(f, "duration"),
             SyntaxShape::Operator => write!(f, "operator"),
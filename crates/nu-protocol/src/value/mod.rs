@@ -17,6 +17,7 @@ pub use stream::*;
 use sys_locale::get_locale;
 pub use unit::*;
 
+use std::cell::RefCell;
 use std::collections::HashMap;
 use std::path::PathBuf;
 use std::{cmp::Ordering, fmt::Debug};
@@ -29,6 +30,14 @@ pub use custom_value::CustomValue;
 
 use crate::ShellError;
 
+/// Upper bound on how many compiled patterns `=~` and `!~` keep around
+const REGEX_CACHE_CAPACITY: usize = 256;
+
+thread_local! {
+    // Patterns are usually literals evaluated once per row, so compile each one only once
+    static REGEX_CACHE: RefCell<HashMap<String, regex::Regex>> = RefCell::new(HashMap::new());
+}
+
 /// Core structured values that pass through the pipeline in engine-q
 #[derive(Debug, Serialize, Deserialize)]
 pub enum Value {
@@ -413,6 +422,37 @@ impl Value {
         }
     }
 
+    /// Like `into_abbreviated_string`, but records and lists are written out inline for `depth`
+    /// levels before they're summarized. Values are trees, so the depth bounds the work.
+    pub fn into_nested_string(&self, config: &Config, depth: usize) -> String {
+        if depth == 0 {
+            return self.into_abbreviated_string(config);
+        }
+
+        match self {
+            Value::List { vals, .. } => format!(
+                "[{}]",
+                vals.iter()
+                    .map(|val| val.into_nested_string(config, depth - 1))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            Value::Record { cols, vals, .. } => format!(
+                "{{{}}}",
+                cols.iter()
+                    .zip(vals)
+                    .map(|(col, val)| format!(
+                        "{}: {}",
+                        col,
+                        val.into_nested_string(config, depth - 1)
+                    ))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            _ => self.into_abbreviated_string(config),
+        }
+    }
+
     /// Convert Value into string. Note that Streams will be consumed.
     pub fn into_abbreviated_string(&self, config: &Config) -> String {
         match self {
@@ -517,6 +557,17 @@ impl Value {
         }
     }
 
+    /// The first error held by the value, itself or anywhere inside its lists and records
+    pub fn find_error(&self) -> Option<&ShellError> {
+        match self {
+            Value::Error { error } => Some(error),
+            Value::List { vals, .. } | Value::Record { vals, .. } => {
+                vals.iter().find_map(|val| val.find_error())
+            }
+            _ => None,
+        }
+    }
+
     /// Create a new `Nothing` value
     pub fn nothing(span: Span) -> Value {
         Value::Nothing { span }
@@ -612,6 +663,14 @@ impl Value {
                     Value::CustomValue { val, .. } => {
                         current = val.follow_path_string(column_name.clone(), *origin_span)?;
                     }
+                    // Errors can be looked into through their record form, any other
+                    // column raises the error itself
+                    Value::Error { error } => {
+                        match error.to_record(*origin_span).get_data_by_key(column_name) {
+                            Some(found) => current = found,
+                            None => return Err(error.clone()),
+                        }
+                    }
                     x => {
                         return Err(ShellError::IncompatiblePathAccess(
                             format!("{}", x.get_type()),
@@ -625,6 +684,39 @@ impl Value {
         Ok(current)
     }
 
+    /// Follow a given column path like `follow_cell_path`, using `default` wherever the path leads
+    /// to a missing or null value. Rows of a table are followed one by one, so only the rows
+    /// lacking the column get the default. Errors are passed on, not replaced.
+    pub fn follow_cell_path_or(self, cell_path: &[PathMember], default: &Value) -> Value {
+        if let Value::Error { .. } = self {
+            return self;
+        }
+
+        let (member, rest) = match cell_path.split_first() {
+            Some(split) => split,
+            None => {
+                return match self {
+                    Value::Nothing { .. } => default.clone(),
+                    value => value,
+                }
+            }
+        };
+
+        match (self, member) {
+            (Value::List { vals, span }, PathMember::String { .. }) => Value::List {
+                vals: vals
+                    .into_iter()
+                    .map(|val| val.follow_cell_path_or(cell_path, default))
+                    .collect(),
+                span,
+            },
+            (value, member) => match value.follow_cell_path(std::slice::from_ref(member)) {
+                Ok(next) => next.follow_cell_path_or(rest, default),
+                Err(_) => default.clone(),
+            },
+        }
+    }
+
     /// Follow a given column path into the value: for example accessing nth elements in a stream or list
     pub fn update_cell_path(
         &mut self,
@@ -641,6 +733,33 @@ impl Value {
         }
     }
 
+    /// Replace the data at the cell path, failing if it doesn't exist yet
+    pub fn update_data_at_cell_path(
+        &mut self,
+        cell_path: &[PathMember],
+        new_val: Value,
+    ) -> Result<(), ShellError> {
+        self.clone().follow_cell_path(cell_path)?;
+        self.replace_data_at_cell_path(cell_path, new_val)
+    }
+
+    /// Add the data at the cell path, failing if something is already there
+    pub fn insert_data_at_cell_path(
+        &mut self,
+        cell_path: &[PathMember],
+        new_val: Value,
+    ) -> Result<(), ShellError> {
+        if let Some(PathMember::String { span, .. } | PathMember::Int { span, .. }) =
+            cell_path.last()
+        {
+            if self.clone().follow_cell_path(cell_path).is_ok() {
+                return Err(ShellError::ColumnAlreadyExists(*span, self.span()?));
+            }
+        }
+
+        self.replace_data_at_cell_path(cell_path, new_val)
+    }
+
     pub fn replace_data_at_cell_path(
         &mut self,
         cell_path: &[PathMember],
@@ -820,7 +939,15 @@ impl PartialOrd for Value {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
         // Compare two floating point numbers. The decision interval for equality is dynamically
         // scaled as the value being compared increases in magnitude.
+        // NaN sorts after every other number and is equal to itself, so floats always compare.
         fn compare_floats(val: f64, other: f64) -> Option<Ordering> {
+            match (val.is_nan(), other.is_nan()) {
+                (true, true) => return Some(Ordering::Equal),
+                (true, false) => return Some(Ordering::Greater),
+                (false, true) => return Some(Ordering::Less),
+                (false, false) => {}
+            }
+
             let prec = f64::EPSILON.max(val.abs() * f64::EPSILON);
 
             if (other - val).abs() < prec {
@@ -836,9 +963,7 @@ impl PartialOrd for Value {
             (Value::Float { val: lhs, .. }, Value::Float { val: rhs, .. }) => {
                 compare_floats(*lhs, *rhs)
             }
-            (Value::Date { val: lhs, .. }, Value::Date { val: rhs, .. }) => {
-                lhs.date().to_string().partial_cmp(&rhs.date().to_string())
-            }
+            (Value::Date { val: lhs, .. }, Value::Date { val: rhs, .. }) => lhs.partial_cmp(rhs),
             (Value::String { val: lhs, .. }, Value::String { val: rhs, .. }) => {
                 lhs.partial_cmp(rhs)
             }
@@ -874,12 +999,125 @@ impl PartialOrd for Value {
                 lhs.partial_cmp(rhs)
             }
             (Value::CustomValue { val: lhs, .. }, rhs) => lhs.partial_cmp(rhs),
+            // Only the custom value knows how to compare, so it's asked the other way around
+            (lhs, Value::CustomValue { val: rhs, .. }) => {
+                rhs.partial_cmp(lhs).map(Ordering::reverse)
+            }
             (Value::Nothing { .. }, Value::Nothing { .. }) => Some(Ordering::Equal),
             (_, _) => None,
         }
     }
 }
 
+impl Value {
+    /// Where a type sorts relative to the others when two values can't be compared directly:
+    /// bool, numbers (int and float together), filesize, duration, date, string, binary, range,
+    /// list, record, block, cell path, custom values, errors and finally nothing.
+    fn type_rank(&self) -> u8 {
+        match self {
+            Value::Bool { .. } => 0,
+            Value::Int { .. } | Value::Float { .. } => 1,
+            Value::Filesize { .. } => 2,
+            Value::Duration { .. } => 3,
+            Value::Date { .. } => 4,
+            Value::String { .. } => 5,
+            Value::Binary { .. } => 6,
+            Value::Range { .. } => 7,
+            Value::List { .. } => 8,
+            Value::Record { .. } => 9,
+            Value::Block { .. } => 10,
+            Value::CellPath { .. } => 11,
+            Value::CustomValue { .. } => 12,
+            Value::Error { .. } => 13,
+            Value::Nothing { .. } => 14,
+        }
+    }
+
+    /// A total ordering over all values, for sorting and grouping data of mixed types.
+    ///
+    /// Numbers compare by value across ints and floats. Unlike `partial_cmp`, floats compare
+    /// exactly rather than within a tolerance, as `f64::total_cmp` does, except that -0.0 equals
+    /// 0.0 and NaN sorts after every other number. Otherwise it agrees with `partial_cmp`
+    /// whenever that returns an ordering. Values `partial_cmp` can't order are compared by their
+    /// type's rank (see `type_rank`), so nothing always sorts last. Lists and records compare
+    /// element by element, records by their columns first.
+    pub fn total_cmp(&self, other: &Value) -> Ordering {
+        // Every NaN is the same positive NaN, and -0.0 is 0.0, so they sort together
+        fn canonical(val: f64) -> f64 {
+            if val.is_nan() {
+                f64::NAN.abs()
+            } else if val == 0.0 {
+                0.0
+            } else {
+                val
+            }
+        }
+
+        fn compare_int_float(int: i64, float: f64) -> Ordering {
+            // The cast can round the int, so a tie is checked again exactly. A float equal to a
+            // rounded int is a whole number, so it converts to i128 without loss.
+            match (int as f64).total_cmp(&canonical(float)) {
+                Ordering::Equal => (int as i128).cmp(&(float as i128)),
+                ordering => ordering,
+            }
+        }
+
+        fn compare_lists(lhs: &[Value], rhs: &[Value]) -> Ordering {
+            lhs.iter()
+                .zip(rhs)
+                .map(|(lhs, rhs)| lhs.total_cmp(rhs))
+                .find(|ordering| ordering.is_ne())
+                .unwrap_or_else(|| lhs.len().cmp(&rhs.len()))
+        }
+
+        match (self, other) {
+            (Value::Float { val: lhs, .. }, Value::Float { val: rhs, .. }) => {
+                canonical(*lhs).total_cmp(&canonical(*rhs))
+            }
+            (Value::Int { val: lhs, .. }, Value::Float { val: rhs, .. }) => {
+                compare_int_float(*lhs, *rhs)
+            }
+            (Value::Float { val: lhs, .. }, Value::Int { val: rhs, .. }) => {
+                compare_int_float(*rhs, *lhs).reverse()
+            }
+            (Value::List { vals: lhs, .. }, Value::List { vals: rhs, .. }) => {
+                compare_lists(lhs, rhs)
+            }
+            (
+                Value::Record {
+                    cols: lhs_cols,
+                    vals: lhs,
+                    ..
+                },
+                Value::Record {
+                    cols: rhs_cols,
+                    vals: rhs,
+                    ..
+                },
+            ) => lhs_cols.cmp(rhs_cols).then_with(|| compare_lists(lhs, rhs)),
+            (Value::Range { val: lhs, .. }, Value::Range { val: rhs, .. }) => lhs
+                .from
+                .total_cmp(&rhs.from)
+                .then_with(|| lhs.to.total_cmp(&rhs.to))
+                .then_with(|| lhs.incr.total_cmp(&rhs.incr))
+                .then_with(|| {
+                    let inclusive = |range: &Range| {
+                        matches!(range.inclusion, crate::ast::RangeInclusion::Inclusive)
+                    };
+                    inclusive(lhs).cmp(&inclusive(rhs))
+                }),
+            (Value::Block { val: lhs, .. }, Value::Block { val: rhs, .. }) => lhs.cmp(rhs),
+            (Value::CellPath { val: lhs, .. }, Value::CellPath { val: rhs, .. }) => {
+                lhs.into_string().cmp(&rhs.into_string())
+            }
+            _ => match self.partial_cmp(other) {
+                Some(ordering) => ordering,
+                None => self.type_rank().cmp(&other.type_rank()),
+            },
+        }
+    }
+}
+
 impl PartialEq for Value {
     fn eq(&self, other: &Self) -> bool {
         self.partial_cmp(other).map_or(false, Ordering::is_eq)
@@ -1069,16 +1307,25 @@ impl Value {
         }
     }
     pub fn div(&self, op: Span, rhs: &Value) -> Result<Value, ShellError> {
-        let span = span(&[self.span()?, rhs.span()?]);
+        let rhs_span = rhs.span()?;
+        let span = span(&[self.span()?, rhs_span]);
 
         match (self, rhs) {
             (Value::Int { val: lhs, .. }, Value::Int { val: rhs, .. }) => {
                 if *rhs != 0 {
-                    if lhs % rhs == 0 {
-                        Ok(Value::Int {
-                            val: lhs / rhs,
+                    if lhs.checked_rem(*rhs) == Some(0) {
+                        match lhs.checked_div(*rhs) {
+                            Some(val) => Ok(Value::Int { val, span }),
+                            None => Err(ShellError::OperatorOverflow(
+                                "division operation overflowed".into(),
+                                span,
+                            )),
+                        }
+                    } else if *rhs == -1 {
+                        Err(ShellError::OperatorOverflow(
+                            "division operation overflowed".into(),
                             span,
-                        })
+                        ))
                     } else {
                         Ok(Value::Float {
                             val: (*lhs as f64) / (*rhs as f64),
@@ -1086,7 +1333,7 @@ impl Value {
                         })
                     }
                 } else {
-                    Err(ShellError::DivisionByZero(op))
+                    Err(ShellError::DivisionByZero(op, rhs_span))
                 }
             }
             (Value::Int { val: lhs, .. }, Value::Float { val: rhs, .. }) => {
@@ -1096,7 +1343,7 @@ impl Value {
                         span,
                     })
                 } else {
-                    Err(ShellError::DivisionByZero(op))
+                    Err(ShellError::DivisionByZero(op, rhs_span))
                 }
             }
             (Value::Float { val: lhs, .. }, Value::Int { val: rhs, .. }) => {
@@ -1106,7 +1353,7 @@ impl Value {
                         span,
                     })
                 } else {
-                    Err(ShellError::DivisionByZero(op))
+                    Err(ShellError::DivisionByZero(op, rhs_span))
                 }
             }
             (Value::Float { val: lhs, .. }, Value::Float { val: rhs, .. }) => {
@@ -1116,12 +1363,12 @@ impl Value {
                         span,
                     })
                 } else {
-                    Err(ShellError::DivisionByZero(op))
+                    Err(ShellError::DivisionByZero(op, rhs_span))
                 }
             }
             (Value::Filesize { val: lhs, .. }, Value::Filesize { val: rhs, .. }) => {
                 if *rhs != 0 {
-                    if lhs % rhs == 0 {
+                    if lhs.checked_rem(*rhs) == Some(0) {
                         Ok(Value::Int {
                             val: lhs / rhs,
                             span,
@@ -1133,12 +1380,12 @@ impl Value {
                         })
                     }
                 } else {
-                    Err(ShellError::DivisionByZero(op))
+                    Err(ShellError::DivisionByZero(op, rhs_span))
                 }
             }
             (Value::Duration { val: lhs, .. }, Value::Duration { val: rhs, .. }) => {
                 if *rhs != 0 {
-                    if lhs % rhs == 0 {
+                    if lhs.checked_rem(*rhs) == Some(0) {
                         Ok(Value::Int {
                             val: lhs / rhs,
                             span,
@@ -1150,7 +1397,7 @@ impl Value {
                         })
                     }
                 } else {
-                    Err(ShellError::DivisionByZero(op))
+                    Err(ShellError::DivisionByZero(op, rhs_span))
                 }
             }
             (Value::CustomValue { val: lhs, span }, rhs) => {
@@ -1369,38 +1616,52 @@ impl Value {
         }
     }
 
-    pub fn contains(&self, op: Span, rhs: &Value) -> Result<Value, ShellError> {
+    pub fn regex_match(&self, op: Span, rhs: &Value, invert: bool) -> Result<Value, ShellError> {
         let span = span(&[self.span()?, rhs.span()?]);
 
         match (self, rhs) {
-            (Value::String { val: lhs, .. }, Value::String { val: rhs, .. }) => Ok(Value::Bool {
-                val: lhs.contains(rhs),
-                span,
-            }),
-            (Value::CustomValue { val: lhs, span }, rhs) => {
-                lhs.operation(*span, Operator::Contains, op, rhs)
-            }
-            _ => Err(ShellError::OperatorMismatch {
-                op_span: op,
-                lhs_ty: self.get_type(),
-                lhs_span: self.span()?,
-                rhs_ty: rhs.get_type(),
-                rhs_span: rhs.span()?,
-            }),
-        }
-    }
+            (
+                Value::String { val: lhs, .. },
+                Value::String {
+                    val: rhs,
+                    span: rhs_span,
+                },
+            ) => {
+                let is_match = REGEX_CACHE.with(|cache| {
+                    let mut cache = cache.borrow_mut();
 
-    pub fn not_contains(&self, op: Span, rhs: &Value) -> Result<Value, ShellError> {
-        let span = span(&[self.span()?, rhs.span()?]);
+                    if let Some(regex) = cache.get(rhs) {
+                        return Ok(regex.is_match(lhs));
+                    }
 
-        match (self, rhs) {
-            (Value::String { val: lhs, .. }, Value::String { val: rhs, .. }) => Ok(Value::Bool {
-                val: !lhs.contains(rhs),
-                span,
-            }),
-            (Value::CustomValue { val: lhs, span }, rhs) => {
-                lhs.operation(*span, Operator::NotContains, op, rhs)
+                    let regex = regex::Regex::new(rhs).map_err(|e| {
+                        ShellError::UnsupportedInput(format!("invalid regex: {}", e), *rhs_span)
+                    })?;
+                    let is_match = regex.is_match(lhs);
+
+                    if cache.len() >= REGEX_CACHE_CAPACITY {
+                        cache.clear();
+                    }
+                    cache.insert(rhs.clone(), regex);
+
+                    Ok(is_match)
+                })?;
+
+                Ok(Value::Bool {
+                    val: if invert { !is_match } else { is_match },
+                    span,
+                })
             }
+            (Value::CustomValue { val: lhs, span }, rhs) => lhs.operation(
+                *span,
+                if invert {
+                    Operator::NotRegexMatch
+                } else {
+                    Operator::RegexMatch
+                },
+                op,
+                rhs,
+            ),
             _ => Err(ShellError::OperatorMismatch {
                 op_span: op,
                 lhs_ty: self.get_type(),
@@ -1412,49 +1673,56 @@ impl Value {
     }
 
     pub fn modulo(&self, op: Span, rhs: &Value) -> Result<Value, ShellError> {
-        let span = span(&[self.span()?, rhs.span()?]);
+        let rhs_span = rhs.span()?;
+        let span = span(&[self.span()?, rhs_span]);
 
         match (self, rhs) {
-            (Value::Int { val: lhs, .. }, Value::Int { val: rhs, .. }) => {
-                if *rhs != 0 {
-                    Ok(Value::Int {
-                        val: lhs % rhs,
-                        span,
-                    })
-                } else {
-                    Err(ShellError::DivisionByZero(op))
-                }
-            }
+            (Value::Int { val: lhs, .. }, Value::Int { val: rhs, .. }) => Ok(Value::Int {
+                val: floor_mod(*lhs, *rhs, op, rhs_span, span)?,
+                span,
+            }),
             (Value::Int { val: lhs, .. }, Value::Float { val: rhs, .. }) => {
                 if *rhs != 0.0 {
                     Ok(Value::Float {
-                        val: *lhs as f64 % *rhs,
+                        val: floor_mod_float(*lhs as f64, *rhs),
                         span,
                     })
                 } else {
-                    Err(ShellError::DivisionByZero(op))
+                    Err(ShellError::DivisionByZero(op, rhs_span))
                 }
             }
             (Value::Float { val: lhs, .. }, Value::Int { val: rhs, .. }) => {
                 if *rhs != 0 {
                     Ok(Value::Float {
-                        val: *lhs % *rhs as f64,
+                        val: floor_mod_float(*lhs, *rhs as f64),
                         span,
                     })
                 } else {
-                    Err(ShellError::DivisionByZero(op))
+                    Err(ShellError::DivisionByZero(op, rhs_span))
                 }
             }
             (Value::Float { val: lhs, .. }, Value::Float { val: rhs, .. }) => {
                 if *rhs != 0.0 {
                     Ok(Value::Float {
-                        val: lhs % rhs,
+                        val: floor_mod_float(*lhs, *rhs),
                         span,
                     })
                 } else {
-                    Err(ShellError::DivisionByZero(op))
+                    Err(ShellError::DivisionByZero(op, rhs_span))
                 }
             }
+            (Value::Filesize { val: lhs, .. }, Value::Filesize { val: rhs, .. }) => {
+                Ok(Value::Filesize {
+                    val: floor_mod(*lhs, *rhs, op, rhs_span, span)?,
+                    span,
+                })
+            }
+            (Value::Duration { val: lhs, .. }, Value::Duration { val: rhs, .. }) => {
+                Ok(Value::Duration {
+                    val: floor_mod(*lhs, *rhs, op, rhs_span, span)?,
+                    span,
+                })
+            }
             (Value::CustomValue { val: lhs, span }, rhs) => {
                 lhs.operation(*span, Operator::Modulo, op, rhs)
             }
@@ -1516,7 +1784,18 @@ impl Value {
 
         match (self, rhs) {
             (Value::Int { val: lhs, .. }, Value::Int { val: rhs, .. }) => {
-                if let Some(val) = lhs.checked_pow(*rhs as u32) {
+                if *rhs < 0 {
+                    // A negative exponent can't produce an integer. Exponents past what
+                    // `powi` takes fall back to `powf` rather than being truncated
+                    let val = match i32::try_from(*rhs) {
+                        Ok(rhs) => (*lhs as f64).powi(rhs),
+                        Err(_) => (*lhs as f64).powf(*rhs as f64),
+                    };
+                    Ok(Value::Float { val, span })
+                } else if let Some(val) = u32::try_from(*rhs)
+                    .ok()
+                    .and_then(|rhs| lhs.checked_pow(rhs))
+                {
                     Ok(Value::Int { val, span })
                 } else {
                     Err(ShellError::OperatorOverflow(
@@ -1550,6 +1829,163 @@ impl Value {
             }),
         }
     }
+
+    pub fn bit_or(&self, op: Span, rhs: &Value) -> Result<Value, ShellError> {
+        let span = span(&[self.span()?, rhs.span()?]);
+
+        match (self, rhs) {
+            (Value::Int { val: lhs, .. }, Value::Int { val: rhs, .. }) => Ok(Value::Int {
+                val: *lhs | *rhs,
+                span,
+            }),
+            (Value::CustomValue { val: lhs, span }, rhs) => {
+                lhs.operation(*span, Operator::BitOr, op, rhs)
+            }
+            _ => Err(ShellError::OperatorMismatch {
+                op_span: op,
+                lhs_ty: self.get_type(),
+                lhs_span: self.span()?,
+                rhs_ty: rhs.get_type(),
+                rhs_span: rhs.span()?,
+            }),
+        }
+    }
+
+    pub fn bit_xor(&self, op: Span, rhs: &Value) -> Result<Value, ShellError> {
+        let span = span(&[self.span()?, rhs.span()?]);
+
+        match (self, rhs) {
+            (Value::Int { val: lhs, .. }, Value::Int { val: rhs, .. }) => Ok(Value::Int {
+                val: *lhs ^ *rhs,
+                span,
+            }),
+            (Value::CustomValue { val: lhs, span }, rhs) => {
+                lhs.operation(*span, Operator::BitXor, op, rhs)
+            }
+            _ => Err(ShellError::OperatorMismatch {
+                op_span: op,
+                lhs_ty: self.get_type(),
+                lhs_span: self.span()?,
+                rhs_ty: rhs.get_type(),
+                rhs_span: rhs.span()?,
+            }),
+        }
+    }
+
+    pub fn bit_and(&self, op: Span, rhs: &Value) -> Result<Value, ShellError> {
+        let span = span(&[self.span()?, rhs.span()?]);
+
+        match (self, rhs) {
+            (Value::Int { val: lhs, .. }, Value::Int { val: rhs, .. }) => Ok(Value::Int {
+                val: *lhs & *rhs,
+                span,
+            }),
+            (Value::CustomValue { val: lhs, span }, rhs) => {
+                lhs.operation(*span, Operator::BitAnd, op, rhs)
+            }
+            _ => Err(ShellError::OperatorMismatch {
+                op_span: op,
+                lhs_ty: self.get_type(),
+                lhs_span: self.span()?,
+                rhs_ty: rhs.get_type(),
+                rhs_span: rhs.span()?,
+            }),
+        }
+    }
+
+    /// Shift left, filling with zeros. Bits shifted past the sign bit are lost.
+    pub fn bit_shl(&self, op: Span, rhs: &Value) -> Result<Value, ShellError> {
+        let span = span(&[self.span()?, rhs.span()?]);
+
+        match (self, rhs) {
+            (
+                Value::Int { val: lhs, .. },
+                Value::Int {
+                    val: rhs,
+                    span: rhs_span,
+                },
+            ) => match u32::try_from(*rhs)
+                .ok()
+                .and_then(|rhs| lhs.checked_shl(rhs))
+            {
+                Some(val) => Ok(Value::Int { val, span }),
+                None => Err(ShellError::UnsupportedInput(
+                    format!("shift amount must be between 0 and 63, got {}", rhs),
+                    *rhs_span,
+                )),
+            },
+            (Value::CustomValue { val: lhs, span }, rhs) => {
+                lhs.operation(*span, Operator::ShiftLeft, op, rhs)
+            }
+            _ => Err(ShellError::OperatorMismatch {
+                op_span: op,
+                lhs_ty: self.get_type(),
+                lhs_span: self.span()?,
+                rhs_ty: rhs.get_type(),
+                rhs_span: rhs.span()?,
+            }),
+        }
+    }
+
+    /// Arithmetic shift right: the sign bit is copied in, so `-8 bit-shr 1` is `-4`
+    pub fn bit_shr(&self, op: Span, rhs: &Value) -> Result<Value, ShellError> {
+        let span = span(&[self.span()?, rhs.span()?]);
+
+        match (self, rhs) {
+            (
+                Value::Int { val: lhs, .. },
+                Value::Int {
+                    val: rhs,
+                    span: rhs_span,
+                },
+            ) => match u32::try_from(*rhs)
+                .ok()
+                .and_then(|rhs| lhs.checked_shr(rhs))
+            {
+                Some(val) => Ok(Value::Int { val, span }),
+                None => Err(ShellError::UnsupportedInput(
+                    format!("shift amount must be between 0 and 63, got {}", rhs),
+                    *rhs_span,
+                )),
+            },
+            (Value::CustomValue { val: lhs, span }, rhs) => {
+                lhs.operation(*span, Operator::ShiftRight, op, rhs)
+            }
+            _ => Err(ShellError::OperatorMismatch {
+                op_span: op,
+                lhs_ty: self.get_type(),
+                lhs_span: self.span()?,
+                rhs_ty: rhs.get_type(),
+                rhs_span: rhs.span()?,
+            }),
+        }
+    }
+}
+
+/// Integer modulo with the sign of the divisor (so `-5 mod 3` is `1`), matching floored division
+fn floor_mod(lhs: i64, rhs: i64, op: Span, rhs_span: Span, span: Span) -> Result<i64, ShellError> {
+    if rhs == 0 {
+        return Err(ShellError::DivisionByZero(op, rhs_span));
+    }
+
+    match lhs.checked_rem(rhs) {
+        Some(rem) if rem != 0 && ((rem < 0) != (rhs < 0)) => Ok(rem + rhs),
+        Some(rem) => Ok(rem),
+        None => Err(ShellError::OperatorOverflow(
+            "modulo operation overflowed".into(),
+            span,
+        )),
+    }
+}
+
+fn floor_mod_float(lhs: f64, rhs: f64) -> f64 {
+    let rem = lhs % rhs;
+
+    if rem != 0.0 && ((rem < 0.0) != (rhs < 0.0)) {
+        rem + rhs
+    } else {
+        rem
+    }
 }
 
 /// Create a Value::Record from a spanned hashmap
@@ -1637,7 +2073,10 @@ pub fn format_duration(duration: i64) -> String {
     )
 }
 
-fn format_filesize(num_bytes: i64, config: &Config) -> String {
+/// Format a size in bytes the way `filesize_format` and `filesize_metric` in the config ask for.
+/// Commands turning a filesize into text should go through this (or `into_string`), so they all
+/// follow the config.
+pub fn format_filesize(num_bytes: i64, config: &Config) -> String {
     // Allow the user to specify how they want their numbers formatted
     let filesize_format_var = get_config_filesize_format(config);
 
@@ -1,5 +1,6 @@
 use crate::*;
 use std::{
+    collections::VecDeque,
     fmt::Debug,
     sync::{
         atomic::{AtomicBool, Ordering},
@@ -77,6 +78,45 @@ impl StringStream {
             ctrlc,
         }
     }
+
+    /// Re-chunk the stream into lines, so a line split across two chunks comes out whole. The line
+    /// endings (`\n` or `\r\n`) are dropped.
+    pub fn into_lines(self) -> impl Iterator<Item = Result<String, ShellError>> + Send + 'static {
+        let mut stream = self;
+        let mut pending = String::new();
+        let mut lines = VecDeque::new();
+        let mut done = false;
+
+        std::iter::from_fn(move || loop {
+            if let Some(line) = lines.pop_front() {
+                return Some(Ok(line));
+            }
+            if done {
+                return None;
+            }
+
+            match stream.next() {
+                Some(Ok(chunk)) => {
+                    pending.push_str(&chunk);
+                    while let Some(end) = pending.find('\n') {
+                        let mut line: String = pending.drain(..=end).collect();
+                        line.pop();
+                        if line.ends_with('\r') {
+                            line.pop();
+                        }
+                        lines.push_back(line);
+                    }
+                }
+                Some(Err(err)) => return Some(Err(err)),
+                None => {
+                    done = true;
+                    if !pending.is_empty() {
+                        lines.push_back(std::mem::take(&mut pending));
+                    }
+                }
+            }
+        })
+    }
 }
 impl Debug for StringStream {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
@@ -0,0 +1,210 @@
+use nu_protocol::ast::{Operator, PathMember};
+use nu_protocol::{CustomValue, IntoPipelineData, PipelineData, ShellError, Span, Value};
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+
+/// A counter that only its own commands know how to look inside
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Counter {
+    count: i64,
+}
+
+#[typetag::serde]
+impl CustomValue for Counter {
+    fn clone_value(&self, span: Span) -> Value {
+        Value::CustomValue {
+            val: Box::new(self.clone()),
+            span,
+        }
+    }
+
+    fn value_string(&self) -> String {
+        format!("counter at {}", self.count)
+    }
+
+    fn to_base_value(&self, span: Span) -> Result<Value, ShellError> {
+        Ok(Value::Int {
+            val: self.count,
+            span,
+        })
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn follow_path_string(&self, column_name: String, span: Span) -> Result<Value, ShellError> {
+        match column_name.as_str() {
+            "count" => Ok(Value::Int {
+                val: self.count,
+                span,
+            }),
+            _ => Err(ShellError::CantFindColumn(span, span)),
+        }
+    }
+
+    fn partial_cmp(&self, other: &Value) -> Option<Ordering> {
+        match other {
+            Value::CustomValue { val, .. } => val
+                .as_any()
+                .downcast_ref::<Counter>()
+                .map(|other| self.count.cmp(&other.count)),
+            Value::Int { val, .. } => Some(self.count.cmp(val)),
+            _ => None,
+        }
+    }
+
+    fn operation(
+        &self,
+        _lhs_span: Span,
+        operator: Operator,
+        op: Span,
+        right: &Value,
+    ) -> Result<Value, ShellError> {
+        match (operator, right) {
+            (Operator::Plus, Value::Int { val, .. }) => Ok(counter(self.count + val)),
+            _ => Err(ShellError::UnsupportedOperator(operator, op)),
+        }
+    }
+}
+
+fn counter(count: i64) -> Value {
+    Value::CustomValue {
+        val: Box::new(Counter { count }),
+        span: Span::test_data(),
+    }
+}
+
+fn count_of(value: &Value) -> Option<i64> {
+    match value {
+        Value::CustomValue { val, .. } => val
+            .as_any()
+            .downcast_ref::<Counter>()
+            .map(|counter| counter.count),
+        _ => None,
+    }
+}
+
+fn column(name: &str) -> PathMember {
+    PathMember::String {
+        val: name.into(),
+        span: Span::test_data(),
+    }
+}
+
+#[test]
+fn custom_value_downcasts_to_its_type() {
+    assert_eq!(count_of(&counter(3)), Some(3));
+    assert_eq!(count_of(&counter(3).clone()), Some(3));
+}
+
+#[test]
+fn custom_value_follows_cell_paths_it_supports() {
+    let found = counter(3)
+        .follow_cell_path(&[column("count")])
+        .expect("count is a column of counters");
+    assert!(matches!(found, Value::Int { val: 3, .. }));
+
+    let missing = counter(3).follow_cell_path(&[column("size")]);
+    assert!(missing.is_err());
+
+    let by_index = counter(3).follow_cell_path(&[PathMember::Int {
+        val: 0,
+        span: Span::test_data(),
+    }]);
+    assert!(matches!(
+        by_index,
+        Err(ShellError::IncompatiblePathAccess(..))
+    ));
+}
+
+#[test]
+fn custom_values_in_a_list_are_followed_one_by_one() {
+    let list = Value::List {
+        vals: vec![counter(1), counter(2)],
+        span: Span::test_data(),
+    };
+
+    let counts = list
+        .follow_cell_path(&[column("count")])
+        .expect("every counter has a count");
+
+    assert_eq!(counts.into_string(",", &Default::default()), "[1,2]");
+}
+
+#[test]
+fn custom_values_stay_opaque_through_a_pipeline() {
+    let input = Value::List {
+        vals: vec![counter(1), counter(2)],
+        span: Span::test_data(),
+    }
+    .into_pipeline_data();
+
+    let output = input
+        .map(
+            |value| match value.add(Span::test_data(), &Value::test_int(10)) {
+                Ok(value) => value,
+                Err(error) => Value::Error { error },
+            },
+            None,
+        )
+        .expect("a list can be mapped");
+
+    let counts = output
+        .into_iter()
+        .map(|value| count_of(&value))
+        .collect::<Vec<_>>();
+
+    assert_eq!(counts, vec![Some(11), Some(12)]);
+}
+
+#[test]
+fn custom_value_describes_itself() {
+    let value = counter(3);
+
+    assert_eq!(value.get_type().to_string(), "custom");
+    assert_eq!(value.into_string("", &Default::default()), "counter at 3");
+}
+
+#[test]
+fn custom_values_compare_through_the_implementer() {
+    assert_eq!(counter(1).partial_cmp(&counter(2)), Some(Ordering::Less));
+    assert_eq!(counter(1).partial_cmp(&Value::test_string("a")), None);
+}
+
+#[test]
+fn custom_values_compare_the_same_from_either_side() {
+    assert_eq!(
+        counter(1).partial_cmp(&Value::test_int(2)),
+        Some(Ordering::Less)
+    );
+    assert_eq!(
+        Value::test_int(2).partial_cmp(&counter(1)),
+        Some(Ordering::Greater)
+    );
+    assert_eq!(Value::test_string("a").partial_cmp(&counter(1)), None);
+}
+
+#[test]
+fn custom_value_round_trips_through_serde() {
+    let value = counter(7);
+
+    let json = serde_json::to_string(&value).expect("counters serialize");
+    let returned: Value = serde_json::from_str(&json).expect("counters deserialize");
+
+    assert_eq!(count_of(&returned), Some(7));
+    assert!(matches!(
+        returned,
+        Value::CustomValue { span, .. } if span == Span::test_data()
+    ));
+}
+
+#[test]
+fn custom_value_in_a_pipeline_is_a_single_value() {
+    let data: PipelineData = counter(3).into_pipeline_data();
+
+    let values = data.into_iter().collect::<Vec<_>>();
+
+    assert_eq!(values.len(), 1);
+    assert_eq!(count_of(&values[0]), Some(3));
+}
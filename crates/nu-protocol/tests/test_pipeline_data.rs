@@ -0,0 +1,124 @@
+use nu_protocol::{
+    ByteStream, Config, PipelineData, ShellError, Span, StringStream, Value, ValueStream,
+};
+use std::io::Write;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+fn chunked(chunks: &[&str]) -> StringStream {
+    let chunks: Vec<_> = chunks.iter().map(|chunk| Ok(chunk.to_string())).collect();
+    StringStream::from_stream(chunks.into_iter(), None)
+}
+
+#[test]
+fn lines_are_joined_across_chunks() {
+    let lines: Vec<String> = chunked(&["fir", "st\nsec", "ond\r\nthird"])
+        .into_lines()
+        .collect::<Result<_, _>>()
+        .unwrap();
+
+    assert_eq!(lines, vec!["first", "second", "third"]);
+}
+
+#[test]
+fn string_stream_is_filtered_by_line() {
+    let input = PipelineData::StringStream(
+        chunked(&["INFO a\nERR", "OR b\nINFO c\n", "ERROR d"]),
+        Span::test_data(),
+        None,
+    );
+
+    let output: Vec<Value> = input
+        .filter(
+            |value| value.as_string().unwrap_or_default().starts_with("ERROR"),
+            None,
+        )
+        .unwrap()
+        .into_iter()
+        .collect();
+
+    assert_eq!(
+        output,
+        vec![Value::test_string("ERROR b"), Value::test_string("ERROR d")]
+    );
+}
+
+/// Checks each byte against the pattern the stream was made from, without keeping any of them,
+/// and that no more than one chunk was pulled from the stream ahead of what was written
+struct CheckingSink {
+    pulled: Arc<AtomicUsize>,
+    written_chunks: usize,
+    position: usize,
+}
+
+impl Write for CheckingSink {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.written_chunks += 1;
+        assert_eq!(self.pulled.load(Ordering::SeqCst), self.written_chunks);
+
+        for byte in buf {
+            assert_eq!(*byte, (self.position % 251) as u8);
+            self.position += 1;
+        }
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+#[test]
+fn byte_stream_is_drained_a_chunk_at_a_time() {
+    const CHUNK_SIZE: usize = 64 * 1024;
+    const CHUNKS: usize = 1024;
+
+    let pulled = Arc::new(AtomicUsize::new(0));
+    let counter = pulled.clone();
+    let stream = (0..CHUNKS).map(move |chunk| {
+        counter.fetch_add(1, Ordering::SeqCst);
+        let start = chunk * CHUNK_SIZE;
+        Ok((start..start + CHUNK_SIZE)
+            .map(|position| (position % 251) as u8)
+            .collect())
+    });
+
+    let input = PipelineData::ByteStream(
+        ByteStream {
+            stream: Box::new(stream),
+            ctrlc: None,
+        },
+        Span::test_data(),
+        None,
+    );
+    let mut sink = CheckingSink {
+        pulled: pulled.clone(),
+        written_chunks: 0,
+        position: 0,
+    };
+
+    input
+        .drain_to_writer(&mut sink, &Config::default())
+        .unwrap();
+
+    assert_eq!(sink.written_chunks, CHUNKS);
+    assert_eq!(sink.position, CHUNKS * CHUNK_SIZE);
+}
+
+#[test]
+fn draining_stops_at_an_error_value() {
+    let values = vec![
+        Value::test_string("kept"),
+        Value::Error {
+            error: ShellError::IOError("broken".into()),
+        },
+        Value::test_string("dropped"),
+    ];
+    let input = PipelineData::ListStream(ValueStream::from_stream(values.into_iter(), None), None);
+    let mut output = vec![];
+
+    let result = input.drain_to_writer(&mut output, &Config::default());
+
+    assert!(result.is_err());
+    assert_eq!(output, b"kept");
+}
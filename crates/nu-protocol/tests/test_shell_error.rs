@@ -0,0 +1,133 @@
+use nu_protocol::{ast::PathMember, ShellError, Span, Value};
+
+fn column(value: &Value, name: &str) -> String {
+    value
+        .get_data_by_key(name)
+        .and_then(|val| val.as_string().ok())
+        .unwrap_or_default()
+}
+
+#[test]
+fn error_to_record() {
+    let error = ShellError::FileNotFoundCustom("no such file".into(), Span::new(3, 8));
+    let record = error.to_record(Span::test_data());
+
+    assert_eq!(column(&record, "type"), "error");
+    assert_eq!(column(&record, "kind"), "nu::shell::file_not_found");
+    assert_eq!(column(&record, "msg"), "File not found");
+    assert_eq!(column(&record, "label"), "no such file");
+
+    let span = record.get_data_by_key("span").unwrap();
+    assert_eq!(
+        span.get_data_by_key("start").unwrap().as_integer().unwrap(),
+        3
+    );
+    assert_eq!(
+        span.get_data_by_key("end").unwrap().as_integer().unwrap(),
+        8
+    );
+}
+
+#[test]
+fn error_without_label_uses_help() {
+    let error = ShellError::IOError("permission denied".into());
+    let record = error.to_record(Span::new(1, 2));
+
+    assert_eq!(column(&record, "kind"), "nu::shell::io_error");
+    assert_eq!(column(&record, "label"), "permission denied");
+
+    let span = record.get_data_by_key("span").unwrap();
+    assert_eq!(
+        span.get_data_by_key("start").unwrap().as_integer().unwrap(),
+        1
+    );
+}
+
+#[test]
+fn error_record_round_trip() {
+    let error = ShellError::DivisionByZero(Span::new(10, 12), Span::new(13, 14));
+    let record = error.to_record(Span::test_data());
+
+    let raised = ShellError::from_value(&record).expect("record is an error");
+    assert!(matches!(
+        &raised,
+        ShellError::ErrorValue { kind, span, .. }
+            if kind == "nu::shell::division_by_zero" && *span == Span::new(10, 12)
+    ));
+
+    let again = raised.to_record(Span::test_data());
+    for name in ["type", "kind", "msg", "label"] {
+        assert_eq!(column(&record, name), column(&again, name));
+    }
+}
+
+#[test]
+fn error_value_round_trip() {
+    let value = Value::Error {
+        error: ShellError::CommandNotFound(Span::new(0, 3)),
+    };
+
+    assert!(matches!(
+        ShellError::from_value(&value),
+        Some(ShellError::CommandNotFound(span)) if span == Span::new(0, 3)
+    ));
+}
+
+#[test]
+fn non_error_values_are_not_errors() {
+    let record = Value::Record {
+        cols: vec!["type".into()],
+        vals: vec![Value::string("file", Span::test_data())],
+        span: Span::test_data(),
+    };
+
+    assert!(ShellError::from_value(&record).is_none());
+    assert!(ShellError::from_value(&Value::int(1, Span::test_data())).is_none());
+}
+
+#[test]
+fn error_value_columns() {
+    let value = Value::Error {
+        error: ShellError::CommandNotFound(Span::new(0, 3)),
+    };
+
+    let path = |name: &str| {
+        vec![PathMember::String {
+            val: name.into(),
+            span: Span::test_data(),
+        }]
+    };
+
+    let ty = value.clone().follow_cell_path(&path("type")).unwrap();
+    assert_eq!(ty.as_string().unwrap(), "error");
+
+    // Columns that aren't part of the error raise the error itself
+    assert!(matches!(
+        value.follow_cell_path(&path("name")),
+        Err(ShellError::CommandNotFound(_))
+    ));
+}
+
+#[test]
+fn finds_nested_errors() {
+    let error = Value::Error {
+        error: ShellError::DivisionByZero(Span::new(3, 4), Span::new(5, 6)),
+    };
+    let nested = Value::List {
+        vals: vec![
+            Value::int(1, Span::test_data()),
+            Value::Record {
+                cols: vec!["x".into()],
+                vals: vec![error],
+                span: Span::test_data(),
+            },
+        ],
+        span: Span::test_data(),
+    };
+
+    assert!(matches!(
+        nested.find_error(),
+        Some(ShellError::DivisionByZero(..))
+    ));
+    assert!(Value::int(1, Span::test_data()).find_error().is_none());
+}
@@ -0,0 +1,64 @@
+use nu_protocol::engine::{EngineState, Stack};
+use nu_protocol::{Span, Value};
+
+fn string(val: &str) -> Value {
+    Value::String {
+        val: val.into(),
+        span: Span::test_data(),
+    }
+}
+
+#[test]
+fn snapshot_sees_parent_state() {
+    let mut stack = Stack::new();
+    stack.add_var(0, string("var"));
+    stack.add_env_var("FOO".into(), string("foo"));
+
+    let engine_state = EngineState::new();
+    let snapshot = stack.snapshot();
+
+    assert_eq!(snapshot.get_var(0).unwrap(), string("var"));
+    assert_eq!(
+        snapshot.get_env_var(&engine_state, "FOO"),
+        Some(string("foo"))
+    );
+}
+
+#[test]
+fn snapshot_does_not_leak_mutations() {
+    let mut engine_state = EngineState::new();
+    engine_state.env_vars.insert("BAR".into(), string("bar"));
+
+    let mut stack = Stack::new();
+    stack.add_var(0, string("var"));
+    stack.add_env_var("FOO".into(), string("foo"));
+
+    let mut snapshot = stack.snapshot();
+    snapshot.add_var(0, string("changed"));
+    snapshot.add_var(1, string("new"));
+    snapshot.add_env_var("FOO".into(), string("changed"));
+    snapshot.add_env_var("BAZ".into(), string("baz"));
+    snapshot.remove_env_var(&engine_state, "BAR");
+
+    assert_eq!(stack.get_var(0).unwrap(), string("var"));
+    assert!(stack.get_var(1).is_err());
+    assert_eq!(stack.get_env_var(&engine_state, "FOO"), Some(string("foo")));
+    assert_eq!(stack.get_env_var(&engine_state, "BAZ"), None);
+    assert_eq!(stack.get_env_var(&engine_state, "BAR"), Some(string("bar")));
+}
+
+#[test]
+fn restore_discards_changes() {
+    let engine_state = EngineState::new();
+
+    let mut stack = Stack::new();
+    stack.add_env_var("FOO".into(), string("foo"));
+
+    let snapshot = stack.snapshot();
+    stack.add_var(0, string("var"));
+    stack.add_env_var("FOO".into(), string("changed"));
+    stack.restore(snapshot);
+
+    assert!(stack.get_var(0).is_err());
+    assert_eq!(stack.get_env_var(&engine_state, "FOO"), Some(string("foo")));
+}
@@ -1,4 +1,5 @@
 use nu_protocol::{Span, Value};
+use std::cmp::Ordering;
 
 #[test]
 fn test_comparison_nothing() {
@@ -43,3 +44,115 @@ fn test_comparison_nothing() {
         ));
     }
 }
+
+#[test]
+fn test_total_cmp_orders_numbers_across_types() {
+    let mut values = vec![
+        Value::test_float(2.5),
+        Value::test_int(3),
+        Value::test_float(f64::NAN),
+        Value::test_int(-1),
+        Value::test_float(0.5),
+    ];
+
+    values.sort_by(|a, b| a.total_cmp(b));
+
+    assert_eq!(values[0], Value::test_int(-1));
+    assert_eq!(values[1], Value::test_float(0.5));
+    assert_eq!(values[2], Value::test_float(2.5));
+    assert_eq!(values[3], Value::test_int(3));
+    assert!(matches!(values[4], Value::Float { val, .. } if val.is_nan()));
+}
+
+#[test]
+fn test_total_cmp_orders_by_type_rank() {
+    let nothing = Value::Nothing {
+        span: Span::test_data(),
+    };
+    let mut values = vec![
+        nothing.clone(),
+        Value::test_string("a"),
+        Value::test_int(10),
+        Value::test_bool(true),
+        Value::test_float(1.5),
+    ];
+
+    values.sort_by(|a, b| a.total_cmp(b));
+
+    assert_eq!(values[0], Value::test_bool(true));
+    assert_eq!(values[1], Value::test_float(1.5));
+    assert_eq!(values[2], Value::test_int(10));
+    assert_eq!(values[3], Value::test_string("a"));
+    assert_eq!(values[4], nothing);
+}
+
+#[test]
+fn test_total_cmp_agrees_with_partial_cmp() {
+    let pairs = vec![
+        (Value::test_int(1), Value::test_float(1.0)),
+        (Value::test_int(1), Value::test_int(2)),
+        (Value::test_string("b"), Value::test_string("a")),
+        (Value::test_float(f64::NAN), Value::test_float(1.0)),
+        (Value::test_float(f64::NAN), Value::test_float(f64::NAN)),
+    ];
+
+    for (lhs, rhs) in pairs {
+        assert_eq!(lhs.partial_cmp(&rhs), Some(lhs.total_cmp(&rhs)));
+    }
+}
+
+#[test]
+fn test_total_cmp_compares_lists_element_wise() {
+    let list = |vals| Value::List {
+        vals,
+        span: Span::test_data(),
+    };
+    let short = list(vec![Value::test_int(1)]);
+    let long = list(vec![Value::test_int(1), Value::test_int(0)]);
+    let bigger = list(vec![Value::test_int(2)]);
+
+    assert_eq!(short.total_cmp(&long), Ordering::Less);
+    assert_eq!(long.total_cmp(&bigger), Ordering::Less);
+}
+
+#[test]
+fn test_dates_compare_by_instant() {
+    let date = |s| Value::Date {
+        val: chrono::DateTime::parse_from_rfc3339(s).expect("valid date"),
+        span: Span::test_data(),
+    };
+    let earlier = date("2021-01-01T10:00:00+00:00");
+    let later = date("2021-01-01T11:00:00+00:00");
+
+    assert_eq!(earlier.total_cmp(&later), Ordering::Less);
+}
+
+#[test]
+fn test_total_cmp_is_exact_for_floats() {
+    let float = Value::test_float(1.5);
+    let next = Value::test_float(1.5 + f64::EPSILON);
+
+    // partial_cmp calls these equal, but sorting needs an order that's transitive
+    assert_eq!(float.partial_cmp(&next), Some(Ordering::Equal));
+    assert_eq!(float.total_cmp(&next), Ordering::Less);
+    assert_eq!(next.total_cmp(&float), Ordering::Greater);
+    assert_eq!(
+        Value::test_float(-0.0).total_cmp(&Value::test_float(0.0)),
+        Ordering::Equal
+    );
+    assert_eq!(
+        Value::test_float(-f64::NAN).total_cmp(&Value::test_float(f64::INFINITY)),
+        Ordering::Greater
+    );
+}
+
+#[test]
+fn test_total_cmp_compares_large_ints_with_floats_exactly() {
+    let float = Value::test_float(9007199254740992.0);
+    let int = Value::test_int(9007199254740993);
+    let smaller = Value::test_int(9007199254740992);
+
+    assert_eq!(int.total_cmp(&float), Ordering::Greater);
+    assert_eq!(float.total_cmp(&int), Ordering::Less);
+    assert_eq!(smaller.total_cmp(&float), Ordering::Equal);
+}
@@ -2,4 +2,4 @@ mod table;
 mod wrap;
 
 pub use table::{draw_table, StyledString, Table, TextStyle, Theme};
-pub use wrap::Alignment;
+pub use wrap::{truncate_to_width, Alignment};
@@ -73,6 +73,33 @@ fn unicode_width_strip_ansi(astring: &str) -> usize {
     UnicodeWidthStr::width(&stripped_string[..])
 }
 
+/// Shorten `contents` to at most `max_width` columns on screen, marking the cut with '…'. Wide
+/// characters count double and ANSI escapes don't count. A cut string loses its ANSI styling, as
+/// the escapes can't be split safely
+pub fn truncate_to_width(contents: &str, max_width: usize) -> String {
+    if unicode_width_strip_ansi(contents) <= max_width {
+        return contents.to_string();
+    }
+
+    // Leave room for the ellipsis
+    let budget = max_width.saturating_sub(1);
+    let mut width = 0;
+    let mut output = String::new();
+
+    for c in strip_ansi(contents).chars() {
+        let char_width = UnicodeWidthChar::width(c).unwrap_or(0);
+        if width + char_width > budget {
+            break;
+        }
+
+        width += char_width;
+        output.push(c);
+    }
+
+    output.push('…');
+    output
+}
+
 // fn special_width(astring: &str) -> usize {
 //     // remove the zwj's '\u{200d}'
 //     // remove the fe0f's
@@ -1,10 +1,11 @@
 use crate::utils::{eval_source, report_error};
 use nu_protocol::engine::{EngineState, Stack, StateDelta, StateWorkingSet};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 const NUSHELL_FOLDER: &str = "nushell";
 const PLUGIN_FILE: &str = "plugin.nu";
 const CONFIG_FILE: &str = "config.nu";
+const ENV_FILE: &str = "env.nu";
 const HISTORY_FILE: &str = "history.txt";
 
 pub(crate) fn read_plugin_file(engine_state: &mut EngineState, stack: &mut Stack) {
@@ -24,48 +25,104 @@ pub(crate) fn read_plugin_file(engine_state: &mut EngineState, stack: &mut Stack
     }
 }
 
-pub(crate) fn read_config_file(engine_state: &mut EngineState, stack: &mut Stack) {
-    // Load config startup file
-    if let Some(mut config_path) = nu_path::config_dir() {
-        config_path.push(NUSHELL_FOLDER);
+/// A startup file to load: the config or the env file
+pub(crate) enum ConfigFile {
+    /// The file of that name in the platform config directory
+    Default,
+    /// A file given with `--config` or `--env-config`
+    Path(PathBuf),
+    /// `--no-config`: start without loading it
+    None,
+}
 
-        // Create config directory if it does not exist
-        if !config_path.exists() {
-            if let Err(err) = std::fs::create_dir_all(&config_path) {
-                eprintln!("Failed to create config directory: {}", err);
+impl ConfigFile {
+    /// Where the file is, if it is to be loaded. `default_name` is its name in the platform
+    /// config directory
+    fn path(&self, default_name: &str) -> Option<PathBuf> {
+        match self {
+            ConfigFile::None => None,
+            ConfigFile::Path(path) => {
+                if !path.exists() {
+                    eprintln!("Config file not found: {}", path.display());
+                    return None;
+                }
+                Some(path.clone())
             }
-        } else {
-            config_path.push(CONFIG_FILE);
-
-            if config_path.exists() {
-                // FIXME: remove this message when we're ready
-                println!("Loading config from: {:?}", config_path);
-                let config_filename = config_path.to_string_lossy().to_owned();
-
-                if let Ok(contents) = std::fs::read_to_string(&config_path) {
-                    eval_source(engine_state, stack, &contents, &config_filename);
-                    // Merge the delta in case env vars changed in the config
-                    match nu_engine::env::current_dir(engine_state, stack) {
-                        Ok(cwd) => {
-                            if let Err(e) =
-                                engine_state.merge_delta(StateDelta::new(), Some(stack), cwd)
-                            {
-                                let working_set = StateWorkingSet::new(engine_state);
-                                report_error(&working_set, &e);
-                            }
-                        }
-                        Err(e) => {
-                            let working_set = StateWorkingSet::new(engine_state);
-                            report_error(&working_set, &e);
-                        }
+            ConfigFile::Default => {
+                let mut path = nu_path::config_dir()?;
+                path.push(NUSHELL_FOLDER);
+
+                // Create config directory if it does not exist
+                if !path.exists() {
+                    if let Err(err) = std::fs::create_dir_all(&path) {
+                        eprintln!("Failed to create config directory: {}", err);
                     }
+                    return None;
                 }
+
+                path.push(default_name);
+                Some(path)
+            }
+        }
+    }
+}
+
+/// Load the env file and then the config, so the config can build on the env vars. The env file
+/// is found on its own rather than next to the config, so `--config` doesn't pick up whatever
+/// `env.nu` happens to sit beside it
+pub(crate) fn read_config_file(
+    engine_state: &mut EngineState,
+    stack: &mut Stack,
+    config_file: &ConfigFile,
+    env_file: &ConfigFile,
+) {
+    let env_path = env_file.path(ENV_FILE);
+    let config_path = config_file.path(CONFIG_FILE);
+
+    if env_path.is_some() {
+        engine_state.env_path = env_path.clone();
+    }
+    if config_path.is_some() {
+        engine_state.config_path = config_path.clone();
+    }
+
+    for path in env_path.into_iter().chain(config_path) {
+        if path.exists() {
+            eval_config_source(engine_state, stack, &path);
+        }
+    }
+}
+
+/// Evaluate a startup file. Errors are reported against the file, but the shell still starts
+fn eval_config_source(engine_state: &mut EngineState, stack: &mut Stack, path: &Path) {
+    let filename = path.to_string_lossy().to_string();
+
+    let contents = match std::fs::read_to_string(path) {
+        Ok(contents) => contents,
+        Err(err) => {
+            eprintln!("Failed to read {}: {}", filename, err);
+            return;
+        }
+    };
+
+    eval_source(engine_state, stack, &contents, &filename);
+
+    // Merge the delta in case env vars changed in the config
+    match nu_engine::env::current_dir(engine_state, stack) {
+        Ok(cwd) => {
+            if let Err(e) = engine_state.merge_delta(StateDelta::new(), Some(stack), cwd) {
+                let working_set = StateWorkingSet::new(engine_state);
+                report_error(&working_set, &e);
             }
         }
+        Err(e) => {
+            let working_set = StateWorkingSet::new(engine_state);
+            report_error(&working_set, &e);
+        }
     }
 }
 
-pub(crate) fn create_history_path() -> Option<PathBuf> {
+pub(crate) fn create_history_path(max_history_size: usize) -> Option<PathBuf> {
     nu_path::config_dir().and_then(|mut history_path| {
         history_path.push(NUSHELL_FOLDER);
         history_path.push(HISTORY_FILE);
@@ -77,7 +134,38 @@ pub(crate) fn create_history_path() -> Option<PathBuf> {
                 Err(_) => None,
             }
         } else {
+            compact_history(&history_path, max_history_size);
             Some(history_path)
         }
     })
 }
+
+/// Drop consecutive duplicate entries and keep only the newest `max_history_size` ones.
+/// The file is only rewritten when something changed
+fn compact_history(history_path: &Path, max_history_size: usize) {
+    let contents = match std::fs::read_to_string(history_path) {
+        Ok(contents) => contents,
+        Err(_) => return,
+    };
+
+    let mut entries: Vec<&str> = vec![];
+    for line in contents.lines() {
+        if entries.last() != Some(&line) {
+            entries.push(line);
+        }
+    }
+
+    let total = contents.lines().count();
+    if entries.len() > max_history_size {
+        entries.drain(..entries.len() - max_history_size);
+    }
+
+    if entries.len() != total {
+        let mut compacted = entries.join("\n");
+        compacted.push('\n');
+
+        if let Err(err) = std::fs::write(history_path, compacted) {
+            eprintln!("Failed to compact the history file: {}", err);
+        }
+    }
+}
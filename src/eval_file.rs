@@ -1,24 +1,62 @@
+use crossterm::tty::IsTty;
 use log::trace;
 use miette::{IntoDiagnostic, Result};
 use nu_engine::{convert_env_values, eval_block};
 use nu_parser::parse;
 use nu_protocol::{
-    engine::{EngineState, StateDelta, StateWorkingSet},
-    Config, PipelineData, Span, Value, CONFIG_VARIABLE_ID,
+    engine::{EngineState, Stack, StateDelta, StateWorkingSet},
+    Config, PipelineData, ShellError, Span, StringStream, Value, CONFIG_VARIABLE_ID,
 };
 use std::path::PathBuf;
+use std::time::Instant;
 
-use crate::utils::{gather_parent_env_vars, report_error};
+use crate::config_files::{read_config_file, ConfigFile};
+use crate::utils::{gather_parent_env_vars, report_error, report_pipeline_types};
 
 /// Main function used when a file path is found as argument for nu
+///
+/// Scripts don't read the default config or env file, so they run the same for everyone. Files
+/// given with `--config` or `--env-config` are loaded before the script is parsed, so their
+/// definitions are available to it.
+///
+/// If the script defines `main`, it's called with the arguments that followed the script path.
+/// Piped stdin becomes the input of `main`, or of the script itself when there is no `main`. Once
+/// everything has run, nu exits with the status of the last external the script ran.
 pub(crate) fn evaluate(
     path: String,
+    args: Vec<String>,
+    config_file: &ConfigFile,
+    env_file: &ConfigFile,
     init_cwd: PathBuf,
     engine_state: &mut EngineState,
+    entire_start_time: Instant,
 ) -> Result<()> {
     // First, set up env vars as strings only
     gather_parent_env_vars(engine_state);
 
+    let mut stack = Stack::new();
+
+    // Set up our initial config to start from
+    stack.vars.insert(
+        CONFIG_VARIABLE_ID,
+        Value::Record {
+            cols: vec![],
+            vals: vec![],
+            span: Span { start: 0, end: 0 },
+        },
+    );
+
+    let given = |file: &ConfigFile| match file {
+        ConfigFile::Path(path) => ConfigFile::Path(path.clone()),
+        _ => ConfigFile::None,
+    };
+    read_config_file(
+        engine_state,
+        &mut stack,
+        &given(config_file),
+        &given(env_file),
+    );
+
     let file = std::fs::read(&path).into_diagnostic()?;
 
     let (block, delta) = {
@@ -31,6 +69,9 @@ pub(crate) fn evaluate(
 
             std::process::exit(1);
         }
+        if report_pipeline_types(&working_set, &stack, &output) {
+            std::process::exit(1);
+        }
         (output, working_set.render())
     };
 
@@ -39,18 +80,6 @@ pub(crate) fn evaluate(
         report_error(&working_set, &err);
     }
 
-    let mut stack = nu_protocol::engine::Stack::new();
-
-    // Set up our initial config to start from
-    stack.vars.insert(
-        CONFIG_VARIABLE_ID,
-        Value::Record {
-            cols: vec![],
-            vals: vec![],
-            span: Span { start: 0, end: 0 },
-        },
-    );
-
     let config = match stack.get_config() {
         Ok(config) => config,
         Err(e) => {
@@ -82,18 +111,27 @@ pub(crate) fn evaluate(
         std::process::exit(1);
     }
 
-    match eval_block(
-        engine_state,
-        &mut stack,
-        &block,
-        PipelineData::new(Span::new(0, 0)), // Don't try this at home, 0 span is ignored
-    ) {
+    engine_state.startup_time = entire_start_time.elapsed().as_nanos() as i64;
+
+    let has_main = engine_state.find_decl(b"main").is_some();
+    let mut input = Some(stdin_input(engine_state));
+
+    let script_input = if has_main {
+        PipelineData::new(Span::new(0, 0)) // Don't try this at home, 0 span is ignored
+    } else {
+        input
+            .take()
+            .unwrap_or_else(|| PipelineData::new(Span::new(0, 0)))
+    };
+
+    match eval_block(engine_state, &mut stack, &block, script_input) {
         Ok(pipeline_data) => {
             for item in pipeline_data {
-                if let Value::Error { error } = item {
+                // An error nested in a list or record would otherwise only show its message
+                if let Some(error) = item.find_error() {
                     let working_set = StateWorkingSet::new(engine_state);
 
-                    report_error(&working_set, &error);
+                    report_error(&working_set, error);
 
                     std::process::exit(1);
                 }
@@ -101,13 +139,17 @@ pub(crate) fn evaluate(
             }
 
             // Next, let's check if there are any flags we want to pass to the main function
-            let args: Vec<String> = std::env::args().skip(2).collect();
 
-            if args.is_empty() && engine_state.find_decl(b"main").is_none() {
+            if args.is_empty() && !has_main {
+                exit_with_last_exit_code(&mut stack);
                 return Ok(());
             }
 
-            let args = format!("main {}", args.join(" ")).as_bytes().to_vec();
+            let args = std::iter::once("main".to_string())
+                .chain(args.iter().map(|arg| quote_arg(arg)))
+                .collect::<Vec<_>>()
+                .join(" ")
+                .into_bytes();
 
             let (block, delta) = {
                 let mut working_set = StateWorkingSet::new(engine_state);
@@ -127,18 +169,15 @@ pub(crate) fn evaluate(
                 report_error(&working_set, &err);
             }
 
-            match eval_block(
-                engine_state,
-                &mut stack,
-                &block,
-                PipelineData::new(Span::new(0, 0)), // Don't try this at home, 0 span is ignored
-            ) {
+            let main_input = input.unwrap_or_else(|| PipelineData::new(Span::new(0, 0)));
+
+            match eval_block(engine_state, &mut stack, &block, main_input) {
                 Ok(pipeline_data) => {
                     for item in pipeline_data {
-                        if let Value::Error { error } = item {
+                        if let Some(error) = item.find_error() {
                             let working_set = StateWorkingSet::new(engine_state);
 
-                            report_error(&working_set, &error);
+                            report_error(&working_set, error);
 
                             std::process::exit(1);
                         }
@@ -163,5 +202,61 @@ pub(crate) fn evaluate(
         }
     }
 
+    exit_with_last_exit_code(&mut stack);
+
     Ok(())
 }
+
+/// Stdin as a lazy string stream when something is piped into nu, so a script that never uses
+/// `$in` leaves it for `input` or the externals it runs
+fn stdin_input(engine_state: &EngineState) -> PipelineData {
+    if std::io::stdin().is_tty() {
+        return PipelineData::new(Span::new(0, 0));
+    }
+
+    let stream = std::iter::from_fn(|| {
+        let mut buf = String::new();
+
+        match std::io::stdin().read_line(&mut buf) {
+            Ok(0) => None,
+            Ok(_) => Some(Ok(buf)),
+            Err(err) => Some(Err(ShellError::IOError(err.to_string()))),
+        }
+    });
+
+    PipelineData::StringStream(
+        StringStream::from_stream(stream, engine_state.ctrlc.clone()),
+        Span::new(0, 0),
+        None,
+    )
+}
+
+/// Quote an argument for the `main` call, so one with spaces or quotes stays a single string.
+/// Plain arguments are passed as they are, so they still parse as numbers or flags. Strings have
+/// no escapes, so an argument holding both kinds of quote can't be passed through whole.
+fn quote_arg(arg: &str) -> String {
+    let needs_quotes = arg.is_empty()
+        || arg.chars().any(|c| {
+            c.is_whitespace()
+                || matches!(
+                    c,
+                    '"' | '\'' | '`' | '(' | ')' | '[' | ']' | '{' | '}' | ';' | '|' | '$' | '#'
+                )
+        });
+
+    if !needs_quotes {
+        arg.to_string()
+    } else if arg.contains('"') {
+        format!("'{}'", arg)
+    } else {
+        format!("\"{}\"", arg)
+    }
+}
+
+/// Exit with the status of the last external the script ran, if it failed
+fn exit_with_last_exit_code(stack: &mut Stack) {
+    match stack.update_last_exit_code() {
+        Some(exit_code) if exit_code != 0 => std::process::exit(exit_code as i32),
+        _ => {}
+    }
+}
@@ -9,14 +9,19 @@ mod utils;
 #[cfg(test)]
 mod tests;
 
+use config_files::ConfigFile;
 use miette::Result;
 use nu_command::create_default_context;
+use std::path::PathBuf;
 use std::sync::{
     atomic::{AtomicBool, Ordering},
     Arc,
 };
+use std::time::Instant;
 
 fn main() -> Result<()> {
+    let entire_start_time = Instant::now();
+
     // miette::set_panic_hook();
     let miette_hook = std::panic::take_hook();
     std::panic::set_hook(Box::new(move |x| {
@@ -42,9 +47,77 @@ fn main() -> Result<()> {
     engine_state.ctrlc = Some(engine_state_ctrlc);
     // End ctrl-c protection section
 
-    if let Some(path) = std::env::args().nth(1) {
-        eval_file::evaluate(path, init_cwd, &mut engine_state)
+    let args = parse_args(std::env::args().skip(1));
+
+    if let Some(path) = args.script {
+        eval_file::evaluate(
+            path,
+            args.script_args,
+            &args.config_file,
+            &args.env_file,
+            init_cwd,
+            &mut engine_state,
+            entire_start_time,
+        )
     } else {
-        repl::evaluate(ctrlc, &mut engine_state)
+        repl::evaluate(
+            ctrlc,
+            &args.config_file,
+            &args.env_file,
+            &mut engine_state,
+            entire_start_time,
+        )
+    }
+}
+
+/// What the shell was started with: options for the shell itself, then an optional script
+/// and the arguments for its `main`
+struct NuArgs {
+    config_file: ConfigFile,
+    env_file: ConfigFile,
+    script: Option<String>,
+    script_args: Vec<String>,
+}
+
+fn parse_args(mut args: impl Iterator<Item = String>) -> NuArgs {
+    let mut config_file = ConfigFile::Default;
+    let mut env_file = ConfigFile::Default;
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--no-config" => {
+                config_file = ConfigFile::None;
+                env_file = ConfigFile::None;
+            }
+            "--config" => match args.next() {
+                Some(path) => config_file = ConfigFile::Path(PathBuf::from(path)),
+                None => {
+                    eprintln!("--config needs the path of a config file");
+                    std::process::exit(1);
+                }
+            },
+            "--env-config" => match args.next() {
+                Some(path) => env_file = ConfigFile::Path(PathBuf::from(path)),
+                None => {
+                    eprintln!("--env-config needs the path of an env file");
+                    std::process::exit(1);
+                }
+            },
+            _ => {
+                return NuArgs {
+                    config_file,
+                    env_file,
+                    script: Some(arg),
+                    script_args: args.collect(),
+                }
+            }
+        }
+    }
+
+    NuArgs {
+        config_file,
+        env_file,
+        script: None,
+        script_args: vec![],
     }
 }
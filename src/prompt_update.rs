@@ -3,7 +3,7 @@ use nu_engine::eval_block;
 use nu_parser::parse;
 use nu_protocol::{
     engine::{EngineState, Stack, StateWorkingSet},
-    Config, PipelineData, Span, Value,
+    Config, PipelineData, ShellError, Span, Value,
 };
 use reedline::Prompt;
 
@@ -12,6 +12,7 @@ pub(crate) const PROMPT_COMMAND: &str = "PROMPT_COMMAND";
 pub(crate) const PROMPT_COMMAND_RIGHT: &str = "PROMPT_COMMAND_RIGHT";
 pub(crate) const PROMPT_INDICATOR: &str = "PROMPT_INDICATOR";
 pub(crate) const PROMPT_INDICATOR_VI_INSERT: &str = "PROMPT_INDICATOR_VI_INSERT";
+pub(crate) const PROMPT_INDICATOR_VI_NORMAL: &str = "PROMPT_INDICATOR_VI_NORMAL";
 pub(crate) const PROMPT_INDICATOR_VI_VISUAL: &str = "PROMPT_INDICATOR_VI_VISUAL";
 pub(crate) const PROMPT_INDICATOR_MENU: &str = "PROMPT_INDICATOR_MENU";
 pub(crate) const PROMPT_MULTILINE_INDICATOR: &str = "PROMPT_MULTILINE_INDICATOR";
@@ -20,7 +21,7 @@ pub(crate) fn get_prompt_indicators(
     config: &Config,
     engine_state: &EngineState,
     stack: &Stack,
-) -> (String, String, String, String, String) {
+) -> (String, String, String, String, String, String) {
     let prompt_indicator = match stack.get_env_var(engine_state, PROMPT_INDICATOR) {
         Some(pi) => pi.into_string("", config),
         None => "〉".to_string(),
@@ -31,6 +32,12 @@ pub(crate) fn get_prompt_indicators(
         None => ": ".to_string(),
     };
 
+    // Normal mode reads like the emacs prompt unless it was given its own indicator
+    let prompt_vi_normal = match stack.get_env_var(engine_state, PROMPT_INDICATOR_VI_NORMAL) {
+        Some(pvin) => pvin.into_string("", config),
+        None => prompt_indicator.clone(),
+    };
+
     let prompt_vi_visual = match stack.get_env_var(engine_state, PROMPT_INDICATOR_VI_VISUAL) {
         Some(pviv) => pviv.into_string("", config),
         None => "v ".to_string(),
@@ -49,6 +56,7 @@ pub(crate) fn get_prompt_indicators(
     (
         prompt_indicator,
         prompt_vi_insert,
+        prompt_vi_normal,
         prompt_vi_visual,
         prompt_menu,
         prompt_multiline,
@@ -60,62 +68,124 @@ fn get_prompt_string(
     config: &Config,
     engine_state: &EngineState,
     stack: &mut Stack,
-) -> Option<String> {
-    stack
-        .get_env_var(engine_state, prompt)
-        .and_then(|v| match v {
-            Value::Block { val: block_id, .. } => {
-                let block = engine_state.get_block(block_id);
-                eval_block(
-                    engine_state,
-                    stack,
-                    block,
-                    PipelineData::new(Span::new(0, 0)), // Don't try this at home, 0 span is ignored
-                )
-                .ok()
-            }
-            Value::String { val: source, .. } => {
-                let mut working_set = StateWorkingSet::new(engine_state);
-                let (block, _) = parse(&mut working_set, None, source.as_bytes(), true);
-                eval_block(
-                    engine_state,
-                    stack,
-                    &block,
-                    PipelineData::new(Span::new(0, 0)), // Don't try this at home, 0 span is ignored
-                )
-                .ok()
+) -> Result<Option<String>, ShellError> {
+    let pipeline_data = match stack.get_env_var(engine_state, prompt) {
+        Some(Value::Block { val: block_id, .. }) => {
+            let block = engine_state.get_block(block_id);
+            eval_block(
+                engine_state,
+                stack,
+                block,
+                PipelineData::new(Span::new(0, 0)), // Don't try this at home, 0 span is ignored
+            )?
+        }
+        Some(Value::String { val: source, .. }) => {
+            // The source is only parsed here, so its spans can't be shown: report the message
+            let failed = |err: String| {
+                ShellError::LabeledError(format!("Failed to evaluate {}", prompt), err)
+            };
+
+            let mut working_set = StateWorkingSet::new(engine_state);
+            let (block, err) = parse(&mut working_set, None, source.as_bytes(), true);
+            if let Some(err) = err {
+                return Err(failed(err.to_string()));
             }
-            _ => None,
-        })
-        .and_then(|pipeline_data| pipeline_data.collect_string("", config).ok())
+
+            eval_block(
+                engine_state,
+                stack,
+                &block,
+                PipelineData::new(Span::new(0, 0)), // Don't try this at home, 0 span is ignored
+            )
+            .map_err(|err| failed(err.to_string()))?
+        }
+        _ => return Ok(None),
+    };
+
+    pipeline_data.collect_string("", config).map(Some)
 }
 
+/// Update the prompt strings. A prompt command that fails falls back to the default prompt,
+/// and its error is returned to be reported
 pub(crate) fn update_prompt<'prompt>(
     config: &Config,
     engine_state: &EngineState,
     stack: &Stack,
     nu_prompt: &'prompt mut NushellPrompt,
-) -> &'prompt dyn Prompt {
+) -> (&'prompt dyn Prompt, Vec<ShellError>) {
     // get the other indicators
     let (
         prompt_indicator_string,
         prompt_vi_insert_string,
+        prompt_vi_normal_string,
         prompt_vi_visual_string,
         prompt_indicator_menu,
         prompt_multiline_string,
     ) = get_prompt_indicators(config, engine_state, stack);
 
     let mut stack = stack.clone();
+    let mut errors = vec![];
+
+    let mut prompt_string = |prompt| {
+        get_prompt_string(prompt, config, engine_state, &mut stack)
+            .map_err(|err| errors.push(err))
+            .unwrap_or_default()
+    };
+    let left_prompt_string = prompt_string(PROMPT_COMMAND);
+    let right_prompt_string = prompt_string(PROMPT_COMMAND_RIGHT);
 
     // apply the other indicators
     nu_prompt.update_all_prompt_strings(
-        get_prompt_string(PROMPT_COMMAND, config, engine_state, &mut stack),
-        get_prompt_string(PROMPT_COMMAND_RIGHT, config, engine_state, &mut stack),
+        left_prompt_string,
+        right_prompt_string,
         prompt_indicator_string,
         prompt_indicator_menu,
         prompt_multiline_string,
-        (prompt_vi_insert_string, prompt_vi_visual_string),
+        (
+            prompt_vi_insert_string,
+            prompt_vi_normal_string,
+            prompt_vi_visual_string,
+        ),
     );
 
-    nu_prompt as &dyn Prompt
+    (nu_prompt as &dyn Prompt, errors)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn prompt_for(command: &str) -> (String, Vec<ShellError>) {
+        let engine_state = EngineState::new();
+        let mut stack = Stack::new();
+        stack.add_env_var(
+            PROMPT_COMMAND.into(),
+            Value::String {
+                val: command.into(),
+                span: Span::test_data(),
+            },
+        );
+
+        let mut nu_prompt = NushellPrompt::new();
+        let (prompt, errors) =
+            update_prompt(&Config::default(), &engine_state, &stack, &mut nu_prompt);
+
+        (prompt.render_prompt_left().to_string(), errors)
+    }
+
+    #[test]
+    fn prompt_command_sets_the_prompt() {
+        let (prompt, errors) = prompt_for("'ok> '");
+
+        assert_eq!(prompt, "ok> ");
+        assert!(errors.is_empty());
+    }
+
+    #[test]
+    fn failing_prompt_command_falls_back_to_the_default() {
+        let (prompt, errors) = prompt_for("(");
+
+        assert_eq!(prompt, NushellPrompt::new().render_prompt_left());
+        assert_eq!(errors.len(), 1);
+    }
 }
@@ -1,5 +1,9 @@
-use crossterm::event::{KeyCode, KeyModifiers};
+use crossterm::event::KeyCode;
 use nu_color_config::lookup_ansi_color_style;
+use nu_command::{
+    keybinding_names, KeybindingEdit, KeybindingEvent, KEYBINDING_CHAR_PREFIX, KEYBINDING_EDITS,
+    KEYBINDING_EVENTS, KEYBINDING_KEYCODES, KEYBINDING_MODIFIERS,
+};
 use nu_protocol::{extract_value, Config, ParsedKeybinding, ShellError, Span, Type, Value};
 use reedline::{
     default_emacs_keybindings, default_vi_insert_keybindings, default_vi_normal_keybindings,
@@ -66,47 +70,81 @@ pub enum KeybindingsMode {
     },
 }
 
-pub(crate) fn create_keybindings(config: &Config) -> Result<KeybindingsMode, ShellError> {
-    let parsed_keybindings = &config.keybindings;
-    match config.edit_mode.as_str() {
-        "emacs" => {
-            let mut keybindings = default_emacs_keybindings();
-            // temporal keybinding with multiple events
-            keybindings.add_binding(
-                KeyModifiers::SHIFT,
-                KeyCode::BackTab,
-                ReedlineEvent::Multiple(vec![
-                    ReedlineEvent::Edit(vec![EditCommand::InsertChar('p')]),
-                    ReedlineEvent::Enter,
-                ]),
-            );
-
-            for parsed_keybinding in parsed_keybindings {
-                if parsed_keybinding.mode.into_string("", config).as_str() == "emacs" {
-                    add_keybinding(&mut keybindings, parsed_keybinding, config)?
+/// Build the keybindings for the edit mode. Entries that can't be translated are left out
+/// and returned as errors, so the rest of the keybindings still work
+pub(crate) fn create_keybindings(config: &Config) -> (KeybindingsMode, Vec<ShellError>) {
+    let mut errors = vec![];
+
+    let mut parsed_keybindings = default_keybindings(&mut errors);
+    parsed_keybindings.extend(config.keybindings.iter().cloned());
+
+    let mut add_for_mode = |keybindings: &mut Keybindings, mode: &str| {
+        for parsed_keybinding in &parsed_keybindings {
+            if parsed_keybinding.mode.into_string("", config).as_str() == mode {
+                if let Err(e) = add_keybinding(keybindings, parsed_keybinding, config) {
+                    errors.push(e);
                 }
             }
-
-            Ok(KeybindingsMode::Emacs(keybindings))
         }
-        _ => {
+    };
+
+    let mode = match config.edit_mode.as_str() {
+        "vi" => {
             let mut insert_keybindings = default_vi_insert_keybindings();
             let mut normal_keybindings = default_vi_normal_keybindings();
+            add_for_mode(&mut insert_keybindings, "vi_insert");
+            add_for_mode(&mut normal_keybindings, "vi_normal");
 
-            for parsed_keybinding in parsed_keybindings {
-                if parsed_keybinding.mode.into_string("", config).as_str() == "vi_insert" {
-                    add_keybinding(&mut insert_keybindings, parsed_keybinding, config)?
-                } else if parsed_keybinding.mode.into_string("", config).as_str() == "vi_normal" {
-                    add_keybinding(&mut normal_keybindings, parsed_keybinding, config)?
-                }
-            }
-
-            Ok(KeybindingsMode::Vi {
+            KeybindingsMode::Vi {
                 insert_keybindings,
                 normal_keybindings,
-            })
+            }
         }
-    }
+        edit_mode => {
+            if edit_mode != "emacs" {
+                errors.push(ShellError::LabeledError(
+                    "Unsupported edit mode".into(),
+                    format!(
+                        "$config.edit_mode is '{}', expected 'emacs' or 'vi'. Using emacs instead",
+                        edit_mode
+                    ),
+                ));
+            }
+
+            let mut keybindings = default_emacs_keybindings();
+            add_for_mode(&mut keybindings, "emacs");
+
+            KeybindingsMode::Emacs(keybindings)
+        }
+    };
+
+    (mode, errors)
+}
+
+// The keybindings nushell adds on top of the ones of reedline, as listed by `keybindings default`
+fn default_keybindings(errors: &mut Vec<ShellError>) -> Vec<ParsedKeybinding> {
+    nu_command::default_keybindings(Span::new(0, 0))
+        .iter()
+        .filter_map(|value| match value {
+            Value::Record { cols, vals, span } => parse_keybinding(cols, vals, span)
+                .map_err(|e| errors.push(e))
+                .ok(),
+            _ => None,
+        })
+        .collect()
+}
+
+fn parse_keybinding(
+    cols: &[String],
+    vals: &[Value],
+    span: &Span,
+) -> Result<ParsedKeybinding, ShellError> {
+    Ok(ParsedKeybinding {
+        modifier: extract_value("modifier", cols, vals, span)?.clone(),
+        keycode: extract_value("keycode", cols, vals, span)?.clone(),
+        mode: extract_value("mode", cols, vals, span)?.clone(),
+        event: extract_value("event", cols, vals, span)?.clone(),
+    })
 }
 
 fn add_keybinding(
@@ -114,68 +152,50 @@ fn add_keybinding(
     keybinding: &ParsedKeybinding,
     config: &Config,
 ) -> Result<(), ShellError> {
-    let modifier = match keybinding
-        .modifier
-        .into_string("", config)
-        .to_lowercase()
-        .as_str()
+    let modifier_name = keybinding.modifier.into_string("", config).to_lowercase();
+    let modifier = match KEYBINDING_MODIFIERS
+        .iter()
+        .find(|(name, _)| *name == modifier_name)
     {
-        "control" => KeyModifiers::CONTROL,
-        "shift" => KeyModifiers::SHIFT,
-        "alt" => KeyModifiers::ALT,
-        "none" => KeyModifiers::NONE,
-        "control | shift" => KeyModifiers::CONTROL | KeyModifiers::SHIFT,
-        "control | alt" => KeyModifiers::CONTROL | KeyModifiers::ALT,
-        "control | alt | shift" => KeyModifiers::CONTROL | KeyModifiers::ALT | KeyModifiers::SHIFT,
-        _ => {
+        Some((_, modifier)) => *modifier,
+        None => {
             return Err(ShellError::UnsupportedConfigValue(
                 keybinding.modifier.into_abbreviated_string(config),
-                "CONTROL, SHIFT, ALT or NONE".to_string(),
+                format!(
+                    "one of {}",
+                    keybinding_names(KEYBINDING_MODIFIERS).join(", ")
+                ),
                 keybinding.modifier.span()?,
             ))
         }
     };
 
-    let keycode = match keybinding
-        .keycode
-        .into_string("", config)
-        .to_lowercase()
-        .as_str()
-    {
-        "backspace" => KeyCode::Backspace,
-        "enter" => KeyCode::Enter,
-        c if c.starts_with("char_") => {
-            let char = c.replace("char_", "");
-            let char = char.chars().next().ok_or({
-                ShellError::UnsupportedConfigValue(
-                    c.to_string(),
-                    "char_ plus char".to_string(),
-                    keybinding.keycode.span()?,
-                )
-            })?;
-            KeyCode::Char(char)
-        }
-        "down" => KeyCode::Down,
-        "up" => KeyCode::Up,
-        "left" => KeyCode::Left,
-        "right" => KeyCode::Right,
-        "home" => KeyCode::Home,
-        "end" => KeyCode::End,
-        "pageup" => KeyCode::PageUp,
-        "pagedown" => KeyCode::PageDown,
-        "tab" => KeyCode::Tab,
-        "backtab" => KeyCode::BackTab,
-        "delete" => KeyCode::Delete,
-        "insert" => KeyCode::Insert,
-        // TODO: Add KeyCode::F(u8) for function keys
-        "null" => KeyCode::Null,
-        "esc" | "escape" => KeyCode::Esc,
-        _ => {
-            return Err(ShellError::UnsupportedConfigValue(
-                keybinding.keycode.into_abbreviated_string(config),
-                "crossterm KeyCode".to_string(),
+    let keycode_name = keybinding.keycode.into_string("", config).to_lowercase();
+    let keycode = if let Some(char) = keycode_name.strip_prefix(KEYBINDING_CHAR_PREFIX) {
+        let char = char.chars().next().ok_or({
+            ShellError::UnsupportedConfigValue(
+                keycode_name.clone(),
+                "char_ plus char".to_string(),
                 keybinding.keycode.span()?,
-            ))
+            )
+        })?;
+        KeyCode::Char(char)
+    } else {
+        match KEYBINDING_KEYCODES
+            .iter()
+            .find(|(name, _)| *name == keycode_name)
+        {
+            Some((_, keycode)) => *keycode,
+            None => {
+                return Err(ShellError::UnsupportedConfigValue(
+                    keybinding.keycode.into_abbreviated_string(config),
+                    format!(
+                        "one of {}, or char_ plus char",
+                        keybinding_names(KEYBINDING_KEYCODES).join(", ")
+                    ),
+                    keybinding.keycode.span()?,
+                ))
+            }
         }
     };
 
@@ -190,49 +210,40 @@ fn parse_event(value: Value, config: &Config) -> Result<ReedlineEvent, ShellErro
     match value {
         Value::Record { cols, vals, span } => {
             let event = match extract_value("send", &cols, &vals, &span) {
-                Ok(event) => match event.into_string("", config).to_lowercase().as_str() {
-                    "none" => ReedlineEvent::None,
-                    "actionhandler" => ReedlineEvent::ActionHandler,
-                    "clearscreen" => ReedlineEvent::ClearScreen,
-                    "historyhintcomplete" => ReedlineEvent::HistoryHintComplete,
-                    "historyhintwordcomplete" => ReedlineEvent::HistoryHintWordComplete,
-                    "ctrld" => ReedlineEvent::CtrlD,
-                    "ctrlc" => ReedlineEvent::CtrlC,
-                    "enter" => ReedlineEvent::Enter,
-                    "esc" | "escape" => ReedlineEvent::Esc,
-                    "up" => ReedlineEvent::Up,
-                    "down" => ReedlineEvent::Down,
-                    "right" => ReedlineEvent::Right,
-                    "left" => ReedlineEvent::Left,
-                    "searchhistory" => ReedlineEvent::SearchHistory,
-                    "nexthistory" => ReedlineEvent::NextHistory,
-                    "previoushistory" => ReedlineEvent::PreviousHistory,
-                    "repaint" => ReedlineEvent::Repaint,
-                    "contextmenu" => ReedlineEvent::ContextMenu,
-                    "menudown" => ReedlineEvent::MenuDown,
-                    "menuup" => ReedlineEvent::MenuUp,
-                    "menuleft" => ReedlineEvent::MenuLeft,
-                    "menuright" => ReedlineEvent::MenuRight,
-                    "menunext" => ReedlineEvent::MenuNext,
-                    "menuprevious" => ReedlineEvent::MenuPrevious,
-
-                    // TODO: add ReedlineEvent::Mouse
-                    // TODO: add ReedlineEvent::Resize
-                    // TODO: add ReedlineEvent::Paste
-                    "edit" => {
-                        let edit = extract_value("edit", &cols, &vals, &span)?;
-                        let edit = parse_edit(edit, config)?;
-
-                        ReedlineEvent::Edit(vec![edit])
-                    }
-                    v => {
-                        return Err(ShellError::UnsupportedConfigValue(
-                            "Reedline event".to_string(),
-                            v.to_string(),
-                            span,
-                        ))
+                Ok(event) => {
+                    let name = event.into_string("", config).to_lowercase();
+
+                    match KEYBINDING_EVENTS.iter().find(|(event, _)| *event == name) {
+                        Some((_, KeybindingEvent::Event(event))) => event.clone(),
+                        Some((_, KeybindingEvent::Edit)) => {
+                            let edit = extract_value("edit", &cols, &vals, &span)?;
+                            let edit = parse_edit(edit, config)?;
+
+                            ReedlineEvent::Edit(vec![edit])
+                        }
+                        Some((_, KeybindingEvent::ExecuteHostCommand)) => {
+                            let cmd = extract_value("cmd", &cols, &vals, &span)?;
+
+                            ReedlineEvent::Multiple(vec![
+                                ReedlineEvent::Edit(vec![EditCommand::Clear]),
+                                ReedlineEvent::Edit(vec![EditCommand::InsertString(
+                                    cmd.into_string("", config),
+                                )]),
+                                ReedlineEvent::Enter,
+                            ])
+                        }
+                        None => {
+                            return Err(ShellError::UnsupportedConfigValue(
+                                format!(
+                                    "one of {}",
+                                    keybinding_names(KEYBINDING_EVENTS).join(", ")
+                                ),
+                                name,
+                                span,
+                            ))
+                        }
                     }
-                },
+                }
                 Err(_) => {
                     let edit = extract_value("edit", &cols, &vals, &span);
                     let edit = match edit {
@@ -285,81 +296,21 @@ fn parse_edit(edit: &Value, config: &Config) -> Result<EditCommand, ShellError>
         } => {
             let cmd = extract_value("cmd", edit_cols, edit_vals, edit_span)?;
 
-            match cmd.into_string("", config).to_lowercase().as_str() {
-                "movetostart" => EditCommand::MoveToStart,
-                "movetolinestart" => EditCommand::MoveToLineStart,
-                "movetoend" => EditCommand::MoveToEnd,
-                "movetolineend" => EditCommand::MoveToLineEnd,
-                "moveleft" => EditCommand::MoveLeft,
-                "moveright" => EditCommand::MoveRight,
-                "movewordleft" => EditCommand::MoveWordLeft,
-                "movewordright" => EditCommand::MoveWordRight,
-                "insertchar" => {
-                    let char = extract_char("value", edit_cols, edit_vals, config, edit_span)?;
-                    EditCommand::InsertChar(char)
-                }
-                "insertstring" => {
+            let name = cmd.into_string("", config).to_lowercase();
+
+            match KEYBINDING_EDITS.iter().find(|(edit, _)| *edit == name) {
+                Some((_, KeybindingEdit::Edit(edit))) => edit.clone(),
+                Some((_, KeybindingEdit::WithChar(edit))) => edit(extract_char(
+                    "value", edit_cols, edit_vals, config, edit_span,
+                )?),
+                Some((_, KeybindingEdit::WithString(edit))) => {
                     let value = extract_value("value", edit_cols, edit_vals, edit_span)?;
-                    EditCommand::InsertString(value.into_string("", config))
-                }
-                "backspace" => EditCommand::Backspace,
-                "delete" => EditCommand::Delete,
-                "backspaceword" => EditCommand::BackspaceWord,
-                "deleteword" => EditCommand::DeleteWord,
-                "clear" => EditCommand::Clear,
-                "cleartolineend" => EditCommand::ClearToLineEnd,
-                "cutcurrentline" => EditCommand::CutCurrentLine,
-                "cutfromstart" => EditCommand::CutFromStart,
-                "cutfromlinestart" => EditCommand::CutFromLineStart,
-                "cuttoend" => EditCommand::CutToEnd,
-                "cuttolineend" => EditCommand::CutToLineEnd,
-                "cutwordleft" => EditCommand::CutWordLeft,
-                "cutwordright" => EditCommand::CutWordRight,
-                "pastecutbufferbefore" => EditCommand::PasteCutBufferBefore,
-                "pastecutbufferafter" => EditCommand::PasteCutBufferAfter,
-                "uppercaseword" => EditCommand::UppercaseWord,
-                "lowercaseword" => EditCommand::LowercaseWord,
-                "capitalizechar" => EditCommand::CapitalizeChar,
-                "swapwords" => EditCommand::SwapWords,
-                "swapgraphemes" => EditCommand::SwapGraphemes,
-                "undo" => EditCommand::Undo,
-                "redo" => EditCommand::Redo,
-                "cutrightuntil" => {
-                    let char = extract_char("value", edit_cols, edit_vals, config, edit_span)?;
-                    EditCommand::CutRightUntil(char)
-                }
-                "cutrightbefore" => {
-                    let char = extract_char("value", edit_cols, edit_vals, config, edit_span)?;
-                    EditCommand::CutRightBefore(char)
-                }
-                "moverightuntil" => {
-                    let char = extract_char("value", edit_cols, edit_vals, config, edit_span)?;
-                    EditCommand::MoveRightUntil(char)
-                }
-                "moverightbefore" => {
-                    let char = extract_char("value", edit_cols, edit_vals, config, edit_span)?;
-                    EditCommand::MoveRightBefore(char)
-                }
-                "cutleftuntil" => {
-                    let char = extract_char("value", edit_cols, edit_vals, config, edit_span)?;
-                    EditCommand::CutLeftUntil(char)
-                }
-                "cutleftbefore" => {
-                    let char = extract_char("value", edit_cols, edit_vals, config, edit_span)?;
-                    EditCommand::CutLeftBefore(char)
-                }
-                "moveleftuntil" => {
-                    let char = extract_char("value", edit_cols, edit_vals, config, edit_span)?;
-                    EditCommand::MoveLeftUntil(char)
-                }
-                "moveleftbefore" => {
-                    let char = extract_char("value", edit_cols, edit_vals, config, edit_span)?;
-                    EditCommand::MoveLeftBefore(char)
+                    edit(value.into_string("", config))
                 }
-                e => {
+                None => {
                     return Err(ShellError::UnsupportedConfigValue(
-                        e.to_string(),
-                        "reedline EditCommand".to_string(),
+                        format!("one of {}", keybinding_names(KEYBINDING_EDITS).join(", ")),
+                        name,
                         edit.span()?,
                     ))
                 }
@@ -6,7 +6,8 @@ use std::{
     time::Instant,
 };
 
-use crate::{config_files, prompt_update, reedline_config};
+use crate::config_files::{self, ConfigFile};
+use crate::{prompt_update, reedline_config};
 use crate::{
     reedline_config::KeybindingsMode,
     utils::{eval_source, gather_parent_env_vars, report_error},
@@ -23,11 +24,19 @@ use nu_protocol::{
 };
 use reedline::{DefaultHinter, Emacs, Vi};
 
-pub(crate) fn evaluate(ctrlc: Arc<AtomicBool>, engine_state: &mut EngineState) -> Result<()> {
+pub(crate) fn evaluate(
+    ctrlc: Arc<AtomicBool>,
+    config_file: &ConfigFile,
+    env_file: &ConfigFile,
+    engine_state: &mut EngineState,
+    entire_start_time: Instant,
+) -> Result<()> {
     use crate::logger::{configure, logger};
     use reedline::{FileBackedHistory, Reedline, Signal};
 
     let mut entry_num = 0;
+    let mut reported_keybinding_errors = String::new();
+    let mut reported_prompt_errors = String::new();
 
     let mut nu_prompt = NushellPrompt::new();
     let mut stack = nu_protocol::engine::Stack::new();
@@ -48,8 +57,7 @@ pub(crate) fn evaluate(ctrlc: Arc<AtomicBool>, engine_state: &mut EngineState) -
     #[cfg(feature = "plugin")]
     config_files::read_plugin_file(engine_state, &mut stack);
 
-    config_files::read_config_file(engine_state, &mut stack);
-    let history_path = config_files::create_history_path();
+    config_files::read_config_file(engine_state, &mut stack, config_file, env_file);
 
     // Load config struct form config variable
     let config = match stack.get_config() {
@@ -62,6 +70,8 @@ pub(crate) fn evaluate(ctrlc: Arc<AtomicBool>, engine_state: &mut EngineState) -
         }
     };
 
+    let history_path = config_files::create_history_path(config.max_history_size as usize);
+
     logger(|builder| {
         configure(&config.log_level, builder)?;
         // trace_filters(self, builder)?;
@@ -76,6 +86,17 @@ pub(crate) fn evaluate(ctrlc: Arc<AtomicBool>, engine_state: &mut EngineState) -
         report_error(&working_set, &e);
     }
 
+    engine_state.startup_time = entire_start_time.elapsed().as_nanos() as i64;
+
+    // seed the last_exit_code env var
+    stack.add_env_var(
+        "LAST_EXIT_CODE".into(),
+        Value::Int {
+            val: 0,
+            span: Span { start: 0, end: 0 },
+        },
+    );
+
     // seed the cmd_duration_ms env var
     stack.add_env_var(
         "CMD_DURATION_MS".into(),
@@ -117,7 +138,7 @@ pub(crate) fn evaluate(ctrlc: Arc<AtomicBool>, engine_state: &mut EngineState) -
             }))
             .with_ansi_colors(config.use_ansi_coloring)
             .with_menu_completer(
-                Box::new(NuCompleter::new(engine_state.clone())),
+                Box::new(NuCompleter::new(engine_state.clone(), stack.clone())),
                 reedline_config::create_menu_input(&config),
             );
 
@@ -149,28 +170,32 @@ pub(crate) fn evaluate(ctrlc: Arc<AtomicBool>, engine_state: &mut EngineState) -
         };
 
         // Changing the line editor based on the found keybindings
-        let mut line_editor = match reedline_config::create_keybindings(&config) {
-            Ok(keybindings) => match keybindings {
-                KeybindingsMode::Emacs(keybindings) => {
-                    let edit_mode = Box::new(Emacs::new(keybindings));
-                    line_editor.with_edit_mode(edit_mode)
-                }
-                KeybindingsMode::Vi {
-                    insert_keybindings,
-                    normal_keybindings,
-                } => {
-                    let edit_mode = Box::new(Vi::new(insert_keybindings, normal_keybindings));
-                    line_editor.with_edit_mode(edit_mode)
-                }
-            },
-            Err(e) => {
-                let working_set = StateWorkingSet::new(engine_state);
-                report_error(&working_set, &e);
-                line_editor
+        let (keybindings, keybinding_errors) = reedline_config::create_keybindings(&config);
+
+        report_changed_errors(
+            engine_state,
+            &keybinding_errors,
+            &mut reported_keybinding_errors,
+        );
+
+        // Changing the line editor based on the found keybindings
+        let mut line_editor = match keybindings {
+            KeybindingsMode::Emacs(keybindings) => {
+                let edit_mode = Box::new(Emacs::new(keybindings));
+                line_editor.with_edit_mode(edit_mode)
+            }
+            KeybindingsMode::Vi {
+                insert_keybindings,
+                normal_keybindings,
+            } => {
+                let edit_mode = Box::new(Vi::new(insert_keybindings, normal_keybindings));
+                line_editor.with_edit_mode(edit_mode)
             }
         };
 
-        let prompt = prompt_update::update_prompt(&config, engine_state, &stack, &mut nu_prompt);
+        let (prompt, prompt_errors) =
+            prompt_update::update_prompt(&config, engine_state, &stack, &mut nu_prompt);
+        report_changed_errors(engine_state, &prompt_errors, &mut reported_prompt_errors);
 
         entry_num += 1;
 
@@ -241,13 +266,30 @@ pub(crate) fn evaluate(ctrlc: Arc<AtomicBool>, engine_state: &mut EngineState) -
                 } else {
                     trace!("eval source: {}", s);
 
-                    eval_source(
+                    let succeeded = eval_source(
                         engine_state,
                         &mut stack,
                         &s,
                         &format!("entry #{}", entry_num),
                     );
 
+                    // An external in the last statement reports its own status once its
+                    // output has been printed. After an error there is nothing to wait for.
+                    let exit_code = if succeeded {
+                        stack.update_last_exit_code().unwrap_or(0)
+                    } else {
+                        stack.pending_exit_code = None;
+                        1
+                    };
+
+                    stack.add_env_var(
+                        "LAST_EXIT_CODE".into(),
+                        Value::Int {
+                            val: exit_code,
+                            span: Span { start: 0, end: 0 },
+                        },
+                    );
+
                     stack.add_env_var(
                         "CMD_DURATION_MS".into(),
                         Value::String {
@@ -286,3 +328,16 @@ pub(crate) fn evaluate(ctrlc: Arc<AtomicBool>, engine_state: &mut EngineState) -
 
     Ok(())
 }
+
+// Report errors that come back each time the prompt is shown only when they are new, so a broken
+// config shows its errors once instead of before every prompt
+fn report_changed_errors(engine_state: &EngineState, errors: &[ShellError], reported: &mut String) {
+    let current = format!("{:?}", errors);
+    if current != *reported {
+        let working_set = StateWorkingSet::new(engine_state);
+        for error in errors {
+            report_error(&working_set, error);
+        }
+        *reported = current;
+    }
+}
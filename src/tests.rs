@@ -1,14 +1,19 @@
 mod test_conditionals;
+mod test_config_files;
 mod test_converters;
 mod test_custom_commands;
 mod test_engine;
 mod test_env;
+mod test_filesystem;
 mod test_hiding;
+mod test_input;
 mod test_iteration;
 mod test_math;
 mod test_modules;
 mod test_parser;
+mod test_path;
 mod test_ranges;
+mod test_scripts;
 mod test_strings;
 mod test_table_operations;
 mod test_type_check;
@@ -16,7 +21,7 @@ mod test_type_check;
 use assert_cmd::prelude::*;
 use pretty_assertions::assert_eq;
 use std::io::Write;
-use std::process::Command;
+use std::process::{Command, Output, Stdio};
 use tempfile::NamedTempFile;
 
 pub type TestResult = Result<(), Box<dyn std::error::Error>>;
@@ -98,6 +103,116 @@ pub fn fail_test(input: &str, expected: &str) -> TestResult {
     Ok(())
 }
 
+#[cfg(test)]
+pub fn exit_code_test(input: &str, expected: i32) -> TestResult {
+    let mut file = NamedTempFile::new()?;
+    let name = file.path();
+
+    let mut cmd = Command::cargo_bin("engine-q")?;
+    cmd.arg(name);
+
+    writeln!(file, "{}", input)?;
+
+    let output = cmd.output()?;
+
+    let stderr = String::from_utf8_lossy(&output.stderr).to_string();
+    let stdout = String::from_utf8_lossy(&output.stdout).to_string();
+
+    println!("stdout: {}", stdout);
+    println!("stderr: {}", stderr);
+
+    assert_eq!(output.status.code(), Some(expected));
+
+    Ok(())
+}
+
+/// Run `script` with `args` after it and `stdin` piped into it
+#[cfg(test)]
+pub fn run_script(
+    script: &str,
+    args: &[&str],
+    stdin: &str,
+) -> Result<Output, Box<dyn std::error::Error>> {
+    let mut file = NamedTempFile::new()?;
+    writeln!(file, "{}", script)?;
+
+    let mut child = Command::cargo_bin("engine-q")?
+        .arg(file.path())
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+
+    if let Some(mut child_stdin) = child.stdin.take() {
+        child_stdin.write_all(stdin.as_bytes())?;
+    }
+
+    let output = child.wait_with_output()?;
+
+    println!("stdout: {}", String::from_utf8_lossy(&output.stdout));
+    println!("stderr: {}", String::from_utf8_lossy(&output.stderr));
+
+    Ok(output)
+}
+
+/// The trimmed output of `run_script`, which has to succeed
+#[cfg(test)]
+pub fn script_output(
+    script: &str,
+    args: &[&str],
+    stdin: &str,
+) -> Result<String, Box<dyn std::error::Error>> {
+    let output = run_script(script, args, stdin)?;
+
+    assert!(output.status.success());
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// A scratch directory for tests that work on files, removed again when it's dropped
+#[cfg(test)]
+pub struct Playground {
+    dir: tempfile::TempDir,
+}
+
+#[cfg(test)]
+impl Playground {
+    pub fn new() -> Result<Self, std::io::Error> {
+        Ok(Playground {
+            dir: tempfile::tempdir()?,
+        })
+    }
+
+    /// Write `contents` to the file at `name`, making the directories above it
+    pub fn file(&self, name: &str, contents: impl AsRef<[u8]>) -> Result<&Self, std::io::Error> {
+        let path = self.path(name);
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, contents)?;
+
+        Ok(self)
+    }
+
+    /// Make the directory at `name` and the ones above it
+    pub fn dir(&self, name: &str) -> Result<&Self, std::io::Error> {
+        std::fs::create_dir_all(self.path(name))?;
+
+        Ok(self)
+    }
+
+    /// Where `name` is inside the playground
+    pub fn path(&self, name: &str) -> std::path::PathBuf {
+        self.dir.path().join(name)
+    }
+
+    /// `script`, run from inside the playground. The path is quoted, as temporary directories
+    /// can have spaces in them
+    pub fn script(&self, script: &str) -> String {
+        format!("cd \"{}\"; {}", self.dir.path().display(), script)
+    }
+}
+
 #[cfg(test)]
 pub fn not_found_msg() -> &'static str {
     "can't run executable"
@@ -1,4 +1,4 @@
-use crate::tests::{run_test, TestResult};
+use crate::tests::{fail_test, run_test, TestResult};
 
 #[test]
 fn if_test1() -> TestResult {
@@ -59,3 +59,71 @@ fn if_elseif3() -> TestResult {
 fn if_elseif4() -> TestResult {
     run_test("if 2 > 3 { 5 } else if 6 < 7 { 4 } else { 8 } ", "4")
 }
+
+#[test]
+fn if_elseif_chain_in_pipeline() -> TestResult {
+    run_test(
+        r#"[3 7 12] | each { |x| if $x > 10 { "big" } else if $x > 5 { "medium" } else { "small" } } | str collect ','"#,
+        "small,medium,big",
+    )
+}
+
+#[test]
+fn if_elseif_deep_chain() -> TestResult {
+    run_test(
+        "if $false { 1 } else if $false { 2 } else if $false { 3 } else if $true { 4 } else { 5 }",
+        "4",
+    )
+}
+
+#[test]
+fn if_dangling_else_belongs_to_last_if() -> TestResult {
+    run_test("if $true { 1 } else if $false { 2 } else { 3 }", "1")?;
+    run_test("if $false { 1 } else if $false { 2 } else { 3 }", "3")
+}
+
+#[test]
+fn if_condition_must_be_bool() -> TestResult {
+    fail_test("if 1 { 2 }", "Type mismatch")
+}
+
+#[test]
+fn elseif_condition_must_be_bool() -> TestResult {
+    fail_test(r#"if $false { 1 } else if "yes" { 2 }"#, "Type mismatch")
+}
+
+#[test]
+fn if_missing_block() -> TestResult {
+    fail_test("if $true", "missing then_block")
+}
+
+#[test]
+fn if_else_missing_branch() -> TestResult {
+    fail_test("if $false { 1 } else", "Missing argument to `else`")
+}
+
+// The externals used here aren't there on Windows
+#[cfg(not(windows))]
+#[test]
+fn if_external_exit_status() -> TestResult {
+    run_test(r#"if (^true) { "yes" } else { "no" }"#, "yes")?;
+    run_test(r#"if (^false) { "yes" } else { "no" }"#, "no")?;
+    run_test(
+        r#"if (^echo nushell | ^grep -q shell) { "found" } else { "missing" }"#,
+        "found",
+    )
+}
+
+#[cfg(not(windows))]
+#[test]
+fn if_external_sets_last_exit_code() -> TestResult {
+    run_test(
+        r#"if (^sh -c 'exit 3') { 0 } else { $env.LAST_EXIT_CODE }"#,
+        "3",
+    )
+}
+
+#[test]
+fn if_internal_string_is_not_a_condition() -> TestResult {
+    fail_test(r#"if ("yes") { 1 }"#, "bool")
+}
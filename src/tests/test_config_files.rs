@@ -0,0 +1,134 @@
+use crate::tests::TestResult;
+use assert_cmd::prelude::*;
+use std::ffi::OsStr;
+use std::io::Write;
+use std::path::Path;
+use std::process::{Command, Output};
+use tempfile::NamedTempFile;
+
+fn run_with_config(config: &Path, script: &str) -> Result<Output, Box<dyn std::error::Error>> {
+    run_with_args(&["--config".as_ref(), config.as_os_str()], script)
+}
+
+fn run_with_args(args: &[&OsStr], script: &str) -> Result<Output, Box<dyn std::error::Error>> {
+    let mut file = NamedTempFile::new()?;
+    writeln!(file, "{}", script)?;
+
+    let output = Command::cargo_bin("engine-q")?
+        .args(args)
+        .arg(file.path())
+        .output()?;
+
+    println!("stdout: {}", String::from_utf8_lossy(&output.stdout));
+    println!("stderr: {}", String::from_utf8_lossy(&output.stderr));
+
+    Ok(output)
+}
+
+#[test]
+fn config_alias_is_usable() -> TestResult {
+    let dir = tempfile::tempdir()?;
+    let config = dir.path().join("config.nu");
+    std::fs::write(&config, "alias greet = echo hello\n")?;
+
+    let output = run_with_config(&config, "greet")?;
+
+    assert!(output.status.success());
+    assert_eq!(String::from_utf8_lossy(&output.stdout).trim(), "hello");
+    Ok(())
+}
+
+#[test]
+fn env_file_is_read_before_config() -> TestResult {
+    let dir = tempfile::tempdir()?;
+    let config = dir.path().join("config.nu");
+    let env = dir.path().join("env.nu");
+    std::fs::write(&env, "let-env GREETING = hello\n")?;
+    std::fs::write(&config, "def greet [] { $env.GREETING }\n")?;
+
+    let output = run_with_args(
+        &[
+            "--env-config".as_ref(),
+            env.as_os_str(),
+            "--config".as_ref(),
+            config.as_os_str(),
+        ],
+        "greet",
+    )?;
+
+    assert!(output.status.success());
+    assert_eq!(String::from_utf8_lossy(&output.stdout).trim(), "hello");
+    Ok(())
+}
+
+#[test]
+fn env_file_next_to_config_is_not_read() -> TestResult {
+    let dir = tempfile::tempdir()?;
+    let config = dir.path().join("config.nu");
+    std::fs::write(dir.path().join("env.nu"), "let-env GREETING = hello\n")?;
+    std::fs::write(&config, "")?;
+
+    let output = run_with_config(&config, "$env | columns | str collect ' '")?;
+
+    assert!(output.status.success());
+    assert!(!String::from_utf8_lossy(&output.stdout).contains("GREETING"));
+    Ok(())
+}
+
+#[test]
+fn config_path_is_exposed() -> TestResult {
+    let dir = tempfile::tempdir()?;
+    let config = dir.path().join("my-config.nu");
+    std::fs::write(&config, "")?;
+
+    let output = run_with_config(&config, "$nu.config-path")?;
+
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout).trim(),
+        config.to_string_lossy()
+    );
+    Ok(())
+}
+
+#[test]
+fn config_errors_do_not_stop_startup() -> TestResult {
+    let dir = tempfile::tempdir()?;
+    let config = dir.path().join("config.nu");
+    std::fs::write(&config, "alias greet = echo hello\nlet x = (\n")?;
+
+    let output = run_with_config(&config, "echo started")?;
+    let stderr = String::from_utf8_lossy(&output.stderr);
+
+    assert!(output.status.success());
+    assert!(stderr.contains("config.nu"));
+    assert_eq!(String::from_utf8_lossy(&output.stdout).trim(), "started");
+    Ok(())
+}
+
+#[test]
+fn strict_pipeline_types_stop_the_script() -> TestResult {
+    let dir = tempfile::tempdir()?;
+    let config = dir.path().join("config.nu");
+    std::fs::write(&config, "let config = {strict_pipeline_types: $true}\n")?;
+
+    let output = run_with_config(&config, "ls | lines | length")?;
+
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Pipeline stages don't fit together"));
+    assert_eq!(String::from_utf8_lossy(&output.stdout).trim(), "");
+    Ok(())
+}
+
+#[test]
+fn strict_pipeline_types_run_ranges() -> TestResult {
+    let dir = tempfile::tempdir()?;
+    let config = dir.path().join("config.nu");
+    std::fs::write(&config, "let config = {strict_pipeline_types: $true}\n")?;
+
+    let output = run_with_config(&config, "1..3 | length")?;
+
+    assert!(output.status.success());
+    assert_eq!(String::from_utf8_lossy(&output.stdout).trim(), "3");
+    Ok(())
+}
@@ -1,4 +1,4 @@
-use crate::tests::{run_test, TestResult};
+use crate::tests::{fail_test, run_test, Playground, TestResult};
 
 #[test]
 fn from_json_1() -> TestResult {
@@ -37,3 +37,233 @@ fn to_json_raw_flag_3() -> TestResult {
         r#"[{"a b": "jim smith","c d": "susie roberts"},{"a b": 3,"c d": 4}]"#,
     )
 }
+
+#[test]
+fn save_and_open_json_round_trip() -> TestResult {
+    let dir = tempfile::tempdir()?;
+    let file = dir.path().join("listing.json");
+
+    run_test(
+        &format!(
+            "ls | save \"{0}\"; (open \"{0}\" | length) == (ls | length)",
+            file.display()
+        ),
+        "true",
+    )
+}
+
+#[test]
+fn save_and_open_csv_round_trip() -> TestResult {
+    let dir = tempfile::tempdir()?;
+    let file = dir.path().join("table.csv");
+
+    run_test(
+        &format!(
+            "[[a b]; [1 2] [3 4]] | save \"{0}\"; open \"{0}\" | get b.1",
+            file.display()
+        ),
+        "4",
+    )
+}
+
+#[test]
+fn save_append_and_mkdir() -> TestResult {
+    let dir = tempfile::tempdir()?;
+    let file = dir.path().join("logs").join("out.txt");
+
+    run_test(
+        &format!(
+            "'one' | save --mkdir \"{0}\"; 'two' | save --append \"{0}\"; open \"{0}\"",
+            file.display()
+        ),
+        "onetwo",
+    )
+}
+
+#[test]
+fn save_raw_byte_stream_and_hash_it() -> TestResult {
+    let dir = tempfile::tempdir()?;
+    let source = dir.path().join("source.bin");
+    let copy = dir.path().join("copy.bin");
+
+    // Large enough to come through `open --raw` in several chunks
+    let bytes: Vec<u8> = (0..1_000_000u32).map(|i| (i % 251) as u8).collect();
+    std::fs::write(&source, &bytes)?;
+
+    run_test(
+        &format!(
+            "open --raw \"{0}\" | save --raw \"{1}\"; (open --raw \"{0}\" | hash md5) == (open --raw \"{1}\" | hash md5)",
+            source.display(),
+            copy.display()
+        ),
+        "true",
+    )?;
+
+    assert_eq!(std::fs::read(&copy)?, bytes);
+    Ok(())
+}
+
+#[cfg(not(windows))]
+#[test]
+fn hash_external_output_as_a_whole() -> TestResult {
+    run_test("^printf abc | hash md5", "900150983cd24fb0d6963f7d28e17f72")
+}
+
+#[test]
+fn into_int_radix() -> TestResult {
+    run_test(r#"'ff' | into int -r 16"#, "255")?;
+    run_test(r#"'0o17' | into int"#, "15")?;
+    run_test(r#"['101' '11'] | into int --radix 2 | math sum"#, "8")
+}
+
+#[test]
+fn into_int_unparseable() -> TestResult {
+    fail_test(r#"'zz' | into int -r 16"#, "Can't convert to int")
+}
+
+#[test]
+fn into_int_ignore_errors() -> TestResult {
+    run_test(r#"['1' 'one'] | into int -i | get 1 | describe"#, "nothing")
+}
+
+#[test]
+fn into_float_ignore_errors_in_columns() -> TestResult {
+    run_test(
+        r#"[[n]; ['1.5'] ['x']] | into float -i n | get n.1 | describe"#,
+        "nothing",
+    )?;
+    fail_test(
+        r#"[[n]; ['1.5'] ['x']] | into float n"#,
+        "Can't convert to float",
+    )
+}
+
+#[test]
+fn to_text_aligns_columns() -> TestResult {
+    run_test(
+        "[[name kind]; [a.txt file] [src dir]] | to text",
+        "name   kind\na.txt  file\nsrc    dir",
+    )
+}
+
+#[test]
+fn to_md_fills_missing_columns() -> TestResult {
+    run_test("[{a: 1, b: 2} {a: 3}] | to md", "|a|b|\n|-|-|\n|1|2|\n|3||")
+}
+
+#[test]
+fn ls_to_md() -> TestResult {
+    let dir = Playground::new()?;
+    dir.file("a.txt", "a")?.file("b.txt", "b")?;
+
+    run_test(
+        &dir.script("ls | select name type | to md --pretty"),
+        "| name  | type |\n| ----- | ---- |\n| a.txt | file |\n| b.txt | file |",
+    )
+}
+
+#[test]
+fn from_and_to_with_format_argument() -> TestResult {
+    run_test(
+        "let fmt = 'json'; [1 2] | to $fmt | from $fmt | length",
+        "2",
+    )
+}
+
+#[test]
+fn from_unknown_format() -> TestResult {
+    fail_test("'a' | from nope", "Unknown format nope")
+}
+
+#[test]
+fn to_list_shows_formats() -> TestResult {
+    run_test(
+        "to --list | where format == md | get extensions.0.1",
+        "markdown",
+    )?;
+    run_test("from --list | where format == md | length", "0")
+}
+
+#[test]
+fn save_and_open_yml_extension() -> TestResult {
+    let dir = tempfile::tempdir()?;
+    let file = dir.path().join("table.yml");
+
+    run_test(
+        &format!(
+            "[[a]; [1]] | save \"{0}\"; open \"{0}\" | get a.0",
+            file.display()
+        ),
+        "1",
+    )
+}
+
+#[test]
+fn open_unregistered_extension_as_text() -> TestResult {
+    let dir = tempfile::tempdir()?;
+    let file = dir.path().join("notes.txt");
+    std::fs::write(&file, "hello")?;
+
+    run_test(&format!("open \"{}\"", file.display()), "hello")
+}
+
+#[test]
+fn open_byte_range() -> TestResult {
+    let dir = tempfile::tempdir()?;
+    let file = dir.path().join("big.log");
+    std::fs::write(&file, "hello world\n")?;
+
+    run_test(
+        &format!("open \"{}\" --offset 6 --length 5", file.display()),
+        "world",
+    )?;
+    run_test(&format!("open \"{}\" --offset 100", file.display()), "")
+}
+
+#[test]
+fn open_line_range() -> TestResult {
+    let dir = tempfile::tempdir()?;
+    let file = dir.path().join("big.log");
+    std::fs::write(&file, "a\nb\nc\nd\n")?;
+
+    run_test(&format!("open \"{}\" --lines 1..2", file.display()), "b\nc")?;
+    run_test(&format!("open \"{}\" --lines 2..", file.display()), "c\nd")?;
+    run_test(&format!("open \"{}\" --lines 10..20", file.display()), "")?;
+    run_test(
+        &format!(
+            "open \"{}\" --lines 0..0 | metadata | get source",
+            file.display()
+        ),
+        &file.display().to_string(),
+    )
+}
+
+#[test]
+fn open_line_range_reaches_the_end_when_given_as_a_value() -> TestResult {
+    let dir = tempfile::tempdir()?;
+    let file = dir.path().join("big.log");
+    let contents: String = (0..200).map(|line| format!("{}\n", line)).collect();
+    std::fs::write(&file, contents)?;
+
+    run_test(
+        &format!("let r = 198..; open \"{}\" --lines $r", file.display()),
+        "198\n199",
+    )?;
+    run_test(
+        &format!(
+            "open \"{}\" --lines (150..) | lines | length",
+            file.display()
+        ),
+        "50",
+    )
+}
+
+#[test]
+fn open_line_range_with_step() -> TestResult {
+    fail_test("open foo.txt --lines 0..2..10", "can't have a step")
+}
+
+#[test]
+fn open_lines_with_byte_range() -> TestResult {
+    fail_test("open foo.txt --lines 0..1 --offset 3", "Incompatible")
+}
@@ -1,4 +1,4 @@
-use crate::tests::{fail_test, run_test, TestResult};
+use crate::tests::{fail_test, run_test, run_test_contains, TestResult};
 
 #[test]
 fn no_scope_leak1() -> TestResult {
@@ -117,3 +117,42 @@ fn allow_missing_optional_params() -> TestResult {
         "5",
     )
 }
+
+#[test]
+fn extern_rejects_unknown_flag() -> TestResult {
+    fail_test(
+        r#"extern "git push" [remote?: string, --force(-f)]; git push --forse"#,
+        "doesn't have flag",
+    )
+}
+
+#[test]
+fn extern_checks_positional_types() -> TestResult {
+    fail_test(r#"extern "spam" [count: int]; spam eggs"#, "Parse mismatch")
+}
+
+#[test]
+fn extern_has_help() -> TestResult {
+    run_test_contains(
+        r#"extern "git push" [remote?: string, --force(-f)]; git push --help"#,
+        "--force",
+    )
+}
+
+#[cfg(not(windows))]
+#[test]
+fn extern_runs_the_external() -> TestResult {
+    run_test(
+        r#"extern "printf" [format: string, ...rest]; printf "%s-%s" a b"#,
+        "a-b",
+    )
+}
+
+#[cfg(not(windows))]
+#[test]
+fn extern_keeps_argument_order() -> TestResult {
+    run_test(
+        r#"extern "printf" [format: string, ...rest, --flag]; printf "%s %s %s" a --flag b"#,
+        "a --flag b",
+    )
+}
@@ -1,4 +1,6 @@
-use crate::tests::{fail_test, run_test, TestResult};
+use crate::tests::{
+    exit_code_test, fail_test, run_test, run_test_contains, Playground, TestResult,
+};
 
 #[test]
 fn concrete_variable_assignment() -> TestResult {
@@ -40,6 +42,25 @@ fn config_filesize_format_with_metric_false_kb() -> TestResult {
     )
 }
 
+#[test]
+fn config_filesize_format_in_table() -> TestResult {
+    run_test_contains(
+        r#"let config = {"filesize_metric": $false "filesize_format": "kib" }; do { [[size]; [40kb]] | table }"#,
+        "39.1 KiB",
+    )
+}
+
+#[test]
+fn config_filesize_format_in_ls() -> TestResult {
+    let dir = Playground::new()?;
+    dir.file("a.bin", vec![0u8; 2048])?;
+
+    run_test_contains(
+        &dir.script(r#"let config = {"filesize_metric": $false "filesize_format": "kib" }; do { ls | select name size | table }"#),
+        "2.0 KiB",
+    )
+}
+
 #[test]
 fn in_variable_1() -> TestResult {
     run_test(r#"[3] | if $in.0 > 4 { "yay!" } else { "boo" }"#, "boo")
@@ -55,6 +76,14 @@ fn in_variable_3() -> TestResult {
     run_test(r#"3 | if $in > 4 { "yay!" } else { $in }"#, "3")
 }
 
+#[test]
+fn in_variable_elseif_chain() -> TestResult {
+    run_test(
+        r#"7 | if $in > 10 { "big" } else if $in > 5 { "medium" } else { "small" }"#,
+        "medium",
+    )
+}
+
 #[test]
 fn in_variable_4() -> TestResult {
     run_test(r#"3 | do { $in }"#, "3")
@@ -75,11 +104,77 @@ fn help_works_with_missing_requirements() -> TestResult {
     run_test(r#"each --help | lines | length"#, "15")
 }
 
+#[test]
+fn help_lists_flags_with_shorts_and_shapes() -> TestResult {
+    run_test(
+        r#"(help ls | str contains "-q, --stat <string>") && (each --help | str contains "-n, --numbered")"#,
+        "true",
+    )
+}
+
+#[test]
+fn help_lists_parameter_shapes() -> TestResult {
+    run_test(r#"help rename | str contains "...rest <string>""#, "true")
+}
+
+#[test]
+fn help_for_custom_command() -> TestResult {
+    run_test(
+        r#"def greet [name: string, --loud(-l)] { $name }; let h = (help greet); ($h | str contains "name <string>") && ($h | str contains "-l, --loud")"#,
+        "true",
+    )
+}
+
+#[test]
+fn help_lists_commands_by_category() -> TestResult {
+    run_test(
+        r#"let h = (help); ($h | str contains "filters:") && ($h | str contains "  rename - ")"#,
+        "true",
+    )
+}
+
+#[test]
+fn help_find_searches_names() -> TestResult {
+    run_test(r#"help --find rename | where name == rename | length"#, "1")
+}
+
 #[test]
 fn scope_variable() -> TestResult {
     run_test(r#"let x = 3; $scope.vars.'$x'"#, "int")
 }
 
+#[test]
+fn scope_lists_custom_commands() -> TestResult {
+    run_test(
+        r#"def greet [name: string] { $name }; $scope.commands | where is_custom | get command | str collect ','"#,
+        "greet",
+    )
+}
+
+#[test]
+fn scope_lists_alias_expansions() -> TestResult {
+    run_test(
+        r#"alias hi = echo hello; $scope.aliases | where name == hi | get expansion.0"#,
+        "echo hello",
+    )
+}
+
+#[test]
+fn scope_lists_module_exports() -> TestResult {
+    run_test(
+        r#"module spam { export def foo [] { 1 } }; $scope.modules | where name == spam | get commands.0 | str collect"#,
+        "foo",
+    )
+}
+
+#[test]
+fn scope_skips_hidden_commands() -> TestResult {
+    run_test(
+        r#"def greet [] { 1 }; hide greet; $scope.commands | where command == greet | length"#,
+        "0",
+    )
+}
+
 #[test]
 fn earlier_errors() -> TestResult {
     fail_test(
@@ -149,6 +244,35 @@ fn proper_variable_for() -> TestResult {
     run_test(r#"for x in 1..3 { if $x == 2 { "bob" } } | get 1"#, "bob")
 }
 
+#[test]
+fn proper_variable_for_record() -> TestResult {
+    run_test(
+        r#"for x in {a: 1, b: 2} { $"($x.key)($x.value)" } | str collect ','"#,
+        "a1,b2",
+    )
+}
+
+#[test]
+fn proper_variable_for_numbered() -> TestResult {
+    run_test("for x in [5 6 7] --numbered { $x.index } | math sum", "3")
+}
+
+#[test]
+fn proper_variable_for_stream() -> TestResult {
+    run_test(
+        "for x in ([1 2 3] | each { |y| $y * 2 }) { $x } | math sum",
+        "12",
+    )
+}
+
+#[test]
+fn proper_variable_for_infinite_stream_is_lazy() -> TestResult {
+    run_test(
+        "for x in (1..1000000000 | each { |y| $y }) { $x } | first 2 | math sum",
+        "3",
+    )
+}
+
 #[test]
 fn divide_duration() -> TestResult {
     run_test(r#"4ms / 4ms"#, "1")
@@ -158,3 +282,245 @@ fn divide_duration() -> TestResult {
 fn divide_filesize() -> TestResult {
     run_test(r#"4mb / 4mb"#, "1")
 }
+
+#[test]
+fn metadata_of_variable() -> TestResult {
+    run_test("let a = 42; metadata $a | get origin", "42")
+}
+
+#[test]
+fn metadata_of_variable_span() -> TestResult {
+    run_test(
+        "let a = 42; let md = (metadata $a); $md.span.end - $md.span.start",
+        "2",
+    )
+}
+
+#[test]
+fn metadata_of_ls_input() -> TestResult {
+    run_test("ls | metadata | get source", "ls")
+}
+
+#[test]
+fn metadata_of_value_input() -> TestResult {
+    run_test("[1 2 3] | metadata | get origin", "[1 2 3]")
+}
+
+#[test]
+fn describe_basic_values() -> TestResult {
+    run_test("1 | describe", "int")?;
+    run_test("1.5 | describe", "float")?;
+    run_test("$true | describe", "bool")?;
+    run_test("'a' | describe", "string")?;
+    run_test("1kb | describe", "filesize")?;
+    run_test("1sec | describe", "duration")?;
+    run_test("date now | describe", "date")?;
+    run_test("$nothing | describe", "nothing")
+}
+
+#[test]
+fn describe_other_values() -> TestResult {
+    run_test("1..3 | describe", "range")?;
+    run_test("{ 1 } | describe", "block")?;
+    run_test("0x[ff] | describe", "binary")
+}
+
+#[test]
+fn describe_lists() -> TestResult {
+    run_test("[1 2] | describe", "list<int>")?;
+    run_test("[1 'a'] | describe", "list<any>")?;
+    run_test("[] | describe", "list<any>")?;
+    run_test("[[a b]; [1 2]] | describe", "table")
+}
+
+#[test]
+fn describe_record() -> TestResult {
+    run_test(
+        "{name: 'a', size: 1kb} | describe",
+        "record<name: string, size: filesize>",
+    )
+}
+
+#[test]
+fn describe_stream() -> TestResult {
+    run_test("[1 2 3] | each { |x| $x } | describe", "stream<int>")
+}
+
+#[test]
+fn ignore_discards_output() -> TestResult {
+    run_test("echo done | ignore", "")
+}
+
+#[cfg(not(windows))]
+#[test]
+fn ignore_runs_external_to_completion() -> TestResult {
+    let dir = tempfile::tempdir()?;
+    let marker = dir.path().join("marker");
+
+    run_test(
+        &format!(
+            "^sh -c 'echo start; touch \"{}\"; echo end' | ignore",
+            marker.display()
+        ),
+        "",
+    )?;
+
+    assert!(marker.exists());
+    Ok(())
+}
+
+#[test]
+fn exit_with_code() -> TestResult {
+    exit_code_test("exit 3", 3)
+}
+
+#[test]
+fn exit_with_code_from_block() -> TestResult {
+    exit_code_test("do { exit 7 }; echo unreachable", 7)
+}
+
+#[test]
+fn exit_without_code() -> TestResult {
+    exit_code_test("exit; exit 5", 0)
+}
+
+#[test]
+fn keybindings_list_events() -> TestResult {
+    run_test(
+        r#"keybindings list --events | where name == executehostcommand | get type.0"#,
+        "event",
+    )
+}
+
+#[test]
+fn keybindings_list_edits_with_values() -> TestResult {
+    run_test(
+        r#"keybindings list --edits | where name == insertchar | get type.0"#,
+        "edit",
+    )
+}
+
+#[test]
+fn keybindings_list_filters_by_flag() -> TestResult {
+    run_test(
+        r#"keybindings list -m -k | where type == event | length"#,
+        "0",
+    )
+}
+
+#[test]
+fn keybindings_default_has_config_shape() -> TestResult {
+    run_test(
+        r#"keybindings default | get 0 | columns | str collect ','"#,
+        "mode,modifier,keycode,event",
+    )
+}
+
+#[cfg(not(windows))]
+#[test]
+fn last_exit_code_of_failing_external() -> TestResult {
+    run_test("^sh -c 'exit 3'; $env.LAST_EXIT_CODE", "3")
+}
+
+#[cfg(not(windows))]
+#[test]
+fn last_exit_code_drives_conditionals() -> TestResult {
+    run_test(
+        "^sh -c 'exit 1'; if $env.LAST_EXIT_CODE != 0 { 'not found' } else { 'found' }",
+        "not found",
+    )
+}
+
+#[cfg(not(windows))]
+#[test]
+fn last_exit_code_of_streamed_external() -> TestResult {
+    run_test(
+        "^sh -c 'echo a; echo b; exit 4' | lines | length; $env.LAST_EXIT_CODE",
+        "2\n4",
+    )
+}
+
+#[cfg(not(windows))]
+#[test]
+fn last_exit_code_of_partially_consumed_external() -> TestResult {
+    run_test(
+        "^sh -c 'echo a; sleep 0.1; exit 5' | lines | first 1 | ignore; $env.LAST_EXIT_CODE",
+        "5",
+    )
+}
+
+#[cfg(not(windows))]
+#[test]
+fn last_exit_code_of_signalled_external() -> TestResult {
+    run_test("^sh -c 'kill -TERM $$'; $env.LAST_EXIT_CODE", "143")
+}
+
+#[test]
+fn runtime_error_shows_source() -> TestResult {
+    fail_test("let x = 0; 10 / $x", "10 / $x")
+}
+
+#[test]
+fn division_by_zero_labels_the_divisor() -> TestResult {
+    fail_test("10 / 0", "divisor is zero")
+}
+
+#[test]
+fn nested_runtime_error_shows_source() -> TestResult {
+    fail_test("[1 0] | each { 10 / $it } | wrap x", "10 / $it")
+}
+
+#[test]
+fn nu_variable_has_paths_and_startup_info() -> TestResult {
+    run_test(
+        "$nu | select config-path env-path history-path loglevel home-path temp-path pid current-exe startup-time | describe",
+        "record<config-path: string, env-path: string, history-path: string, loglevel: string, home-path: string, temp-path: string, pid: int, current-exe: string, startup-time: duration>",
+    )
+}
+
+#[test]
+fn nu_pid_is_the_shell_process() -> TestResult {
+    run_test("$nu.pid > 0", "true")
+}
+
+#[test]
+fn tutor_lists_lessons() -> TestResult {
+    run_test(
+        "tutor | get topic | str collect ','",
+        "pipelines,variables,filters,commands",
+    )
+}
+
+#[test]
+fn tutor_shows_a_lesson() -> TestResult {
+    run_test_contains("tutor pipelines", "> ls | get name")
+}
+
+#[test]
+fn tutor_find_searches_lesson_bodies() -> TestResult {
+    run_test(
+        "tutor --find let-env | get topic | str collect ','",
+        "variables",
+    )
+}
+
+#[test]
+fn tutor_unknown_topic() -> TestResult {
+    fail_test("tutor nonsense", "the lessons are: pipelines")
+}
+
+#[test]
+fn config_table_collapse_depth() -> TestResult {
+    run_test_contains(
+        r#"let config = {"table_collapse_depth": 1}; do { [[a]; [{b: 1, c: [x y]}]] | table }"#,
+        "{b: 1, c: [list 2 items]}",
+    )
+}
+
+#[test]
+fn table_expand_shows_nested_values() -> TestResult {
+    run_test_contains(
+        r#"[[a]; [{b: 1, c: [x y]}]] | table --expand"#,
+        "{b: 1, c: [x, y]}",
+    )
+}
@@ -14,3 +14,17 @@ fn shorthand_env_2() -> TestResult {
 fn shorthand_env_3() -> TestResult {
     run_test(r#"FOO=BAZ BAR=MOO $env.FOO"#, "BAZ")
 }
+
+#[test]
+fn path_is_a_list() -> TestResult {
+    run_test(r#"$env.PATH | describe"#, "list<string>")
+}
+
+#[cfg(not(windows))]
+#[test]
+fn path_list_is_joined_for_externals() -> TestResult {
+    run_test(
+        r#"let-env PATH = ['/bin', '/usr/bin']; ^printenv PATH"#,
+        "/bin:/usr/bin",
+    )
+}
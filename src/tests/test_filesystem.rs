@@ -0,0 +1,570 @@
+use crate::tests::{fail_test, run_test, run_test_contains, Playground, TestResult};
+
+#[test]
+fn ls_index_column() -> TestResult {
+    run_test("ls --index | columns | first", "index")?;
+    run_test("ls --sort name -r --index | get index.0", "0")
+}
+
+#[cfg(not(windows))]
+fn single_child_chain() -> Result<Playground, std::io::Error> {
+    let dir = Playground::new()?;
+    dir.file("a/b/c/f.txt", "")?.dir("d")?.file("e.txt", "")?;
+
+    Ok(dir)
+}
+
+#[cfg(not(windows))]
+#[test]
+fn ls_compact_collapses_single_child_chain() -> TestResult {
+    let dir = single_child_chain()?;
+
+    run_test(
+        &dir.script("ls --compact | get name | str collect ','"),
+        "a/b/c,d,e.txt",
+    )
+}
+
+#[cfg(not(windows))]
+#[test]
+fn ls_compact_recursive() -> TestResult {
+    let dir = single_child_chain()?;
+
+    run_test(
+        &dir.script("ls --compact **/* | get name | str collect ','"),
+        "a/b/c,a/b/c/f.txt,d,e.txt",
+    )
+}
+
+#[cfg(not(windows))]
+#[test]
+fn ls_no_symlinks() -> TestResult {
+    let dir = Playground::new()?;
+    dir.dir("d")?.file("f.txt", "")?;
+    std::os::unix::fs::symlink(dir.path("f.txt"), dir.path("link.txt"))?;
+    std::os::unix::fs::symlink(dir.path("d"), dir.path("link_d"))?;
+
+    run_test(
+        &dir.script("ls --no-symlinks | get name | str collect ','"),
+        "d,f.txt",
+    )?;
+    run_test(
+        &dir.script("ls --no-symlinks --sort name -r | get name | str collect ','"),
+        "f.txt,d",
+    )
+}
+
+#[cfg(feature = "git-status")]
+fn git(dir: &std::path::Path, args: &[&str]) -> Result<(), std::io::Error> {
+    let status = std::process::Command::new("git")
+        .args(["-c", "user.name=nu", "-c", "user.email=nu@example.com"])
+        .args(args)
+        .current_dir(dir)
+        .output()?
+        .status;
+    assert!(status.success(), "git {:?} failed", args);
+
+    Ok(())
+}
+
+#[cfg(feature = "git-status")]
+#[test]
+fn ls_git_status() -> TestResult {
+    let dir = Playground::new()?;
+    let path = dir.path("");
+
+    git(&path, &["init", "-q"])?;
+    dir.file(".gitignore", "ignored.txt\n")?
+        .file("committed.txt", "a")?
+        .file("modified.txt", "a")?
+        .file("src/lib.rs", "a")?;
+    git(&path, &["add", "."])?;
+    git(&path, &["commit", "-q", "-m", "init"])?;
+
+    dir.file("modified.txt", "b")?
+        .file("src/lib.rs", "b")?
+        .file("staged.txt", "a")?;
+    git(&path, &["add", "staged.txt"])?;
+    dir.file("untracked.txt", "a")?.file("ignored.txt", "a")?;
+
+    run_test(
+        &dir.script("ls --git-status | get git | str collect ','"),
+        "clean,ignored,modified,modified,staged,untracked",
+    )
+}
+
+#[cfg(feature = "git-status")]
+#[test]
+fn ls_git_status_outside_a_repo() -> TestResult {
+    let dir = Playground::new()?;
+    dir.file("a.txt", "a")?;
+
+    run_test(
+        &dir.script("ls --git-status | columns | str collect ','"),
+        "name,type,size,modified",
+    )
+}
+
+#[test]
+fn ls_size_units() -> TestResult {
+    let dir = Playground::new()?;
+    dir.file("a.txt", vec![b'a'; 3000])?;
+
+    run_test(
+        &dir.script("ls --binary | get size.0 | str ends-with ' KiB'"),
+        "true",
+    )?;
+    run_test(
+        &dir.script("ls --decimal | get size.0 | str ends-with ' KB'"),
+        "true",
+    )?;
+    run_test(
+        &dir.script("ls --human-readable | get size.0 | describe"),
+        "string",
+    )
+}
+
+#[test]
+fn ls_size_units_override_config() -> TestResult {
+    let dir = Playground::new()?;
+    dir.file("a.txt", vec![b'a'; 3000])?;
+
+    run_test(
+        &dir.script("let config = {filesize_metric: $true}; ls --human-readable | get size.0 | str ends-with ' KB'"),
+        "true",
+    )?;
+    run_test(
+        &dir.script("let config = {filesize_metric: $true}; ls --binary | get size.0 | str ends-with ' KiB'"),
+        "true",
+    )
+}
+
+#[test]
+fn ls_size_units_sort_by_size() -> TestResult {
+    let dir = Playground::new()?;
+    dir.file("a.txt", vec![b'a'; 3000])?
+        .file("b.txt", vec![b'a'; 200])?;
+
+    run_test(
+        &dir.script("ls --decimal --sort size | get name | str collect ','"),
+        "b.txt,a.txt",
+    )
+}
+
+#[test]
+fn ls_binary_and_decimal() -> TestResult {
+    fail_test("ls --binary --decimal", "--decimal")
+}
+
+#[test]
+fn ls_total_only() -> TestResult {
+    let dir = Playground::new()?;
+    dir.file("a.txt", "abc")?
+        .file("b.txt", "abcde")?
+        .file("d/c.txt", "abcdefg")?;
+
+    run_test(&dir.script("ls --total-only *.txt | get count"), "2")?;
+    run_test(
+        &dir.script("ls --total-only *.txt | get total_size | into int"),
+        "8",
+    )?;
+    run_test(&dir.script("ls --total-only **/*.txt | get count"), "3")?;
+    run_test(
+        &dir.script("ls --total-only **/*.txt | get total_size | into int"),
+        "15",
+    )?;
+    run_test(&dir.script("ls --total-only | get count"), "3")
+}
+
+#[test]
+fn ls_offset() -> TestResult {
+    let dir = Playground::new()?;
+    for name in ["a.txt", "b.txt", "c.txt", "d.txt", "e.txt"] {
+        dir.file(name, "")?;
+    }
+
+    run_test(
+        &dir.script("ls --offset 3 | get name | str collect ' '"),
+        "d.txt e.txt",
+    )?;
+    run_test(
+        &dir.script("ls --offset 1 | first 2 | get name | str collect ' '"),
+        "b.txt c.txt",
+    )?;
+    run_test(
+        &dir.script("ls --sort name -r --offset 1 | first 2 | get name | str collect ' '"),
+        "d.txt c.txt",
+    )?;
+    run_test(&dir.script("ls --offset 10 | length"), "0")?;
+    fail_test(&dir.script("ls --offset -1"), "offset cannot be negative")
+}
+
+#[test]
+fn ls_group_directories_first() -> TestResult {
+    let dir = Playground::new()?;
+    dir.file("b.txt", "")?
+        .file("z.txt", "")?
+        .dir("a_dir")?
+        .dir("y_dir")?;
+
+    run_test(
+        &dir.script("ls --group-directories-first | get name | str collect ' '"),
+        "a_dir y_dir b.txt z.txt",
+    )?;
+    run_test(
+        &dir.script("ls --group-directories-first --sort name -r | get name | str collect ' '"),
+        "y_dir a_dir z.txt b.txt",
+    )
+}
+
+#[test]
+fn ls_count_only() -> TestResult {
+    let dir = Playground::new()?;
+    for name in ["a.txt", "b.txt", ".hidden", "sub/c.txt"] {
+        dir.file(name, "")?;
+    }
+
+    for args in ["", "-a", "**/*", "*.txt", "--offset 1"] {
+        run_test(
+            &dir.script(&format!(
+                "(ls --count-only {}) == (ls {} | length)",
+                args, args
+            )),
+            "true",
+        )?;
+    }
+    run_test(&dir.script("ls --count-only"), "3")?;
+    run_test(&dir.script("ls --count-only -a"), "4")
+}
+
+#[test]
+fn ls_stat_single_field() -> TestResult {
+    let dir = Playground::new()?;
+    dir.file("a.txt", "abc")?
+        .file("b.txt", "hello")?
+        .dir("sub")?;
+
+    run_test(
+        &dir.script("ls --stat name | str collect ','"),
+        "a.txt,b.txt,sub",
+    )?;
+    run_test(
+        &dir.script("(ls --stat size *.txt | math sum) == (ls *.txt | get size | math sum)"),
+        "true",
+    )?;
+    run_test(
+        &dir.script("ls -q type --sort name -r | str collect ','"),
+        "dir,file,file",
+    )?;
+    run_test(
+        &dir.script("(ls --stat modified) == (ls | get modified)"),
+        "true",
+    )?;
+    fail_test(
+        &dir.script("ls --stat inode"),
+        "expected 'name', 'type', 'size'",
+    )
+}
+
+#[cfg(unix)]
+#[test]
+fn ls_filters_by_link_count() -> TestResult {
+    let dir = Playground::new()?;
+    dir.file("a.txt", "shared")?;
+    std::fs::hard_link(dir.path("a.txt"), dir.path("b.txt"))?;
+    dir.file("c.txt", "alone")?;
+
+    run_test(
+        &dir.script("ls --min-links 2 | get name | str collect ','"),
+        "a.txt,b.txt",
+    )?;
+    run_test(
+        &dir.script("ls --max-links 1 | get name | str collect ','"),
+        "c.txt",
+    )?;
+    run_test(&dir.script("ls --min-links 2 --count-only"), "2")?;
+    fail_test(
+        &dir.script("ls --min-links -1"),
+        "link count cannot be negative",
+    )
+}
+
+#[cfg(unix)]
+#[test]
+fn ls_sizes_of_a_sparse_file() -> TestResult {
+    let dir = Playground::new()?;
+
+    // Nothing is ever written, so the file takes no blocks even though it's 10 MiB long
+    let sparse = std::fs::File::create(dir.path("sparse.img"))?;
+    sparse.set_len(10 * 1024 * 1024)?;
+
+    run_test(&dir.script("ls | get size.0 | into int"), "10485760")?;
+    run_test(
+        &dir.script("ls --apparent-size | get size.0 | into int"),
+        "10485760",
+    )?;
+    run_test(
+        &dir.script("(ls --disk-usage | get size.0 | into int) < 10485760"),
+        "true",
+    )?;
+    run_test(
+        &dir.script("ls --all-sizes | each { $it.disk_usage < $it.apparent_size } | get 0"),
+        "true",
+    )?;
+    run_test(
+        &dir.script("(ls --disk-usage --total-only | get total_size | into int) < 10485760"),
+        "true",
+    )?;
+    fail_test(&dir.script("ls --disk-usage --all-sizes"), "can be used")
+}
+
+#[test]
+fn ls_hook_sees_the_listing() -> TestResult {
+    let dir = Playground::new()?;
+    dir.file("a.txt", "")?.file("b.txt", "")?;
+
+    run_test(
+        &dir.script(r#"let config = {hooks: {ls: {|name, summary| $"($name) ($summary.count)" | save hook.log}}}; ls | length; open hook.log"#),
+        "ls 2",
+    )
+}
+
+#[test]
+fn ls_hook_failure_leaves_ls_alone() -> TestResult {
+    let dir = Playground::new()?;
+    dir.file("a.txt", "")?;
+
+    run_test(
+        &dir.script("let config = {hooks: {ls: {|name, summary| $summary.missing}}}; ls | get name | str collect"),
+        "a.txt",
+    )
+}
+
+#[test]
+fn ls_short_flags_batch() -> TestResult {
+    let dir = Playground::new()?;
+    dir.file(".hidden", "")?.file("a.txt", "abc")?;
+
+    run_test(
+        &dir.script(
+            "(ls -la | reject accessed | to json) == (ls -l -a | reject accessed | to json)",
+        ),
+        "true",
+    )
+}
+
+#[test]
+fn ls_unknown_flag_in_batch() -> TestResult {
+    fail_test("ls -lx", "doesn't have flag `-x`")
+}
+
+#[test]
+fn ls_jsonl_matches_ls() -> TestResult {
+    let dir = Playground::new()?;
+    dir.file("a.txt", "abc")?.dir("d")?;
+
+    run_test(
+        &dir.script("(ls --jsonl | from json --objects | select name type size | to json) == (ls | select name type size | update size { get size | into int } | to json)"),
+        "true",
+    )
+}
+
+#[test]
+fn ls_jsonl_is_one_line_per_entry() -> TestResult {
+    let dir = Playground::new()?;
+    dir.file("a.txt", "")?.file("b.txt", "")?;
+
+    run_test(
+        &dir.script("ls --jsonl | decode utf-8 | lines | length"),
+        "2",
+    )
+}
+
+#[test]
+fn ls_jsonl_long_is_written_like_the_records() -> TestResult {
+    let dir = Playground::new()?;
+    dir.file("a \"quoted\" name.txt", "abc")?;
+
+    // Sorting makes records first, so this compares both ways of writing the lines
+    run_test(
+        &dir.script("(ls -l --jsonl | from json --objects | reject accessed | to json) == (ls -l --jsonl --sort name | from json --objects | reject accessed | to json)"),
+        "true",
+    )
+}
+
+// Windows doesn't allow control characters in file names
+#[cfg(not(windows))]
+#[test]
+fn ls_exotic_names_round_trip() -> TestResult {
+    let dir = Playground::new()?;
+    dir.file("new\nline", "")?
+        .file("tab\tand\rreturn", "")?
+        .file("bell\u{7}", "")?;
+
+    run_test(
+        &dir.script("(ls | to json | from json | get name) == (ls | get name)"),
+        "true",
+    )?;
+    run_test(
+        &dir.script("(ls | select name | to csv | from csv | get name) == (ls | get name)"),
+        "true",
+    )?;
+    run_test(
+        &dir.script("ls | to json -r | from json | get name | str length | math sum"),
+        "27",
+    )
+}
+
+#[test]
+fn ls_timeout() -> TestResult {
+    let dir = Playground::new()?;
+    dir.file("a.txt", "a")?;
+
+    run_test(&dir.script("ls --timeout 1min | length"), "1")?;
+    run_test(&dir.script("ls --timeout 0sec | length"), "0")?;
+    run_test(
+        &dir.script("ls --timeout 0sec --keep-unread | get timed_out.0"),
+        "true",
+    )?;
+    run_test(
+        &dir.script("ls --timeout 1min --keep-unread | get timed_out.0"),
+        "false",
+    )
+}
+
+#[test]
+fn ls_keep_unread_needs_timeout() -> TestResult {
+    fail_test("ls --keep-unread", "Missing --timeout")
+}
+
+#[cfg(unix)]
+#[test]
+fn ls_long_names_shared_owners() -> TestResult {
+    let dir = Playground::new()?;
+    for name in ["a.txt", "b.txt", "c.txt", "d.txt"] {
+        dir.file(name, name)?;
+    }
+
+    run_test(&dir.script("ls -l | get uid | uniq | length"), "1")?;
+    run_test(&dir.script("ls -l | get group | uniq | length"), "1")
+}
+
+#[cfg(unix)]
+#[test]
+fn ls_check_links() -> TestResult {
+    let dir = Playground::new()?;
+    dir.file("a.txt", "a")?;
+    std::os::unix::fs::symlink(dir.path("a.txt"), dir.path("good"))?;
+    std::os::unix::fs::symlink(dir.path("missing"), dir.path("bad"))?;
+
+    run_test(
+        &dir.script("ls --check-links | where broken == $true | get name.0"),
+        "bad",
+    )?;
+    run_test(
+        &dir.script("ls --check-links | where name == good | get broken.0"),
+        "false",
+    )?;
+    run_test(
+        &dir.script("ls --check-links | where name == a.txt | get broken.0 | describe"),
+        "nothing",
+    )
+}
+
+#[cfg(unix)]
+#[test]
+fn ls_check_links_only_missing_targets_are_broken() -> TestResult {
+    let dir = Playground::new()?;
+    std::os::unix::fs::symlink(dir.path("missing"), dir.path("bad"))?;
+    // A loop can't be followed, but nothing in it is missing
+    std::os::unix::fs::symlink(dir.path("loop2"), dir.path("loop1"))?;
+    std::os::unix::fs::symlink(dir.path("loop1"), dir.path("loop2"))?;
+
+    run_test(
+        &dir.script("ls --check-links | where name == loop1 | get broken.0 | describe"),
+        "nothing",
+    )?;
+    run_test(
+        &dir.script("ls --check-links --timeout 1min | where broken == $true | get name.0"),
+        "bad",
+    )
+}
+
+#[test]
+fn cp_verbose_reports_each_file() -> TestResult {
+    let dir = Playground::new()?;
+    dir.file("a.txt", "a")?.file("b.txt", "b")?.dir("dest")?;
+
+    run_test(
+        &dir.script("cp -v *.txt dest | sort-by source | get status | str collect ','"),
+        "copied,copied",
+    )?;
+
+    assert_eq!(std::fs::read_to_string(dir.path("dest/b.txt"))?, "b");
+    Ok(())
+}
+
+#[test]
+fn cp_recursive_copies_nested_files() -> TestResult {
+    let dir = Playground::new()?;
+    dir.file("src/inner/deep.txt", "deep")?;
+
+    run_test(&dir.script("cp -r src copy"), "")?;
+
+    assert_eq!(
+        std::fs::read_to_string(dir.path("copy/inner/deep.txt"))?,
+        "deep"
+    );
+    Ok(())
+}
+
+#[test]
+fn cp_directory_needs_recursive() -> TestResult {
+    let dir = Playground::new()?;
+    dir.dir("src")?;
+
+    fail_test(&dir.script("cp src copy"), "--recursive")
+}
+
+#[test]
+fn mv_verbose_reports_the_move() -> TestResult {
+    let dir = Playground::new()?;
+    dir.file("a.txt", "a")?;
+
+    run_test(&dir.script("mv -v a.txt b.txt | get status.0"), "moved")?;
+
+    assert!(!dir.path("a.txt").exists());
+    assert_eq!(std::fs::read_to_string(dir.path("b.txt"))?, "a");
+    Ok(())
+}
+
+#[cfg(feature = "trash-support")]
+#[test]
+fn rm_trash_reports_each_path() -> TestResult {
+    let dir = Playground::new()?;
+    dir.file("a.txt", "a")?.file("full/b.txt", "b")?;
+
+    run_test(
+        &dir.script("rm --trash a.txt full | get status | str collect ','"),
+        "trashed,cannot remove, try --recursive",
+    )?;
+
+    assert!(!dir.path("a.txt").exists());
+    assert!(dir.path("full/b.txt").exists());
+    Ok(())
+}
+
+#[cfg(feature = "trash-support")]
+#[test]
+fn rm_trash_directory() -> TestResult {
+    let dir = Playground::new()?;
+    dir.file("full/b.txt", "b")?;
+
+    run_test(
+        &dir.script("rm --trash --recursive full | get status.0"),
+        "trashed",
+    )?;
+
+    assert!(!dir.path("full").exists());
+    Ok(())
+}
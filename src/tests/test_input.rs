@@ -0,0 +1,53 @@
+use crate::tests::{script_output, TestResult};
+
+#[test]
+fn input_reads_piped_lines() -> TestResult {
+    let output = script_output("[(input) (input)] | str collect ','", &[], "one\ntwo\n")?;
+
+    assert_eq!(output, "one,two");
+    Ok(())
+}
+
+#[test]
+fn input_strips_windows_line_endings() -> TestResult {
+    let output = script_output("(input) == 'one'", &[], "one\r\n")?;
+
+    assert_eq!(output, "true");
+    Ok(())
+}
+
+#[test]
+fn input_returns_nothing_at_end_of_input() -> TestResult {
+    let output = script_output("(input) == $nothing", &[], "")?;
+
+    assert_eq!(output, "true");
+    Ok(())
+}
+
+#[test]
+fn while_over_input_stops_at_end_of_input() -> TestResult {
+    let output = script_output(
+        "while (input) != $nothing { 'line' } | length",
+        &[],
+        "a\nb\nc\n",
+    )?;
+
+    assert_eq!(output, "3");
+    Ok(())
+}
+
+#[test]
+fn suppressed_input_reads_piped_lines() -> TestResult {
+    let output = script_output("input -s", &[], "secret\n")?;
+
+    assert_eq!(output, "secret");
+    Ok(())
+}
+
+#[test]
+fn bytes_until_returns_nothing_at_end_of_input() -> TestResult {
+    let output = script_output("(input --bytes-until x) == $nothing", &[], "")?;
+
+    assert_eq!(output, "true");
+    Ok(())
+}
@@ -1,4 +1,4 @@
-use crate::tests::{run_test, TestResult};
+use crate::tests::{fail_test, run_test, TestResult};
 
 #[test]
 fn better_block_types() -> TestResult {
@@ -49,3 +49,101 @@ fn par_each() -> TestResult {
         "false",
     )
 }
+
+#[test]
+fn where_in_binds_to_each_line() -> TestResult {
+    run_test(
+        r#""INFO start
+ERROR disk full
+INFO retry
+ERROR gave up" | lines | where ($in =~ 'ERROR') | length"#,
+        "2",
+    )
+}
+
+#[test]
+fn where_in_and_it_agree() -> TestResult {
+    run_test(
+        r#"([1 2 3 4] | where ($in > 2)) == ([1 2 3 4] | where $it > 2)"#,
+        "true",
+    )
+}
+
+#[test]
+fn while_counter() -> TestResult {
+    run_test(
+        "let-env N = 0; while $env.N < 4 { let-env N = ($env.N + 1); $env.N } | math sum",
+        "10",
+    )
+}
+
+#[test]
+fn while_env_does_not_leak() -> TestResult {
+    run_test(
+        "let-env N = 0; while $env.N < 4 { let-env N = ($env.N + 1) } | ignore; $env.N",
+        "0",
+    )
+}
+
+#[test]
+fn while_break() -> TestResult {
+    run_test(
+        "let-env N = 0; while $true { let-env N = ($env.N + 1); if $env.N > 3 { break }; $env.N } | length",
+        "3",
+    )
+}
+
+#[test]
+fn break_from_nested_for() -> TestResult {
+    run_test(
+        "for x in 1..3 { for y in 1..3 { if $y == 2 { break }; $x * 10 + $y } } | flatten | math sum",
+        "63",
+    )
+}
+
+#[test]
+fn continue_skips_iterations() -> TestResult {
+    run_test(
+        "for x in 1..6 { if $x mod 2 == 0 { continue }; $x } | math sum",
+        "9",
+    )
+}
+
+#[test]
+fn break_outside_loop() -> TestResult {
+    fail_test("if $true { break }", "outside of a loop")
+}
+
+#[test]
+fn break_in_while_condition() -> TestResult {
+    fail_test("while (break) { 1 }", "outside of a loop")
+}
+
+#[test]
+fn continue_in_for_list() -> TestResult {
+    fail_test("for x in (continue; [1 2]) { $x }", "outside of a loop")
+}
+
+#[test]
+fn continue_in_def_inside_loop() -> TestResult {
+    fail_test(
+        "for x in 1..3 { def foo [] { continue }; foo }",
+        "outside of a loop",
+    )
+}
+
+#[test]
+fn break_in_each_is_an_error() -> TestResult {
+    fail_test(
+        "for x in [1] { [1 2] | each { break } | get 0 }",
+        "outside of a loop",
+    )
+}
+
+#[test]
+fn loop_control_in_nested_branches() -> TestResult {
+    run_test(
+        "for x in 1..6 { if $x > 4 { if $true { break } } else if $x == 2 { continue } else { $x } } | math sum",
+        "8",
+    )
+}
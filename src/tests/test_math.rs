@@ -50,6 +50,44 @@ fn not_contains() -> TestResult {
     run_test("'testme' !~ 'test'", "false")
 }
 
+#[test]
+fn regex_match() -> TestResult {
+    run_test("'test_me' =~ '^test_'", "true")
+}
+
+#[test]
+fn regex_not_match() -> TestResult {
+    run_test("'me_test' !~ '^test_'", "true")
+}
+
+#[test]
+fn regex_match_in_where() -> TestResult {
+    run_test(
+        "[[name]; [test_a] [b_test] [test_c]] | where name =~ '^test_' | length",
+        "2",
+    )
+}
+
+#[test]
+fn regex_match_non_literal_pattern() -> TestResult {
+    run_test("let pat = 'e{2}'; 'beet' =~ $pat", "true")
+}
+
+#[test]
+fn regex_match_invalid_pattern() -> TestResult {
+    fail_test("'test' =~ '(unclosed'", "Invalid regex")
+}
+
+#[test]
+fn regex_match_invalid_non_literal_pattern() -> TestResult {
+    fail_test("let pat = '(unclosed'; 'test' =~ $pat", "invalid regex")
+}
+
+#[test]
+fn regex_match_on_int() -> TestResult {
+    fail_test("3 =~ '3'", "mismatched for operation")
+}
+
 #[test]
 fn floating_add() -> TestResult {
     run_test("10.1 + 0.8", "10.9")
@@ -59,3 +97,195 @@ fn floating_add() -> TestResult {
 fn precedence_of_or_groups() -> TestResult {
     run_test(r#"4 mod 3 == 0 || 5 mod 5 == 0"#, "true")
 }
+
+#[test]
+fn pow_binds_tighter_than_add() -> TestResult {
+    run_test("2 + 3 ** 2 == 11", "true")
+}
+
+#[test]
+fn pow_binds_tighter_than_multiply() -> TestResult {
+    run_test("2 * 3 ** 2 - 1", "17")
+}
+
+#[test]
+fn pow_is_right_associative() -> TestResult {
+    run_test("2 ** 3 ** 2", "512")
+}
+
+#[test]
+fn pow_negative_exponent() -> TestResult {
+    run_test("2 ** -1", "0.5")
+}
+
+#[test]
+fn pow_large_negative_exponent() -> TestResult {
+    // -4294967296 would be 0 if it were cut down to an i32
+    run_test("(2 ** -4294967296) < 1", "true")
+}
+
+#[test]
+fn mixed_precedence() -> TestResult {
+    run_test("10 - 2 * 3 mod 4 + 1", "9")
+}
+
+#[test]
+fn modulo_negative_dividend() -> TestResult {
+    run_test("-5 mod 3", "1")
+}
+
+#[test]
+fn modulo_negative_divisor() -> TestResult {
+    run_test("5 mod -3", "-1")
+}
+
+#[test]
+fn modulo_duration() -> TestResult {
+    run_test("(7min mod 2min) == 1min", "true")
+}
+
+#[test]
+fn modulo_filesize() -> TestResult {
+    run_test("(5kb mod 2kb) == 1kb", "true")
+}
+
+#[test]
+fn modulo_by_zero() -> TestResult {
+    fail_test("5 mod 0", "division by zero")
+}
+
+#[test]
+fn add_overflow() -> TestResult {
+    fail_test("9223372036854775807 + 1", "overflow")
+}
+
+#[test]
+fn multiply_overflow() -> TestResult {
+    fail_test("9223372036854775807 * 2", "overflow")
+}
+
+#[test]
+fn pow_overflow() -> TestResult {
+    fail_test("2 ** 64", "overflow")
+}
+
+#[test]
+fn bit_and() -> TestResult {
+    run_test("12 bit-and 10", "8")
+}
+
+#[test]
+fn bit_or() -> TestResult {
+    run_test("12 bit-or 10", "14")
+}
+
+#[test]
+fn bit_xor() -> TestResult {
+    run_test("12 bit-xor 10", "6")
+}
+
+#[test]
+fn bit_shl() -> TestResult {
+    run_test("1 bit-shl 10", "1024")
+}
+
+#[test]
+fn bit_shr() -> TestResult {
+    run_test("1024 bit-shr 3", "128")
+}
+
+#[test]
+fn bit_and_negative() -> TestResult {
+    run_test("-1 bit-and 255", "255")
+}
+
+#[test]
+fn bit_shr_is_arithmetic() -> TestResult {
+    run_test("-8 bit-shr 1", "-4")
+}
+
+#[test]
+fn bit_shl_into_sign_bit() -> TestResult {
+    run_test("1 bit-shl 63", "-9223372036854775808")
+}
+
+#[test]
+fn bit_shift_negative_amount() -> TestResult {
+    fail_test("1 bit-shl -1", "shift amount")
+}
+
+#[test]
+fn bit_shift_too_far() -> TestResult {
+    fail_test("1 bit-shr 64", "shift amount")
+}
+
+#[test]
+fn bit_and_binds_tighter_than_comparison() -> TestResult {
+    run_test("0o755 bit-and 0o100 == 0o100", "true")
+}
+
+#[test]
+fn bit_shift_binds_looser_than_add() -> TestResult {
+    run_test("1 bit-shl 1 + 1", "4")
+}
+
+#[test]
+fn bit_and_on_float() -> TestResult {
+    fail_test("1.5 bit-and 1", "mismatched for operation")
+}
+
+#[test]
+fn bits_not() -> TestResult {
+    run_test("5 | bits not", "-6")
+}
+
+#[test]
+fn math_sum_of_filesizes_is_a_filesize() -> TestResult {
+    run_test("([1kb 2kb] | math sum) == 3kb", "true")?;
+    run_test("ls | get size | math sum | describe", "filesize")
+}
+
+#[test]
+fn math_avg_of_durations_is_a_duration() -> TestResult {
+    run_test("([1sec 3sec] | math avg) == 2sec", "true")
+}
+
+#[test]
+fn math_stddev_of_filesizes_is_a_filesize() -> TestResult {
+    run_test("([1kb 3kb] | math stddev) == 1kb", "true")
+}
+
+#[test]
+fn math_min_max_of_filesizes() -> TestResult {
+    run_test("([3kb 1kb 2kb] | math min) == 1kb", "true")?;
+    run_test("([3kb 1kb 2kb] | math max) == 3kb", "true")
+}
+
+#[test]
+fn math_sum_and_product_of_nothing() -> TestResult {
+    run_test("[] | math sum", "0")?;
+    run_test("[] | math product", "1")
+}
+
+#[test]
+fn math_avg_and_stddev_of_nothing_fail() -> TestResult {
+    fail_test("[] | math avg", "Empty input")?;
+    fail_test("[] | math stddev", "standard deviation")?;
+    fail_test("[] | math min", "Empty input")
+}
+
+#[test]
+fn division_by_zero_labels_the_divisor() -> TestResult {
+    fail_test("let x = 10sec; $x / 0ms", "divisor is zero")?;
+    fail_test("5 mod 0", "divisor is zero")?;
+    fail_test("[[a]; [1] [0]] | each { 10 / $it.a }", "divisor is zero")
+}
+
+#[test]
+fn type_mismatch_labels_both_operands() -> TestResult {
+    let script = r#"def add-one [x] { $x + 1 }; add-one "a""#;
+
+    fail_test(script, "type mismatch for operator")?;
+    fail_test(script, "string")?;
+    fail_test(script, "int")
+}
@@ -184,3 +184,102 @@ fn commands_have_usage() -> TestResult {
         "cool usage",
     )
 }
+
+#[test]
+fn binary_literal() -> TestResult {
+    run_test("0x[de ad be ef] | length", "4")
+}
+
+#[test]
+fn binary_literal_index() -> TestResult {
+    run_test("(0x[de ad be ef]).1", "173")
+}
+
+#[test]
+fn binary_literal_odd_digits() -> TestResult {
+    run_test("0x[abc] == 0x[0a bc]", "true")
+}
+
+#[test]
+fn binary_literal_bad_digit() -> TestResult {
+    fail_test("0x[de ag]", "hex digit")
+}
+
+#[test]
+fn binary_first_bytes() -> TestResult {
+    run_test("(0x[de ad be ef] | first 2) == 0x[de ad]", "true")
+}
+
+#[test]
+fn binary_literal_typed_let() -> TestResult {
+    run_test("let x: binary = 0x[01 02 03]; $x | length", "3")
+}
+
+#[test]
+fn binary_literal_as_binary_argument() -> TestResult {
+    run_test(
+        "def bytes [x: binary] { $x | length }; bytes 0x[01 02]",
+        "2",
+    )
+}
+
+#[test]
+fn binary_argument_rejects_other_values() -> TestResult {
+    fail_test(
+        "def bytes [x: binary] { $x | length }; bytes abc",
+        "Parse mismatch",
+    )
+}
+
+#[test]
+fn ast_flatten_shapes() -> TestResult {
+    run_test(
+        r#"ast --flatten 'echo 1 "a" 2.5' | get shape | str collect ','"#,
+        "flatshape_internalcall,flatshape_int,flatshape_string,flatshape_float",
+    )
+}
+
+#[test]
+fn ast_flatten_spans_are_relative() -> TestResult {
+    run_test(
+        "ast --flatten 'echo 1' | get span.start | into string | str collect ','",
+        "0,5",
+    )
+}
+
+#[test]
+fn ast_expression_parts() -> TestResult {
+    run_test(
+        "(ast '1 + 2').statements.0.expressions.0.parts | get expr | str collect ','",
+        "Int,Operator,Int",
+    )
+}
+
+#[test]
+fn ast_call_names() -> TestResult {
+    run_test(
+        "(ast 'ls | length').statements.0.expressions | get name | str collect ','",
+        "ls,length",
+    )
+}
+
+#[test]
+fn ast_keeps_parse_errors() -> TestResult {
+    run_test("(ast 'echo (').error != $nothing", "true")
+}
+
+#[test]
+fn duration_units_are_exact() -> TestResult {
+    run_test(
+        "[(1us == 1000ns) (1ms == 1000us) (1wk == 7day) (3ns + 1us == 1003ns)] | all? $it == $true",
+        "true",
+    )
+}
+
+#[test]
+fn filesize_units_are_exact() -> TestResult {
+    run_test(
+        "[(1kib == 1024b) (1mib == 1024kib) (1gib == 1024mib) (1tib == 1024gib) (1pib == 1024tib)] | all? $it == $true",
+        "true",
+    )
+}
@@ -0,0 +1,55 @@
+use crate::tests::{run_test, TestResult};
+
+#[cfg(not(windows))]
+#[test]
+fn path_join_appends() -> TestResult {
+    run_test("'/home/joe' | path join test.txt", "/home/joe/test.txt")
+}
+
+#[cfg(not(windows))]
+#[test]
+fn path_basename_of_each_path() -> TestResult {
+    run_test(
+        "['/home/joe/test.txt' '/tmp/other'] | path basename | str collect ','",
+        "test.txt,other",
+    )
+}
+
+#[cfg(not(windows))]
+#[test]
+fn path_dirname_drops_last_part() -> TestResult {
+    run_test("'/home/joe/test.txt' | path dirname", "/home/joe")
+}
+
+#[test]
+fn path_exists_is_relative_to_pwd() -> TestResult {
+    run_test(
+        "cd crates; ['nu-path' 'not-a-crate'] | path exists | str collect ','",
+        "true,false",
+    )
+}
+
+#[test]
+fn path_expand_is_relative_to_pwd() -> TestResult {
+    run_test(
+        "cd crates; 'nu-path/../nu-cli' | path expand | path basename",
+        "nu-cli",
+    )
+}
+
+#[test]
+fn path_type_is_relative_to_pwd() -> TestResult {
+    run_test(
+        "cd crates; ['nu-path' 'nu-path/Cargo.toml' 'nothing-here'] | path type | str collect ','",
+        "dir,file,",
+    )
+}
+
+#[cfg(not(windows))]
+#[test]
+fn path_commands_take_external_output_by_line() -> TestResult {
+    run_test(
+        r#"^printf "a/one\nb/two\n" | path basename | str collect ','"#,
+        "one,two",
+    )
+}
@@ -0,0 +1,69 @@
+use crate::tests::{run_script, script_output, TestResult};
+
+#[test]
+fn main_gets_positionals_and_flags() -> TestResult {
+    let script = r#"def main [name: string, --shout] { if $shout { $"HELLO ($name)" } else { $"hello ($name)" } }"#;
+
+    assert_eq!(script_output(script, &["nu"], "")?, "hello nu");
+    assert_eq!(script_output(script, &["nu", "--shout"], "")?, "HELLO nu");
+    Ok(())
+}
+
+#[test]
+fn main_gets_typed_arguments() -> TestResult {
+    let output = script_output("def main [x: int] { $x + 1 }", &["41"], "")?;
+
+    assert_eq!(output, "42");
+    Ok(())
+}
+
+#[test]
+fn main_keeps_arguments_with_spaces_whole() -> TestResult {
+    let script = "def main [...rest] { $rest | str collect ',' }";
+    let output = script_output(script, &["a b", "it's", "(c)"], "")?;
+
+    assert_eq!(output, "a b,it's,(c)");
+    Ok(())
+}
+
+#[test]
+fn main_help_lists_its_flags() -> TestResult {
+    let output = script_output("def main [--verbose] { 'ran' }", &["--help"], "")?;
+
+    assert!(output.contains("--verbose"));
+    assert!(!output.contains("ran"));
+    Ok(())
+}
+
+#[test]
+fn script_reads_piped_stdin_as_input() -> TestResult {
+    let output = script_output("$in | lines | length", &[], "a\nb\nc\n")?;
+
+    assert_eq!(output, "3");
+    Ok(())
+}
+
+#[test]
+fn main_reads_piped_stdin_as_input() -> TestResult {
+    let output = script_output("def main [] { $in | lines | first }", &[], "x\ny\n")?;
+
+    assert_eq!(output, "x");
+    Ok(())
+}
+
+#[cfg(not(windows))]
+#[test]
+fn script_exits_with_last_external_exit_code() -> TestResult {
+    let output = run_script("^sh -c 'exit 3'", &[], "")?;
+
+    assert_eq!(output.status.code(), Some(3));
+    Ok(())
+}
+
+#[test]
+fn script_error_exits_with_failure() -> TestResult {
+    let output = run_script("def main [x: int] { $x }", &["not-a-number"], "")?;
+
+    assert_eq!(output.status.code(), Some(1));
+    Ok(())
+}
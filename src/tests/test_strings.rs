@@ -89,3 +89,42 @@ fn single_tick_interpolation() -> TestResult {
 fn detect_newlines() -> TestResult {
     run_test("'hello\r\nworld' | lines | get 0 | str length", "5")
 }
+
+#[test]
+fn str_replace_literal() -> TestResult {
+    run_test(r#"'a.b.c' | str replace '.' '$1'"#, "a$1b.c")
+}
+
+#[test]
+fn str_replace_literal_all() -> TestResult {
+    run_test(r#"'a.b.c' | str replace -a '.' '-'"#, "a-b-c")
+}
+
+#[test]
+fn str_replace_regex() -> TestResult {
+    run_test(
+        r#"'abc123def456' | str replace -r -a '\d+' '#'"#,
+        "abc#def#",
+    )
+}
+
+#[test]
+fn str_replace_capture_groups() -> TestResult {
+    run_test(
+        r#"[report.txt notes.txt] | str replace -r '(\w+)\.txt' '${1}.md' | str collect ' '"#,
+        "report.md notes.md",
+    )
+}
+
+#[test]
+fn str_replace_invalid_regex() -> TestResult {
+    fail_test(
+        r#"'abc' | str replace -r '(' 'x'"#,
+        "Couldn't parse the regex",
+    )
+}
+
+#[test]
+fn str_find_replace_is_a_regex() -> TestResult {
+    run_test(r#"'abc123def' | str find-replace '\d+' '#'"#, "abc#def")
+}
@@ -1,4 +1,4 @@
-use crate::tests::{fail_test, run_test, TestResult};
+use crate::tests::{fail_test, run_test, run_test_contains, Playground, TestResult};
 
 #[test]
 fn cell_path_subexpr1() -> TestResult {
@@ -67,6 +67,35 @@ fn flatten_should_flatten_inner_table() -> TestResult {
     )
 }
 
+#[test]
+fn flatten_nested_list_column_into_rows() -> TestResult {
+    run_test(
+        r#"[[name, tags]; [a, [x y]] [b, [z]]] | flatten | each { $"($it.name)($it.tags)" } | str collect ','"#,
+        "ax,ay,bz",
+    )
+}
+
+#[test]
+fn flatten_nested_record_prefixes_collisions() -> TestResult {
+    run_test(
+        "[[name, meta]; [a, {name: b, size: 1}]] | flatten | columns | str collect ','",
+        "name,meta_name,size",
+    )
+}
+
+#[test]
+fn flatten_all_levels() -> TestResult {
+    run_test(
+        "[[name, meta]; [a, {tags: [x y]}]] | flatten --all | get tags | str collect ','",
+        "x,y",
+    )
+}
+
+#[test]
+fn flatten_all_stops_at_ranges() -> TestResult {
+    run_test("[(1..3) 4] | flatten --all | length", "2")
+}
+
 #[test]
 fn command_filter_reject_1() -> TestResult {
     run_test(
@@ -115,6 +144,37 @@ fn command_drop_column_1() -> TestResult {
     )
 }
 
+#[test]
+fn drop_column_defaults_to_one() -> TestResult {
+    run_test(
+        "[[a b c]; [1 2 3]] | drop column | columns | str collect ','",
+        "a,b",
+    )
+}
+
+#[test]
+fn drop_column_clamps_to_column_count() -> TestResult {
+    run_test("{a: 1, b: 2} | drop column 5 | columns | length", "0")
+}
+
+#[test]
+fn drop_column_on_record() -> TestResult {
+    run_test("{a: 1, b: 2, c: 3} | drop column 1 | get b", "2")
+}
+
+#[test]
+fn drop_column_streams() -> TestResult {
+    run_test(
+        "1.. | each { |it| {n: $it, sq: ($it * $it)} } | drop column | first 2 | get n | str collect ','",
+        "1,2",
+    )
+}
+
+#[test]
+fn drop_column_negative() -> TestResult {
+    fail_test("{a: 1} | drop column -1", "Negative column count")
+}
+
 #[test]
 fn record_1() -> TestResult {
     run_test(r#"{'a': 'b'} | get a"#, "b")
@@ -177,6 +237,25 @@ fn wrap() -> TestResult {
     run_test(r#"([1, 2, 3] | wrap foo).foo.1"#, "2")
 }
 
+#[test]
+fn wrap_keeps_order() -> TestResult {
+    run_test(r#"[a b c] | wrap item | get item.1"#, "b")
+}
+
+#[test]
+fn wrap_range_streams() -> TestResult {
+    // Far too many rows to collect, and `first` comes before `get`, which would collect them
+    run_test(
+        r#"1..1000000000 | wrap n | first 3 | get n | str collect ','"#,
+        "1,2,3",
+    )
+}
+
+#[test]
+fn wrap_single_value() -> TestResult {
+    run_test(r#"'hello' | wrap text | get text"#, "hello")
+}
+
 #[test]
 fn get() -> TestResult {
     run_test(
@@ -202,8 +281,103 @@ fn select_2() -> TestResult {
 }
 
 #[test]
-fn update_will_insert() -> TestResult {
-    run_test(r#"{} | update a b | get a"#, "b")
+fn select_glob() -> TestResult {
+    run_test(
+        r#"[[apple, banana, avocado]; [1, 2, 3]] | select --glob 'a*' | columns | str collect ','"#,
+        "apple,avocado",
+    )
+}
+
+#[cfg(unix)]
+#[test]
+fn select_glob_from_ls() -> TestResult {
+    run_test(
+        r#"ls -l | select --glob 'n*' | columns | str collect ','"#,
+        "name,num_links",
+    )
+}
+
+#[test]
+fn select_glob_matches_each_row() -> TestResult {
+    run_test(
+        r#"[{a1: 1, b: 0}, {a2: 2, b: 0}] | select --glob 'a*' | each { |it| $it | columns | str collect ',' } | str collect ';'"#,
+        "a1;a2",
+    )
+}
+
+#[test]
+fn select_glob_without_matches_is_empty() -> TestResult {
+    run_test(
+        r#"[[name, size]; [a, 1]] | select --glob 'zz*' | get 0 | columns | length"#,
+        "0",
+    )
+}
+
+#[test]
+fn select_glob_with_columns_fails() -> TestResult {
+    fail_test(r#"[[a, b]; [1, 2]] | select a --glob 'b'"#, "same time")
+}
+
+#[test]
+fn update_missing_column() -> TestResult {
+    fail_test(r#"{} | update a b"#, "Cannot find column")
+}
+
+#[test]
+fn update_keeps_column_order() -> TestResult {
+    run_test(
+        r#"[[name, size]; [a 1] [b 2]] | update name { $it.name + "!" } | columns | str collect ','"#,
+        "name,size",
+    )
+}
+
+#[test]
+fn update_with_block() -> TestResult {
+    run_test(
+        r#"[[name, size]; [a 1] [b 2]] | update size { $it.size * 10 } | get size | math sum"#,
+        "30",
+    )
+}
+
+#[test]
+fn insert_new_column() -> TestResult {
+    run_test(
+        r#"[[name, size]; [a 1] [b 2]] | insert kind "local" | get 1.kind"#,
+        "local",
+    )
+}
+
+#[test]
+fn insert_appends_column() -> TestResult {
+    run_test(
+        r#"{} | insert a b | insert c d | columns | str collect ','"#,
+        "a,c",
+    )
+}
+
+#[test]
+fn insert_existing_column() -> TestResult {
+    fail_test(
+        r#"[[name, size]; [a 1] [b 2]] | insert size 3"#,
+        "Column already exists",
+    )
+}
+
+#[test]
+fn merge_record_into_rows() -> TestResult {
+    run_test(
+        r#"[[name, size]; [a 1] [b 2]] | merge { {kind: local} } | get kind | str collect ','"#,
+        "local,local",
+    )
+}
+
+#[test]
+fn merge_overrides_existing_columns() -> TestResult {
+    run_test(
+        r#"{a: 1, b: 2} | merge { {a: 3, c: 4} } | columns | str collect ','"#,
+        "a,b,c",
+    )?;
+    run_test(r#"{a: 1, b: 2} | merge { {a: 3, c: 4} } | get a"#, "3")
 }
 
 #[test]
@@ -223,3 +397,532 @@ fn length_for_rows() -> TestResult {
 fn get_fuzzy() -> TestResult {
     run_test("(ls | get -i foo) == $nothing", "true")
 }
+
+#[test]
+fn get_default_present() -> TestResult {
+    run_test("{a: 1} | get a --default 5", "1")
+}
+
+#[test]
+fn get_default_null() -> TestResult {
+    run_test("{a: $nothing} | get a --default 5", "5")
+}
+
+#[test]
+fn get_default_missing_column() -> TestResult {
+    run_test("{a: 1} | get b -d 5", "5")
+}
+
+#[test]
+fn get_default_missing_in_some_rows() -> TestResult {
+    run_test("[{a: 1} {b: 2}] | get a -d 10 | math sum", "11")
+}
+
+#[test]
+fn get_default_missing_index() -> TestResult {
+    run_test("[1 2] | get 5 -d 0", "0")
+}
+
+#[test]
+fn get_default_block() -> TestResult {
+    run_test("{a: 1} | get b -d { 2 + 3 }", "5")
+}
+
+#[test]
+fn get_default_keeps_errors() -> TestResult {
+    fail_test(
+        "[{a: 1} {a: x}] | each { |r| {a: ($r.a + 1)} } | get a -d 0 | math sum",
+        "Type mismatch",
+    )
+}
+
+#[test]
+fn get_default_streams() -> TestResult {
+    run_test(
+        "1..100000000 | each { |x| {a: $x} } | get a -d 0 | first 3 | str collect ','",
+        "1,2,3",
+    )
+}
+
+#[test]
+fn get_default_stream_index() -> TestResult {
+    run_test("[{a: 1} {b: 2}] | each { |r| $r } | get 1.a -d 7", "7")
+}
+
+#[test]
+fn table_literal_get_column() -> TestResult {
+    run_test(r#"[[name, size]; ["a", 10] ["b", 20]] | get size.1"#, "20")
+}
+
+#[test]
+fn table_literal_rows_on_separate_lines() -> TestResult {
+    run_test(
+        "[[name, size];
+          [a, 10]
+          [b, 20]
+          [c, 30]] | length",
+        "3",
+    )
+}
+
+#[test]
+fn table_literal_empty() -> TestResult {
+    run_test("[[a b]; ] | length", "0")
+}
+
+#[test]
+fn table_literal_missing_columns() -> TestResult {
+    fail_test("[[a b]; [1 2] [3]]", "Missing columns")
+}
+
+#[test]
+fn table_literal_extra_columns() -> TestResult {
+    fail_test("[[a b]; [1 2 3] [4 5]]", "Extra columns")
+}
+
+#[test]
+fn multiline_list_literal() -> TestResult {
+    run_test(
+        "[1
+          2
+          3] | length",
+        "3",
+    )
+}
+
+#[test]
+fn sort_by_multiple_columns() -> TestResult {
+    run_test(
+        "[[a, b]; [2, x] [1, z] [2, w] [1, y]] | sort-by a b | get b | str collect",
+        "yzwx",
+    )
+}
+
+#[test]
+fn sort_by_descending_column() -> TestResult {
+    run_test(
+        "[[a, b]; [2, x] [1, z] [2, w] [1, y]] | sort-by a b -d [b] | get b | str collect",
+        "zyxw",
+    )
+}
+
+#[test]
+fn sort_by_is_stable() -> TestResult {
+    run_test(
+        "[[a, b]; [1, z] [0, q] [1, y] [1, x]] | sort-by a | get b | str collect",
+        "qzyx",
+    )
+}
+
+#[test]
+fn sort_by_natural() -> TestResult {
+    run_test(
+        "[file10 file2 file1] | sort-by -n | str collect ' '",
+        "file1 file2 file10",
+    )
+}
+
+#[test]
+fn sort_by_mixed_types() -> TestResult {
+    run_test(
+        "[3 'b' 1.5 'a' 2] | sort-by | str collect ','",
+        "1.5,2,3,a,b",
+    )
+}
+
+#[test]
+fn sort_by_puts_nothing_last() -> TestResult {
+    run_test("[$nothing 2 1] | sort-by | last | describe", "nothing")
+}
+
+#[test]
+fn transpose_record_into_table() -> TestResult {
+    run_test(
+        "{name: nu, stars: 5} | transpose | each { $\"($it.column)=($it.value)\" } | str collect ' '",
+        "name=nu stars=5",
+    )
+}
+
+#[test]
+fn transpose_table_into_record() -> TestResult {
+    run_test(
+        "[[column, value]; [name, nu] [stars, 5]] | transpose -r -d | get stars",
+        "5",
+    )
+}
+
+#[test]
+fn transpose_record_round_trip() -> TestResult {
+    run_test(
+        "{a: 1, b: 2} | transpose | transpose -r --as-record | columns | str collect ','",
+        "a,b",
+    )
+}
+
+#[test]
+fn transpose_list_of_records() -> TestResult {
+    run_test(
+        "[[a, b]; [1, 2] [3, 4]] | transpose key x y | where key == b | get y.0",
+        "4",
+    )
+}
+
+#[test]
+fn transpose_as_record_needs_single_row() -> TestResult {
+    fail_test(
+        "[[a, b]; [1, 2]] | transpose --as-record",
+        "single row as a record",
+    )
+}
+
+#[test]
+fn table_max_rows_on_list() -> TestResult {
+    run_test_contains(
+        "let config = {table_max_rows: 3}; [1 2 3 4 5 6 7 8 9 10]; 'done'",
+        "... and 7 more rows",
+    )
+}
+
+#[test]
+fn table_max_rows_on_stream() -> TestResult {
+    run_test_contains(
+        "let config = {table_max_rows: 3}; 1..2500 | each { $it }; 'done'",
+        "... and 2497 more rows",
+    )
+}
+
+#[test]
+fn table_max_rows_leaves_short_tables_alone() -> TestResult {
+    run_test(
+        "let config = {table_max_rows: 3}; [1 2 3] | table | str contains 'more rows'",
+        "false",
+    )
+}
+
+#[test]
+fn range_positive() -> TestResult {
+    run_test("[a b c d e] | range 1..2 | str collect", "bc")
+}
+
+#[test]
+fn range_negative() -> TestResult {
+    run_test("[a b c d e] | range (-3)..-2 | str collect", "cd")
+}
+
+#[test]
+fn range_open_ended_reaches_the_end() -> TestResult {
+    run_test("1..500 | each { $it } | range 150.. | length", "351")
+}
+
+#[test]
+fn range_clamps_out_of_range_bounds() -> TestResult {
+    run_test("[a b c] | range 1..10 | str collect", "bc")
+}
+
+#[test]
+fn uniq_collapses_consecutive_duplicates() -> TestResult {
+    run_test("[a a b a c c] | uniq | str collect", "abac")
+}
+
+#[test]
+fn uniq_global_removes_all_duplicates() -> TestResult {
+    run_test("[a a b a c c] | uniq --global | str collect", "abc")
+}
+
+#[test]
+fn uniq_counts_runs() -> TestResult {
+    run_test(
+        "[a a b a] | uniq --count | each { $it.count } | into string | str collect ','",
+        "2,1,1",
+    )
+}
+
+#[test]
+fn uniq_streams_without_global() -> TestResult {
+    run_test("1..1000000000 | each { $it } | uniq | first", "1")
+}
+
+#[test]
+fn compact_drops_rows_with_empty_column() -> TestResult {
+    run_test(
+        r#"[[name size]; [a 1] [b $nothing] [c ""] [d 4]] | compact size | get name | str collect"#,
+        "ad",
+    )
+}
+
+#[test]
+fn compact_drops_empty_values() -> TestResult {
+    run_test(r#"[a "" $nothing [] b] | compact | str collect"#, "ab")
+}
+
+#[test]
+fn compact_streams() -> TestResult {
+    run_test("1..1000000000 | each { $it } | compact | first", "1")
+}
+
+#[test]
+fn rename_columns_in_order() -> TestResult {
+    run_test(
+        "[[a b c]; [1 2 3]] | rename x y | columns | str collect ','",
+        "x,y,c",
+    )
+}
+
+#[test]
+fn rename_column_by_name() -> TestResult {
+    run_test(
+        "[[a b c]; [1 2 3]] | rename --column [b=y c=z] | columns | str collect ','",
+        "a,y,z",
+    )
+}
+
+#[test]
+fn rename_keeps_values() -> TestResult {
+    run_test("[[a b]; [1 2]] | rename -c b=y | get y.0", "2")
+}
+
+#[test]
+fn rename_rows_without_the_column() -> TestResult {
+    run_test(
+        "[{a: 1} {a: 2, b: 3}] | rename -c b=y | each { $it | columns | str collect ',' } | str collect ';'",
+        "a;a,y",
+    )
+}
+
+#[test]
+fn rename_to_existing_column_fails() -> TestResult {
+    fail_test("[[a b]; [1 2]] | rename -c a=b", "Duplicate column name")
+}
+
+#[test]
+fn rename_two_columns_to_the_same_name_fails() -> TestResult {
+    fail_test(
+        "[[a b]; [1 2]] | rename -c [a=x b=x]",
+        "Duplicate column name",
+    )
+}
+
+#[test]
+fn every_starts_from_the_first_row() -> TestResult {
+    run_test(
+        "1..100 | every 10 | str collect ','",
+        "1,11,21,31,41,51,61,71,81,91",
+    )
+}
+
+#[test]
+fn every_with_offset() -> TestResult {
+    run_test(
+        "1..100 | every 10 --offset 9 | str collect ','",
+        "10,20,30,40,50,60,70,80,90,100",
+    )
+}
+
+#[test]
+fn every_skip_with_offset() -> TestResult {
+    run_test(
+        "1..7 | every 3 --offset 1 --skip | str collect ','",
+        "1,3,4,6,7",
+    )
+}
+
+#[test]
+fn every_streams() -> TestResult {
+    // Far too many rows to collect, so this only finishes if every streams them
+    run_test(
+        "1..1000000000 | each { $it } | every 1000 | first 3 | str collect ','",
+        "1,1001,2001",
+    )
+}
+
+#[test]
+fn every_negative_offset() -> TestResult {
+    fail_test("[1 2 3] | every 2 --offset -1", "offset cannot be negative")
+}
+
+#[test]
+fn cal_leap_year() -> TestResult {
+    run_test(
+        "cal --full-year 2020 --month | where month == 2 | get saturday | last",
+        "29",
+    )
+}
+
+#[test]
+fn cal_common_year() -> TestResult {
+    run_test(
+        "cal --full-year 2021 --month | where month == 2 | get sunday | last",
+        "28",
+    )
+}
+
+#[test]
+fn cal_pinned_month() -> TestResult {
+    run_test(
+        "cal --full-year 2022 --month | where month == 3 | get tuesday | str collect ','",
+        "1,8,15,22,29",
+    )
+}
+
+#[test]
+fn cal_week_start() -> TestResult {
+    run_test(
+        "cal --full-year 2022 --week-start monday | columns | first",
+        "monday",
+    )
+}
+
+#[test]
+fn cal_rows_can_be_filtered() -> TestResult {
+    run_test(
+        "cal --full-year 2022 --month | where month == 1 && saturday != $nothing | length",
+        "5",
+    )
+}
+
+#[test]
+fn cal_metadata() -> TestResult {
+    run_test("cal | metadata | get source", "cal")
+}
+
+#[test]
+fn format_filesize_column() -> TestResult {
+    run_test(
+        "[[name size]; [a 1mib] [b 512kib]] | format filesize MiB size | get size | str collect ','",
+        "1 MiB,0.5 MiB",
+    )
+}
+
+#[test]
+fn format_duration_column() -> TestResult {
+    run_test(
+        "[[task time]; [build 1min] [test 250ms]] | format duration sec time | get time | str collect ','",
+        "60 sec,0.25 sec",
+    )
+}
+
+#[test]
+fn format_filesize_unknown_unit() -> TestResult {
+    fail_test("1kb | format filesize parsecs", "Unknown unit")
+}
+
+#[test]
+fn to_json_escapes_control_characters() -> TestResult {
+    run_test(
+        r#"[(char nl) (char tab) (char bel)] | str collect | to json"#,
+        r#""\n\t\u0007""#,
+    )
+}
+
+#[test]
+fn histogram_counts_a_column() -> TestResult {
+    run_test(
+        "[[kind]; [file] [dir] [file] [symlink] [file] [dir]] | histogram kind | each { |it| $\"($it.kind)=($it.count)\" } | str collect ','",
+        "file=3,dir=2,symlink=1",
+    )
+}
+
+#[test]
+fn histogram_bars() -> TestResult {
+    run_test(
+        "[a a b b b b] | histogram --bars | get bar | each { |it| $it | str length } | str collect ','",
+        "20,10",
+    )
+}
+
+#[test]
+fn histogram_keeps_types_apart() -> TestResult {
+    run_test(
+        r#"[1 "1" 1.0 1 "1"] | histogram | get count | str collect ','"#,
+        "2,2,1",
+    )
+}
+
+#[test]
+fn histogram_missing_column() -> TestResult {
+    fail_test("[[a]; [1]] | histogram b", "Cannot find column")
+}
+
+#[test]
+fn enumerate_then_filter_by_index() -> TestResult {
+    run_test(
+        "[a b c d e] | enumerate | where index mod 2 == 0 | get item | str collect ','",
+        "a,c,e",
+    )
+}
+
+#[test]
+fn enumerate_keeps_rows_as_items() -> TestResult {
+    run_test(
+        "[[name]; [x] [y]] | enumerate --offset 1 | last | get index",
+        "2",
+    )?;
+    run_test(
+        "[[name]; [x] [y]] | enumerate --offset 1 | last | get item.name",
+        "y",
+    )
+}
+
+#[test]
+fn enumerate_index_overflow() -> TestResult {
+    fail_test(
+        "[a b] | enumerate --offset 9223372036854775807 | last | get index",
+        "overflow",
+    )
+}
+
+#[test]
+fn enumerate_streams() -> TestResult {
+    run_test("1.. | enumerate | first 3 | get index | math sum", "3")
+}
+
+#[test]
+fn keep_columns_by_name() -> TestResult {
+    let dir = Playground::new()?;
+    dir.file("a.txt", "a")?;
+
+    run_test(
+        &dir.script(
+            "ls -l | keep columns {|name| $name =~ '^(name|size)$' } | columns | str collect ','",
+        ),
+        "name,size",
+    )
+}
+
+#[test]
+fn keep_and_drop_columns_per_row() -> TestResult {
+    run_test(
+        "[{a: 1, b: 2} {a: 3, c: 4}] | keep columns {|name| $name != a } | to json --raw",
+        r#"[{"b": 2},{"c": 4}]"#,
+    )?;
+    run_test(
+        "[{a: 1, b: 2} {a: 3, c: 1}] | drop columns {|name, value| $value > 2 } | to json --raw",
+        r#"[{"a": 1,"b": 2},{"c": 1}]"#,
+    )
+}
+
+#[test]
+fn roll_left_and_right() -> TestResult {
+    run_test("[1 2 3 4] | roll left 1 | str collect ','", "2,3,4,1")?;
+    run_test("[1 2 3 4] | roll right 1 | str collect ','", "4,1,2,3")
+}
+
+#[test]
+fn roll_wraps_around() -> TestResult {
+    run_test("[1 2 3 4] | roll left 6 | str collect ','", "3,4,1,2")?;
+    run_test("[1 2 3 4] | roll right -1 | str collect ','", "2,3,4,1")?;
+    run_test("[] | roll left 3 | length", "0")
+}
+
+#[test]
+fn roll_record_columns() -> TestResult {
+    run_test(
+        "{a: 1, b: 2, c: 3} | roll right | columns | str collect ','",
+        "c,a,b",
+    )
+}
+
+#[test]
+fn table_pager_passes_through_when_not_a_terminal() -> TestResult {
+    run_test("([1 2 3] | table --pager) == ([1 2 3] | table)", "true")
+}
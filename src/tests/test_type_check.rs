@@ -1,4 +1,4 @@
-use crate::tests::{fail_test, run_test, TestResult};
+use crate::tests::{fail_test, run_script, run_test, run_test_contains, TestResult};
 
 #[test]
 fn chained_operator_typecheck() -> TestResult {
@@ -24,3 +24,57 @@ fn number_int() -> TestResult {
 fn number_float() -> TestResult {
     run_test(r#"def foo [x:number] { $x }; foo 1.4"#, "1.4")
 }
+
+#[test]
+fn pipeline_type_mismatch_warns() -> TestResult {
+    fail_test(r#"ls | lines"#, "Pipeline stages don't fit together")
+}
+
+#[test]
+fn pipeline_type_mismatch_still_runs() -> TestResult {
+    // lines skips what isn't text, so nothing is left to count
+    run_test(r#"ls | lines | length"#, "0")?;
+    fail_test(r#"ls | lines | length"#, "Warning")
+}
+
+#[test]
+fn pipeline_types_take_ranges_as_lists() -> TestResult {
+    for (script, expected) in [("1..3 | length", "3"), ("1..3 | str collect ','", "1,2,3")] {
+        let output = run_script(script, &[], "")?;
+        let stderr = String::from_utf8_lossy(&output.stderr);
+
+        assert!(output.status.success());
+        assert!(
+            !stderr.contains("Warning"),
+            "unexpected warning: {}",
+            stderr
+        );
+        assert_eq!(String::from_utf8_lossy(&output.stdout).trim(), expected);
+    }
+    Ok(())
+}
+
+#[test]
+fn pipeline_types_take_single_values_where_they_run() -> TestResult {
+    let output = run_script("'abc' | length", &[], "")?;
+
+    assert!(output.status.success());
+    assert!(!String::from_utf8_lossy(&output.stderr).contains("Warning"));
+    assert_eq!(String::from_utf8_lossy(&output.stdout).trim(), "1");
+    Ok(())
+}
+
+#[test]
+fn pipeline_types_map_over_lists() -> TestResult {
+    run_test(r#"[a b] | str upcase | str collect ','"#, "A,B")
+}
+
+#[test]
+fn pipeline_types_skip_column_paths() -> TestResult {
+    run_test(r#"[[x]; [a]] | str upcase x | get x.0"#, "A")
+}
+
+#[test]
+fn pipeline_types_in_help() -> TestResult {
+    run_test_contains(r#"help ls"#, "nothing -> table")
+}
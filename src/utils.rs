@@ -1,9 +1,9 @@
 use log::trace;
 use nu_cli::CliError;
 use nu_engine::eval_block;
-use nu_parser::{lex, parse, trim_quotes, Token, TokenContents};
+use nu_parser::{check_pipeline_types, lex, parse, trim_quotes, Token, TokenContents};
 use nu_protocol::{
-    ast::Call,
+    ast::{Block, Call},
     engine::{EngineState, Stack, StateWorkingSet},
     PipelineData, ShellError, Span, Value,
 };
@@ -240,8 +240,8 @@ fn print_pipeline_data(
             for item in table {
                 let stdout = std::io::stdout();
 
-                if let Value::Error { error } = item {
-                    return Err(error);
+                if let Some(error) = item.find_error() {
+                    return Err(error.clone());
                 }
 
                 let mut out = item.into_string("\n", &config);
@@ -257,8 +257,8 @@ fn print_pipeline_data(
             for item in input {
                 let stdout = std::io::stdout();
 
-                if let Value::Error { error } = item {
-                    return Err(error);
+                if let Some(error) = item.find_error() {
+                    return Err(error.clone());
                 }
 
                 let mut out = item.into_string("\n", &config);
@@ -295,6 +295,9 @@ pub(crate) fn eval_source(
             report_error(&working_set, &err);
             return false;
         }
+        if report_pipeline_types(&working_set, stack, &output) {
+            return false;
+        }
 
         (output, working_set.render())
     };
@@ -381,6 +384,29 @@ pub fn report_error(
     }
 }
 
+/// Report the pipelines of `block` whose stages don't fit together, as warnings unless
+/// $config.strict_pipeline_types is set. Returns whether the block should be kept from running.
+pub(crate) fn report_pipeline_types(
+    working_set: &StateWorkingSet,
+    stack: &Stack,
+    block: &Block,
+) -> bool {
+    let strict = stack
+        .get_config()
+        .map_or(false, |config| config.strict_pipeline_types);
+    let mismatches = check_pipeline_types(working_set, block);
+
+    for mismatch in &mismatches {
+        if strict {
+            report_error(working_set, mismatch);
+        } else {
+            eprintln!("Warning: {:?}", CliError(mismatch, working_set));
+        }
+    }
+
+    strict && !mismatches.is_empty()
+}
+
 pub(crate) fn get_init_cwd() -> PathBuf {
     match std::env::current_dir() {
         Ok(cwd) => cwd,